@@ -0,0 +1,24 @@
+// cargo +nightly fuzz run execute -- -timeout=5
+// endless scripts are reported as timeouts, anything that aborts the process is a bug
+#![no_main]
+
+use frugurt::interpreter::{
+    options::{Capabilities, InterpreterOptions},
+    runner::Interpreter,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(source_code) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let interpreter = Interpreter::new(InterpreterOptions {
+        capabilities: Capabilities::none(),
+        memory_limit: Some(16 * 1024 * 1024),
+        stack_limit: Some(1024 * 1024),
+        ..Default::default()
+    });
+
+    let _ = interpreter.execute(source_code.to_owned());
+});
@@ -2,7 +2,10 @@ use proc_macro::TokenStream;
 use std::hash::{DefaultHasher, Hash, Hasher};
 
 use quote::quote;
-use syn::LitStr;
+use syn::{
+    parse::{Parse, ParseStream},
+    Data, DeriveInput, Fields, Ident, LitStr, Path, Token,
+};
 
 #[proc_macro]
 pub fn static_ident(input: TokenStream) -> TokenStream {
@@ -25,3 +28,249 @@ pub fn static_ident(input: TokenStream) -> TokenStream {
     }
     .into()
 }
+
+struct NatArgs {
+    type_name: LitStr,
+    hooks: Vec<(Ident, Path)>,
+    immutable: bool,
+}
+
+impl Parse for NatArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let type_name = input.parse()?;
+        let mut hooks = Vec::new();
+        let mut immutable = false;
+
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            let key: Ident = input.parse()?;
+
+            if key == "immutable" {
+                immutable = true;
+                continue;
+            }
+
+            input.parse::<Token![=]>()?;
+            hooks.push((key, input.parse()?));
+        }
+
+        Ok(Self {
+            type_name,
+            hooks,
+            immutable,
+        })
+    }
+}
+
+/// Implements `INativeObject` for a struct, its named fields become props.
+/// Fields marked `#[fru(skip)]` are hidden, `#[fru(readonly)]` ones can't be set from code.
+/// `methods`, `call`, `instantiate`, `eq` and `debug` name functions that the rest of the props,
+/// calls, instantiation, comparison and printing are given to. Values of unit structs are all equal.
+/// Values of `immutable` types and unit structs count as frozen:
+///
+/// `#[derive_nat("Point", methods = Self::method)]`
+#[proc_macro_attribute]
+pub fn derive_nat(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = syn::parse_macro_input!(attr as NatArgs);
+    let mut input = syn::parse_macro_input!(item as DeriveInput);
+
+    let Data::Struct(data) = &mut input.data else {
+        return syn::Error::new_spanned(&input.ident, "`derive_nat` expects a struct")
+            .to_compile_error()
+            .into();
+    };
+
+    let mut readable = Vec::new();
+    let mut settable = Vec::new();
+    let mut readonly = Vec::new();
+
+    for field in data.fields.iter_mut() {
+        let Some(ident) = field.ident.clone() else {
+            continue;
+        };
+
+        let mut markers = Vec::new();
+
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("fru")) {
+            match attr.parse_args::<Ident>() {
+                Ok(marker) if marker == "skip" || marker == "readonly" => markers.push(marker),
+                Ok(marker) => {
+                    return syn::Error::new_spanned(marker, "expected `skip` or `readonly`")
+                        .to_compile_error()
+                        .into()
+                }
+                Err(err) => return err.to_compile_error().into(),
+            }
+        }
+
+        field.attrs.retain(|attr| !attr.path().is_ident("fru"));
+
+        if markers.iter().any(|marker| marker == "skip") {
+            continue;
+        }
+
+        readable.push(ident.clone());
+
+        if markers.iter().any(|marker| marker == "readonly") {
+            readonly.push(ident);
+        } else {
+            settable.push(ident);
+        }
+    }
+
+    let is_unit = matches!(data.fields, Fields::Unit);
+
+    let type_name = &args.type_name;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let names = |idents: &[Ident]| -> Vec<String> { idents.iter().map(|x| x.to_string()).collect() };
+    let (readable_names, settable_names, readonly_names) =
+        (names(&readable), names(&settable), names(&readonly));
+
+    let mut rest_of_props = quote! {
+        FruError::new_res(format!("prop `{}` not found", ident))
+    };
+    let mut hooks = Vec::new();
+
+    for (key, path) in &args.hooks {
+        match key.to_string().as_str() {
+            "methods" => rest_of_props = quote! { #path(self, ident) },
+
+            "call" => hooks.push(quote! {
+                fn call(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+                    #path(self, args)
+                }
+            }),
+
+            "instantiate" => hooks.push(quote! {
+                fn instantiate(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+                    #path(self, args)
+                }
+            }),
+
+            "eq" => hooks.push(quote! {
+                fn fru_eq(&self, other: &dyn INativeObject) -> Option<bool> {
+                    #path(self, other)
+                }
+            }),
+
+            "debug" => hooks.push(quote! {
+                fn fmt_debug(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    #path(self, f)
+                }
+            }),
+
+            _ => {
+                return syn::Error::new_spanned(
+                    key,
+                    "expected `methods`, `call`, `instantiate`, `eq` or `debug`",
+                )
+                .to_compile_error()
+                .into()
+            }
+        }
+    }
+
+    let has_eq = args.hooks.iter().any(|(key, _)| key == "eq");
+
+    if is_unit && !has_eq {
+        hooks.push(quote! {
+            fn fru_eq(&self, other: &dyn INativeObject) -> Option<bool> {
+                Some(other.as_any().is::<Self>())
+            }
+        });
+    }
+
+    if is_unit || args.immutable {
+        hooks.push(quote! {
+            fn freeze(&self) -> Option<Vec<FruValue>> {
+                Some(Vec::new())
+            }
+
+            fn is_frozen(&self) -> bool {
+                true
+            }
+        });
+    }
+
+    quote! {
+        #input
+
+        const _: () = {
+            #[allow(unused_imports)]
+            use ::macros::static_ident;
+            #[allow(unused_imports)]
+            use ::std::{any::Any, rc::Rc};
+
+            #[allow(unused_imports)]
+            use crate::interpreter::{
+                error::FruError,
+                identifier::Identifier,
+                value::fru_value::FruValue,
+                value::function::EvaluatedArgumentList,
+                value::native::field::NatField,
+                value::native::object::{INativeObject, NativeObject},
+            };
+
+            impl #impl_generics #name #ty_generics #where_clause {
+                pub fn into_value(self) -> FruValue {
+                    FruValue::NativeObject(NativeObject::new(Rc::new(self)))
+                }
+            }
+
+            impl #impl_generics INativeObject for #name #ty_generics #where_clause {
+                fn as_any(&self) -> &dyn Any {
+                    self
+                }
+
+                fn get_type_identifier(&self) -> Identifier {
+                    static_ident!(#type_name)
+                }
+
+                fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+                    #(
+                        if ident == static_ident!(#readable_names) {
+                            return Ok(NatField::get_value(&self.#readable));
+                        }
+                    )*
+
+                    #rest_of_props
+                }
+
+                fn set_prop(&self, ident: Identifier, value: FruValue) -> Result<(), FruError> {
+                    #(
+                        if ident == static_ident!(#settable_names) {
+                            return NatField::set_value(&self.#settable, value, ident);
+                        }
+                    )*
+
+                    #(
+                        if ident == static_ident!(#readonly_names) {
+                            return FruError::new_res(format!("prop `{}` is read-only", ident));
+                        }
+                    )*
+
+                    FruError::new_res(format!("cannot set prop of `{}`", #type_name))
+                }
+
+                fn list_props(&self) -> Vec<Identifier> {
+                    vec![#(static_ident!(#readable_names)),*]
+                }
+
+                #(#hooks)*
+
+                fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+                    self
+                }
+            }
+
+            impl #impl_generics ::std::fmt::Debug for #name #ty_generics #where_clause {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    INativeObject::fmt_debug(self, f)
+                }
+            }
+        };
+    }
+    .into()
+}
@@ -1,8 +1,10 @@
 use std::rc::Rc;
 
 use crate::interpreter::{
-    expression::FruExpression, identifier::Identifier, statement::FruStatement,
-    value::function::FormalParameters,
+    expression::FruExpression,
+    identifier::Identifier,
+    statement::FruStatement,
+    value::function::{FormalParameters, SourceLocation},
 };
 
 #[derive(Debug, Clone)]
@@ -17,4 +19,6 @@ pub struct RawMethod {
     pub ident: Identifier,
     pub parameters: FormalParameters,
     pub body: Rc<FruStatement>,
+    pub location: SourceLocation,
+    pub doc: Option<Rc<str>>,
 }
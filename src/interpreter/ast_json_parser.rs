@@ -0,0 +1,636 @@
+use std::{collections::HashMap, rc::Rc};
+
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+use crate::interpreter::{
+    ast_helpers::{RawMethod, RawStaticField},
+    expression::FruExpression,
+    identifier::Identifier,
+    statement::FruStatement,
+    value::{
+        fru_type::{FruField, Property, TypeType},
+        fru_value::FruValue,
+        function::{ArgumentList, FormalParameters, SourceLocation},
+    },
+};
+
+/// Version of the JSON layout of the AST, bumped on every incompatible change
+pub const AST_JSON_VERSION: u64 = 2;
+
+/// `path` points at the bad value, like `$.ast.body[2].value`
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{message} at {path}")]
+pub struct AstJsonError {
+    pub path: String,
+    pub message: String,
+}
+
+impl AstJsonError {
+    pub fn new(path: &str, message: impl Into<String>) -> AstJsonError {
+        AstJsonError {
+            path: path.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Parses `{"version": 2, "ast": ...}` as made by `ast_json_serializer::to_json`,
+/// unknown nodes and fields are errors, so typos in generated ASTs don't go unnoticed
+pub fn parse(data: &str) -> Result<Box<FruStatement>, AstJsonError> {
+    let value: Value = serde_json::from_str(data)
+        .map_err(|err| AstJsonError::new("$", format!("invalid JSON: {}", err)))?;
+
+    parse_value(&value).map(Box::new)
+}
+
+pub fn parse_value(value: &Value) -> Result<FruStatement, AstJsonError> {
+    let root = JsonNode::new(value, "$".to_string())?;
+    root.expect_fields(&["version", "ast"])?;
+
+    let (version, path) = root.field("version")?;
+
+    match version.as_u64() {
+        Some(AST_JSON_VERSION) => {}
+        Some(version) => {
+            return Err(AstJsonError::new(
+                &path,
+                format!(
+                    "unsupported AST version {}, expected {}",
+                    version, AST_JSON_VERSION
+                ),
+            ))
+        }
+        None => return Err(AstJsonError::new(&path, "expected a version number")),
+    }
+
+    root.statement("ast")
+}
+
+#[derive(Clone)]
+struct JsonNode<'a> {
+    map: &'a Map<String, Value>,
+    path: String,
+}
+
+impl<'a> JsonNode<'a> {
+    fn new(value: &'a Value, path: String) -> Result<Self, AstJsonError> {
+        match value {
+            Value::Object(map) => Ok(JsonNode { map, path }),
+            _ => Err(AstJsonError::new(&path, "expected an object")),
+        }
+    }
+
+    fn kind(&self) -> Result<&'a str, AstJsonError> {
+        let (kind, path) = self.field("node")?;
+
+        kind.as_str().ok_or_else(|| AstJsonError::new(&path, "expected a node kind"))
+    }
+
+    // `node` is always allowed, other fields must be listed
+    fn expect_fields(&self, fields: &[&str]) -> Result<(), AstJsonError> {
+        match self.map.keys().find(|x| *x != "node" && !fields.contains(&x.as_str())) {
+            Some(unknown) => Err(AstJsonError::new(
+                &self.path,
+                format!("unknown field `{}`", unknown),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn field(&self, name: &str) -> Result<(&'a Value, String), AstJsonError> {
+        match self.map.get(name) {
+            Some(value) => Ok((value, format!("{}.{}", self.path, name))),
+            None => Err(AstJsonError::new(
+                &self.path,
+                format!("missing field `{}`", name),
+            )),
+        }
+    }
+
+    // the field must be present, `null` means nothing
+    fn optional_field(&self, name: &str) -> Result<Option<(&'a Value, String)>, AstJsonError> {
+        let (value, path) = self.field(name)?;
+
+        Ok(match value {
+            Value::Null => None,
+            value => Some((value, path)),
+        })
+    }
+
+    fn child(&self, name: &str) -> Result<JsonNode<'a>, AstJsonError> {
+        let (value, path) = self.field(name)?;
+        JsonNode::new(value, path)
+    }
+
+    fn children(&self, name: &str) -> Result<Vec<JsonNode<'a>>, AstJsonError> {
+        let (value, path) = self.field(name)?;
+
+        match value {
+            Value::Array(items) => items
+                .iter()
+                .enumerate()
+                .map(|(k, item)| JsonNode::new(item, format!("{}[{}]", path, k)))
+                .collect(),
+
+            _ => Err(AstJsonError::new(&path, "expected an array")),
+        }
+    }
+
+    fn bool(&self, name: &str) -> Result<bool, AstJsonError> {
+        let (value, path) = self.field(name)?;
+        value.as_bool().ok_or_else(|| AstJsonError::new(&path, "expected a bool"))
+    }
+
+    fn usize(&self, name: &str) -> Result<usize, AstJsonError> {
+        let (value, path) = self.field(name)?;
+        value
+            .as_u64()
+            .map(|x| x as usize)
+            .ok_or_else(|| AstJsonError::new(&path, "expected a non-negative integer"))
+    }
+
+    fn ident(&self, name: &str) -> Result<Identifier, AstJsonError> {
+        let (value, path) = self.field(name)?;
+        as_ident(value, &path)
+    }
+
+    fn optional_ident(&self, name: &str) -> Result<Option<Identifier>, AstJsonError> {
+        self.optional_field(name)?
+            .map(|(value, path)| as_ident(value, &path))
+            .transpose()
+    }
+
+    fn doc(&self) -> Result<Option<Rc<str>>, AstJsonError> {
+        self.optional_field("doc")?
+            .map(|(value, path)| {
+                value
+                    .as_str()
+                    .map(Rc::from)
+                    .ok_or_else(|| AstJsonError::new(&path, "expected a string"))
+            })
+            .transpose()
+    }
+
+    fn location(&self, name: &str) -> Result<SourceLocation, AstJsonError> {
+        let location = self.child(name)?;
+        location.expect_fields(&["line", "column"])?;
+
+        Ok(SourceLocation {
+            line: location.usize("line")?,
+            column: location.usize("column")?,
+        })
+    }
+
+    fn statement(&self, name: &str) -> Result<FruStatement, AstJsonError> {
+        parse_statement(self.child(name)?)
+    }
+
+    fn statements(&self, name: &str) -> Result<Vec<FruStatement>, AstJsonError> {
+        self.children(name)?.into_iter().map(parse_statement).collect()
+    }
+
+    fn expression(&self, name: &str) -> Result<FruExpression, AstJsonError> {
+        parse_expression(self.child(name)?)
+    }
+
+    fn optional_expression(&self, name: &str) -> Result<Option<FruExpression>, AstJsonError> {
+        self.optional_field(name)?
+            .map(|(value, path)| parse_expression(JsonNode::new(value, path)?))
+            .transpose()
+    }
+
+    fn parameters(&self, name: &str) -> Result<FormalParameters, AstJsonError> {
+        let args = self
+            .children(name)?
+            .into_iter()
+            .map(|parameter| {
+                parameter.expect_fields(&["ident", "default"])?;
+                Ok((
+                    parameter.ident("ident")?,
+                    parameter.optional_expression("default")?,
+                ))
+            })
+            .collect::<Result<_, AstJsonError>>()?;
+
+        Ok(FormalParameters { args })
+    }
+
+    fn arguments(&self, name: &str) -> Result<ArgumentList, AstJsonError> {
+        let args = self
+            .children(name)?
+            .into_iter()
+            .map(|argument| {
+                argument.expect_fields(&["ident", "value"])?;
+                Ok((
+                    argument.optional_ident("ident")?,
+                    argument.expression("value")?,
+                ))
+            })
+            .collect::<Result<_, AstJsonError>>()?;
+
+        Ok(ArgumentList { args })
+    }
+}
+
+fn as_ident(value: &Value, path: &str) -> Result<Identifier, AstJsonError> {
+    match value.as_str() {
+        Some(name) if !name.is_empty() => Ok(Identifier::new(name)),
+        _ => Err(AstJsonError::new(path, "expected an identifier")),
+    }
+}
+
+fn parse_statement(node: JsonNode) -> Result<FruStatement, AstJsonError> {
+    let kind = node.kind()?;
+
+    let (fields, statement): (&[&str], _) = match kind {
+        "SourceCode" => (
+            &["body"],
+            FruStatement::SourceCode {
+                body: node.statements("body")?,
+            },
+        ),
+
+        "Block" => (
+            &["body"],
+            FruStatement::Block {
+                body: node.statements("body")?,
+            },
+        ),
+
+        "ScopeModifier" => (
+            &["what", "body"],
+            FruStatement::ScopeModifier {
+                what: Box::new(node.expression("what")?),
+                body: node.statements("body")?,
+            },
+        ),
+
+        "Expression" => (
+            &["value"],
+            FruStatement::Expression {
+                value: Box::new(node.expression("value")?),
+            },
+        ),
+
+        "Let" => (
+            &["ident", "value"],
+            FruStatement::Let {
+                ident: node.ident("ident")?,
+                value: Box::new(node.expression("value")?),
+            },
+        ),
+
+        "Set" => (
+            &["ident", "value"],
+            FruStatement::Set {
+                ident: node.ident("ident")?,
+                value: Box::new(node.expression("value")?),
+            },
+        ),
+
+        "SetProp" => (
+            &["what", "ident", "value"],
+            FruStatement::SetProp {
+                what: Box::new(node.expression("what")?),
+                ident: node.ident("ident")?,
+                value: Box::new(node.expression("value")?),
+            },
+        ),
+
+        "If" => (
+            &["condition", "then_body", "else_body"],
+            FruStatement::If {
+                condition: Box::new(node.expression("condition")?),
+                then_body: Box::new(node.statement("then_body")?),
+                else_body: node
+                    .optional_field("else_body")?
+                    .map(|(value, path)| parse_statement(JsonNode::new(value, path)?))
+                    .transpose()?
+                    .map(Box::new),
+            },
+        ),
+
+        "While" => (
+            &["condition", "body"],
+            FruStatement::While {
+                condition: Box::new(node.expression("condition")?),
+                body: Box::new(node.statement("body")?),
+            },
+        ),
+
+        "Return" => (
+            &["value"],
+            FruStatement::Return {
+                value: node.optional_expression("value")?.map(Box::new),
+            },
+        ),
+
+        "Break" => (&[], FruStatement::Break),
+
+        "Continue" => (&[], FruStatement::Continue),
+
+        "Operator" => (
+            &[
+                "ident",
+                "commutative",
+                "left_ident",
+                "left_type_ident",
+                "right_ident",
+                "right_type_ident",
+                "body",
+                "doc",
+            ],
+            FruStatement::Operator {
+                ident: node.ident("ident")?,
+                commutative: node.bool("commutative")?,
+                left_ident: node.ident("left_ident")?,
+                left_type_ident: node.ident("left_type_ident")?,
+                right_ident: node.ident("right_ident")?,
+                right_type_ident: node.ident("right_type_ident")?,
+                body: Rc::new(node.statement("body")?),
+                doc: node.doc()?,
+            },
+        ),
+
+        "Type" => (
+            &[
+                "type_type",
+                "ident",
+                "fields",
+                "static_fields",
+                "properties",
+                "static_properties",
+                "methods",
+                "doc",
+            ],
+            parse_type(&node)?,
+        ),
+
+        "Located" => (
+            &["location", "end", "statement"],
+            FruStatement::Located {
+                location: node.location("location")?,
+                end: node.location("end")?,
+                statement: Box::new(node.statement("statement")?),
+            },
+        ),
+
+        _ => {
+            return Err(AstJsonError::new(
+                &node.path,
+                format!("unknown statement `{}`", kind),
+            ))
+        }
+    };
+
+    node.expect_fields(fields)?;
+
+    Ok(statement)
+}
+
+fn parse_type(node: &JsonNode) -> Result<FruStatement, AstJsonError> {
+    let (type_type, path) = node.field("type_type")?;
+
+    let type_type = match type_type.as_str() {
+        Some("struct") => TypeType::Struct,
+        Some("class") => TypeType::Class,
+        Some("data") => TypeType::Data,
+        _ => {
+            return Err(AstJsonError::new(
+                &path,
+                "expected `struct`, `class` or `data`",
+            ))
+        }
+    };
+
+    let fields = node
+        .children("fields")?
+        .into_iter()
+        .map(|field| {
+            field.expect_fields(&["is_public", "ident", "type_ident", "doc"])?;
+            Ok(FruField {
+                is_public: field.bool("is_public")?,
+                ident: field.ident("ident")?,
+                type_ident: field.optional_ident("type_ident")?,
+                doc: field.doc()?,
+            })
+        })
+        .collect::<Result<_, AstJsonError>>()?;
+
+    let static_fields = node
+        .children("static_fields")?
+        .into_iter()
+        .map(|field| {
+            field.expect_fields(&["ident", "value"])?;
+            Ok(RawStaticField {
+                ident: field.ident("ident")?,
+                value: field.optional_expression("value")?.map(Box::new),
+            })
+        })
+        .collect::<Result<_, AstJsonError>>()?;
+
+    let methods = node
+        .children("methods")?
+        .into_iter()
+        .map(|method| {
+            method.expect_fields(&[
+                "is_static",
+                "ident",
+                "parameters",
+                "body",
+                "location",
+                "doc",
+            ])?;
+            Ok(RawMethod {
+                is_static: method.bool("is_static")?,
+                ident: method.ident("ident")?,
+                parameters: method.parameters("parameters")?,
+                body: Rc::new(method.statement("body")?),
+                location: method.location("location")?,
+                doc: method.doc()?,
+            })
+        })
+        .collect::<Result<_, AstJsonError>>()?;
+
+    Ok(FruStatement::Type {
+        type_type,
+        ident: node.ident("ident")?,
+        fields,
+        static_fields,
+        properties: parse_properties(node, "properties")?,
+        static_properties: parse_properties(node, "static_properties")?,
+        methods,
+        doc: node.doc()?,
+    })
+}
+
+fn parse_properties(
+    node: &JsonNode,
+    name: &str,
+) -> Result<HashMap<Identifier, Property>, AstJsonError> {
+    let mut properties = HashMap::new();
+
+    for property in node.children(name)? {
+        property.expect_fields(&["ident", "getter", "setter"])?;
+
+        let ident = property.ident("ident")?;
+
+        let setter = match property.optional_field("setter")? {
+            Some((value, path)) => {
+                let setter = JsonNode::new(value, path)?;
+                setter.expect_fields(&["ident", "body"])?;
+                Some((setter.ident("ident")?, Rc::new(setter.statement("body")?)))
+            }
+            None => None,
+        };
+
+        let property_value = Property {
+            ident,
+            getter: property.optional_expression("getter")?.map(Rc::new),
+            setter,
+        };
+
+        if properties.insert(ident, property_value).is_some() {
+            return Err(AstJsonError::new(
+                &property.path,
+                format!("duplicate property `{}`", ident),
+            ));
+        }
+    }
+
+    Ok(properties)
+}
+
+fn parse_expression(node: JsonNode) -> Result<FruExpression, AstJsonError> {
+    let kind = node.kind()?;
+
+    let (fields, expression): (&[&str], _) = match kind {
+        "Literal" => {
+            let (value, path) = node.field("value")?;
+
+            let value = match value {
+                Value::Null => FruValue::Nah,
+                Value::Bool(b) => FruValue::Bool(*b),
+                Value::String(s) => FruValue::String(s.clone()),
+                Value::Number(n) => match n.as_f64() {
+                    Some(n) => FruValue::Number(n),
+                    None => return Err(AstJsonError::new(&path, "number is out of range")),
+                },
+                _ => {
+                    return Err(AstJsonError::new(
+                        &path,
+                        "expected null, a bool, a number or a string",
+                    ))
+                }
+            };
+
+            (&["value"][..], FruExpression::Literal { value })
+        }
+
+        "Variable" => (
+            &["ident"],
+            FruExpression::Variable {
+                ident: node.ident("ident")?,
+            },
+        ),
+
+        "ScopeAccessor" => (&[], FruExpression::ScopeAccessor),
+
+        "Function" => (
+            &["args", "body", "name", "location", "doc"],
+            FruExpression::Function {
+                args: node.parameters("args")?,
+                body: Rc::new(node.statement("body")?),
+                name: node.optional_ident("name")?,
+                location: node.location("location")?,
+                doc: node.doc()?,
+            },
+        ),
+
+        "Block" => (
+            &["body", "expr"],
+            FruExpression::Block {
+                body: node.statements("body")?,
+                expr: Box::new(node.expression("expr")?),
+            },
+        ),
+
+        "ScopeModifier" => (
+            &["what", "body", "expr"],
+            FruExpression::ScopeModifier {
+                what: Box::new(node.expression("what")?),
+                body: node.statements("body")?,
+                expr: Box::new(node.expression("expr")?),
+            },
+        ),
+
+        "Call" => (
+            &["what", "args"],
+            FruExpression::Call {
+                what: Box::new(node.expression("what")?),
+                args: node.arguments("args")?,
+            },
+        ),
+
+        "CurryCall" => (
+            &["what", "args"],
+            FruExpression::CurryCall {
+                what: Box::new(node.expression("what")?),
+                args: node.arguments("args")?,
+            },
+        ),
+
+        "Instantiation" => (
+            &["what", "args"],
+            FruExpression::Instantiation {
+                what: Box::new(node.expression("what")?),
+                args: node.arguments("args")?,
+            },
+        ),
+
+        "PropAccess" => (
+            &["what", "ident"],
+            FruExpression::PropAccess {
+                what: Box::new(node.expression("what")?),
+                ident: node.ident("ident")?,
+            },
+        ),
+
+        "Binary" => (
+            &["operator", "left", "right"],
+            FruExpression::Binary {
+                operator: node.ident("operator")?,
+                left: Box::new(node.expression("left")?),
+                right: Box::new(node.expression("right")?),
+            },
+        ),
+
+        "If" => (
+            &["condition", "then_body", "else_body"],
+            FruExpression::If {
+                condition: Box::new(node.expression("condition")?),
+                then_body: Box::new(node.expression("then_body")?),
+                else_body: Box::new(node.expression("else_body")?),
+            },
+        ),
+
+        "Import" => (
+            &["path"],
+            FruExpression::Import {
+                path: Box::new(node.expression("path")?),
+            },
+        ),
+
+        _ => {
+            return Err(AstJsonError::new(
+                &node.path,
+                format!("unknown expression `{}`", kind),
+            ))
+        }
+    };
+
+    node.expect_fields(fields)?;
+
+    Ok(expression)
+}
@@ -0,0 +1,398 @@
+use std::rc::Rc;
+
+use serde_json::{json, Value};
+
+use crate::interpreter::{
+    ast_json_parser::{AstJsonError, AST_JSON_VERSION},
+    expression::FruExpression,
+    statement::FruStatement,
+    value::{
+        fru_type::{Property, TypeType},
+        fru_value::FruValue,
+        function::{ArgumentList, FormalParameters, SourceLocation},
+    },
+};
+
+/// AST in the layout read by `ast_json_parser::parse`,
+/// literals that are not `nah`, bools, finite numbers or strings can't be written
+pub fn to_json(ast: &FruStatement) -> Result<Value, AstJsonError> {
+    Ok(json!({
+        "version": AST_JSON_VERSION,
+        "ast": statement_to_json(ast, "$.ast")?,
+    }))
+}
+
+fn statements_to_json(body: &[FruStatement], path: &str) -> Result<Value, AstJsonError> {
+    body.iter()
+        .enumerate()
+        .map(|(k, statement)| statement_to_json(statement, &format!("{}[{}]", path, k)))
+        .collect()
+}
+
+fn location_to_json(location: &SourceLocation) -> Value {
+    json!({ "line": location.line, "column": location.column })
+}
+
+fn doc_to_json(doc: &Option<Rc<str>>) -> Value {
+    json!(doc.as_deref())
+}
+
+fn statement_to_json(statement: &FruStatement, path: &str) -> Result<Value, AstJsonError> {
+    let child = |name: &str| format!("{}.{}", path, name);
+
+    Ok(match statement {
+        FruStatement::SourceCode { body } => json!({
+            "node": "SourceCode",
+            "body": statements_to_json(body, &child("body"))?,
+        }),
+
+        FruStatement::Block { body } => json!({
+            "node": "Block",
+            "body": statements_to_json(body, &child("body"))?,
+        }),
+
+        FruStatement::ScopeModifier { what, body } => json!({
+            "node": "ScopeModifier",
+            "what": expression_to_json(what, &child("what"))?,
+            "body": statements_to_json(body, &child("body"))?,
+        }),
+
+        FruStatement::Expression { value } => json!({
+            "node": "Expression",
+            "value": expression_to_json(value, &child("value"))?,
+        }),
+
+        FruStatement::Let { ident, value } => json!({
+            "node": "Let",
+            "ident": ident.to_string(),
+            "value": expression_to_json(value, &child("value"))?,
+        }),
+
+        FruStatement::Set { ident, value } => json!({
+            "node": "Set",
+            "ident": ident.to_string(),
+            "value": expression_to_json(value, &child("value"))?,
+        }),
+
+        FruStatement::SetProp { what, ident, value } => json!({
+            "node": "SetProp",
+            "what": expression_to_json(what, &child("what"))?,
+            "ident": ident.to_string(),
+            "value": expression_to_json(value, &child("value"))?,
+        }),
+
+        FruStatement::If {
+            condition,
+            then_body,
+            else_body,
+        } => json!({
+            "node": "If",
+            "condition": expression_to_json(condition, &child("condition"))?,
+            "then_body": statement_to_json(then_body, &child("then_body"))?,
+            "else_body": else_body
+                .as_deref()
+                .map(|x| statement_to_json(x, &child("else_body")))
+                .transpose()?,
+        }),
+
+        FruStatement::While { condition, body } => json!({
+            "node": "While",
+            "condition": expression_to_json(condition, &child("condition"))?,
+            "body": statement_to_json(body, &child("body"))?,
+        }),
+
+        FruStatement::Return { value } => json!({
+            "node": "Return",
+            "value": value
+                .as_deref()
+                .map(|x| expression_to_json(x, &child("value")))
+                .transpose()?,
+        }),
+
+        FruStatement::Break => json!({ "node": "Break" }),
+
+        FruStatement::Continue => json!({ "node": "Continue" }),
+
+        FruStatement::Operator {
+            ident,
+            commutative,
+            left_ident,
+            left_type_ident,
+            right_ident,
+            right_type_ident,
+            body,
+            doc,
+        } => json!({
+            "node": "Operator",
+            "ident": ident.to_string(),
+            "commutative": commutative,
+            "left_ident": left_ident.to_string(),
+            "left_type_ident": left_type_ident.to_string(),
+            "right_ident": right_ident.to_string(),
+            "right_type_ident": right_type_ident.to_string(),
+            "body": statement_to_json(body, &child("body"))?,
+            "doc": doc_to_json(doc),
+        }),
+
+        FruStatement::Type {
+            type_type,
+            ident,
+            fields,
+            static_fields,
+            properties,
+            static_properties,
+            methods,
+            doc,
+        } => {
+            let type_type = match type_type {
+                TypeType::Struct => "struct",
+                TypeType::Class => "class",
+                TypeType::Data => "data",
+            };
+
+            let fields: Vec<_> = fields
+                .iter()
+                .map(|field| {
+                    json!({
+                        "is_public": field.is_public,
+                        "ident": field.ident.to_string(),
+                        "type_ident": field.type_ident.map(|x| x.to_string()),
+                        "doc": doc_to_json(&field.doc),
+                    })
+                })
+                .collect();
+
+            let static_fields = static_fields
+                .iter()
+                .enumerate()
+                .map(|(k, field)| {
+                    let path = format!("{}[{}].value", child("static_fields"), k);
+
+                    Ok(json!({
+                        "ident": field.ident.to_string(),
+                        "value": field
+                            .value
+                            .as_deref()
+                            .map(|x| expression_to_json(x, &path))
+                            .transpose()?,
+                    }))
+                })
+                .collect::<Result<Vec<_>, AstJsonError>>()?;
+
+            let methods = methods
+                .iter()
+                .enumerate()
+                .map(|(k, method)| {
+                    let path = format!("{}[{}]", child("methods"), k);
+
+                    Ok(json!({
+                        "is_static": method.is_static,
+                        "ident": method.ident.to_string(),
+                        "parameters": parameters_to_json(&method.parameters, &format!("{}.parameters", path))?,
+                        "body": statement_to_json(&method.body, &format!("{}.body", path))?,
+                        "location": location_to_json(&method.location),
+                        "doc": doc_to_json(&method.doc),
+                    }))
+                })
+                .collect::<Result<Vec<_>, AstJsonError>>()?;
+
+            json!({
+                "node": "Type",
+                "type_type": type_type,
+                "ident": ident.to_string(),
+                "fields": fields,
+                "static_fields": static_fields,
+                "properties": properties_to_json(properties.values(), &child("properties"))?,
+                "static_properties": properties_to_json(static_properties.values(), &child("static_properties"))?,
+                "methods": methods,
+                "doc": doc_to_json(doc),
+            })
+        }
+
+        FruStatement::Located {
+            location,
+            end,
+            statement,
+        } => json!({
+            "node": "Located",
+            "location": location_to_json(location),
+            "end": location_to_json(end),
+            "statement": statement_to_json(statement, &child("statement"))?,
+        }),
+    })
+}
+
+// sorted by name, so the same AST is always written the same way
+fn properties_to_json<'a>(
+    properties: impl Iterator<Item = &'a Property>,
+    path: &str,
+) -> Result<Value, AstJsonError> {
+    let mut properties: Vec<_> = properties.collect();
+    properties.sort_by_key(|x| x.ident.to_string());
+
+    properties
+        .into_iter()
+        .enumerate()
+        .map(|(k, property)| {
+            let path = format!("{}[{}]", path, k);
+
+            let setter = match &property.setter {
+                Some((ident, body)) => json!({
+                    "ident": ident.to_string(),
+                    "body": statement_to_json(body, &format!("{}.setter.body", path))?,
+                }),
+                None => Value::Null,
+            };
+
+            Ok(json!({
+                "ident": property.ident.to_string(),
+                "getter": property
+                    .getter
+                    .as_deref()
+                    .map(|x| expression_to_json(x, &format!("{}.getter", path)))
+                    .transpose()?,
+                "setter": setter,
+            }))
+        })
+        .collect()
+}
+
+fn parameters_to_json(parameters: &FormalParameters, path: &str) -> Result<Value, AstJsonError> {
+    parameters
+        .args
+        .iter()
+        .enumerate()
+        .map(|(k, (ident, default))| {
+            Ok(json!({
+                "ident": ident.to_string(),
+                "default": default
+                    .as_ref()
+                    .map(|x| expression_to_json(x, &format!("{}[{}].default", path, k)))
+                    .transpose()?,
+            }))
+        })
+        .collect()
+}
+
+fn arguments_to_json(arguments: &ArgumentList, path: &str) -> Result<Value, AstJsonError> {
+    arguments
+        .args
+        .iter()
+        .enumerate()
+        .map(|(k, (ident, value))| {
+            Ok(json!({
+                "ident": ident.map(|x| x.to_string()),
+                "value": expression_to_json(value, &format!("{}[{}].value", path, k))?,
+            }))
+        })
+        .collect()
+}
+
+fn expression_to_json(expression: &FruExpression, path: &str) -> Result<Value, AstJsonError> {
+    let child = |name: &str| format!("{}.{}", path, name);
+
+    Ok(match expression {
+        FruExpression::Literal { value } => {
+            let value = match value {
+                FruValue::Nah => Value::Null,
+                FruValue::Bool(b) => json!(b),
+                FruValue::Number(n) if n.is_finite() => json!(n),
+                FruValue::String(s) => json!(s),
+                other => {
+                    return Err(AstJsonError::new(
+                        &child("value"),
+                        format!("literal `{:?}` can't be written as JSON", other),
+                    ))
+                }
+            };
+
+            json!({ "node": "Literal", "value": value })
+        }
+
+        FruExpression::Variable { ident } => json!({
+            "node": "Variable",
+            "ident": ident.to_string(),
+        }),
+
+        FruExpression::ScopeAccessor => json!({ "node": "ScopeAccessor" }),
+
+        FruExpression::Function {
+            args,
+            body,
+            name,
+            location,
+            doc,
+        } => json!({
+            "node": "Function",
+            "args": parameters_to_json(args, &child("args"))?,
+            "body": statement_to_json(body, &child("body"))?,
+            "name": name.map(|x| x.to_string()),
+            "location": location_to_json(location),
+            "doc": doc_to_json(doc),
+        }),
+
+        FruExpression::Block { body, expr } => json!({
+            "node": "Block",
+            "body": statements_to_json(body, &child("body"))?,
+            "expr": expression_to_json(expr, &child("expr"))?,
+        }),
+
+        FruExpression::ScopeModifier { what, body, expr } => json!({
+            "node": "ScopeModifier",
+            "what": expression_to_json(what, &child("what"))?,
+            "body": statements_to_json(body, &child("body"))?,
+            "expr": expression_to_json(expr, &child("expr"))?,
+        }),
+
+        FruExpression::Call { what, args } => json!({
+            "node": "Call",
+            "what": expression_to_json(what, &child("what"))?,
+            "args": arguments_to_json(args, &child("args"))?,
+        }),
+
+        FruExpression::CurryCall { what, args } => json!({
+            "node": "CurryCall",
+            "what": expression_to_json(what, &child("what"))?,
+            "args": arguments_to_json(args, &child("args"))?,
+        }),
+
+        FruExpression::Instantiation { what, args } => json!({
+            "node": "Instantiation",
+            "what": expression_to_json(what, &child("what"))?,
+            "args": arguments_to_json(args, &child("args"))?,
+        }),
+
+        FruExpression::PropAccess { what, ident } => json!({
+            "node": "PropAccess",
+            "what": expression_to_json(what, &child("what"))?,
+            "ident": ident.to_string(),
+        }),
+
+        FruExpression::Binary {
+            operator,
+            left,
+            right,
+        } => json!({
+            "node": "Binary",
+            "operator": operator.to_string(),
+            "left": expression_to_json(left, &child("left"))?,
+            "right": expression_to_json(right, &child("right"))?,
+        }),
+
+        FruExpression::If {
+            condition,
+            then_body,
+            else_body,
+        } => json!({
+            "node": "If",
+            "condition": expression_to_json(condition, &child("condition"))?,
+            "then_body": expression_to_json(then_body, &child("then_body"))?,
+            "else_body": expression_to_json(else_body, &child("else_body"))?,
+        }),
+
+        FruExpression::Import { path: import } => json!({
+            "node": "Import",
+            "path": expression_to_json(import, &child("path"))?,
+        }),
+    })
+}
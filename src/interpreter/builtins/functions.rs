@@ -5,9 +5,32 @@ use std::{collections::HashMap, io, io::Write};
 use crate::interpreter::{
     error::FruError,
     identifier::Identifier,
+    options::Capability,
+    runtime::{print_to_host, require_capability},
     value::fru_value::{FruValue, TFnBuiltin},
     value::function::{AnyFunction, BuiltinFunction, EvaluatedArgumentList},
 };
+use crate::stdlib::{
+    ast::fru_ast::{b_case, b_fallthrough, b_macro, b_op, b_quote, b_unquote, b_when},
+    clone::fru_clone::{b_clone, b_deep_clone},
+    decorators::fru_decorators::{b_deprecated, b_memoize, b_timed},
+    freeze::fru_freeze::{b_freeze, b_is_frozen},
+    future::fru_future::b_await,
+    introspection::{
+        fru_inspect::b_inspect,
+        fru_introspection::{b_doc_of, b_operators_of, b_props_of, b_type_of},
+    },
+    lazy::fru_lazy::b_lazy,
+    number::fru_number::{b_format_number, b_parse_number, b_to_fixed, b_to_precision},
+    observe::fru_observe::b_observe,
+    scope::fru_scope::b_global,
+    string::fru_string::b_dedent,
+    sys::fru_sys::b_has_feature,
+    task::fru_task::{b_spawn, b_yield_now},
+    timer::fru_timer::b_sleep,
+    weak::fru_weak::b_weak,
+    with::fru_with::b_with,
+};
 
 pub fn builtin_functions() -> HashMap<Identifier, FruValue> {
     HashMap::from(
@@ -15,17 +38,62 @@ pub fn builtin_functions() -> HashMap<Identifier, FruValue> {
             ("print", b_print as TFnBuiltin),
             ("input", b_input as TFnBuiltin),
             ("assert_eq", b_assert_eq as TFnBuiltin),
+            ("spawn", b_spawn as TFnBuiltin),
+            ("yield_now", b_yield_now as TFnBuiltin),
+            ("await", b_await as TFnBuiltin),
+            ("sleep", b_sleep as TFnBuiltin),
+            ("weak", b_weak as TFnBuiltin),
+            ("with", b_with as TFnBuiltin),
+            ("memoize", b_memoize as TFnBuiltin),
+            ("timed", b_timed as TFnBuiltin),
+            ("deprecated", b_deprecated as TFnBuiltin),
+            ("lazy", b_lazy as TFnBuiltin),
+            ("doc_of", b_doc_of as TFnBuiltin),
+            ("inspect", b_inspect as TFnBuiltin),
+            ("operators_of", b_operators_of as TFnBuiltin),
+            ("props_of", b_props_of as TFnBuiltin),
+            ("type_of", b_type_of as TFnBuiltin),
+            ("macro", b_macro as TFnBuiltin),
+            ("quote", b_quote as TFnBuiltin),
+            ("unquote", b_unquote as TFnBuiltin),
+            ("op", b_op as TFnBuiltin),
+            ("when", b_when as TFnBuiltin),
+            ("case", b_case as TFnBuiltin),
+            ("fallthrough", b_fallthrough as TFnBuiltin),
+            ("global", b_global as TFnBuiltin),
+            ("freeze", b_freeze as TFnBuiltin),
+            ("is_frozen", b_is_frozen as TFnBuiltin),
+            ("observe", b_observe as TFnBuiltin),
+            ("clone", b_clone as TFnBuiltin),
+            ("deep_clone", b_deep_clone as TFnBuiltin),
+            ("parse_number", b_parse_number as TFnBuiltin),
+            ("to_fixed", b_to_fixed as TFnBuiltin),
+            ("to_precision", b_to_precision as TFnBuiltin),
+            ("format_number", b_format_number as TFnBuiltin),
+            ("dedent", b_dedent as TFnBuiltin),
+            ("has_feature", b_has_feature as TFnBuiltin),
         ]
         .map(|(ident, function)| {
             (
                 Identifier::new(ident),
-                FruValue::Function(AnyFunction::BuiltinFunction(BuiltinFunction::new(function))),
+                FruValue::Function(AnyFunction::BuiltinFunction(
+                    BuiltinFunction::new(function).with_name(Identifier::new(ident)),
+                )),
             )
         }),
     )
 }
 
 fn b_print(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    let line = (args.args.iter())
+        .map(|(_, arg)| format!("{:?}", arg))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    if print_to_host(&line) {
+        return Ok(FruValue::Nah);
+    }
+
     for arg in args.args {
         print!("{:?} ", arg.1);
     }
@@ -35,13 +103,19 @@ fn b_print(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
 }
 
 fn b_input(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    require_capability(Capability::Stdin)?;
+
     if args.args.len() == 1 {
         print!("{:?}", args.args[0].1);
-        io::stdout().flush().unwrap();
+        io::stdout()
+            .flush()
+            .map_err(|err| FruError::new(format!("can't write prompt: {}", err)))?;
     }
 
     let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();
+    io::stdin()
+        .read_line(&mut input)
+        .map_err(|err| FruError::new(format!("can't read input: {}", err)))?;
     Ok(FruValue::String(input.trim().to_string()))
 }
 
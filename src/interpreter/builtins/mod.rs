@@ -1,2 +1,3 @@
 pub mod functions;
 pub mod operators;
+pub mod types;
@@ -3,17 +3,25 @@ use std::collections::HashMap;
 use crate::interpreter::{
     error::FruError,
     identifier::{id, OperatorIdentifier},
+    runtime::check_allocation,
     value::{fru_value::FruValue, operator::AnyOperator},
 };
 
+/// Values of a type declared by the program reach builtin operators when it is named like a builtin type
+pub fn unexpected_operand<T>(value: &FruValue) -> Result<T, FruError> {
+    FruError::new_res(format!(
+        "`{}` declared by the program has no builtin operators",
+        value.get_type_identifier()
+    ))
+}
+
 macro_rules! builtin_operator {
     ($Name:ident, $L:ident, $R:ident, $Res:ident, $OP:tt) => {
         fn $Name(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
-            if let (FruValue::$L(l), FruValue::$R(r)) = (left, right) {
-                return Ok(FruValue::$Res(l $OP r));
+            match (left, right) {
+                (FruValue::$L(l), FruValue::$R(r)) => Ok(FruValue::$Res(l $OP r)),
+                (FruValue::$L(_), other) | (other, _) => unexpected_operand(&other),
             }
-
-            unreachable!();
         }
     };
 }
@@ -92,33 +100,37 @@ builtin_operator!(num_plus_num, Number, Number, Number, +);
 builtin_operator!(num_minus_num, Number, Number, Number, -);
 builtin_operator!(num_mul_num, Number, Number, Number, *);
 fn num_div_num(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
-    if let (FruValue::Number(l), FruValue::Number(r)) = (left, right) {
-        if r == 0.0 {
-            return FruError::new_res("division by zero");
+    match (left, right) {
+        (FruValue::Number(l), FruValue::Number(r)) => {
+            if r == 0.0 {
+                return FruError::new_res("division by zero");
+            }
+            Ok(FruValue::Number(l / r))
         }
-        return Ok(FruValue::Number(l / r));
-    }
 
-    unreachable!();
+        (FruValue::Number(_), other) | (other, _) => unexpected_operand(&other),
+    }
 }
 
 fn num_mod_num(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
-    if let (FruValue::Number(l), FruValue::Number(r)) = (left, right) {
-        if r == 0.0 {
-            return FruError::new_res("division by zero");
+    match (left, right) {
+        (FruValue::Number(l), FruValue::Number(r)) => {
+            if r == 0.0 {
+                return FruError::new_res("division by zero");
+            }
+            Ok(FruValue::Number(l.rem_euclid(r)))
         }
-        return Ok(FruValue::Number(l.rem_euclid(r)));
-    }
 
-    unreachable!();
+        (FruValue::Number(_), other) | (other, _) => unexpected_operand(&other),
+    }
 }
 
 fn num_pow_num(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
-    if let (FruValue::Number(l), FruValue::Number(r)) = (left, right) {
-        return Ok(FruValue::Number(l.powf(r)));
-    }
+    match (left, right) {
+        (FruValue::Number(l), FruValue::Number(r)) => Ok(FruValue::Number(l.powf(r))),
 
-    unreachable!();
+        (FruValue::Number(_), other) | (other, _) => unexpected_operand(&other),
+    }
 }
 builtin_operator!(num_less_num, Number, Number, Bool, <);
 builtin_operator!(num_less_eq_num, Number, Number, Bool, <=);
@@ -140,23 +152,52 @@ builtin_operator!(string_eq_string, String, String, Bool, ==);
 builtin_operator!(string_not_eq_string, String, String, Bool, !=);
 
 fn string_concat(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
-    if let (FruValue::String(l), FruValue::String(r)) = (left, right) {
-        return Ok(FruValue::String(l + &*r));
-    }
+    match (left, right) {
+        (FruValue::String(l), FruValue::String(r)) => {
+            check_allocation(l.len() + r.len())?;
 
-    unreachable!();
+            Ok(FruValue::String(l + &*r))
+        }
+
+        (FruValue::String(_), other) | (other, _) => unexpected_operand(&other),
+    }
 }
 
 fn string_mul_num(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
-    if let (FruValue::String(l), FruValue::Number(r)) = (left, right) {
-        if r.fract() != 0.0 || r < 0.0 {
-            return FruError::new_res("String * number must be a positive integer");
+    match (left, right) {
+        (FruValue::String(l), FruValue::Number(r)) => {
+            if r.fract() != 0.0 || r < 0.0 {
+                return FruError::new_res("String * number must be a non-negative integer");
+            }
+
+            let too_long =
+                || FruError::new_res(format!("string of {} * {} bytes is too long", l.len(), r));
+
+            // `r as usize` saturates, so huge counts are reported instead of overflowing
+            let size = match l.len().checked_mul(r as usize) {
+                Some(size) if size <= isize::MAX as usize => size,
+                _ => return too_long(),
+            };
+
+            check_allocation(size)?;
+
+            // without a memory limit the allocator can still refuse, that is an error too
+            let mut result = String::new();
+            if result.try_reserve_exact(size).is_err() {
+                return too_long();
+            }
+
+            if !l.is_empty() {
+                for _ in 0..r as usize {
+                    result.push_str(&l);
+                }
+            }
+
+            Ok(FruValue::String(result))
         }
 
-        return Ok(FruValue::String(l.repeat(r as usize)));
+        (FruValue::String(_), other) | (other, _) => unexpected_operand(&other),
     }
-
-    unreachable!();
 }
 
 fn num_mul_string(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use macros::static_ident;
+
+use crate::interpreter::{identifier::Identifier, value::fru_value::FruValue};
+use crate::stdlib::{
+    ast::fru_ast::FruAstType,
+    channel::fru_channel::FruChannelType,
+    complex::fru_complex::FruComplexType,
+    crypto::fru_crypto::{FruHash, FruRandom},
+    decimal::fru_decimal::FruDecimalType,
+    events::fru_events::FruEventsType,
+    fs::fru_fs::FruFs,
+    primitive::fru_primitive::{
+        FruBoolType, FruFunctionType, FruNahType, FruNumberType, FruStringType, FruTypeType,
+    },
+    queue::fru_queue::{FruDequeType, FruHeapType},
+    regex::fru_regex::FruRegexType,
+    store::fru_store::FruStoreType,
+    string::{fru_char::FruCharType, fru_template::FruTemplateType},
+    sys::fru_sys::FruSys,
+    timer::fru_timer::FruTimerType,
+    tree::fru_tree::{FruTreeMapType, FruTreeSetType},
+    tuple::fru_tuple::FruTupleType,
+    units::fru_units::FruUnitType,
+    vector::fru_vector::{FruMat3Type, FruVecType},
+    worker::fru_worker::FruWorkerType,
+};
+
+#[cfg(feature = "compression")]
+use crate::stdlib::compression::fru_compression::{FruGzip, FruZip};
+#[cfg(feature = "locale")]
+use crate::stdlib::locale::fru_locale::FruLocale;
+#[cfg(feature = "sqlite")]
+use crate::stdlib::sqlite::fru_sqlite::FruSqliteType;
+
+pub fn builtin_types() -> HashMap<Identifier, FruValue> {
+    #[allow(unused_mut)]
+    let mut types = HashMap::from([
+        (static_ident!("Ast"), FruAstType::new_value()),
+        (static_ident!("Bool"), FruBoolType::new_value()),
+        (static_ident!("Channel"), FruChannelType::new_value()),
+        (static_ident!("Char"), FruCharType::new_value()),
+        (static_ident!("Complex"), FruComplexType::new_value()),
+        (static_ident!("Decimal"), FruDecimalType::new_value()),
+        (static_ident!("Deque"), FruDequeType::new_value()),
+        (static_ident!("Events"), FruEventsType::new_value()),
+        (static_ident!("Fs"), FruFs::new_value()),
+        (static_ident!("Function"), FruFunctionType::new_value()),
+        (static_ident!("Hash"), FruHash::new_value()),
+        (static_ident!("Heap"), FruHeapType::new_value()),
+        (static_ident!("Nah"), FruNahType::new_value()),
+        (static_ident!("Number"), FruNumberType::new_value()),
+        (static_ident!("Random"), FruRandom::new_value()),
+        (static_ident!("Regex"), FruRegexType::new_value()),
+        (static_ident!("Store"), FruStoreType::new_value()),
+        (static_ident!("String"), FruStringType::new_value()),
+        (static_ident!("Sys"), FruSys::new_value()),
+        (static_ident!("Template"), FruTemplateType::new_value()),
+        (static_ident!("Timer"), FruTimerType::new_value()),
+        (static_ident!("TreeMap"), FruTreeMapType::new_value()),
+        (static_ident!("TreeSet"), FruTreeSetType::new_value()),
+        (static_ident!("Tuple"), FruTupleType::new_value()),
+        (static_ident!("Type"), FruTypeType::new_value()),
+        (static_ident!("Unit"), FruUnitType::new_value()),
+        (static_ident!("Worker"), FruWorkerType::new_value()),
+        (static_ident!("Vec2"), FruVecType::<2>::new_value()),
+        (static_ident!("Vec3"), FruVecType::<3>::new_value()),
+        (static_ident!("Mat3"), FruMat3Type::new_value()),
+    ]);
+
+    #[cfg(feature = "compression")]
+    {
+        types.insert(static_ident!("Gzip"), FruGzip::new_value());
+        types.insert(static_ident!("Zip"), FruZip::new_value());
+    }
+
+    #[cfg(feature = "locale")]
+    types.insert(static_ident!("Locale"), FruLocale::new_value());
+
+    #[cfg(feature = "sqlite")]
+    types.insert(static_ident!("Sqlite"), FruSqliteType::new_value());
+
+    types
+}
@@ -1,4 +1,9 @@
-use crate::interpreter::{error::FruError, value::fru_value::FruValue};
+use crate::interpreter::{
+    error::FruError,
+    runtime::{truthy_conditions_enabled, warn, WarningKind},
+    value::fru_value::FruValue,
+};
+use crate::stdlib::primitive::fru_primitive::to_bool;
 
 #[derive(Debug)]
 pub enum Control {
@@ -20,6 +25,32 @@ impl From<FruError> for Control {
     }
 }
 
+/// Conditions of `if` and `while` must be `Bool`, other values are never converted implicitly
+pub fn check_condition(value: FruValue, context: &str) -> Result<bool, Control> {
+    match value {
+        FruValue::Bool(b) => Ok(b),
+
+        other if truthy_conditions_enabled() => {
+            warn(
+                WarningKind::ImplicitConversion,
+                format!(
+                    "`{}` in {} condition is converted to `Bool`",
+                    other.get_type_identifier(),
+                    context
+                ),
+            );
+
+            Ok(to_bool(other)?)
+        }
+
+        other => Control::new_err(format!(
+            "Expected `Bool` in {} condition, got `{}`, convert it with `Bool(x)` or compare it explicitly",
+            context,
+            other.get_type_identifier()
+        )),
+    }
+}
+
 pub fn returned(x: Result<FruValue, Control>) -> Result<FruValue, FruError> {
     match x {
         Ok(x) => Ok(x),
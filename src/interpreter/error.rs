@@ -1,27 +1,224 @@
+use std::{
+    fmt::{self, Write},
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+
 use thiserror::Error;
 
-use crate::interpreter::value::function::ArgumentError;
+use crate::interpreter::value::function::{ArgumentError, SourceLocation};
 
 #[derive(Debug, Error)]
-#[error("{message}")]
+#[error("{message}{}", .hint.as_ref().map_or(String::new(), |hint| format!(", {}", hint)))]
 pub struct FruError {
     message: String,
+    kind: FruErrorKind,
+    // likely fix, like `did you mean `counter`?`
+    hint: Option<String>,
+    // innermost statement where the error happened
+    span: Option<Box<Span>>,
+    trace: Vec<TraceFrame>,
+}
+
+/// Start and end of a statement, `file` is `None` for the file that was executed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub file: Option<PathBuf>,
+    pub start: SourceLocation,
+    pub end: SourceLocation,
+}
+
+/// Function or module the error went through, `call` is where it was called from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceFrame {
+    pub name: String,
+    pub call: Option<Span>,
+}
+
+/// Lets embedders tell apart errors that they may want to handle specially
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FruErrorKind {
+    Runtime,
+    MemoryLimitExceeded,
+    StackLimitExceeded,
+    Cancelled,
+    OutOfFuel,
 }
 
 impl FruError {
     pub fn new(message: String) -> FruError {
-        FruError { message }
+        FruError::new_with_kind(FruErrorKind::Runtime, message)
+    }
+
+    pub fn new_with_kind(kind: FruErrorKind, message: String) -> FruError {
+        FruError {
+            message,
+            kind,
+            hint: None,
+            span: None,
+            trace: Vec::new(),
+        }
     }
 
     pub fn new_res<T>(message: impl Into<String>) -> Result<T, FruError> {
-        Err(FruError {
-            message: message.into(),
-        })
+        Err(FruError::new(message.into()))
+    }
+
+    pub fn with_hint(mut self, hint: impl Into<String>) -> FruError {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    pub fn kind(&self) -> FruErrorKind {
+        self.kind
+    }
+
+    pub fn hint(&self) -> Option<&str> {
+        self.hint.as_deref()
+    }
+
+    pub fn span(&self) -> Option<&Span> {
+        self.span.as_deref()
+    }
+
+    /// Innermost frame first
+    pub fn trace(&self) -> &[TraceFrame] {
+        &self.trace
+    }
+
+    // the first statement to see the error is where it happened,
+    // the next one after leaving a function is the call site
+    pub(crate) fn at(mut self, start: SourceLocation, end: SourceLocation) -> FruError {
+        let span = Some(Span {
+            file: None,
+            start,
+            end,
+        });
+
+        if self.span.is_none() {
+            self.span = span.map(Box::new);
+        } else if let Some(frame) = self.trace.last_mut().filter(|x| x.call.is_none()) {
+            frame.call = span;
+        }
+
+        self
     }
+
+    /// Records that the error left a named part of the program, like `getter of `Foo.bar``,
+    /// the next statement it goes through is where that part was used from
+    pub fn in_frame(mut self, name: String) -> FruError {
+        self.trace.push(TraceFrame { name, call: None });
+        self
+    }
+
+    /// Prepends where the error happened to the message, like `in function `add``,
+    /// unlike making a new error it keeps the kind, the hint and the trace
+    pub fn in_context(mut self, context: impl fmt::Display) -> FruError {
+        self.message = format!("{}: {}", context, self.message);
+        self
+    }
+
+    // spans collected so far belong to an imported file
+    pub(crate) fn in_file(mut self, file: &Path) -> FruError {
+        let spans = (self.span.iter_mut().map(|x| &mut **x))
+            .chain(self.trace.iter_mut().flat_map(|x| &mut x.call));
+
+        for span in spans.filter(|x| x.file.is_none()) {
+            span.file = Some(file.to_path_buf());
+        }
+
+        self.in_frame(format!("module `{}`", file.display()))
+    }
+
+    /// Message with the source line under the error and the stack trace,
+    /// `file` is the executed file, others are read as needed
+    pub fn render(&self, file: &Path) -> String {
+        let mut out = format!("error: {}\n", self.message);
+
+        if let Some(span) = &self.span {
+            render_span(&mut out, span, file);
+        }
+
+        if let Some(hint) = &self.hint {
+            let _ = writeln!(out, " = help: {}", hint);
+        }
+
+        for frame in &self.trace {
+            match &frame.call {
+                Some(call) => {
+                    let path = call.file.as_deref().unwrap_or(file);
+                    let _ = writeln!(
+                        out,
+                        "  in {}, called at {}:{}:{}",
+                        frame.name,
+                        path.display(),
+                        call.start.line,
+                        call.start.column
+                    );
+                }
+
+                None => {
+                    let _ = writeln!(out, "  in {}", frame.name);
+                }
+            }
+        }
+
+        out
+    }
+}
+
+fn render_span(out: &mut String, span: &Span, file: &Path) {
+    let path = span.file.as_deref().unwrap_or(file);
+
+    let _ = writeln!(
+        out,
+        " --> {}:{}:{}",
+        path.display(),
+        span.start.line,
+        span.start.column
+    );
+
+    let line = match read_to_string(path) {
+        Ok(source) => match source.lines().nth(span.start.line - 1) {
+            Some(line) => line.to_string(),
+            None => return,
+        },
+        Err(_) => return,
+    };
+
+    // multiline statements are underlined up to the end of their first line
+    let end = if span.end.line == span.start.line {
+        span.end.column
+    } else {
+        line.trim_end().len() + 1
+    };
+
+    let number = span.start.line.to_string();
+    let padding = " ".repeat(number.len());
+
+    let prefix: String = line
+        .bytes()
+        .take(span.start.column - 1)
+        .map(|x| if x == b'\t' { '\t' } else { ' ' })
+        .collect();
+
+    let underline = line
+        .get(span.start.column - 1..end.saturating_sub(1).max(span.start.column))
+        .map_or(1, |x| x.chars().count());
+
+    let _ = writeln!(out, "{} |", padding);
+    let _ = writeln!(out, "{} | {}", number, line);
+    let _ = writeln!(
+        out,
+        "{} | {}{}",
+        padding,
+        prefix,
+        "^".repeat(underline.max(1))
+    );
 }
 
 impl From<ArgumentError> for FruError {
     fn from(err: ArgumentError) -> Self {
-        FruError::new(format!("{:?}", err))
+        FruError::new(err.to_string())
     }
 }
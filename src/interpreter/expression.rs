@@ -1,13 +1,17 @@
 use std::{path::PathBuf, rc::Rc};
 
 use crate::interpreter::{
-    control::Control,
+    control::{check_condition, Control},
     identifier::{Identifier, OperatorIdentifier},
     runner,
+    runtime::check_stack,
     scope::Scope,
     statement::FruStatement,
     value::fru_value::FruValue,
-    value::function::{ArgumentList, EvaluatedArgumentList, FormalParameters, FruFunction},
+    value::function::{
+        ArgumentList, EvaluatedArgumentList, FormalParameters, FruFunction, SourceLocation,
+    },
+    value::operator::structural_operator,
 };
 use crate::stdlib::scope::fru_scope::{extract_scope_from_value, FruScope};
 
@@ -23,6 +27,9 @@ pub enum FruExpression {
     Function {
         args: FormalParameters,
         body: Rc<FruStatement>,
+        name: Option<Identifier>,
+        location: SourceLocation,
+        doc: Option<Rc<str>>,
     },
     Block {
         body: Vec<FruStatement>,
@@ -78,6 +85,8 @@ fn eval_args(args: &ArgumentList, scope: Rc<Scope>) -> Result<EvaluatedArgumentL
 
 impl FruExpression {
     pub fn evaluate(&self, mut scope: Rc<Scope>) -> Result<FruValue, Control> {
+        check_stack()?;
+
         match self {
             FruExpression::Literal { value } => Ok(value.clone()),
 
@@ -85,15 +94,25 @@ impl FruExpression {
 
             FruExpression::ScopeAccessor => Ok(FruScope::new_value(scope)),
 
-            FruExpression::Function { args, body } => Ok(FruFunction {
+            FruExpression::Function {
+                args,
+                body,
+                name,
+                location,
+                doc,
+            } => Ok(FruFunction {
                 parameters: args.clone(),
                 body: body.clone(),
                 scope: scope.clone(),
+                name: *name,
+                location: Some(*location),
+                doc: doc.clone(),
+                contract: None,
             }
             .into()),
 
             FruExpression::Block { body, expr } => {
-                scope = Scope::new_with_parent(scope.clone());
+                scope = Scope::new_block(scope.clone(), body);
 
                 for statement in body {
                     statement.execute(scope.clone())?;
@@ -121,6 +140,21 @@ impl FruExpression {
             }
 
             FruExpression::Call { what, args } => {
+                if let FruExpression::PropAccess { what, ident } = what.as_ref() {
+                    let what = what.evaluate(scope.clone())?;
+
+                    if let FruValue::NativeObject(object) = what {
+                        let args = eval_args(args, scope)?;
+
+                        return Ok(object.call_method(*ident, args)?);
+                    }
+
+                    let callee = what.get_prop(*ident)?;
+                    let args = eval_args(args, scope)?;
+
+                    return Ok(callee.call(args)?);
+                }
+
                 let callee = what.evaluate(scope.clone())?;
 
                 let args = eval_args(args, scope)?;
@@ -160,30 +194,30 @@ impl FruExpression {
                 let type_left = left_val.get_type_identifier();
                 let type_right = right_val.get_type_identifier();
 
-                let op = scope
-                    .get_operator(OperatorIdentifier::new(*operator, type_left, type_right))?;
+                let op =
+                    scope.get_operator(OperatorIdentifier::new(*operator, type_left, type_right));
+
+                let result = match op {
+                    Ok(op) => op.operate(left_val, right_val),
+                    Err(err) => {
+                        structural_operator(*operator, &left_val, &right_val).unwrap_or(Err(err))
+                    }
+                };
 
-                Ok(op.operate(left_val, right_val)?)
+                Ok(result?)
             }
 
             FruExpression::If {
                 condition,
                 then_body,
                 else_body,
-            } => match condition.evaluate(scope.clone())? {
-                FruValue::Bool(b) => {
-                    if b {
-                        then_body.evaluate(scope.clone())
-                    } else {
-                        else_body.evaluate(scope.clone())
-                    }
+            } => {
+                if check_condition(condition.evaluate(scope.clone())?, "if")? {
+                    then_body.evaluate(scope.clone())
+                } else {
+                    else_body.evaluate(scope.clone())
                 }
-
-                unexpected => Control::new_err(format!(
-                    "Expected `Bool` in if condition, got `{}`",
-                    unexpected.get_type_identifier()
-                )),
-            },
+            }
 
             FruExpression::Import { path } => {
                 let path = path.evaluate(scope.clone())?;
@@ -201,7 +235,7 @@ impl FruExpression {
 
                 let path = PathBuf::from(path);
 
-                let result_scope = runner::execute_file(&path)?;
+                let result_scope = runner::import_file(&path)?;
 
                 Ok(FruScope::new_value(result_scope))
             }
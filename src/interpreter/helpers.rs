@@ -25,3 +25,58 @@ where
         Rc::new(self)
     }
 }
+
+/// Note of a `@deprecated("use bar")` line in a doc comment, empty when it is just `@deprecated`
+pub fn deprecation_note(doc: Option<&str>) -> Option<String> {
+    let line = doc?.lines().find_map(|line| line.trim().strip_prefix("@deprecated"))?;
+
+    let note = match line.trim().strip_prefix('(').and_then(|x| x.strip_suffix(')')) {
+        Some(note) => note.trim(),
+        None => line.trim(),
+    };
+
+    Some(snailquote::unescape(note).unwrap_or_else(|_| note.to_string()))
+}
+
+/// Candidate that is a likely typo of `name`, names shorter than 3 characters get no suggestions
+pub fn closest_name<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let max_distance = name.chars().count() / 3;
+
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+// edits that turn one name into the other, swapping two neighbouring chars is a single edit
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    d[0] = (0..=b.len()).collect();
+
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+
+            d[i][j] = (d[i - 1][j] + 1).min(d[i][j - 1] + 1).min(d[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    d[a.len()][b.len()]
+}
@@ -1,14 +1,77 @@
 use std::{
+    cell::RefCell,
     collections::HashMap,
     fmt::{Debug, Display},
     hash::{DefaultHasher, Hash, Hasher},
+    mem,
+    rc::{Rc, Weak},
     sync::Mutex,
 };
 
 use once_cell::sync::Lazy;
 
-// this map is used for Identifier visualization
-static BACKWARDS_MAP: Lazy<Mutex<HashMap<u64, String>>> = Lazy::new(Default::default);
+use crate::interpreter::runtime::Runtime;
+
+type Names = HashMap<u64, Box<str>>;
+
+// names interned outside of any interpreter, by `static_ident!` or the embedder, never trimmed
+static PERMANENT: Lazy<Mutex<Names>> = Lazy::new(Default::default);
+
+thread_local! {
+    // tables of the interpreters alive on this thread, for values displayed outside of them
+    static LIVE: RefCell<Vec<Weak<RefCell<Names>>>> = const { RefCell::new(Vec::new()) };
+
+    // names of interpreters dropped on this thread, values kept by the host still show them until `trim`
+    static DROPPED: RefCell<Names> = RefCell::new(HashMap::new());
+}
+
+/// Names an interpreter has interned, they leave with it instead of growing a table of the process
+pub(crate) struct NameTable {
+    names: Rc<RefCell<Names>>,
+}
+
+impl NameTable {
+    pub(crate) fn new() -> Self {
+        let names = Rc::new(RefCell::new(HashMap::new()));
+        let _ = LIVE.try_with(|live| live.borrow_mut().push(Rc::downgrade(&names)));
+
+        Self { names }
+    }
+
+    fn intern(&self, hashed_ident: u64, ident: &str) {
+        self.names.borrow_mut().entry(hashed_ident).or_insert_with(|| ident.into());
+    }
+
+    fn find(&self, hashed_ident: u64) -> Option<String> {
+        self.names.borrow().get(&hashed_ident).map(|x| x.to_string())
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.names.borrow().len()
+    }
+}
+
+impl Drop for NameTable {
+    fn drop(&mut self) {
+        let names = mem::take(&mut *self.names.borrow_mut());
+
+        let _ = LIVE
+            .try_with(|live| live.borrow_mut().retain(|x| x.as_ptr() != Rc::as_ptr(&self.names)));
+        let _ = DROPPED.try_with(|dropped| dropped.borrow_mut().extend(names));
+    }
+}
+
+/// Sizes of the tables of identifier names seen from the current thread
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InternerStats {
+    // entries of all tables, a name used by several interpreters is counted in each of them
+    pub names: usize,
+    pub permanent: usize,
+    // names of interpreters dropped on this thread, `trim` removes them
+    pub unused: usize,
+    pub bytes: usize,
+    pub capacity: usize,
+}
 
 #[derive(Hash, PartialEq, Eq, Copy, Clone, PartialOrd, Ord)]
 pub struct Identifier {
@@ -29,24 +92,100 @@ impl Identifier {
         ident.hash(&mut hasher);
         let hashed_ident = hasher.finish();
 
-        BACKWARDS_MAP
-            .lock()
-            .unwrap()
-            .entry(hashed_ident)
-            .or_insert_with(|| ident.to_string());
+        // names that scripts make up at runtime, like props found by reflection, belong to the interpreter
+        match Runtime::current() {
+            Some(runtime) => runtime.names().intern(hashed_ident, ident),
+            None => {
+                (PERMANENT.lock().unwrap()).entry(hashed_ident).or_insert_with(|| ident.into());
+            }
+        }
 
         Self { hashed_ident }
     }
 
+    pub fn interner_stats() -> InternerStats {
+        let mut stats = InternerStats {
+            names: 0,
+            permanent: 0,
+            unused: 0,
+            bytes: 0,
+            capacity: 0,
+        };
+
+        let mut count = |names: &Names| {
+            stats.names += names.len();
+            stats.bytes += names.values().map(|x| x.len()).sum::<usize>();
+            stats.capacity += names.capacity();
+            names.len()
+        };
+
+        let permanent = count(&PERMANENT.lock().unwrap());
+
+        LIVE.with(|live| {
+            for names in live.borrow().iter().filter_map(Weak::upgrade) {
+                count(&names.borrow());
+            }
+        });
+
+        let unused = DROPPED.with(|dropped| count(&dropped.borrow()));
+
+        InternerStats {
+            permanent,
+            unused,
+            ..stats
+        }
+    }
+
+    /// Forgets names of interpreters dropped on this thread and releases spare capacity,
+    /// returns how many names were removed. Identifiers with such names that are still kept
+    /// by the embedder are displayed as `<trimmed>`, unless the embedder has interned them
+    /// with `Identifier::new` outside of interpreters
+    pub fn trim() -> usize {
+        PERMANENT.lock().unwrap().shrink_to_fit();
+
+        DROPPED.with(|dropped| mem::take(&mut *dropped.borrow_mut()).len())
+    }
+
     pub const fn new_unchecked(hashed_ident: u64) -> Self {
         Self { hashed_ident }
     }
+
+    // the current interpreter first, it knows almost every name it displays
+    fn name(&self) -> Option<String> {
+        let find = |names: &Names| names.get(&self.hashed_ident).map(|x| x.to_string());
+
+        Runtime::current()
+            .and_then(|runtime| runtime.names().find(self.hashed_ident))
+            .or_else(|| find(&PERMANENT.lock().unwrap()))
+            .or_else(|| {
+                LIVE.try_with(|live| {
+                    (live.borrow().iter())
+                        .filter_map(Weak::upgrade)
+                        .find_map(|names| find(&names.borrow()))
+                })
+                .ok()
+                .flatten()
+            })
+            .or_else(|| DROPPED.try_with(|dropped| find(&dropped.borrow())).ok().flatten())
+    }
 }
 
 impl OperatorIdentifier {
     pub fn new(op: Identifier, left: Identifier, right: Identifier) -> Self {
         Self { op, left, right }
     }
+
+    pub fn op(&self) -> Identifier {
+        self.op
+    }
+
+    pub fn left(&self) -> Identifier {
+        self.left
+    }
+
+    pub fn right(&self) -> Identifier {
+        self.right
+    }
 }
 
 impl Debug for Identifier {
@@ -57,17 +196,22 @@ impl Debug for Identifier {
 
 impl Display for Identifier {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            BACKWARDS_MAP.lock().unwrap().get(&self.hashed_ident).unwrap()
-        )
+        match self.name() {
+            Some(name) => write!(f, "{}", name),
+            None => write!(f, "<trimmed>"),
+        }
     }
 }
 
 impl Debug for OperatorIdentifier {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Operator({} {} {})", self.left, self.op, self.right)
+        write!(f, "Operator({})", self)
+    }
+}
+
+impl Display for OperatorIdentifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.left, self.op, self.right)
     }
 }
 
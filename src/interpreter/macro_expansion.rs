@@ -0,0 +1,675 @@
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use macros::static_ident;
+
+use crate::interpreter::{
+    control::returned,
+    error::FruError,
+    expression::FruExpression,
+    identifier::Identifier,
+    runtime::Runtime,
+    scope::Scope,
+    statement::FruStatement,
+    transformer::{walk_expression, walk_statement, Transformer},
+    value::fru_value::FruValue,
+    value::function::{
+        ArgumentList, BuiltinFunction, EvaluatedArgumentList, FormalParameters, SourceLocation,
+    },
+};
+use crate::stdlib::{
+    ast::fru_ast::{value_to_expression, FruAst},
+    regex::fru_regex::FruRegex,
+};
+
+const MAX_EXPANSION_DEPTH: usize = 100;
+
+// makes names declared in quoted code unique, `$` can't appear in identifiers written in source.
+// Interpreters count their own expansions, this one is for expanding code outside of any
+static HYGIENE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+// calls of these are rewritten here, so the program can't declare variables with these names
+const FORMS: [&str; 8] = [
+    "macro",
+    "quote",
+    "unquote",
+    "op",
+    "when",
+    "case",
+    "fallthrough",
+    "Regex",
+];
+
+/// Replaces macro declarations, macro calls, `quote`, `op`, `when` and literal `Regex` in the AST,
+/// macros are declared at the top level as `let name = macro(fn(...) {...});`.
+/// Names of these forms and of the macros can't be declared or assigned by the program
+pub fn expand_macros(ast: &FruStatement) -> Result<FruStatement, FruError> {
+    let mut expander = Expander {
+        macros: HashMap::new(),
+        forms: FORMS.into_iter().map(Identifier::new).collect(),
+        macro_names: HashSet::new(),
+        depth: 0,
+    };
+
+    match ast.clone() {
+        FruStatement::SourceCode { body } => {
+            let macros: Vec<_> = body.iter().filter_map(macro_declaration).collect();
+
+            for (ident, _) in &macros {
+                expander.check_name(*ident)?;
+            }
+
+            expander.macro_names.extend(macros.into_iter().map(|(ident, _)| ident));
+
+            let mut new_body = Vec::new();
+
+            for statement in body {
+                if !expander.declare_macro(&statement)? {
+                    new_body.push(expander.transform_statement(statement)?);
+                }
+            }
+
+            Ok(FruStatement::SourceCode { body: new_body })
+        }
+
+        other => expander.transform_statement(other),
+    }
+}
+
+struct Expander {
+    // macros declared above the code being expanded
+    macros: HashMap<Identifier, FruValue>,
+    forms: HashSet<Identifier>,
+    // every macro of the file, including ones declared below
+    macro_names: HashSet<Identifier>,
+    depth: usize,
+}
+
+// `name` and arguments of `let name = macro(...);`
+fn macro_declaration(statement: &FruStatement) -> Option<(Identifier, &ArgumentList)> {
+    let statement = match statement {
+        FruStatement::Located { statement, .. } => statement,
+        other => other,
+    };
+
+    let FruStatement::Let { ident, value } = statement else {
+        return None;
+    };
+
+    match value.as_ref() {
+        FruExpression::Call { what, args } => match what.as_ref() {
+            FruExpression::Variable { ident: what } if *what == static_ident!("macro") => {
+                Some((*ident, args))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+impl Expander {
+    fn check_name(&self, ident: Identifier) -> Result<(), FruError> {
+        if self.forms.contains(&ident) || self.macro_names.contains(&ident) {
+            return FruError::new_res(format!(
+                "`{}` can't be used as a name, its calls are rewritten before the program runs",
+                ident
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn check_parameters(&self, parameters: &FormalParameters) -> Result<(), FruError> {
+        parameters.args.iter().try_for_each(|(ident, _)| self.check_name(*ident))
+    }
+
+    // fields and properties are variables in the methods of the type
+    fn check_type(&self, statement: &FruStatement) -> Result<(), FruError> {
+        let FruStatement::Type {
+            ident,
+            fields,
+            static_fields,
+            properties,
+            static_properties,
+            methods,
+            ..
+        } = statement
+        else {
+            return Ok(());
+        };
+
+        self.check_name(*ident)?;
+
+        for property in properties.values().chain(static_properties.values()) {
+            self.check_name(property.ident)?;
+
+            if let Some((ident, _)) = &property.setter {
+                self.check_name(*ident)?;
+            }
+        }
+
+        (fields.iter().map(|field| field.ident))
+            .chain(static_fields.iter().map(|field| field.ident))
+            .try_for_each(|ident| self.check_name(ident))?;
+
+        methods.iter().try_for_each(|method| self.check_parameters(&method.parameters))
+    }
+
+    fn declare_macro(&mut self, statement: &FruStatement) -> Result<bool, FruError> {
+        let Some((ident, args)) = macro_declaration(statement) else {
+            return Ok(false);
+        };
+
+        let [(None, function)] = args.args.as_slice() else {
+            return FruError::new_res(format!("macro `{}` expects 1 function", ident));
+        };
+
+        let function = self.transform_expression(function.clone())?;
+        let function = returned(function.evaluate(Scope::new_global()))?;
+
+        if !matches!(function, FruValue::Function(_)) {
+            return FruError::new_res(format!(
+                "macro `{}` expects a function, got `{}`",
+                ident,
+                function.get_type_identifier()
+            ));
+        }
+
+        self.macros.insert(ident, function);
+
+        Ok(true)
+    }
+
+    fn expand_call(
+        &mut self,
+        ident: Identifier,
+        args: ArgumentList,
+    ) -> Result<FruExpression, FruError> {
+        if self.depth >= MAX_EXPANSION_DEPTH {
+            return FruError::new_res(format!("macro expansion of `{}` is too deep", ident));
+        }
+
+        let args = EvaluatedArgumentList {
+            args: args
+                .args
+                .into_iter()
+                .map(|(name, value)| (name, FruAst::new_value(value)))
+                .collect(),
+        };
+
+        let result = self.macros[&ident].call(args)?;
+
+        let expression = value_to_expression(result);
+
+        self.depth += 1;
+        let expression = self.transform_expression(expression);
+        self.depth -= 1;
+
+        expression
+    }
+
+    fn expand_quote(&mut self, args: ArgumentList) -> Result<FruExpression, FruError> {
+        let template = match <[_; 1]>::try_from(args.args) {
+            Ok([(None, template)]) => template,
+            _ => return FruError::new_res("`quote` expects 1 expression"),
+        };
+
+        let mut unquotes = Unquotes { found: Vec::new() };
+        let template = Rc::new(unquotes.transform_expression(template)?);
+
+        let args = unquotes
+            .found
+            .into_iter()
+            .map(|x| Ok((None, self.transform_expression(x)?)))
+            .collect::<Result<_, FruError>>()?;
+
+        let build = BuiltinFunction::new_closure(move |args| {
+            let template = Hygiene::rename((*template).clone())?;
+
+            let mut splice = Splice {
+                values: args.args.into_iter().map(|(_, value)| value).collect(),
+            };
+
+            Ok(FruAst::new_value(splice.transform_expression(template)?))
+        });
+
+        Ok(FruExpression::Call {
+            what: Box::new(FruExpression::Literal {
+                value: build.into(),
+            }),
+            args: ArgumentList { args },
+        })
+    }
+}
+
+impl Transformer for Expander {
+    fn transform_statement(&mut self, statement: FruStatement) -> Result<FruStatement, FruError> {
+        match statement {
+            FruStatement::Located {
+                location,
+                end,
+                statement,
+            } => Ok(FruStatement::Located {
+                location,
+                end,
+                statement: Box::new(
+                    self.transform_statement(*statement).map_err(|err| err.at(location, end))?,
+                ),
+            }),
+
+            FruStatement::Let { ref ident, .. }
+            | FruStatement::Set { ref ident, .. }
+            | FruStatement::SetProp { ref ident, .. } => {
+                self.check_name(*ident)?;
+                walk_statement(self, statement)
+            }
+
+            FruStatement::Operator {
+                left_ident,
+                right_ident,
+                ..
+            } => {
+                self.check_name(left_ident)?;
+                self.check_name(right_ident)?;
+                walk_statement(self, statement)
+            }
+
+            FruStatement::Type { .. } => {
+                self.check_type(&statement)?;
+                walk_statement(self, statement)
+            }
+
+            other => walk_statement(self, other),
+        }
+    }
+
+    fn transform_expression(
+        &mut self,
+        expression: FruExpression,
+    ) -> Result<FruExpression, FruError> {
+        match expression {
+            FruExpression::Call { what, args } => {
+                let form = match what.as_ref() {
+                    FruExpression::Variable { ident } => Some(*ident),
+                    _ => None,
+                };
+
+                match form {
+                    Some(ident) if self.macros.contains_key(&ident) => {
+                        self.expand_call(ident, args)
+                    }
+
+                    Some(ident) if self.macro_names.contains(&ident) => FruError::new_res(format!(
+                        "macro `{}` is used above its declaration",
+                        ident
+                    )),
+
+                    Some(ident) if ident == static_ident!("quote") => self.expand_quote(args),
+
+                    Some(ident) if ident == static_ident!("op") => operator_section(&args),
+
+                    Some(ident) if ident == static_ident!("when") => {
+                        let expression = expand_when(args)?;
+                        self.transform_expression(expression)
+                    }
+
+                    Some(ident) if ident == static_ident!("Regex") => match regex_literal(&args) {
+                        Some(regex) => regex,
+                        None => walk_expression(self, FruExpression::Call { what, args }),
+                    },
+
+                    _ => walk_expression(self, FruExpression::Call { what, args }),
+                }
+            }
+
+            FruExpression::Function { ref args, .. } => {
+                self.check_parameters(args)?;
+                walk_expression(self, expression)
+            }
+
+            other => walk_expression(self, other),
+        }
+    }
+}
+
+// `Regex("...")` with literal arguments is compiled once, before the program runs,
+// so a bad pattern is reported even if the code with it is never reached
+fn regex_literal(args: &ArgumentList) -> Option<Result<FruExpression, FruError>> {
+    let literals = args
+        .args
+        .iter()
+        .map(|arg| match arg {
+            (
+                None,
+                FruExpression::Literal {
+                    value: FruValue::String(s),
+                },
+            ) => Some(s.as_str()),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let regex = match literals.as_slice() {
+        [pattern] => FruRegex::compile(pattern, ""),
+        [pattern, flags] => FruRegex::compile(pattern, flags),
+        _ => return None,
+    };
+
+    Some(regex.map(|value| FruExpression::Literal { value }))
+}
+
+// `when(x, case(1, 2, a), b)` is `{ let w = x; if w == 1 || w == 2 { a } else { b } }`,
+// arms are checked in order and only the chosen result is evaluated.
+// `case(1, fallthrough(a))` evaluates `a` and then the result of the next arm, or the default
+fn expand_when(args: ArgumentList) -> Result<FruExpression, FruError> {
+    let subject_ident = Identifier::new("$when");
+    let subject = || FruExpression::Variable {
+        ident: subject_ident,
+    };
+
+    let mut args = args.args.into_iter();
+
+    let Some((None, value)) = args.next() else {
+        return FruError::new_res("`when` expects a value and `case(...)` arms");
+    };
+
+    let mut arms = Vec::new();
+    let mut default = FruExpression::Literal {
+        value: FruValue::Nah,
+    };
+
+    while let Some((ident, arm)) = args.next() {
+        match (ident, arm) {
+            (
+                None,
+                FruExpression::Call {
+                    what,
+                    args: case_args,
+                },
+            ) if matches!(*what, FruExpression::Variable { ident } if ident == static_ident!("case")) =>
+            {
+                let mut case_args: Vec<_> = case_args.args.into_iter().collect();
+
+                if case_args.len() < 2 || case_args.iter().any(|(ident, _)| ident.is_some()) {
+                    return FruError::new_res(
+                        "`case` expects values to compare with and a result, without names",
+                    );
+                }
+
+                let (_, result) = case_args.pop().expect("checked above");
+                let result = case_result(result)?;
+
+                let condition = case_args
+                    .into_iter()
+                    .map(|(_, value)| FruExpression::Binary {
+                        operator: Identifier::new("=="),
+                        left: Box::new(subject()),
+                        right: Box::new(value),
+                    })
+                    .reduce(|left, right| FruExpression::Binary {
+                        operator: Identifier::new("||"),
+                        left: Box::new(left),
+                        right: Box::new(right),
+                    })
+                    .expect("checked above");
+
+                arms.push((condition, result));
+            }
+
+            (None, other) if args.len() == 0 => default = other,
+
+            _ => {
+                return FruError::new_res(
+                    "arms of `when` must be `case(value, ..., result)`, only the last one can be a default result",
+                )
+            }
+        }
+    }
+
+    let mut next_result = default.clone();
+    let mut chain = default;
+
+    for (condition, result) in arms.into_iter().rev() {
+        let then_body = match result {
+            CaseResult::Value(result) => result,
+            CaseResult::Fallthrough(first) => FruExpression::Block {
+                body: vec![FruStatement::Expression {
+                    value: Box::new(first),
+                }],
+                expr: Box::new(next_result),
+            },
+        };
+
+        next_result = then_body.clone();
+
+        chain = FruExpression::If {
+            condition: Box::new(condition),
+            then_body: Box::new(then_body),
+            else_body: Box::new(chain),
+        };
+    }
+
+    Ok(FruExpression::Block {
+        body: vec![FruStatement::Let {
+            ident: subject_ident,
+            value: Box::new(value),
+        }],
+        expr: Box::new(chain),
+    })
+}
+
+enum CaseResult {
+    Value(FruExpression),
+    // `fallthrough(a)`
+    Fallthrough(FruExpression),
+}
+
+fn case_result(result: FruExpression) -> Result<CaseResult, FruError> {
+    match result {
+        FruExpression::Call { what, args } if matches!(*what, FruExpression::Variable { ident } if ident == static_ident!("fallthrough")) => {
+            match <[_; 1]>::try_from(args.args) {
+                Ok([(None, first)]) => Ok(CaseResult::Fallthrough(first)),
+                _ => FruError::new_res("`fallthrough` expects 1 expression"),
+            }
+        }
+
+        other => Ok(CaseResult::Value(other)),
+    }
+}
+
+// `op("+")` is `fn(a, b) { a + b }`, so operators are looked up where the section is written
+fn operator_section(args: &ArgumentList) -> Result<FruExpression, FruError> {
+    let [(
+        None,
+        FruExpression::Literal {
+            value: FruValue::String(operator),
+        },
+    )] = args.args.as_slice()
+    else {
+        return FruError::new_res("`op` expects an operator as a string literal, like `op(\"+\")`");
+    };
+
+    // the characters operators can be written with, `//` starts a comment
+    let is_operator = !operator.is_empty()
+        && !operator.contains("//")
+        && operator.chars().all(|c| "+-*/%<>=!&|^?".contains(c));
+
+    if !is_operator {
+        return FruError::new_res(format!("`op` expects an operator, got \"{}\"", operator));
+    }
+
+    let (left, right) = (Identifier::new("left"), Identifier::new("right"));
+
+    Ok(FruExpression::Function {
+        args: FormalParameters {
+            args: vec![(left, None), (right, None)],
+        },
+        body: Rc::new(FruStatement::Return {
+            value: Some(Box::new(FruExpression::Binary {
+                operator: Identifier::new(operator),
+                left: Box::new(FruExpression::Variable { ident: left }),
+                right: Box::new(FruExpression::Variable { ident: right }),
+            })),
+        }),
+        name: Some(Identifier::new(&format!("({})", operator))),
+        location: SourceLocation { line: 0, column: 0 },
+        doc: None,
+    })
+}
+
+fn unquote_placeholder(k: usize) -> Identifier {
+    Identifier::new(&format!("$unquote{}", k))
+}
+
+// replaces `unquote(x)` with placeholders, nested `quote` is left for its own expansion
+struct Unquotes {
+    found: Vec<FruExpression>,
+}
+
+impl Transformer for Unquotes {
+    fn transform_expression(
+        &mut self,
+        expression: FruExpression,
+    ) -> Result<FruExpression, FruError> {
+        if let FruExpression::Call { what, args } = &expression {
+            if let FruExpression::Variable { ident } = what.as_ref() {
+                if *ident == static_ident!("quote") {
+                    return Ok(expression);
+                }
+
+                if *ident == static_ident!("unquote") {
+                    let [(None, value)] = args.args.as_slice() else {
+                        return FruError::new_res("`unquote` expects 1 expression");
+                    };
+
+                    self.found.push(value.clone());
+
+                    return Ok(FruExpression::Variable {
+                        ident: unquote_placeholder(self.found.len() - 1),
+                    });
+                }
+            }
+        }
+
+        walk_expression(self, expression)
+    }
+}
+
+// spliced `Ast` values are inserted as they are, other values become literals
+struct Splice {
+    values: Vec<FruValue>,
+}
+
+impl Transformer for Splice {
+    fn transform_expression(
+        &mut self,
+        expression: FruExpression,
+    ) -> Result<FruExpression, FruError> {
+        if let FruExpression::Variable { ident } = &expression {
+            if let Some(k) = (0..self.values.len()).find(|k| unquote_placeholder(*k) == *ident) {
+                return Ok(value_to_expression(self.values[k].clone()));
+            }
+        }
+
+        walk_expression(self, expression)
+    }
+}
+
+// variables declared by quoted code can't clash with variables at the place of expansion
+struct Hygiene {
+    collecting: bool,
+    renamed: HashMap<Identifier, Identifier>,
+    suffix: usize,
+}
+
+impl Hygiene {
+    fn rename(template: FruExpression) -> Result<FruExpression, FruError> {
+        let mut hygiene = Hygiene {
+            collecting: true,
+            renamed: HashMap::new(),
+            suffix: match Runtime::current() {
+                Some(runtime) => runtime.next_hygiene_suffix(),
+                None => HYGIENE_COUNTER.fetch_add(1, Ordering::Relaxed),
+            },
+        };
+
+        let template = hygiene.transform_expression(template)?;
+        hygiene.collecting = false;
+        hygiene.transform_expression(template)
+    }
+
+    fn declare(&mut self, ident: Identifier) -> Identifier {
+        if self.collecting && !ident.to_string().starts_with("$unquote") {
+            self.renamed
+                .entry(ident)
+                .or_insert_with(|| Identifier::new(&format!("{}$m{}", ident, self.suffix)));
+        }
+
+        self.apply(ident)
+    }
+
+    fn apply(&self, ident: Identifier) -> Identifier {
+        if self.collecting {
+            return ident;
+        }
+
+        self.renamed.get(&ident).copied().unwrap_or(ident)
+    }
+}
+
+impl Transformer for Hygiene {
+    fn transform_statement(&mut self, statement: FruStatement) -> Result<FruStatement, FruError> {
+        match statement {
+            FruStatement::Let { ident, value } => Ok(FruStatement::Let {
+                ident: self.declare(ident),
+                value: Box::new(self.transform_expression(*value)?),
+            }),
+
+            FruStatement::Set { ident, value } => Ok(FruStatement::Set {
+                ident: self.apply(ident),
+                value: Box::new(self.transform_expression(*value)?),
+            }),
+
+            other => walk_statement(self, other),
+        }
+    }
+
+    fn transform_expression(
+        &mut self,
+        expression: FruExpression,
+    ) -> Result<FruExpression, FruError> {
+        match expression {
+            FruExpression::Variable { ident } => Ok(FruExpression::Variable {
+                ident: self.apply(ident),
+            }),
+
+            FruExpression::Function {
+                mut args,
+                body,
+                name,
+                location,
+                doc,
+            } => {
+                for (ident, _) in &mut args.args {
+                    *ident = self.declare(*ident);
+                }
+
+                walk_expression(
+                    self,
+                    FruExpression::Function {
+                        args,
+                        body,
+                        name,
+                        location,
+                        doc,
+                    },
+                )
+            }
+
+            other => walk_expression(self, other),
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use std::{
+    collections::BTreeMap,
+    fs::read_to_string,
+    path::{Component, Path, PathBuf},
+};
+
+use crate::interpreter::error::FruError;
+
+/// Name of the manifest file that marks the root of a project
+pub const MANIFEST_FILE: &str = "fruit.toml";
+
+/// Directory inside of a project where git dependencies are fetched to
+pub const VENDOR_DIR: &str = ".fruit/deps";
+
+/// Contents of `fruit.toml`, all paths are relative to `root`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Manifest {
+    pub root: PathBuf,
+    pub name: String,
+    pub version: String,
+    pub entry: PathBuf,
+    pub source_dirs: Vec<PathBuf>,
+    pub dependencies: BTreeMap<String, Dependency>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Dependency {
+    Path(PathBuf),
+    Git {
+        url: String,
+        rev: Option<String>,
+    },
+}
+
+impl Manifest {
+    pub fn parse(source: &str, root: &Path) -> Result<Manifest, FruError> {
+        let table: toml::Table = source
+            .parse()
+            .map_err(|err| FruError::new(format!("invalid {}: {}", MANIFEST_FILE, err)))?;
+
+        let package = match table.get("package") {
+            Some(toml::Value::Table(package)) => package,
+            _ => return FruError::new_res(format!("{} has no [package] table", MANIFEST_FILE)),
+        };
+
+        let name = match package.get("name") {
+            Some(toml::Value::String(name)) => name.clone(),
+            _ => return FruError::new_res("package name must be a string"),
+        };
+
+        check_name(&name, "package")?;
+
+        let version = match package.get("version") {
+            Some(toml::Value::String(version)) => version.clone(),
+            None => "0.0.0".to_string(),
+            _ => return FruError::new_res("package version must be a string"),
+        };
+
+        let entry = match package.get("entry") {
+            Some(toml::Value::String(entry)) => PathBuf::from(entry),
+            None => PathBuf::from("src/main.fru"),
+            _ => return FruError::new_res("package entry must be a string"),
+        };
+
+        let source_dirs = match package.get("source_dirs") {
+            Some(toml::Value::Array(dirs)) => dirs
+                .iter()
+                .map(|dir| match dir {
+                    toml::Value::String(dir) => Ok(PathBuf::from(dir)),
+                    _ => FruError::new_res("source_dirs must be an array of strings"),
+                })
+                .collect::<Result<_, _>>()?,
+            None => vec![PathBuf::from("src")],
+            _ => return FruError::new_res("source_dirs must be an array of strings"),
+        };
+
+        let mut dependencies = BTreeMap::new();
+
+        match table.get("dependencies") {
+            Some(toml::Value::Table(table)) => {
+                for (name, dependency) in table {
+                    check_name(name, "dependency")?;
+                    dependencies.insert(name.clone(), Dependency::parse(name, dependency)?);
+                }
+            }
+            None => {}
+            _ => return FruError::new_res("dependencies must be a table"),
+        }
+
+        Ok(Manifest {
+            root: root.to_path_buf(),
+            name,
+            version,
+            entry,
+            source_dirs,
+            dependencies,
+        })
+    }
+
+    pub fn load(root: &Path) -> Result<Manifest, FruError> {
+        let path = root.join(MANIFEST_FILE);
+
+        let source = read_to_string(&path)
+            .map_err(|err| FruError::new(format!("Error reading file {path:?} {err}")))?;
+
+        Manifest::parse(&source, root)
+    }
+
+    /// Manifest of the project that `path` belongs to, if any
+    pub fn find(path: &Path) -> Result<Option<Manifest>, FruError> {
+        let start = if path.is_dir() {
+            Some(path)
+        } else {
+            path.parent()
+        };
+
+        for dir in start.into_iter().flat_map(Path::ancestors) {
+            // empty parent of a relative path means the current directory
+            let dir = if dir.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                dir
+            };
+
+            if dir.join(MANIFEST_FILE).is_file() {
+                return Manifest::load(dir).map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+
+    pub fn entry_path(&self) -> PathBuf {
+        self.root.join(&self.entry)
+    }
+
+    pub fn source_paths(&self) -> Vec<PathBuf> {
+        self.source_dirs.iter().map(|dir| self.root.join(dir)).collect()
+    }
+
+    /// Directory of the dependency, git dependencies live in `VENDOR_DIR`
+    pub fn dependency_root(&self, name: &str) -> Option<PathBuf> {
+        match self.dependencies.get(name)? {
+            Dependency::Path(path) => Some(self.root.join(path)),
+            Dependency::Git { .. } => Some(self.root.join(VENDOR_DIR).join(name)),
+        }
+    }
+
+    /// `import "dep"` is the entry of a dependency, `import "dep/file.fru"` is a file in its
+    /// source dirs, other paths are looked up in source dirs of this project
+    pub fn resolve_import(&self, import: &Path) -> Result<Option<PathBuf>, FruError> {
+        let mut components = import.components();
+
+        if let Some(Component::Normal(first)) = components.next() {
+            if let Some(name) = first.to_str().filter(|x| self.dependencies.contains_key(*x)) {
+                let root = self.dependency_root(name).unwrap();
+
+                if !root.is_dir() {
+                    return FruError::new_res(format!(
+                        "dependency `{}` is not fetched, run `frugurt fetch`",
+                        name
+                    ));
+                }
+
+                let manifest = Manifest::load(&root)?;
+                let rest = components.as_path();
+
+                if rest.as_os_str().is_empty() {
+                    return Ok(Some(manifest.entry_path()));
+                }
+
+                return Ok(manifest.find_in_sources(rest));
+            }
+        }
+
+        Ok(self.find_in_sources(import))
+    }
+
+    fn find_in_sources(&self, path: &Path) -> Option<PathBuf> {
+        if path.is_absolute() {
+            return None;
+        }
+
+        self.source_paths().into_iter().map(|dir| dir.join(path)).find(|x| x.is_file())
+    }
+}
+
+/// Names of packages and dependencies become directories and the first part of import paths,
+/// so they are identifiers, which can't escape `VENDOR_DIR` with `..` or `/`
+pub fn check_name(name: &str, what: &str) -> Result<(), FruError> {
+    let mut chars = name.chars();
+
+    let is_identifier = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    if is_identifier {
+        Ok(())
+    } else {
+        FruError::new_res(format!(
+            "{} name `{}` must consist of letters, digits and `_`, and not start with a digit",
+            what, name
+        ))
+    }
+}
+
+impl Dependency {
+    fn parse(name: &str, value: &toml::Value) -> Result<Dependency, FruError> {
+        let rev = match value.get("rev") {
+            Some(toml::Value::String(rev)) => Some(rev.clone()),
+            None => None,
+            _ => {
+                return FruError::new_res(format!("rev of dependency `{}` must be a string", name))
+            }
+        };
+
+        match (value.get("path"), value.get("git")) {
+            (Some(toml::Value::String(path)), None) => Ok(Dependency::Path(PathBuf::from(path))),
+            (None, Some(toml::Value::String(url))) => Ok(Dependency::Git {
+                url: url.clone(),
+                rev,
+            }),
+            _ => FruError::new_res(format!(
+                "dependency `{}` must have either a `path` or a `git`",
+                name
+            )),
+        }
+    }
+}
@@ -1,12 +1,22 @@
-mod ast_helpers;
+pub mod ast_helpers;
+pub mod ast_json_parser;
+pub mod ast_json_serializer;
 pub mod builtins;
 pub mod control;
 pub mod error;
 pub mod expression;
 pub mod helpers;
 pub mod identifier;
+pub mod macro_expansion;
+pub mod manifest;
+pub mod options;
 pub mod runner;
+pub mod runtime;
+pub mod scheduler;
 pub mod scope;
+#[cfg(feature = "send")]
+pub mod send;
 pub mod statement;
+pub mod transformer;
 pub mod tree_sitter_parser;
 pub mod value;
@@ -0,0 +1,138 @@
+use std::fmt::Display;
+
+use crate::interpreter::{error::FruError, manifest::Manifest, runtime::CancellationToken};
+
+#[derive(Debug, Clone, Default)]
+pub struct InterpreterOptions {
+    pub capabilities: Capabilities,
+    // approximate number of bytes that objects and scopes, with the strings they hold, may occupy at once
+    pub memory_limit: Option<usize>,
+    // approximate number of bytes of the host stack that nested calls may use,
+    // without it they may use the stack of the thread up to a margin
+    pub stack_limit: Option<usize>,
+    pub cancellation: Option<CancellationToken>,
+    // statements the program may execute before it fails, the embedder can add more while it runs
+    pub fuel: Option<u64>,
+    // imports are resolved against source dirs and dependencies of the project
+    pub project: Option<Manifest>,
+    // `_requires_` and `_ensures_` methods are not called, for trusted hot code
+    pub skip_contracts: bool,
+    // conditions of `if` and `while` convert values like `Bool(x)` instead of requiring `Bool`
+    pub truthy_conditions: bool,
+    // deprecations, implicit conversions and shadowed variables are collected and printed when the program exits
+    pub warnings: bool,
+}
+
+/// Ambient authorities that builtins are allowed to use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    pub fs: bool,
+    pub net: bool,
+    pub env: bool,
+    pub process: bool,
+    pub time: bool,
+    pub random: bool,
+    // `input` reads the standard input of the host
+    pub stdin: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    Fs,
+    Net,
+    Env,
+    Process,
+    Time,
+    Random,
+    Stdin,
+}
+
+impl Capabilities {
+    pub const fn all() -> Self {
+        Self {
+            fs: true,
+            net: true,
+            env: true,
+            process: true,
+            time: true,
+            random: true,
+            stdin: true,
+        }
+    }
+
+    pub const fn none() -> Self {
+        Self {
+            fs: false,
+            net: false,
+            env: false,
+            process: false,
+            time: false,
+            random: false,
+            stdin: false,
+        }
+    }
+
+    pub fn with(mut self, capability: Capability) -> Self {
+        *self.flag_mut(capability) = true;
+        self
+    }
+
+    pub fn without(mut self, capability: Capability) -> Self {
+        *self.flag_mut(capability) = false;
+        self
+    }
+
+    pub fn is_granted(&self, capability: Capability) -> bool {
+        match capability {
+            Capability::Fs => self.fs,
+            Capability::Net => self.net,
+            Capability::Env => self.env,
+            Capability::Process => self.process,
+            Capability::Time => self.time,
+            Capability::Random => self.random,
+            Capability::Stdin => self.stdin,
+        }
+    }
+
+    pub fn require(&self, capability: Capability) -> Result<(), FruError> {
+        if self.is_granted(capability) {
+            Ok(())
+        } else {
+            FruError::new_res(format!("capability `{}` is not granted", capability))
+        }
+    }
+
+    fn flag_mut(&mut self, capability: Capability) -> &mut bool {
+        match capability {
+            Capability::Fs => &mut self.fs,
+            Capability::Net => &mut self.net,
+            Capability::Env => &mut self.env,
+            Capability::Process => &mut self.process,
+            Capability::Time => &mut self.time,
+            Capability::Random => &mut self.random,
+            Capability::Stdin => &mut self.stdin,
+        }
+    }
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl Display for Capability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Capability::Fs => "fs",
+            Capability::Net => "net",
+            Capability::Env => "env",
+            Capability::Process => "process",
+            Capability::Time => "time",
+            Capability::Random => "random",
+            Capability::Stdin => "stdin",
+        };
+
+        write!(f, "{}", name)
+    }
+}
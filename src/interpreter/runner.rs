@@ -1,30 +1,587 @@
-use std::{fs::read_to_string, path::Path, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fs::read_to_string,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
 
-use crate::interpreter::{control::Control, error::FruError, scope::Scope, tree_sitter_parser};
+use crate::interpreter::{
+    ast_json_parser,
+    control::Control,
+    error::FruError,
+    expression::FruExpression,
+    identifier::Identifier,
+    macro_expansion::expand_macros,
+    options::{Capability, InterpreterOptions},
+    runtime::{require_capability, run_finalizers, Runtime, Warning},
+    scope::Scope,
+    statement::FruStatement,
+    tree_sitter_parser,
+    value::{
+        convert::{FromFruValue, IntoFruArguments},
+        fru_object::FruObject,
+        fru_value::FruValue,
+        function::EvaluatedArgumentList,
+    },
+};
+use crate::stdlib::prelude::fru_prelude::{Prelude, StdModule};
+
+/// Global scope together with the runtime it is executed in, the entry point for embedders
+pub struct Interpreter {
+    runtime: Rc<Runtime>,
+    global_scope: Rc<Scope>,
+    contexts: RefCell<HashMap<String, Rc<Scope>>>,
+}
+
+/// Program that is parsed and expanded once by `Interpreter::compile` and can be run many times.
+/// It runs in a global scope of its own, that sees the builtins of the interpreter but not its globals
+pub struct Script {
+    runtime: Rc<Runtime>,
+    ast: Rc<FruStatement>,
+    scope: Rc<Scope>,
+}
+
+/// Function declared by a script, called from Rust with arguments and result converted
+/// by `IntoFruValue` and `FromFruValue`, like `update.call::<_, f64>((1.0, "player"))`
+pub struct ScriptFunction<'a> {
+    script: &'a Script,
+    function: FruValue,
+}
+
+/// Options and prelude of an interpreter, everything is installed unless told otherwise
+#[derive(Default)]
+pub struct InterpreterBuilder {
+    options: InterpreterOptions,
+    prelude: Prelude,
+}
+
+impl InterpreterBuilder {
+    pub fn options(mut self, options: InterpreterOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn prelude(mut self, prelude: Prelude) -> Self {
+        self.prelude = prelude;
+        self
+    }
+
+    pub fn without_module(mut self, module: StdModule) -> Self {
+        self.prelude = self.prelude.without_module(module);
+        self
+    }
+
+    pub fn build(self) -> Interpreter {
+        let runtime = Runtime::new(self.options);
+        let global_scope = runtime.enter(|| Scope::new_global_with(self.prelude));
+        runtime.set_global_scope(&global_scope);
+
+        Interpreter {
+            runtime,
+            global_scope,
+            contexts: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+impl Interpreter {
+    pub fn new(options: InterpreterOptions) -> Self {
+        Self::builder().options(options).build()
+    }
+
+    pub fn builder() -> InterpreterBuilder {
+        InterpreterBuilder::default()
+    }
+
+    pub fn runtime(&self) -> &Rc<Runtime> {
+        &self.runtime
+    }
+
+    pub fn global_scope(&self) -> Rc<Scope> {
+        self.global_scope.clone()
+    }
+
+    pub fn set_global(&self, ident: &str, value: FruValue) {
+        self.global_scope.let_set_variable(Identifier::new(ident), value);
+    }
+
+    pub fn get_global(&self, ident: &str) -> Result<FruValue, FruError> {
+        self.global_scope.get_variable(Identifier::new(ident))
+    }
+
+    /// Statements that can still be executed, `None` unless `InterpreterOptions::fuel` is set
+    pub fn fuel_remaining(&self) -> Option<u64> {
+        self.runtime.fuel_remaining()
+    }
+
+    pub fn add_fuel(&self, amount: u64) {
+        self.runtime.add_fuel(amount)
+    }
+
+    /// Lets the host top up fuel as it runs low, see `Runtime::on_low_fuel`
+    pub fn on_low_fuel(&self, threshold: u64, callback: impl FnMut(u64) -> u64 + 'static) {
+        self.runtime.on_low_fuel(threshold, callback)
+    }
+
+    /// Sends output of `print` to the host, for example to an in-game console
+    pub fn on_print(&self, hook: impl FnMut(&str) + 'static) {
+        self.runtime.on_print(hook)
+    }
+
+    pub fn on_log(&self, hook: impl FnMut(&str) + 'static) {
+        self.runtime.on_log(hook)
+    }
+
+    /// Lets imports come from memory or an asset system, see `Runtime::set_module_loader`
+    pub fn set_module_loader(&self, loader: impl Fn(&Path) -> Option<String> + 'static) {
+        self.runtime.set_module_loader(loader)
+    }
+
+    /// Lets the host handle errors in one place, including the ones of tasks and timers,
+    /// see `Runtime::set_error_handler`. Methods still return the errors
+    pub fn set_error_handler(&self, handler: impl Fn(&FruError) + 'static) {
+        self.runtime.set_error_handler(handler)
+    }
+
+    /// Warnings emitted so far, empty unless `InterpreterOptions::warnings` is set
+    pub fn warnings(&self) -> Vec<Warning> {
+        self.runtime.warnings()
+    }
+
+    /// Executes code in the global scope and then all tasks it has spawned
+    pub fn execute(&self, source_code: String) -> Result<(), FruError> {
+        self.runtime.enter_reporting(|| {
+            run_source_code_in(source_code, self.global_scope.clone())?;
+
+            self.runtime.scheduler().run_all()?;
+
+            run_finalizers()
+        })
+    }
+
+    /// Starts code in the global scope as a task, for hosts that have an event loop of their own,
+    /// like the ones resolving futures on the same thread. Nothing runs until `run_ready` is called
+    pub fn start(&self, source_code: String) -> Result<(), FruError> {
+        // parsed inside, so the names of the code belong to this interpreter
+        let ast = self.runtime.enter(|| {
+            tree_sitter_parser::parse(source_code).map_err(|err| FruError::new(err.to_string()))
+        })?;
+        let scope = self.global_scope.clone();
+
+        (self.runtime.scheduler())
+            .spawn_native(move || run_ast_in(&ast, scope).map(|_| FruValue::Nah));
+
+        Ok(())
+    }
+
+    /// Runs the tasks and timers that can proceed without blocking, finalizers run once nothing is left.
+    /// Returns whether some tasks still wait for a timer or the host, then it should be called again
+    /// after waking a future or on the next tick of the host loop
+    pub fn run_ready(&self) -> Result<bool, FruError> {
+        self.runtime.enter_reporting(|| {
+            if self.runtime.scheduler().run_ready()? {
+                return Ok(true);
+            }
+
+            run_finalizers()?;
+
+            Ok(false)
+        })
+    }
+
+    /// Same as `execute`, for code that is already parsed
+    pub fn execute_ast(&self, ast: &FruStatement) -> Result<(), FruError> {
+        self.runtime.enter_reporting(|| {
+            run_ast_in(ast, self.global_scope.clone())?;
+
+            self.runtime.scheduler().run_all()?;
+
+            run_finalizers()
+        })
+    }
+
+    /// Executes a new version of the program in the same global scope, keeping its data:
+    /// functions and types come from the new code, other variables that existed before
+    /// keep their values. When the old and new values are of different types, the new one is kept
+    pub fn reload(&self, source_code: String) -> Result<(), FruError> {
+        self.reload_with(source_code, |_, _, new| Ok(new))
+    }
+
+    /// Same as `reload`, `migrate(name, old, new)` decides the value of variables
+    /// whose old and new values are of different types, or objects with different fields.
+    /// Objects that are kept still have the type they were made with, `migrate` can't see them
+    pub fn reload_with(
+        &self,
+        source_code: String,
+        mut migrate: impl FnMut(&str, FruValue, FruValue) -> Result<FruValue, FruError>,
+    ) -> Result<(), FruError> {
+        self.runtime.enter_reporting(|| {
+            let ast = tree_sitter_parser::parse(source_code)
+                .map_err(|err| FruError::new(err.to_string()))?;
+
+            let old = self.global_scope.take_variables();
+
+            let values = run_ast_in(&ast, self.global_scope.clone()).and_then(|_| {
+                let mut values = Vec::new();
+
+                for (ident, old_value) in &old {
+                    let new_value = if self.global_scope.has_variable(*ident) {
+                        Some(self.global_scope.get_variable(*ident)?)
+                    } else {
+                        None
+                    };
+
+                    let value = match new_value {
+                        None => old_value.clone(),
+                        Some(new_value @ (FruValue::Function(_) | FruValue::Type(_))) => new_value,
+                        Some(new_value) if same_shape(old_value, &new_value) => old_value.clone(),
+                        Some(new_value) => {
+                            migrate(&ident.to_string(), old_value.clone(), new_value)?
+                        }
+                    };
+
+                    values.push((*ident, value));
+                }
+
+                Ok(values)
+            });
+
+            // a failed reload or migration leaves the program as it was
+            let values = match values {
+                Ok(values) => values,
+                Err(err) => {
+                    self.global_scope.take_variables();
+
+                    for (ident, value) in old {
+                        self.global_scope.let_set_variable(ident, value);
+                    }
+
+                    return Err(err);
+                }
+            };
+
+            for (ident, value) in values {
+                self.global_scope.let_set_variable(ident, value);
+            }
+
+            self.runtime.scheduler().run_all()?;
+
+            run_finalizers()
+        })
+    }
+
+    /// Global scope of a named context, created on first use. Contexts share the runtime
+    /// and the builtins, but code executed in one of them can't see globals of the others
+    pub fn context(&self, name: &str) -> Rc<Scope> {
+        self.contexts
+            .borrow_mut()
+            .entry(name.to_string())
+            .or_insert_with(|| self.runtime.enter(|| Scope::new_global_like(&self.global_scope)))
+            .clone()
+    }
+
+    /// Returns whether the context existed, its values are dropped once nothing refers to them
+    pub fn remove_context(&self, name: &str) -> bool {
+        self.contexts.borrow_mut().remove(name).is_some()
+    }
+
+    /// Same as `execute`, in the global scope of a named context
+    pub fn execute_in(&self, context: &str, source_code: String) -> Result<(), FruError> {
+        let scope = self.context(context);
+
+        self.in_context(&scope, || {
+            run_source_code_in(source_code, scope.clone())?;
+
+            self.runtime.scheduler().run_all()?;
+
+            run_finalizers()
+        })
+    }
+
+    /// Same as `evaluate`, in the global scope of a named context
+    pub fn evaluate_in(
+        &self,
+        context: &str,
+        expression: &FruExpression,
+    ) -> Result<FruValue, FruError> {
+        let scope = self.context(context);
+
+        self.in_context(&scope, || {
+            let value = expression.evaluate(scope.clone()).map_err(control_to_error)?;
+
+            self.runtime.scheduler().run_all()?;
+            run_finalizers()?;
+
+            Ok(value)
+        })
+    }
+
+    // `global()` returns the root of the context while it runs
+    fn in_context<T>(
+        &self,
+        scope: &Rc<Scope>,
+        f: impl FnOnce() -> Result<T, FruError>,
+    ) -> Result<T, FruError> {
+        self.runtime.set_global_scope(scope);
+        let result = self.runtime.enter_reporting(f);
+        self.runtime.set_global_scope(&self.global_scope);
+
+        result
+    }
+
+    /// Parses the code and expands its macros, without running it
+    pub fn compile(&self, source_code: String) -> Result<Script, FruError> {
+        self.runtime.enter_reporting(|| {
+            let ast = tree_sitter_parser::parse(source_code)
+                .map_err(|err| FruError::new(err.to_string()))?;
+
+            Ok(Script {
+                runtime: self.runtime.clone(),
+                ast: Rc::new(expand_macros(&ast)?),
+                scope: Scope::new_global_like(&self.global_scope),
+            })
+        })
+    }
+
+    /// Value of the expression in the global scope, after the tasks it has spawned are finished
+    pub fn evaluate(&self, expression: &FruExpression) -> Result<FruValue, FruError> {
+        self.runtime.enter_reporting(|| {
+            let value = expression.evaluate(self.global_scope.clone()).map_err(control_to_error)?;
+
+            self.runtime.scheduler().run_all()?;
+            run_finalizers()?;
+
+            Ok(value)
+        })
+    }
+}
+
+impl Script {
+    /// Same code in a new global scope, like a script attached to one more entity of a game
+    pub fn instance(&self) -> Script {
+        Script {
+            runtime: self.runtime.clone(),
+            ast: self.ast.clone(),
+            scope: self.runtime.enter(|| Scope::new_global_like(&self.scope)),
+        }
+    }
+
+    pub fn scope(&self) -> Rc<Scope> {
+        self.scope.clone()
+    }
+
+    /// Executes the code and then all tasks it has spawned, top level declarations stay in the scope
+    pub fn run(&self) -> Result<(), FruError> {
+        self.enter(|| self.ast.execute(self.scope.clone()).map_err(control_to_error))
+    }
+
+    /// Calls a function that the code has declared, the code must have been run before
+    pub fn call(&self, function_name: &str, args: Vec<FruValue>) -> Result<FruValue, FruError> {
+        self.enter(|| {
+            let function = self.scope.get_variable(Identifier::new(function_name))?;
+
+            function.call(EvaluatedArgumentList {
+                args: args.into_iter().map(|arg| (None, arg)).collect(),
+            })
+        })
+    }
+
+    /// Function that the code has declared, the code must have been run before
+    pub fn get_function(&self, function_name: &str) -> Result<ScriptFunction<'_>, FruError> {
+        let function = (self.runtime)
+            .enter_reporting(|| self.scope.get_variable(Identifier::new(function_name)))?;
+
+        match function {
+            FruValue::Function(_) => Ok(ScriptFunction {
+                script: self,
+                function,
+            }),
+
+            other => FruError::new_res(format!(
+                "`{}` is `{}`, not a function",
+                function_name,
+                other.get_type_identifier()
+            )),
+        }
+    }
+
+    // `global()` returns the scope of the script while it runs
+    fn enter<T>(&self, f: impl FnOnce() -> Result<T, FruError>) -> Result<T, FruError> {
+        let previous = self.runtime.global_scope();
+        self.runtime.set_global_scope(&self.scope);
+
+        let result = self.runtime.enter_reporting(|| {
+            let value = f()?;
+
+            self.runtime.scheduler().run_all()?;
+            run_finalizers()?;
+
+            Ok(value)
+        });
+
+        if let Some(previous) = previous {
+            self.runtime.set_global_scope(&previous);
+        }
+
+        result
+    }
+}
+
+impl ScriptFunction<'_> {
+    pub fn call<A: IntoFruArguments, R: FromFruValue>(&self, args: A) -> Result<R, FruError> {
+        let args = args.into_fru_arguments();
+
+        self.script.enter(|| {
+            let result = self.function.call(EvaluatedArgumentList {
+                args: args.into_iter().map(|arg| (None, arg)).collect(),
+            })?;
+
+            R::from_fru_value(result)
+        })
+    }
+}
+
+// values of one type, and objects with the same fields
+fn same_shape(old: &FruValue, new: &FruValue) -> bool {
+    match (old, new) {
+        (FruValue::Object(old), FruValue::Object(new)) => {
+            let fields = |object: &FruObject| -> Vec<Identifier> {
+                object.get_type().get_fields().iter().map(|field| field.ident).collect()
+            };
+
+            old.get_type().get_ident() == new.get_type().get_ident() && fields(old) == fields(new)
+        }
+
+        (old, new) => old.get_type_identifier() == new.get_type_identifier(),
+    }
+}
 
 pub fn execute_file(path: &Path) -> Result<Rc<Scope>, FruError> {
-    let source_code = read_to_string(path)
-        .map_err(|err| FruError::new(format!("Error reading file {path:?} {err}")))?;
+    execute_file_with_options(path, InterpreterOptions::default())
+}
+
+pub fn execute_file_with_options(
+    path: &Path,
+    options: InterpreterOptions,
+) -> Result<Rc<Scope>, FruError> {
+    let source_code = read_source_file(path)?;
 
-    execute_source_code(source_code)
+    execute_source_code_with_options(source_code, options)
+}
+
+/// Executes a file with the AST in JSON, as made by `ast_json_serializer::to_json`
+pub fn execute_ast_file_with_options(
+    path: &Path,
+    options: InterpreterOptions,
+) -> Result<Rc<Scope>, FruError> {
+    let interpreter = Interpreter::new(options);
+
+    let ast = interpreter.runtime().enter(|| {
+        ast_json_parser::parse(&read_source_file(path)?)
+            .map_err(|err| FruError::new(format!("invalid AST in {}: {}", path.display(), err)))
+    })?;
+
+    let result = interpreter.execute_ast(&ast);
+    print_warnings(&interpreter);
+    result?;
+
+    Ok(interpreter.global_scope())
 }
 
 pub fn execute_source_code(source_code: String) -> Result<Rc<Scope>, FruError> {
+    execute_source_code_with_options(source_code, InterpreterOptions::default())
+}
+
+pub fn execute_source_code_with_options(
+    source_code: String,
+    options: InterpreterOptions,
+) -> Result<Rc<Scope>, FruError> {
+    let interpreter = Interpreter::new(options);
+
+    let result = interpreter.execute(source_code);
+    print_warnings(&interpreter);
+    result?;
+
+    Ok(interpreter.global_scope())
+}
+
+// also when the program fails, warnings may explain why
+fn print_warnings(interpreter: &Interpreter) {
+    let warnings = interpreter.warnings();
+
+    if warnings.is_empty() {
+        return;
+    }
+
+    for warning in &warnings {
+        eprintln!("{}", warning);
+    }
+
+    match warnings.len() {
+        1 => eprintln!("1 warning"),
+        n => eprintln!("{} warnings", n),
+    }
+}
+
+// executes file in the runtime of the importing code
+pub fn import_file(path: &Path) -> Result<Rc<Scope>, FruError> {
+    let runtime = Runtime::current();
+
+    // modules provided by the host don't touch the file system, so they don't need `Fs`
+    let (path, source_code) = match (runtime.as_ref()).and_then(|runtime| runtime.load_module(path))
+    {
+        Some(source_code) => (path.to_path_buf(), source_code),
+        None => {
+            require_capability(Capability::Fs)?;
+            let path = resolve_import(path)?;
+            let source_code = read_source_file(&path)?;
+            (path, source_code)
+        }
+    };
+
+    // imported code gets the same prelude as the program
+    let global_scope = match runtime.and_then(|runtime| runtime.global_scope()) {
+        Some(program) => Scope::new_global_like(&program),
+        None => Scope::new_global(),
+    };
+
+    run_source_code_in(source_code, global_scope.clone()).map_err(|err| err.in_file(&path))?;
+
+    Ok(global_scope)
+}
+
+fn resolve_import(path: &Path) -> Result<PathBuf, FruError> {
+    let project = Runtime::current().and_then(|runtime| runtime.options().project.clone());
+
+    match project {
+        Some(project) => Ok(project.resolve_import(path)?.unwrap_or_else(|| path.to_path_buf())),
+        None => Ok(path.to_path_buf()),
+    }
+}
+
+pub fn read_source_file(path: &Path) -> Result<String, FruError> {
+    read_to_string(path).map_err(|err| FruError::new(format!("Error reading file {path:?} {err}")))
+}
+
+fn run_source_code_in(source_code: String, scope: Rc<Scope>) -> Result<(), FruError> {
     let ast = match tree_sitter_parser::parse(source_code) {
         Ok(ast) => ast,
         Err(err) => return Err(FruError::new(err.to_string())),
     };
 
-    let global_scope = Scope::new_global();
+    run_ast_in(&ast, scope)
+}
 
-    let signal = ast.execute(global_scope.clone());
+fn run_ast_in(ast: &FruStatement, scope: Rc<Scope>) -> Result<(), FruError> {
+    let ast = expand_macros(ast)?;
 
-    if let Err(signal) = signal {
-        Err(match signal {
-            Control::Error(err) => err,
-            unexpected => FruError::new(format!("Unexpected signal: {:?}", unexpected)),
-        })
-    } else {
-        Ok(global_scope)
+    ast.execute(scope).map_err(control_to_error)
+}
+
+fn control_to_error(control: Control) -> FruError {
+    match control {
+        Control::Error(err) => err,
+        unexpected => FruError::new(format!("Unexpected signal: {:?}", unexpected)),
     }
 }
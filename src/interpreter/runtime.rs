@@ -0,0 +1,645 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashSet, VecDeque},
+    fmt::{self, Display},
+    mem,
+    path::Path,
+    rc::{Rc, Weak},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use crate::interpreter::{
+    error::{FruError, FruErrorKind},
+    helpers::deprecation_note,
+    identifier::NameTable,
+    options::{Capability, InterpreterOptions},
+    scheduler::Scheduler,
+    scope::Scope,
+    value::{fru_object::FruObject, function::SourceLocation},
+};
+
+thread_local! {
+    // runtime of the interpreter that is currently executing on this thread
+    static CURRENT: RefCell<Option<Rc<Runtime>>> = const { RefCell::new(None) };
+
+    // lowest address of the host stack that the current runtime may reach, 0 outside of runtimes
+    static STACK_END: Cell<usize> = const { Cell::new(0) };
+
+    // statement that is being executed, for warnings
+    static LOCATION: Cell<Option<SourceLocation>> = const { Cell::new(None) };
+}
+
+/// State shared by all code executed by one interpreter, including imported files
+pub struct Runtime {
+    options: InterpreterOptions,
+    memory: Option<Rc<MemoryCounter>>,
+    scheduler: Scheduler,
+    finalizers: RefCell<VecDeque<FruObject>>,
+    // weak, so values stored in the global scope can't keep the runtime alive
+    global_scope: RefCell<Weak<Scope>>,
+    warnings: RefCell<Vec<Warning>>,
+    // deprecation warnings that were already printed, so each site is reported once
+    reported_deprecations: RefCell<HashSet<String>>,
+    // identifier names interned while this runtime is current
+    names: NameTable,
+    // makes names declared by macro expansions unique
+    hygiene_counter: Cell<usize>,
+    fuel: Cell<Option<u64>>,
+    low_fuel: RefCell<Option<LowFuel>>,
+    print_hook: RefCell<Option<OutputHook>>,
+    log_hook: RefCell<Option<OutputHook>>,
+    module_loader: RefCell<Option<Rc<ModuleLoader>>>,
+    error_handler: RefCell<Option<Rc<ErrorHandler>>>,
+}
+
+type OutputHook = Box<dyn FnMut(&str)>;
+type ModuleLoader = dyn Fn(&Path) -> Option<String>;
+type ErrorHandler = dyn Fn(&FruError);
+
+struct LowFuel {
+    threshold: u64,
+    // cleared when the callback is called, set again once fuel is above the threshold
+    armed: bool,
+    callback: Box<dyn FnMut(u64) -> u64>,
+}
+
+struct MemoryCounter {
+    used: Cell<usize>,
+    limit: usize,
+}
+
+/// Accounts bytes against the memory limit of the current runtime until dropped
+pub struct Allocation {
+    size: Cell<usize>,
+    counter: Option<Rc<MemoryCounter>>,
+}
+
+/// Can be tripped from any thread to stop the script at the next statement
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+/// Non-fatal diagnostic collected while running, when `InterpreterOptions::warnings` is set
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    pub kind: WarningKind,
+    pub message: String,
+    // times the same warning was emitted
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WarningKind {
+    Deprecation,
+    ImplicitConversion,
+    Shadowing,
+}
+
+struct EnterGuard {
+    previous: Option<Rc<Runtime>>,
+    previous_stack_end: usize,
+    previous_location: Option<SourceLocation>,
+}
+
+impl Runtime {
+    pub fn new(options: InterpreterOptions) -> Rc<Runtime> {
+        let memory = options.memory_limit.map(|limit| {
+            Rc::new(MemoryCounter {
+                used: Cell::new(0),
+                limit,
+            })
+        });
+
+        let fuel = options.fuel;
+
+        Rc::new(Runtime {
+            options,
+            memory,
+            scheduler: Scheduler::new(),
+            finalizers: RefCell::new(VecDeque::new()),
+            global_scope: RefCell::new(Weak::new()),
+            warnings: RefCell::new(Vec::new()),
+            reported_deprecations: RefCell::new(HashSet::new()),
+            names: NameTable::new(),
+            hygiene_counter: Cell::new(0),
+            fuel: Cell::new(fuel),
+            low_fuel: RefCell::new(None),
+            print_hook: RefCell::new(None),
+            log_hook: RefCell::new(None),
+            module_loader: RefCell::new(None),
+            error_handler: RefCell::new(None),
+        })
+    }
+
+    pub fn options(&self) -> &InterpreterOptions {
+        &self.options
+    }
+
+    pub fn scheduler(&self) -> &Scheduler {
+        &self.scheduler
+    }
+
+    /// Root scope of the interpreter, imported files have their own global scopes but share this one
+    pub fn global_scope(&self) -> Option<Rc<Scope>> {
+        self.global_scope.borrow().upgrade()
+    }
+
+    pub fn set_global_scope(&self, scope: &Rc<Scope>) {
+        *self.global_scope.borrow_mut() = Rc::downgrade(scope);
+    }
+
+    pub fn memory_used(&self) -> usize {
+        self.memory.as_ref().map_or(0, |memory| memory.used.get())
+    }
+
+    pub(crate) fn names(&self) -> &NameTable {
+        &self.names
+    }
+
+    /// Numbers macro expansions, so expanded code gets the same names in every interpreter
+    pub(crate) fn next_hygiene_suffix(&self) -> usize {
+        let suffix = self.hygiene_counter.get();
+        self.hygiene_counter.set(suffix + 1);
+        suffix
+    }
+
+    /// Number of identifier names this runtime has interned
+    pub fn interned_names(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Statements that can still be executed, `None` when there is no limit
+    pub fn fuel_remaining(&self) -> Option<u64> {
+        self.fuel.get()
+    }
+
+    /// Does nothing when there is no limit
+    pub fn add_fuel(&self, amount: u64) {
+        if let Some(fuel) = self.fuel.get() {
+            self.fuel.set(Some(fuel.saturating_add(amount)));
+        }
+    }
+
+    /// Calls `callback` with the remaining fuel when it is at or below `threshold`,
+    /// then not again until fuel goes above `threshold`.
+    /// It returns how much fuel to add, 0 lets the program run out
+    pub fn on_low_fuel(&self, threshold: u64, callback: impl FnMut(u64) -> u64 + 'static) {
+        *self.low_fuel.borrow_mut() = Some(LowFuel {
+            threshold,
+            armed: true,
+            callback: Box::new(callback),
+        });
+    }
+
+    fn burn_fuel(&self) -> Result<(), FruError> {
+        let fuel = match self.fuel.get() {
+            None => return Ok(()),
+            Some(0) => {
+                return Err(FruError::new_with_kind(
+                    FruErrorKind::OutOfFuel,
+                    "out of fuel".to_string(),
+                ))
+            }
+            Some(fuel) => fuel - 1,
+        };
+
+        self.fuel.set(Some(fuel));
+
+        let is_low = (self.low_fuel.borrow_mut().as_mut()).is_some_and(|low| {
+            if fuel > low.threshold {
+                low.armed = true;
+                false
+            } else {
+                mem::replace(&mut low.armed, false)
+            }
+        });
+
+        // taken out for the call, so the callback may replace itself
+        if let Some(mut low) = is_low.then(|| self.low_fuel.borrow_mut().take()).flatten() {
+            let added = (low.callback)(fuel);
+            self.add_fuel(added);
+            self.low_fuel.borrow_mut().get_or_insert(low);
+        }
+
+        Ok(())
+    }
+
+    /// Receives lines written by `print` instead of stdout, without the trailing space
+    pub fn on_print(&self, hook: impl FnMut(&str) + 'static) {
+        *self.print_hook.borrow_mut() = Some(Box::new(hook));
+    }
+
+    /// Receives diagnostics that would go to stderr, like deprecation notes and `timed` reports
+    pub fn on_log(&self, hook: impl FnMut(&str) + 'static) {
+        *self.log_hook.borrow_mut() = Some(Box::new(hook));
+    }
+
+    /// Asked for the source of every imported path before the file system,
+    /// `None` falls back to reading the file
+    pub fn set_module_loader(&self, loader: impl Fn(&Path) -> Option<String> + 'static) {
+        *self.module_loader.borrow_mut() = Some(Rc::new(loader));
+    }
+
+    pub(crate) fn load_module(&self, path: &Path) -> Option<String> {
+        let loader = self.module_loader.borrow().clone()?;
+        loader(path)
+    }
+
+    /// Called with every error that the script doesn't catch. Errors of tasks and timers are
+    /// handled without stopping the scheduler, unless they are limits or cancellation
+    pub fn set_error_handler(&self, handler: impl Fn(&FruError) + 'static) {
+        *self.error_handler.borrow_mut() = Some(Rc::new(handler));
+    }
+
+    fn report_error(&self, err: &FruError) -> bool {
+        let Some(handler) = self.error_handler.borrow().clone() else {
+            return false;
+        };
+
+        handler(err);
+        true
+    }
+
+    /// Warnings in the order they were first emitted
+    pub fn warnings(&self) -> Vec<Warning> {
+        self.warnings.borrow().clone()
+    }
+
+    fn add_warning(&self, kind: WarningKind, message: String) {
+        let mut warnings = self.warnings.borrow_mut();
+
+        match (warnings.iter_mut()).find(|x| x.kind == kind && x.message == message) {
+            Some(warning) => warning.count += 1,
+            None => warnings.push(Warning {
+                kind,
+                message,
+                count: 1,
+            }),
+        }
+    }
+
+    /// Makes this runtime current while `f` is running
+    pub fn enter<T>(self: &Rc<Self>, f: impl FnOnce() -> T) -> T {
+        let previous_stack_end = STACK_END.with(|end| end.get());
+
+        // a runtime entered from a callback of another one can't use more stack than it has left
+        let mut stack_end = match previous_stack_end {
+            0 => thread_stack_end(),
+            end => end,
+        };
+
+        if let Some(limit) = self.options.stack_limit {
+            stack_end = stack_end.max(stack_address().saturating_sub(limit));
+        }
+
+        STACK_END.with(|end| end.set(stack_end));
+
+        let _guard = EnterGuard {
+            previous: CURRENT.with(|current| current.replace(Some(self.clone()))),
+            previous_stack_end,
+            previous_location: set_location(None),
+        };
+
+        f()
+    }
+
+    /// Same as `enter`, and an error that `f` returns is also passed to the error handler
+    pub fn enter_reporting<T>(
+        self: &Rc<Self>,
+        f: impl FnOnce() -> Result<T, FruError>,
+    ) -> Result<T, FruError> {
+        self.enter(f).inspect_err(|err| {
+            self.report_error(err);
+        })
+    }
+
+    // values may be dropped while thread locals are being destroyed
+    pub fn current() -> Option<Rc<Runtime>> {
+        CURRENT.try_with(|current| current.borrow().clone()).ok().flatten()
+    }
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "warning[{}]: {}", self.kind, self.message)?;
+
+        if self.count > 1 {
+            write!(f, " ({} times)", self.count)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for WarningKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            WarningKind::Deprecation => "deprecation",
+            WarningKind::ImplicitConversion => "implicit-conversion",
+            WarningKind::Shadowing => "shadowing",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+impl MemoryCounter {
+    fn check(&self, additional: usize) -> Result<(), FruError> {
+        if self.used.get().saturating_add(additional) > self.limit {
+            Err(FruError::new_with_kind(
+                FruErrorKind::MemoryLimitExceeded,
+                format!("memory limit of {} bytes exceeded", self.limit),
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Allocation {
+    pub fn new(size: usize) -> Allocation {
+        let counter = Runtime::current().and_then(|runtime| runtime.memory.clone());
+
+        if let Some(counter) = &counter {
+            counter.used.set(counter.used.get() + size);
+        }
+
+        Allocation {
+            size: Cell::new(size),
+            counter,
+        }
+    }
+
+    pub fn grow(&self, additional: usize) {
+        if let Some(counter) = &self.counter {
+            counter.used.set(counter.used.get() + additional);
+            self.size.set(self.size.get() + additional);
+        }
+    }
+
+    pub fn shrink(&self, released: usize) {
+        if let Some(counter) = &self.counter {
+            counter.used.set(counter.used.get() - released);
+            self.size.set(self.size.get() - released);
+        }
+    }
+}
+
+impl Drop for Allocation {
+    fn drop(&mut self) {
+        if let Some(counter) = &self.counter {
+            counter.used.set(counter.used.get() - self.size.get());
+        }
+    }
+}
+
+impl Drop for EnterGuard {
+    fn drop(&mut self) {
+        CURRENT.with(|current| *current.borrow_mut() = self.previous.take());
+        STACK_END.with(|end| end.set(self.previous_stack_end));
+        set_location(self.previous_location);
+    }
+}
+
+/// Runs `f` on the stack of a task, that starts at `base`, nested code fails once it reaches `end`
+/// or uses more than the stack limit of the current runtime
+pub(crate) fn on_task_stack<T>(base: usize, end: usize, f: impl FnOnce() -> T) -> T {
+    let end = match Runtime::current().and_then(|runtime| runtime.options.stack_limit) {
+        Some(limit) => end.max(base.saturating_sub(limit)),
+        None => end,
+    };
+
+    let _guard = EnterGuard {
+        previous: Runtime::current(),
+        previous_stack_end: STACK_END.with(|current| current.replace(end)),
+        previous_location: set_location(None),
+    };
+
+    f()
+}
+
+// nested code fails with a stack limit error instead of reaching the end of the host stack
+const HOST_STACK_MARGIN: usize = 256 * 1024;
+
+// lowest address that code entering a runtime on the stack of a host thread may reach,
+// so deep recursion fails even without a stack limit
+fn thread_stack_end() -> usize {
+    match stacker::remaining_stack() {
+        Some(remaining) => {
+            stack_address().saturating_sub(remaining.saturating_sub(HOST_STACK_MARGIN))
+        }
+        None => 0,
+    }
+}
+
+// stacks grow downwards on every platform the interpreter runs on
+#[inline(never)]
+fn stack_address() -> usize {
+    let marker = 0u8;
+    std::hint::black_box(&marker) as *const u8 as usize
+}
+
+/// Whether nested code has used up the stack limit of the current runtime
+pub fn stack_exhausted() -> bool {
+    let end = STACK_END.try_with(|end| end.get()).unwrap_or(0);
+
+    end != 0 && stack_address() < end
+}
+
+/// Checked on every call and expression, so deep recursion fails instead of overflowing the stack
+pub fn check_stack() -> Result<(), FruError> {
+    if stack_exhausted() {
+        Err(FruError::new_with_kind(
+            FruErrorKind::StackLimitExceeded,
+            "stack limit exceeded, calls are nested too deeply".to_string(),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+pub fn current_runtime() -> Result<Rc<Runtime>, FruError> {
+    Runtime::current().ok_or_else(|| FruError::new("no interpreter is running".to_string()))
+}
+
+// code running outside any runtime has the same authority as the default options
+pub fn require_capability(capability: Capability) -> Result<(), FruError> {
+    match Runtime::current() {
+        Some(runtime) => runtime.options().capabilities.require(capability),
+        None => Ok(()),
+    }
+}
+
+pub fn contracts_enabled() -> bool {
+    match Runtime::current() {
+        Some(runtime) => !runtime.options().skip_contracts,
+        None => true,
+    }
+}
+
+pub fn truthy_conditions_enabled() -> bool {
+    Runtime::current().is_some_and(|runtime| runtime.options().truthy_conditions)
+}
+
+/// Whether warnings are collected, checks that only find warnings can be skipped otherwise
+pub fn warnings_enabled() -> bool {
+    Runtime::current().is_some_and(|runtime| runtime.options().warnings)
+}
+
+/// Collects a warning, nothing happens unless warnings are enabled
+pub fn warn(kind: WarningKind, message: String) {
+    if let Some(runtime) = Runtime::current() {
+        if runtime.options().warnings {
+            runtime.add_warning(kind, message);
+        }
+    }
+}
+
+/// Makes `location` the current one, returns the previous one to restore
+pub fn set_location(location: Option<SourceLocation>) -> Option<SourceLocation> {
+    LOCATION.with(|current| current.replace(location))
+}
+
+/// Reports a use of a declaration with `@deprecated` in its doc comment, once per statement that uses it
+pub fn check_deprecated(doc: Option<&str>, what: impl FnOnce() -> String) {
+    let Some(note) = deprecation_note(doc) else {
+        return;
+    };
+
+    let Some(runtime) = Runtime::current() else {
+        return;
+    };
+
+    let mut message = format!("{} is deprecated", what());
+
+    if !note.is_empty() {
+        message.push_str(&format!(": {}", note));
+    }
+
+    if let Some(location) = LOCATION.with(|current| current.get()) {
+        message.push_str(&format!(" at {}:{}", location.line, location.column));
+    }
+
+    if runtime.options().warnings {
+        runtime.add_warning(WarningKind::Deprecation, message);
+    } else if runtime.reported_deprecations.borrow_mut().insert(message.clone()) {
+        let line = format!("warning: {}", message);
+
+        if !call_hook(&runtime.log_hook, &line) {
+            eprintln!("{}", line);
+        }
+    }
+}
+
+// taken out for the call, so output of the hook itself goes to the standard streams
+fn call_hook(hook: &RefCell<Option<OutputHook>>, line: &str) -> bool {
+    let Some(mut f) = hook.borrow_mut().take() else {
+        return false;
+    };
+
+    f(line);
+    hook.borrow_mut().get_or_insert(f);
+    true
+}
+
+/// Passes a line of program output to the host, false if it has not set `on_print`
+pub fn print_to_host(line: &str) -> bool {
+    Runtime::current().is_some_and(|runtime| call_hook(&runtime.print_hook, line))
+}
+
+/// Passes a diagnostic line to the host, false if it has not set `on_log`
+pub fn log_to_host(line: &str) -> bool {
+    Runtime::current().is_some_and(|runtime| call_hook(&runtime.log_hook, line))
+}
+
+/// Passes an error of a task or timer to the error handler, false if there is none
+/// or the error must stop the program
+pub fn report_scheduled_error(err: &FruError) -> bool {
+    err.kind() == FruErrorKind::Runtime
+        && Runtime::current().is_some_and(|runtime| runtime.report_error(err))
+}
+
+/// Checked by the runner between statements
+pub fn check_limits() -> Result<(), FruError> {
+    CURRENT.with(|current| match &*current.borrow() {
+        Some(runtime) => {
+            if let Some(token) = &runtime.options.cancellation {
+                if token.is_cancelled() {
+                    return Err(FruError::new_with_kind(
+                        FruErrorKind::Cancelled,
+                        "execution was cancelled".to_string(),
+                    ));
+                }
+            }
+
+            match &runtime.memory {
+                Some(counter) => counter.check(0),
+                None => Ok(()),
+            }
+        }
+
+        None => Ok(()),
+    })
+}
+
+/// Charged for every statement, unlike other limits it is not checked while tasks sleep
+pub fn burn_fuel() -> Result<(), FruError> {
+    match Runtime::current() {
+        Some(runtime) => runtime.burn_fuel(),
+        None => Ok(()),
+    }
+}
+
+/// Fails if memory that is already accounted exceeds the limit
+pub fn check_memory() -> Result<(), FruError> {
+    check_allocation(0)
+}
+
+/// Fails if a value of `size` bytes, that is not accounted itself, would not fit into the limit
+pub fn check_allocation(size: usize) -> Result<(), FruError> {
+    match Runtime::current().and_then(|runtime| runtime.memory.clone()) {
+        Some(counter) => counter.check(size),
+        None => Ok(()),
+    }
+}
+
+/// Queues an object that is no longer referenced, its `_drop` is called at the next statement
+pub fn schedule_finalizer(object: FruObject) {
+    if let Some(runtime) = Runtime::current() {
+        runtime.finalizers.borrow_mut().push_back(object);
+    }
+}
+
+pub fn run_finalizers() -> Result<(), FruError> {
+    let runtime = match Runtime::current() {
+        Some(runtime) => runtime,
+        None => return Ok(()),
+    };
+
+    loop {
+        let object = match runtime.finalizers.borrow_mut().pop_front() {
+            Some(object) => object,
+            None => return Ok(()),
+        };
+
+        object.finalize()?;
+    }
+}
@@ -0,0 +1,560 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    mem,
+    rc::Rc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use corosensei::{
+    stack::{DefaultStack, Stack},
+    Coroutine, CoroutineResult, Yielder,
+};
+
+use crate::interpreter::{
+    error::FruError,
+    runtime::{check_limits, on_task_stack, report_scheduled_error},
+    value::{fru_value::FruValue, function::EvaluatedArgumentList},
+};
+
+// longest time the scheduler sleeps without checking for cancellation
+const SLEEP_SLICE: Duration = Duration::from_millis(10);
+
+// stacks are reserved lazily by the OS, debug builds need a lot of stack per call
+const TASK_STACK_SIZE: usize = 16 * 1024 * 1024;
+
+// nested code fails with a stack limit error instead of reaching the guard page of a task stack
+const TASK_STACK_MARGIN: usize = 512 * 1024;
+
+// stacks of finished tasks that are kept for new ones
+const SPARE_STACKS: usize = 8;
+
+// Every task runs on its own stack, so it can be suspended anywhere: by `yield_now()`,
+// or while it waits for a channel, another task, a timer or the host. Code that is not
+// in a task, like the program itself, runs other tasks and timers while it waits instead.
+pub struct Scheduler {
+    ready: RefCell<VecDeque<Rc<Task>>>,
+    waiting: RefCell<Vec<Waiter>>,
+    timers: RefCell<Vec<Timer>>,
+    next_timer_id: Cell<u64>,
+    // task whose code is executing now
+    current: RefCell<Option<Rc<Task>>>,
+    stacks: RefCell<Vec<DefaultStack>>,
+}
+
+type TaskCoroutine = Coroutine<Resume, (), Result<FruValue, FruError>>;
+
+pub struct Task {
+    body: RefCell<Option<TaskBody>>,
+    status: RefCell<TaskStatus>,
+    coroutine: RefCell<Option<TaskCoroutine>>,
+    // start and end of the stack of the coroutine
+    stack: Cell<(usize, usize)>,
+    // set by the coroutine when it starts
+    yielder: Rc<Cell<*const Yielder<Resume, ()>>>,
+}
+
+enum TaskBody {
+    Function(FruValue),
+    Native(Box<dyn FnOnce() -> Result<FruValue, FruError>>),
+}
+
+enum TaskStatus {
+    Pending,
+    Running,
+    Finished(FruValue),
+    Failed,
+}
+
+#[derive(Clone, Copy)]
+enum Resume {
+    Continue,
+    // nothing else can run, so what the task waits for will never happen
+    Deadlock,
+}
+
+/// What a suspended task waits for
+#[derive(Clone)]
+pub struct Wait {
+    // cheap check whether the task should try again
+    ready: Rc<dyn Fn() -> bool>,
+    deadline: Option<Instant>,
+    // can be made ready by other threads or the host, so waiting for it is not a deadlock
+    external: bool,
+    // error of a task that waits for nothing else can make ready
+    stuck: &'static str,
+}
+
+struct Waiter {
+    task: Rc<Task>,
+    wait: Wait,
+}
+
+#[derive(Clone)]
+struct Timer {
+    deadline: Instant,
+    id: u64,
+    interval: Option<Duration>,
+    function: FruValue,
+    handle: TimerHandle,
+}
+
+#[derive(Clone)]
+pub struct TimerHandle {
+    active: Rc<Cell<bool>>,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self {
+            ready: RefCell::new(VecDeque::new()),
+            waiting: RefCell::new(Vec::new()),
+            timers: RefCell::new(Vec::new()),
+            next_timer_id: Cell::new(0),
+            current: RefCell::new(None),
+            stacks: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn spawn(&self, function: FruValue) -> Rc<Task> {
+        self.spawn_body(TaskBody::Function(function))
+    }
+
+    /// Runs `f` as a task, like a function given to `spawn`
+    pub fn spawn_native(
+        &self,
+        f: impl FnOnce() -> Result<FruValue, FruError> + 'static,
+    ) -> Rc<Task> {
+        self.spawn_body(TaskBody::Native(Box::new(f)))
+    }
+
+    fn spawn_body(&self, body: TaskBody) -> Rc<Task> {
+        let task = Rc::new(Task {
+            body: RefCell::new(Some(body)),
+            status: RefCell::new(TaskStatus::Pending),
+            coroutine: RefCell::new(None),
+            stack: Cell::new((0, 0)),
+            yielder: Rc::new(Cell::new(std::ptr::null())),
+        });
+
+        self.ready.borrow_mut().push_back(task.clone());
+
+        task
+    }
+
+    /// Calls `function` after `delay`, and then every `interval` if it is given
+    pub fn add_timer(
+        &self,
+        delay: Duration,
+        interval: Option<Duration>,
+        function: FruValue,
+    ) -> TimerHandle {
+        let handle = TimerHandle {
+            active: Rc::new(Cell::new(true)),
+        };
+
+        let id = self.next_timer_id.get();
+        self.next_timer_id.set(id + 1);
+
+        self.timers.borrow_mut().push(Timer {
+            deadline: Instant::now() + delay,
+            id,
+            interval,
+            function,
+            handle: handle.clone(),
+        });
+
+        handle
+    }
+
+    /// Task whose code is executing now, `None` for code that is not in a task
+    pub fn current_task(&self) -> Option<Rc<Task>> {
+        self.current.borrow().clone()
+    }
+
+    /// Lets other tasks run: a task goes to the end of the queue,
+    /// code that is not in a task runs every task that is ready until it is suspended or finished
+    pub fn yield_now(&self) -> Result<(), FruError> {
+        if let Some(task) = self.current_task() {
+            self.ready.borrow_mut().push_back(task);
+            self.suspend();
+            return Ok(());
+        }
+
+        self.wake_waiting();
+
+        let ready = self.ready.borrow().len();
+
+        for _ in 0..ready {
+            let task = self.ready.borrow_mut().pop_front();
+
+            match task {
+                Some(task) => self.resume(&task, Resume::Continue)?,
+                None => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the value of `poll` once there is one, `wait` tells when it is worth polling again.
+    /// A task is suspended meanwhile, other code runs tasks and timers until `poll` succeeds
+    pub fn wait_until<T>(
+        &self,
+        wait: Wait,
+        mut poll: impl FnMut() -> Result<Option<T>, FruError>,
+    ) -> Result<T, FruError> {
+        loop {
+            if let Some(value) = poll()? {
+                return Ok(value);
+            }
+
+            if let Some(task) = self.current_task() {
+                self.waiting.borrow_mut().push(Waiter {
+                    task,
+                    wait: wait.clone(),
+                });
+
+                if let Resume::Deadlock = self.suspend() {
+                    return FruError::new_res(wait.stuck);
+                }
+
+                continue;
+            }
+
+            if self.run_next()? {
+                continue;
+            }
+
+            match self.next_deadline().into_iter().chain(wait.deadline).min() {
+                Some(deadline) => sleep_until(deadline)?,
+                None if wait.external || self.has_external_waiters() => self.park()?,
+                None => return FruError::new_res(wait.stuck),
+            }
+        }
+    }
+
+    /// Blocks for `duration`, running tasks and timers meanwhile
+    pub fn sleep(&self, duration: Duration) -> Result<(), FruError> {
+        let deadline = Instant::now() + duration;
+
+        self.wait_until(Wait::until(deadline), || {
+            Ok((Instant::now() >= deadline).then_some(()))
+        })
+    }
+
+    /// Runs the next task that is ready or due timer, returns false if there is none
+    pub fn run_next(&self) -> Result<bool, FruError> {
+        self.wake_waiting();
+
+        let task = self.ready.borrow_mut().pop_front();
+
+        if let Some(task) = task {
+            self.resume(&task, Resume::Continue)?;
+            return Ok(true);
+        }
+
+        match self.take_due_timer() {
+            // handlers are tasks, so they can wait too
+            Some(timer) => {
+                let task = self.spawn_body(TaskBody::Function(timer.function));
+                self.ready.borrow_mut().pop_back();
+                self.resume(&task, Resume::Continue)?;
+                Ok(true)
+            }
+
+            None => Ok(false),
+        }
+    }
+
+    /// Like `run_next`, but sleeps until the next timer if nothing is ready yet
+    pub fn wait_next(&self) -> Result<bool, FruError> {
+        loop {
+            if self.run_next()? {
+                return Ok(true);
+            }
+
+            match self.next_deadline() {
+                Some(deadline) => sleep_until(deadline)?,
+                None if self.has_external_waiters() => self.park()?,
+                None => return Ok(false),
+            }
+        }
+    }
+
+    /// Runs tasks and timers until none are left, including the ones spawned meanwhile
+    pub fn run_all(&self) -> Result<(), FruError> {
+        loop {
+            while self.wait_next()? {}
+
+            if !self.fail_stuck()? {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Runs tasks and timers that can proceed now without blocking the thread,
+    /// returns whether some are left, for hosts that drive the interpreter from their own loop
+    pub fn run_ready(&self) -> Result<bool, FruError> {
+        loop {
+            while self.run_next()? {}
+
+            let is_idle = self.next_deadline().is_none() && !self.has_external_waiters();
+
+            if !(is_idle && self.fail_stuck()?) {
+                return Ok(!is_idle);
+            }
+        }
+    }
+
+    /// Parks the thread until it is unparked or the next timer is due
+    pub fn park(&self) -> Result<(), FruError> {
+        let timeout = match self.next_deadline() {
+            Some(deadline) => deadline.saturating_duration_since(Instant::now()),
+            None => SLEEP_SLICE,
+        };
+
+        thread::park_timeout(timeout.min(SLEEP_SLICE));
+
+        check_limits()
+    }
+
+    // tasks that wait while nothing else can run wait for each other, their waits fail
+    fn fail_stuck(&self) -> Result<bool, FruError> {
+        let stuck = mem::take(&mut *self.waiting.borrow_mut());
+
+        for waiter in &stuck {
+            self.resume(&waiter.task, Resume::Deadlock)?;
+        }
+
+        Ok(!stuck.is_empty())
+    }
+
+    fn wake_waiting(&self) {
+        let now = Instant::now();
+
+        let woken: Vec<_> = {
+            let mut waiting = self.waiting.borrow_mut();
+            let (woken, still_waiting) = mem::take(&mut *waiting)
+                .into_iter()
+                .partition(|waiter| waiter.wait.is_ready(now));
+            *waiting = still_waiting;
+            woken
+        };
+
+        self.ready.borrow_mut().extend(woken.into_iter().map(|waiter| waiter.task));
+    }
+
+    fn has_external_waiters(&self) -> bool {
+        self.waiting.borrow().iter().any(|waiter| waiter.wait.external)
+    }
+
+    fn resume(&self, task: &Rc<Task>, input: Resume) -> Result<(), FruError> {
+        let coroutine = task.coroutine.borrow_mut().take();
+
+        let mut coroutine = match coroutine {
+            Some(coroutine) => coroutine,
+            None => self.start(task)?,
+        };
+
+        let previous = self.current.replace(Some(task.clone()));
+        let (base, end) = task.stack.get();
+        let result = on_task_stack(base, end, || coroutine.resume(input));
+        *self.current.borrow_mut() = previous;
+
+        match result {
+            CoroutineResult::Yield(()) => {
+                *task.coroutine.borrow_mut() = Some(coroutine);
+                Ok(())
+            }
+
+            CoroutineResult::Return(result) => {
+                let mut stacks = self.stacks.borrow_mut();
+
+                if stacks.len() < SPARE_STACKS {
+                    stacks.push(coroutine.into_stack());
+                }
+
+                *task.status.borrow_mut() = match &result {
+                    Ok(value) => TaskStatus::Finished(value.clone()),
+                    Err(_) => TaskStatus::Failed,
+                };
+
+                handle_error(result)
+            }
+        }
+    }
+
+    fn start(&self, task: &Task) -> Result<TaskCoroutine, FruError> {
+        let body = task.body.borrow_mut().take();
+        let body = body.ok_or_else(|| FruError::new("task is already running".to_string()))?;
+
+        let stack = match self.stacks.borrow_mut().pop() {
+            Some(stack) => stack,
+            None => DefaultStack::new(TASK_STACK_SIZE)
+                .map_err(|err| FruError::new(format!("cannot allocate a task stack: {}", err)))?,
+        };
+
+        *task.status.borrow_mut() = TaskStatus::Running;
+        task.stack.set((stack.base().get(), stack.limit().get() + TASK_STACK_MARGIN));
+        let yielder = task.yielder.clone();
+
+        Ok(Coroutine::with_stack(stack, move |task_yielder, _| {
+            yielder.set(task_yielder);
+
+            match body {
+                TaskBody::Function(function) => {
+                    function.call(EvaluatedArgumentList { args: Vec::new() })
+                }
+                TaskBody::Native(f) => f(),
+            }
+        }))
+    }
+
+    // switches back to the code that has resumed the current task
+    fn suspend(&self) -> Resume {
+        let task = self.current_task().expect("only tasks can be suspended");
+
+        // SAFETY: the yielder lives on the stack of the task's coroutine, which is running now,
+        // since this code is executing in the task
+        let yielder = unsafe { &*task.yielder.get() };
+
+        yielder.suspend(())
+    }
+
+    fn next_deadline(&self) -> Option<Instant> {
+        let mut timers = self.timers.borrow_mut();
+
+        timers.retain(|timer| timer.handle.is_active());
+
+        let timers = timers.iter().map(|timer| timer.deadline);
+        let waiting = self.waiting.borrow();
+
+        timers.chain(waiting.iter().filter_map(|waiter| waiter.wait.deadline)).min()
+    }
+
+    fn take_due_timer(&self) -> Option<Timer> {
+        let mut timers = self.timers.borrow_mut();
+
+        timers.retain(|timer| timer.handle.is_active());
+
+        let now = Instant::now();
+
+        let (k, _) = timers
+            .iter()
+            .enumerate()
+            .filter(|(_, timer)| timer.deadline <= now)
+            .min_by_key(|(_, timer)| (timer.deadline, timer.id))?;
+
+        let timer = timers.swap_remove(k);
+
+        match timer.interval {
+            // slow handler skips missed ticks instead of firing them in a burst
+            Some(interval) => timers.push(Timer {
+                deadline: (timer.deadline + interval).max(now),
+                ..timer.clone()
+            }),
+
+            None => timer.handle.cancel(),
+        }
+
+        Some(timer)
+    }
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Task {
+    pub fn is_pending(&self) -> bool {
+        matches!(*self.status.borrow(), TaskStatus::Pending)
+    }
+
+    pub fn is_done(&self) -> bool {
+        matches!(
+            *self.status.borrow(),
+            TaskStatus::Finished(_) | TaskStatus::Failed
+        )
+    }
+
+    /// Waits until the task is finished and returns its result
+    pub fn join(self: &Rc<Self>, scheduler: &Scheduler) -> Result<FruValue, FruError> {
+        if scheduler.current_task().is_some_and(|current| Rc::ptr_eq(&current, self)) {
+            return FruError::new_res("task is already running");
+        }
+
+        let task = self.clone();
+        let wait = Wait::new("task waits for itself", move || task.is_done());
+
+        scheduler.wait_until(wait, || match &*self.status.borrow() {
+            TaskStatus::Finished(value) => Ok(Some(value.clone())),
+            TaskStatus::Failed => FruError::new_res("task has failed"),
+            _ => Ok(None),
+        })
+    }
+}
+
+impl Wait {
+    /// `ready` must not change anything, it may be called often
+    pub fn new(stuck: &'static str, ready: impl Fn() -> bool + 'static) -> Wait {
+        Wait {
+            ready: Rc::new(ready),
+            deadline: None,
+            external: false,
+            stuck,
+        }
+    }
+
+    /// Something outside of the interpreter, like another thread, makes `ready` true.
+    /// Such waits don't fail when nothing else can run, the thread is parked instead,
+    /// whoever makes `ready` true should unpark it
+    pub fn external(ready: impl Fn() -> bool + 'static) -> Wait {
+        Wait {
+            external: true,
+            ..Wait::new("", ready)
+        }
+    }
+
+    pub fn until(deadline: Instant) -> Wait {
+        Wait {
+            deadline: Some(deadline),
+            ..Wait::new("", move || Instant::now() >= deadline)
+        }
+    }
+
+    fn is_ready(&self, now: Instant) -> bool {
+        self.deadline.is_some_and(|deadline| deadline <= now) || (self.ready)()
+    }
+}
+
+impl TimerHandle {
+    pub fn cancel(&self) {
+        self.active.set(false);
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active.get()
+    }
+}
+
+// with an error handler, a failed task or timer doesn't stop the others
+fn handle_error(result: Result<FruValue, FruError>) -> Result<(), FruError> {
+    match result {
+        Err(err) if !report_scheduled_error(&err) => Err(err),
+        _ => Ok(()),
+    }
+}
+
+fn sleep_until(deadline: Instant) -> Result<(), FruError> {
+    let now = Instant::now();
+
+    if deadline > now {
+        thread::sleep((deadline - now).min(SLEEP_SLICE));
+    }
+
+    check_limits()
+}
@@ -1,20 +1,33 @@
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+    fmt::{self, Debug},
+    mem::size_of,
+    rc::Rc,
+};
 
 use crate::interpreter::{
-    builtins::functions,
-    builtins::operators,
     error::FruError,
+    helpers::closest_name,
     identifier::{Identifier, OperatorIdentifier},
+    runtime::Allocation,
+    statement::FruStatement,
     value::fru_object::FruObject,
     value::fru_type::FruType,
-    value::fru_value::FruValue,
+    value::fru_value::{fmt_without_cycles, FruValue},
     value::operator::AnyOperator,
 };
+use crate::stdlib::prelude::fru_prelude::Prelude;
 
 pub struct Scope {
     variables: RefCell<HashMap<Identifier, FruValue>>,
+    // declared further down in the block, until then they hide nothing and can't be used
+    uninitialized: RefCell<HashSet<Identifier>>,
     operators: RefCell<HashMap<OperatorIdentifier, AnyOperator>>,
     parent: ScopeAncestor,
+    // `let` and assignments to its variables fail, the host can still set them
+    frozen: Cell<bool>,
+    allocation: Allocation,
 }
 
 enum ScopeAncestor {
@@ -31,29 +44,77 @@ enum ScopeAncestor {
 }
 
 impl Scope {
+    // builtins live in a parent scope, so programs can declare their own `Vec2` or `print`
     pub fn new_global() -> Rc<Scope> {
-        Rc::new(Scope {
-            variables: RefCell::new(functions::builtin_functions()),
-            operators: RefCell::new(operators::builtin_operators()),
+        Scope::new_global_with(Prelude::standard())
+    }
+
+    /// Global scope whose parent holds the values of `prelude`
+    pub fn new_global_with(prelude: Prelude) -> Rc<Scope> {
+        let (variables, operators) = prelude.into_parts();
+
+        Scope::new_with_parent(Rc::new(Scope {
+            variables: RefCell::new(variables),
+            uninitialized: RefCell::new(HashSet::new()),
+            operators: RefCell::new(operators),
             parent: ScopeAncestor::None,
-        })
+            frozen: Cell::new(false),
+            allocation: Allocation::new(size_of::<Scope>()),
+        }))
+    }
+
+    /// Global scope with the same builtin values as `global`, but its own variables for them,
+    /// so assigning to `print` in one of them doesn't change the other
+    pub fn new_global_like(global: &Scope) -> Rc<Scope> {
+        let Some(prelude) = global.parent.scope() else {
+            return Scope::new_global();
+        };
+
+        Scope::new_with_parent(Rc::new(Scope {
+            variables: RefCell::new(prelude.variables.borrow().clone()),
+            uninitialized: RefCell::new(HashSet::new()),
+            operators: RefCell::new(prelude.operators.borrow().clone()),
+            parent: ScopeAncestor::None,
+            frozen: Cell::new(false),
+            allocation: Allocation::new(size_of::<Scope>()),
+        }))
     }
 
     pub fn new_with_parent(parent: Rc<Scope>) -> Rc<Scope> {
         Rc::new(Scope {
             variables: RefCell::new(HashMap::new()),
+            uninitialized: RefCell::new(HashSet::new()),
             operators: RefCell::new(HashMap::new()),
             parent: ScopeAncestor::Parent(parent),
+            frozen: Cell::new(false),
+            allocation: Allocation::new(size_of::<Scope>()),
         })
     }
 
+    /// Scope of a block, variables declared by `let` directly in `body` can't be used above their declaration
+    pub fn new_block(parent: Rc<Scope>, body: &[FruStatement]) -> Rc<Scope> {
+        let scope = Scope::new_with_parent(parent);
+
+        scope.uninitialized.borrow_mut().extend(body.iter().filter_map(|statement| {
+            match statement.unlocated() {
+                FruStatement::Let { ident, .. } => Some(*ident),
+                _ => None,
+            }
+        }));
+
+        scope
+    }
+
     pub fn new_with_object(object: FruObject) -> Rc<Scope> {
         let parent = object.get_type().get_scope();
 
         Rc::new(Scope {
             variables: RefCell::new(HashMap::new()),
+            uninitialized: RefCell::new(HashSet::new()),
             operators: RefCell::new(HashMap::new()),
             parent: ScopeAncestor::Object { object, parent },
+            frozen: Cell::new(false),
+            allocation: Allocation::new(size_of::<Scope>()),
         })
     }
 
@@ -62,8 +123,11 @@ impl Scope {
 
         Rc::new(Scope {
             variables: RefCell::new(HashMap::new()),
+            uninitialized: RefCell::new(HashSet::new()),
             operators: RefCell::new(HashMap::new()),
             parent: ScopeAncestor::Type { type_, parent },
+            frozen: Cell::new(false),
+            allocation: Allocation::new(size_of::<Scope>()),
         })
     }
 
@@ -71,40 +135,126 @@ impl Scope {
         if let Some(var) = self.variables.borrow().get(&ident) {
             Ok(var.clone())
         } else {
+            self.check_initialized(ident)?;
             self.parent.get_variable(ident)
         }
     }
 
+    /// Variable of the prelude that this scope descends from, user variables can't hide it
+    pub fn get_builtin(&self, ident: Identifier) -> Option<FruValue> {
+        match self.parent.scope() {
+            Some(parent) => parent.get_builtin(ident),
+            None => self.variables.borrow().get(&ident).cloned(),
+        }
+    }
+
+    /// Declares a variable, declaring one twice in the same scope is an error, hiding one of a parent scope is not
     pub fn let_variable(&self, ident: Identifier, value: FruValue) -> Result<(), FruError> {
+        self.check_not_frozen(ident)?;
+
         if self.variables.borrow().contains_key(&ident) {
-            return FruError::new_res(format!("variable `{:?}` already exists", ident));
+            return FruError::new_res(format!(
+                "variable `{:?}` already exists in this scope, assign it with `{:?} = ...` or declare it in a nested block",
+                ident, ident
+            ));
         }
 
+        self.uninitialized.borrow_mut().remove(&ident);
+        self.allocation.grow(size_of::<(Identifier, FruValue)>() + value.held_bytes());
         self.variables.borrow_mut().insert(ident, value);
         Ok(())
     }
 
     pub fn set_variable(&self, ident: Identifier, value: FruValue) -> Result<(), FruError> {
-        if let Some(v) = self.variables.borrow_mut().get_mut(&ident) {
-            *v = value;
-            Ok(())
-        } else {
-            self.parent.set_variable(ident, value)
+        self.assign(ident, value, self)
+    }
+
+    // `origin` is the scope the assignment started in, its names are suggested when nothing is found
+    fn assign(&self, ident: Identifier, value: FruValue, origin: &Scope) -> Result<(), FruError> {
+        // not borrowed while the parents are searched, the error lists variables of this scope
+        if self.variables.borrow().contains_key(&ident) {
+            self.check_not_frozen(ident)?;
+            self.let_set_variable(ident, value);
+            return Ok(());
         }
+
+        self.check_initialized(ident)?;
+        self.parent.assign(ident, value, origin)
+    }
+
+    /// Names that can be used from this scope: its variables, the ones of its parents,
+    /// and fields of the object or the type whose method is running
+    pub fn visible_names(&self) -> Vec<Identifier> {
+        let mut names = HashSet::new();
+        let mut scope = Some(self);
+
+        while let Some(current) = scope {
+            names.extend(current.variables.borrow().keys().copied());
+
+            match &current.parent {
+                ScopeAncestor::Object { object, .. } => {
+                    names.extend(object.get_type().get_fields().iter().map(|field| field.ident))
+                }
+
+                ScopeAncestor::Type { type_, .. } => names.extend(type_.get_static_field_idents()),
+
+                ScopeAncestor::None | ScopeAncestor::Parent(_) => {}
+            }
+
+            scope = current.parent.scope().map(|x| x.as_ref());
+        }
+
+        let mut names: Vec<_> = names.into_iter().collect();
+        names.sort_by_key(|ident| ident.to_string());
+        names
+    }
+
+    // from its `let` on, so `let x = x + 1;` reads the outer `x`
+    pub fn start_declaration(&self, ident: Identifier) {
+        self.uninitialized.borrow_mut().remove(&ident);
+    }
+
+    /// Makes scripts unable to declare or assign variables in this scope
+    pub fn freeze(&self) {
+        self.frozen.set(true);
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.frozen.get()
+    }
+
+    pub fn check_not_frozen(&self, ident: Identifier) -> Result<(), FruError> {
+        if self.frozen.get() {
+            return FruError::new_res(format!(
+                "scope is frozen, `{:?}` cannot be declared or assigned in it",
+                ident
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn check_initialized(&self, ident: Identifier) -> Result<(), FruError> {
+        if self.uninitialized.borrow().contains(&ident) {
+            return FruError::new_res(format!(
+                "variable `{:?}` is used before its declaration in this block",
+                ident
+            ));
+        }
+
+        Ok(())
     }
 
     pub fn get_operator(&self, ident: OperatorIdentifier) -> Result<AnyOperator, FruError> {
         if let Some(op) = self.operators.borrow().get(&ident) {
             Ok(op.clone())
         } else {
-            match &self.parent {
-                ScopeAncestor::None => Err(FruError::new(format!(
+            match self.parent.scope() {
+                None => Err(FruError::new(format!(
                     "operator `{:?}` does not exist",
                     ident
                 ))),
-                ScopeAncestor::Parent(parent)
-                | ScopeAncestor::Object { parent, .. }
-                | ScopeAncestor::Type { parent, .. } => parent.get_operator(ident),
+                Some(parent) => parent.get_operator(ident),
             }
         }
     }
@@ -113,16 +263,113 @@ impl Scope {
         self.operators.borrow_mut().insert(ident, op);
     }
 
+    /// Operators visible from this scope, including the ones of its parents
+    pub fn operators(&self) -> Vec<(OperatorIdentifier, AnyOperator)> {
+        let mut operators = HashMap::new();
+        let mut scope = Some(self);
+
+        while let Some(current) = scope {
+            for (ident, op) in current.operators.borrow().iter() {
+                // closer declarations hide the outer ones
+                operators.entry(*ident).or_insert_with(|| op.clone());
+            }
+
+            scope = current.parent.scope().map(|x| x.as_ref());
+        }
+
+        operators.into_iter().collect()
+    }
+
+    /// Variables declared in this scope, without the ones of its parents
+    pub fn variables(&self) -> Vec<(Identifier, FruValue)> {
+        self.variables
+            .borrow()
+            .iter()
+            .map(|(ident, value)| (*ident, value.clone()))
+            .collect()
+    }
+
+    /// Removes the variables declared in this scope and returns them
+    pub fn take_variables(&self) -> Vec<(Identifier, FruValue)> {
+        let variables: Vec<_> = self.variables.borrow_mut().drain().collect();
+        self.allocation.shrink(
+            variables
+                .iter()
+                .map(|(_, value)| size_of::<(Identifier, FruValue)>() + value.held_bytes())
+                .sum(),
+        );
+        variables
+    }
+
     pub fn has_variable(&self, ident: Identifier) -> bool {
         self.variables.borrow().contains_key(&ident)
     }
 
+    /// Whether a parent scope has a variable with this name, builtins don't count
+    pub fn shadows(&self, ident: Identifier) -> bool {
+        let mut scope = self.parent.scope();
+
+        while let Some(current) = scope {
+            scope = current.parent.scope();
+
+            // the root scope holds the builtins
+            if scope.is_some() && current.has_variable(ident) {
+                return true;
+            }
+        }
+
+        false
+    }
+
     pub fn let_set_variable(&self, ident: Identifier, value: FruValue) {
-        self.variables.borrow_mut().insert(ident, value);
+        self.allocation.grow(value.held_bytes());
+
+        let old = self.variables.borrow_mut().insert(ident, value);
+
+        match old {
+            Some(old) => self.allocation.shrink(old.held_bytes()),
+            None => self.allocation.grow(size_of::<(Identifier, FruValue)>()),
+        }
+    }
+}
+
+// variables of the scope and of its ancestors, builtins of the root scope are left out
+impl Debug for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_without_cycles(f, self as *const Scope as *const (), |f| {
+            let mut variables = self.variables();
+            variables.sort_by_key(|(ident, _)| ident.to_string());
+
+            write!(f, "Scope{{")?;
+
+            for (k, (ident, value)) in variables.iter().enumerate() {
+                if k > 0 {
+                    write!(f, ", ")?;
+                }
+
+                write!(f, "{}={:?}", ident, value)?;
+            }
+
+            write!(f, "}}")?;
+
+            match self.parent.scope() {
+                Some(parent) if parent.parent.scope().is_some() => write!(f, " < {:?}", parent),
+                _ => Ok(()),
+            }
+        })
     }
 }
 
 impl ScopeAncestor {
+    fn scope(&self) -> Option<&Rc<Scope>> {
+        match self {
+            ScopeAncestor::None => None,
+            ScopeAncestor::Parent(parent)
+            | ScopeAncestor::Object { parent, .. }
+            | ScopeAncestor::Type { parent, .. } => Some(parent),
+        }
+    }
+
     fn get_variable(&self, ident: Identifier) -> Result<FruValue, FruError> {
         match self {
             ScopeAncestor::None => {
@@ -138,21 +385,37 @@ impl ScopeAncestor {
         }
     }
 
-    fn set_variable(&self, ident: Identifier, value: FruValue) -> Result<(), FruError> {
+    fn assign(&self, ident: Identifier, value: FruValue, origin: &Scope) -> Result<(), FruError> {
         match self {
-            ScopeAncestor::None => {
-                FruError::new_res(format!("variable `{:?}` does not exist", ident))
-            }
+            ScopeAncestor::None => Err(undeclared_variable(ident, origin)),
+
+            ScopeAncestor::Parent(parent) => parent.assign(ident, value, origin),
 
-            ScopeAncestor::Parent(parent) => parent.set_variable(ident, value),
+            // errors of fields are not hidden by the lookup in the parent
+            ScopeAncestor::Object { object, .. }
+                if object.get_type().get_field_k(ident).is_some() =>
+            {
+                object.set_prop(ident, value)
+            }
 
             ScopeAncestor::Object { object, parent } => object
                 .set_prop(ident, value.clone())
-                .or_else(|_| parent.set_variable(ident, value)),
+                .or_else(|_| parent.assign(ident, value, origin)),
 
             ScopeAncestor::Type { type_, parent } => type_
                 .set_prop(ident, value.clone())
-                .or_else(|_| parent.set_variable(ident, value)),
+                .or_else(|_| parent.assign(ident, value, origin)),
         }
     }
 }
+
+fn undeclared_variable(ident: Identifier, origin: &Scope) -> FruError {
+    let error = FruError::new(format!("variable `{:?}` does not exist", ident));
+
+    let names: Vec<String> = origin.visible_names().iter().map(|x| x.to_string()).collect();
+
+    match closest_name(&ident.to_string(), names.iter().map(String::as_str)) {
+        Some(name) => error.with_hint(format!("did you mean `{}`?", name)),
+        None => error,
+    }
+}
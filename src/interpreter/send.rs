@@ -0,0 +1,89 @@
+use std::{
+    sync::mpsc::{channel, Sender},
+    thread::{self, JoinHandle},
+};
+
+use crate::interpreter::{
+    error::FruError, options::InterpreterOptions, runner::Interpreter, runtime::Warning,
+    value::sendable::SendableValue,
+};
+
+type Job = Box<dyn FnOnce(&Interpreter) + Send>;
+
+/// Handle to an interpreter that can be moved and shared between threads, like the ones of async servers.
+///
+/// Values of the interpreter are reference counted without atomics, so they can't leave the thread
+/// they were made on. The interpreter lives on a thread of its own, and every call is sent to it,
+/// which costs a round trip through a channel, values cross it as `SendableValue`.
+pub struct SendInterpreter {
+    jobs: Option<Sender<Job>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl SendInterpreter {
+    pub fn new(options: InterpreterOptions) -> Self {
+        let (jobs, inbox) = channel::<Job>();
+
+        let thread = thread::spawn(move || {
+            let interpreter = Interpreter::new(options);
+
+            for job in inbox {
+                job(&interpreter);
+            }
+        });
+
+        Self {
+            jobs: Some(jobs),
+            thread: Some(thread),
+        }
+    }
+
+    /// Calls `f` with the interpreter on its thread, calls of all threads are made one by one
+    pub fn with<T: Send + 'static>(&self, f: impl FnOnce(&Interpreter) -> T + Send + 'static) -> T {
+        let (reply, result) = channel();
+
+        let job: Job = Box::new(move |interpreter| {
+            // the caller may have stopped waiting
+            let _ = reply.send(f(interpreter));
+        });
+
+        (self.jobs.as_ref())
+            .and_then(|jobs| jobs.send(job).ok())
+            .and_then(|_| result.recv().ok())
+            .expect("interpreter thread has panicked")
+    }
+
+    pub fn execute(&self, source_code: String) -> Result<(), FruError> {
+        self.with(move |interpreter| interpreter.execute(source_code))
+    }
+
+    /// Objects get the type of the same name from the global scope of the interpreter
+    pub fn set_global(&self, ident: &str, value: SendableValue) -> Result<(), FruError> {
+        let ident = ident.to_string();
+        self.with(move |interpreter| {
+            let value = value.into_value(&interpreter.global_scope())?;
+            interpreter.set_global(&ident, value);
+            Ok(())
+        })
+    }
+
+    pub fn get_global(&self, ident: &str) -> Result<SendableValue, FruError> {
+        let ident = ident.to_string();
+        self.with(move |interpreter| SendableValue::try_from(&interpreter.get_global(&ident)?))
+    }
+
+    pub fn warnings(&self) -> Vec<Warning> {
+        self.with(|interpreter| interpreter.warnings())
+    }
+}
+
+impl Drop for SendInterpreter {
+    fn drop(&mut self) {
+        // the thread stops when there are no more jobs to wait for
+        self.jobs.take();
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
@@ -1,14 +1,17 @@
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{collections::HashMap, rc::Rc};
 
 use crate::interpreter::{
     ast_helpers::{RawMethod, RawStaticField},
-    control::Control,
+    control::{check_condition, Control},
     expression::FruExpression,
     identifier::{Identifier, OperatorIdentifier},
+    runtime::{
+        burn_fuel, check_limits, run_finalizers, set_location, warn, warnings_enabled, WarningKind,
+    },
     scope::Scope,
-    value::fru_type::{FruField, FruType, Property, TypeType},
+    value::fru_type::{FruField, FruType, Property, TypeParts, TypeType},
     value::fru_value::FruValue,
-    value::function::FruFunction,
+    value::function::{Contract, FruFunction, SourceLocation},
     value::operator::AnyOperator,
 };
 use crate::stdlib::scope::fru_scope::extract_scope_from_value;
@@ -63,6 +66,7 @@ pub enum FruStatement {
         right_ident: Identifier,
         right_type_ident: Identifier,
         body: Rc<FruStatement>,
+        doc: Option<Rc<str>>,
     },
     Type {
         type_type: TypeType,
@@ -72,11 +76,38 @@ pub enum FruStatement {
         properties: HashMap<Identifier, Property>,
         static_properties: HashMap<Identifier, Property>,
         methods: Vec<RawMethod>,
+        doc: Option<Rc<str>>,
+    },
+    // every parsed statement is wrapped into this, so tools and errors can point at the source
+    Located {
+        location: SourceLocation,
+        end: SourceLocation,
+        statement: Box<FruStatement>,
     },
 }
 
 impl FruStatement {
     pub fn execute(&self, scope: Rc<Scope>) -> Result<(), Control> {
+        if let FruStatement::Located {
+            location,
+            end,
+            statement,
+        } = self
+        {
+            let previous = set_location(Some(*location));
+            let result = statement.execute(scope);
+            set_location(previous);
+
+            return result.map_err(|control| match control {
+                Control::Error(err) => Control::Error(err.at(*location, *end)),
+                other => other,
+            });
+        }
+
+        check_limits()?;
+        burn_fuel()?;
+        run_finalizers()?;
+
         match self {
             FruStatement::SourceCode { body } => {
                 for statement in body {
@@ -85,7 +116,7 @@ impl FruStatement {
             }
 
             FruStatement::Block { body } => {
-                let new_scope = Scope::new_with_parent(scope.clone());
+                let new_scope = Scope::new_block(scope.clone(), body);
 
                 for statement in body {
                     statement.execute(new_scope.clone())?;
@@ -114,9 +145,18 @@ impl FruStatement {
             }
 
             FruStatement::Let { ident, value } => {
+                scope.start_declaration(*ident);
+
                 let v = value.evaluate(scope.clone())?;
 
                 scope.let_variable(*ident, v.fru_clone())?;
+
+                if warnings_enabled() && scope.shadows(*ident) {
+                    warn(
+                        WarningKind::Shadowing,
+                        format!("`{:?}` hides a variable of an outer scope", ident),
+                    );
+                }
             }
 
             FruStatement::Set { ident, value } => {
@@ -136,38 +176,15 @@ impl FruStatement {
                 then_body,
                 else_body,
             } => {
-                let result = condition.evaluate(scope.clone())?;
-
-                match result {
-                    FruValue::Bool(true) => then_body.execute(scope.clone())?,
-
-                    FruValue::Bool(false) => {
-                        if let Some(else_body) = else_body {
-                            else_body.execute(scope.clone())?
-                        }
-                    }
-
-                    _ => {
-                        return Control::new_err(format!(
-                            "Expected `Bool` in if condition, got `{}`",
-                            result.get_type_identifier()
-                        ));
-                    }
+                if check_condition(condition.evaluate(scope.clone())?, "if")? {
+                    then_body.execute(scope.clone())?
+                } else if let Some(else_body) = else_body {
+                    else_body.execute(scope.clone())?
                 }
             }
 
             FruStatement::While { condition, body } => {
-                while {
-                    match condition.evaluate(scope.clone())? {
-                        FruValue::Bool(b) => b,
-                        other => {
-                            return Control::new_err(format!(
-                                "Expected `Bool` in while condition, got `{}`",
-                                other.get_type_identifier()
-                            ));
-                        }
-                    }
-                } {
+                while check_condition(condition.evaluate(scope.clone())?, "while")? {
                     if let Err(signal) = body.execute(scope.clone()) {
                         match signal {
                             Control::Continue => continue,
@@ -197,28 +214,26 @@ impl FruStatement {
                 right_ident,
                 right_type_ident,
                 body,
+                ..
             } => {
+                let operator = |left_ident, right_ident| AnyOperator::Operator {
+                    left_ident,
+                    right_ident,
+                    body: body.clone(),
+                    scope: scope.clone(),
+                };
+
+                // `b op a` runs the same body with the arguments swapped back
                 if *commutative {
                     scope.set_operator(
                         OperatorIdentifier::new(*ident, *right_type_ident, *left_type_ident),
-                        AnyOperator::Operator {
-                            left_ident: *right_ident,
-                            right_ident: *left_ident,
-                            body: body.clone(),
-                            scope: scope.clone(),
-                        }
-                        .clone(),
+                        operator(*right_ident, *left_ident),
                     );
                 }
 
                 scope.set_operator(
                     OperatorIdentifier::new(*ident, *left_type_ident, *right_type_ident),
-                    AnyOperator::Operator {
-                        left_ident: *left_ident,
-                        right_ident: *right_ident,
-                        body: body.clone(),
-                        scope: scope.clone(),
-                    },
+                    operator(*left_ident, *right_ident),
                 );
             }
 
@@ -230,6 +245,7 @@ impl FruStatement {
                 properties,
                 static_properties,
                 methods,
+                doc,
             } => {
                 let mut methods_ = HashMap::new();
                 let mut static_methods_ = HashMap::new();
@@ -239,6 +255,10 @@ impl FruStatement {
                         parameters: method.parameters.clone(),
                         body: method.body.clone(),
                         scope: scope.clone(),
+                        name: Some(method.ident),
+                        location: Some(method.location),
+                        doc: method.doc.clone(),
+                        contract: None,
                     };
                     if method.is_static {
                         static_methods_.insert(method.ident, function);
@@ -247,6 +267,9 @@ impl FruStatement {
                     }
                 }
 
+                Contract::attach(*ident, &mut methods_);
+                Contract::attach(*ident, &mut static_methods_);
+
                 let mut static_fields_evaluated = HashMap::new();
                 for static_field in static_fields {
                     let value = if let Some(v) = &static_field.value {
@@ -260,21 +283,31 @@ impl FruStatement {
 
                 scope.let_variable(
                     *ident,
-                    FruType::new_value(
-                        *ident,
-                        *type_type,
-                        fields.clone(),
-                        RefCell::new(static_fields_evaluated),
-                        properties.clone(),
-                        static_properties.clone(),
-                        methods_,
-                        static_methods_,
-                        scope.clone(),
-                    ),
+                    FruType::new_value(TypeParts {
+                        ident: *ident,
+                        type_type: *type_type,
+                        fields: fields.clone(),
+                        static_fields: static_fields_evaluated,
+                        properties: properties.clone(),
+                        static_properties: static_properties.clone(),
+                        methods: methods_,
+                        static_methods: static_methods_,
+                        scope: scope.clone(),
+                        doc: doc.clone(),
+                    }),
                 )?;
             }
+
+            FruStatement::Located { .. } => unreachable!("located statements are executed above"),
         }
 
         Ok(())
     }
+
+    pub fn unlocated(&self) -> &FruStatement {
+        match self {
+            FruStatement::Located { statement, .. } => statement.unlocated(),
+            other => other,
+        }
+    }
 }
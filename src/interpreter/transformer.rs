@@ -0,0 +1,315 @@
+use std::rc::Rc;
+
+use crate::interpreter::{
+    ast_helpers::{RawMethod, RawStaticField},
+    error::FruError,
+    expression::FruExpression,
+    statement::FruStatement,
+    value::{
+        fru_type::Property,
+        function::{ArgumentList, FormalParameters},
+    },
+};
+
+/// Rebuilds the AST, every method defaults to rebuilding the node from transformed children
+pub trait Transformer {
+    fn transform_statement(&mut self, statement: FruStatement) -> Result<FruStatement, FruError> {
+        walk_statement(self, statement)
+    }
+
+    fn transform_expression(
+        &mut self,
+        expression: FruExpression,
+    ) -> Result<FruExpression, FruError> {
+        walk_expression(self, expression)
+    }
+}
+
+fn transform_body<T: Transformer + ?Sized>(
+    transformer: &mut T,
+    body: Vec<FruStatement>,
+) -> Result<Vec<FruStatement>, FruError> {
+    body.into_iter()
+        .map(|statement| transformer.transform_statement(statement))
+        .collect()
+}
+
+fn transform_boxed<T: Transformer + ?Sized>(
+    transformer: &mut T,
+    expression: FruExpression,
+) -> Result<Box<FruExpression>, FruError> {
+    transformer.transform_expression(expression).map(Box::new)
+}
+
+fn transform_shared<T: Transformer + ?Sized>(
+    transformer: &mut T,
+    statement: Rc<FruStatement>,
+) -> Result<Rc<FruStatement>, FruError> {
+    transformer.transform_statement(Rc::unwrap_or_clone(statement)).map(Rc::new)
+}
+
+fn transform_parameters<T: Transformer + ?Sized>(
+    transformer: &mut T,
+    parameters: FormalParameters,
+) -> Result<FormalParameters, FruError> {
+    let args = parameters
+        .args
+        .into_iter()
+        .map(|(ident, default)| {
+            Ok((
+                ident,
+                default.map(|x| transformer.transform_expression(x)).transpose()?,
+            ))
+        })
+        .collect::<Result<_, FruError>>()?;
+
+    Ok(FormalParameters { args })
+}
+
+fn transform_arguments<T: Transformer + ?Sized>(
+    transformer: &mut T,
+    arguments: ArgumentList,
+) -> Result<ArgumentList, FruError> {
+    let args = arguments
+        .args
+        .into_iter()
+        .map(|(ident, value)| Ok((ident, transformer.transform_expression(value)?)))
+        .collect::<Result<_, FruError>>()?;
+
+    Ok(ArgumentList { args })
+}
+
+fn transform_property<T: Transformer + ?Sized>(
+    transformer: &mut T,
+    property: Property,
+) -> Result<Property, FruError> {
+    Ok(Property {
+        ident: property.ident,
+        getter: property
+            .getter
+            .map(|x| transformer.transform_expression(Rc::unwrap_or_clone(x)).map(Rc::new))
+            .transpose()?,
+        setter: property
+            .setter
+            .map(|(ident, body)| Ok::<_, FruError>((ident, transform_shared(transformer, body)?)))
+            .transpose()?,
+    })
+}
+
+pub fn walk_statement<T: Transformer + ?Sized>(
+    transformer: &mut T,
+    statement: FruStatement,
+) -> Result<FruStatement, FruError> {
+    Ok(match statement {
+        FruStatement::SourceCode { body } => FruStatement::SourceCode {
+            body: transform_body(transformer, body)?,
+        },
+
+        FruStatement::Block { body } => FruStatement::Block {
+            body: transform_body(transformer, body)?,
+        },
+
+        FruStatement::ScopeModifier { what, body } => FruStatement::ScopeModifier {
+            what: transform_boxed(transformer, *what)?,
+            body: transform_body(transformer, body)?,
+        },
+
+        FruStatement::Expression { value } => FruStatement::Expression {
+            value: transform_boxed(transformer, *value)?,
+        },
+
+        FruStatement::Let { ident, value } => FruStatement::Let {
+            ident,
+            value: transform_boxed(transformer, *value)?,
+        },
+
+        FruStatement::Set { ident, value } => FruStatement::Set {
+            ident,
+            value: transform_boxed(transformer, *value)?,
+        },
+
+        FruStatement::SetProp { what, ident, value } => FruStatement::SetProp {
+            what: transform_boxed(transformer, *what)?,
+            ident,
+            value: transform_boxed(transformer, *value)?,
+        },
+
+        FruStatement::If {
+            condition,
+            then_body,
+            else_body,
+        } => FruStatement::If {
+            condition: transform_boxed(transformer, *condition)?,
+            then_body: Box::new(transformer.transform_statement(*then_body)?),
+            else_body: else_body
+                .map(|x| transformer.transform_statement(*x).map(Box::new))
+                .transpose()?,
+        },
+
+        FruStatement::While { condition, body } => FruStatement::While {
+            condition: transform_boxed(transformer, *condition)?,
+            body: Box::new(transformer.transform_statement(*body)?),
+        },
+
+        FruStatement::Return { value } => FruStatement::Return {
+            value: value.map(|x| transform_boxed(transformer, *x)).transpose()?,
+        },
+
+        FruStatement::Break => FruStatement::Break,
+
+        FruStatement::Continue => FruStatement::Continue,
+
+        FruStatement::Operator {
+            ident,
+            commutative,
+            left_ident,
+            left_type_ident,
+            right_ident,
+            right_type_ident,
+            body,
+            doc,
+        } => FruStatement::Operator {
+            ident,
+            commutative,
+            left_ident,
+            left_type_ident,
+            right_ident,
+            right_type_ident,
+            body: transform_shared(transformer, body)?,
+            doc,
+        },
+
+        FruStatement::Type {
+            type_type,
+            ident,
+            fields,
+            static_fields,
+            properties,
+            static_properties,
+            methods,
+            doc,
+        } => FruStatement::Type {
+            type_type,
+            ident,
+            fields,
+            static_fields: static_fields
+                .into_iter()
+                .map(|field| {
+                    Ok(RawStaticField {
+                        ident: field.ident,
+                        value: field.value.map(|x| transform_boxed(transformer, *x)).transpose()?,
+                    })
+                })
+                .collect::<Result<_, FruError>>()?,
+            properties: properties
+                .into_iter()
+                .map(|(ident, x)| Ok((ident, transform_property(transformer, x)?)))
+                .collect::<Result<_, FruError>>()?,
+            static_properties: static_properties
+                .into_iter()
+                .map(|(ident, x)| Ok((ident, transform_property(transformer, x)?)))
+                .collect::<Result<_, FruError>>()?,
+            methods: methods
+                .into_iter()
+                .map(|method| {
+                    Ok(RawMethod {
+                        parameters: transform_parameters(transformer, method.parameters)?,
+                        body: transform_shared(transformer, method.body)?,
+                        ..method
+                    })
+                })
+                .collect::<Result<_, FruError>>()?,
+            doc,
+        },
+
+        FruStatement::Located {
+            location,
+            end,
+            statement,
+        } => FruStatement::Located {
+            location,
+            end,
+            statement: Box::new(transformer.transform_statement(*statement)?),
+        },
+    })
+}
+
+pub fn walk_expression<T: Transformer + ?Sized>(
+    transformer: &mut T,
+    expression: FruExpression,
+) -> Result<FruExpression, FruError> {
+    Ok(match expression {
+        FruExpression::Literal { .. }
+        | FruExpression::Variable { .. }
+        | FruExpression::ScopeAccessor => expression,
+
+        FruExpression::Function {
+            args,
+            body,
+            name,
+            location,
+            doc,
+        } => FruExpression::Function {
+            args: transform_parameters(transformer, args)?,
+            body: transform_shared(transformer, body)?,
+            name,
+            location,
+            doc,
+        },
+
+        FruExpression::Block { body, expr } => FruExpression::Block {
+            body: transform_body(transformer, body)?,
+            expr: transform_boxed(transformer, *expr)?,
+        },
+
+        FruExpression::ScopeModifier { what, body, expr } => FruExpression::ScopeModifier {
+            what: transform_boxed(transformer, *what)?,
+            body: transform_body(transformer, body)?,
+            expr: transform_boxed(transformer, *expr)?,
+        },
+
+        FruExpression::Call { what, args } => FruExpression::Call {
+            what: transform_boxed(transformer, *what)?,
+            args: transform_arguments(transformer, args)?,
+        },
+
+        FruExpression::CurryCall { what, args } => FruExpression::CurryCall {
+            what: transform_boxed(transformer, *what)?,
+            args: transform_arguments(transformer, args)?,
+        },
+
+        FruExpression::Instantiation { what, args } => FruExpression::Instantiation {
+            what: transform_boxed(transformer, *what)?,
+            args: transform_arguments(transformer, args)?,
+        },
+
+        FruExpression::PropAccess { what, ident } => FruExpression::PropAccess {
+            what: transform_boxed(transformer, *what)?,
+            ident,
+        },
+
+        FruExpression::Binary {
+            operator,
+            left,
+            right,
+        } => FruExpression::Binary {
+            operator,
+            left: transform_boxed(transformer, *left)?,
+            right: transform_boxed(transformer, *right)?,
+        },
+
+        FruExpression::If {
+            condition,
+            then_body,
+            else_body,
+        } => FruExpression::If {
+            condition: transform_boxed(transformer, *condition)?,
+            then_body: transform_boxed(transformer, *then_body)?,
+            else_body: transform_boxed(transformer, *else_body)?,
+        },
+
+        FruExpression::Import { path } => FruExpression::Import {
+            path: transform_boxed(transformer, *path)?,
+        },
+    })
+}
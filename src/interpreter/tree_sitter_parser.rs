@@ -1,23 +1,28 @@
-use std::{boxed::Box, collections::hash_map::Entry, collections::HashMap, rc::Rc, str::Utf8Error};
+use std::{
+    boxed::Box, cell::RefCell, collections::hash_map::Entry, collections::HashMap, rc::Rc,
+    str::Utf8Error,
+};
 
 use macros::static_ident;
 use snailquote::unescape;
 use thiserror::Error;
-use tree_sitter::{Node, Parser, Range};
+use tree_sitter::{Node, Parser, Range, Tree};
 use tree_sitter_frugurt;
 
 use crate::interpreter::{
     ast_helpers::{RawMethod, RawStaticField},
     expression::FruExpression,
     helpers::WrappingExtension,
-    identifier::Identifier,
+    identifier::{id, Identifier},
+    runtime::stack_exhausted,
     statement::FruStatement,
     value::{
         fru_type::{FruField, Property, TypeType},
         fru_value::FruValue,
-        function::{ArgumentList, FormalParameters},
+        function::{ArgumentList, FormalParameters, SourceLocation},
     },
 };
+use crate::stdlib::sys::fru_sys::cfg_matches;
 
 #[derive(Error, Debug)]
 pub enum ParseError {
@@ -79,6 +84,30 @@ pub enum ParseError {
     },
 }
 
+impl ParseError {
+    pub fn range(&self) -> Range {
+        match self {
+            ParseError::MissingAst { position, .. }
+            | ParseError::InvalidAst { position, .. }
+            | ParseError::Utf8Error { position, .. }
+            | ParseError::ParsingError { position }
+            | ParseError::Error { position, .. } => *position,
+        }
+    }
+
+    /// Description of the error without its position
+    pub fn message(&self) -> String {
+        match self {
+            ParseError::MissingAst { name, .. } => {
+                format!("node {} is not provided by tree-sitter-frugurt", name)
+            }
+            ParseError::InvalidAst { error, .. } | ParseError::Error { error, .. } => error.clone(),
+            ParseError::Utf8Error { .. } => "utf8 decoding error".to_string(),
+            ParseError::ParsingError { .. } => "parsing error".to_string(),
+        }
+    }
+}
+
 enum TypeMember {
     NormalField(FruField),
     StaticField(RawStaticField),
@@ -90,11 +119,21 @@ enum TypeMember {
 struct NodeWrapper<'a> {
     node: Node<'a>,
     source: &'a [u8],
+    // when parsing with recovery, broken statements are reported here and skipped
+    errors: Option<&'a RefCell<Vec<ParseError>>>,
 }
 
 impl<'a> NodeWrapper<'a> {
     fn new(node: Node<'a>, source: &'a [u8]) -> Self {
-        Self { node, source }
+        Self {
+            node,
+            source,
+            errors: None,
+        }
+    }
+
+    fn wrap(&self, node: Node<'a>) -> Self {
+        Self { node, ..*self }
     }
 
     fn grammar_name(&self) -> &str {
@@ -105,6 +144,78 @@ impl<'a> NodeWrapper<'a> {
         self.node.range()
     }
 
+    /// Condition of a `/// @cfg(...)` line before the node, `false` when the node is left out
+    fn cfg_enabled(&self) -> Result<bool, ParseError> {
+        let Some(doc) = self.doc_comment()? else {
+            return Ok(true);
+        };
+
+        for line in doc.lines() {
+            let Some(condition) = line.trim().strip_prefix("@cfg") else {
+                continue;
+            };
+
+            let enabled = (condition.trim().strip_prefix('(').and_then(|x| x.strip_suffix(')')))
+                .ok_or_else(|| "expected `@cfg(condition)`".to_string())
+                .and_then(cfg_matches)
+                .map_err(|error| ParseError::Error {
+                    position: self.range(),
+                    error,
+                })?;
+
+            if !enabled {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Text of `///` comments that directly precede the node
+    fn doc_comment(&self) -> Result<Option<Rc<str>>, ParseError> {
+        let mut lines = Vec::new();
+        let mut current = self.node.prev_sibling();
+
+        while let Some(node) = current {
+            let text = self.wrap(node).text()?;
+
+            if !node.is_extra() || !text.starts_with("///") {
+                break;
+            }
+
+            let line = &text[3..];
+            lines.push(line.strip_prefix(' ').unwrap_or(line).trim_end());
+
+            current = node.prev_sibling();
+        }
+
+        if lines.is_empty() {
+            return Ok(None);
+        }
+
+        lines.reverse();
+
+        Ok(Some(lines.join("\n").into()))
+    }
+
+    fn location(&self) -> SourceLocation {
+        let point = self.node.start_position();
+
+        SourceLocation {
+            line: point.row + 1,
+            column: point.column + 1,
+        }
+    }
+
+    fn end_location(&self) -> SourceLocation {
+        let point = self.node.end_position();
+
+        SourceLocation {
+            line: point.row + 1,
+            column: point.column + 1,
+        }
+    }
+
     fn text(&self) -> Result<&'a str, ParseError> {
         self.node.utf8_text(self.source).map_err(|x| ParseError::Utf8Error {
             position: self.node.range(),
@@ -118,7 +229,7 @@ impl<'a> NodeWrapper<'a> {
 
     fn get_child(&self, name: &str) -> Result<Self, ParseError> {
         match self.node.child_by_field_name(name) {
-            Some(x) => Ok(Self::new(x, self.source)),
+            Some(x) => Ok(self.wrap(x)),
 
             None => Err(ParseError::MissingAst {
                 position: self.node.range(),
@@ -158,84 +269,199 @@ impl<'a> NodeWrapper<'a> {
     ) -> Result<Vec<T>, ParseError> {
         self.node
             .children_by_field_name(name, &mut self.node.walk())
-            .map(|x| parser(Self::new(x, self.source)))
+            .map(|x| parser(self.wrap(x)))
             .collect()
     }
 
+    fn parse_body(self) -> Result<Vec<FruStatement>, ParseError> {
+        let errors = match self.errors {
+            Some(errors) => errors,
+            None => return self.parse_children("body", parse_statement),
+        };
+
+        let mut body = Vec::new();
+
+        for node in self.node.children_by_field_name("body", &mut self.node.walk()) {
+            match parse_statement(self.wrap(node)) {
+                Ok(statement) => body.push(statement),
+
+                // errors inside of the tree are already reported
+                Err(_) if node.has_error() => {}
+
+                Err(err) => errors.borrow_mut().push(err),
+            }
+        }
+
+        Ok(body)
+    }
+
     fn parse_optional_child<T>(
         self,
         name: &str,
         parser: impl Fn(Self) -> Result<T, ParseError>,
     ) -> Result<Option<T>, ParseError> {
         match self.node.child_by_field_name(name) {
-            Some(x) => Ok(Some(parser(Self::new(x, self.source))?)),
+            Some(x) => Ok(Some(parser(self.wrap(x))?)),
             None => Ok(None),
         }
     }
 }
 
 pub fn parse(data: String) -> Result<Box<FruStatement>, ParseError> {
-    let source = data.as_bytes();
+    let tree = parse_tree(&data)?;
+
+    parse_statement(NodeWrapper::new(tree.root_node(), data.as_bytes())).map(Box::new)
+}
+
+/// Syntax tree with positions of every token, for tools that need more than the AST
+pub fn parse_tree(data: &str) -> Result<Tree, ParseError> {
+    let tree = parse_tree_with_errors(data);
+
+    match collect_errors(tree.root_node()).into_iter().next() {
+        Some(err) => Err(err),
+        None => Ok(tree),
+    }
+}
+
+/// AST of the parts of the source that could be parsed and every error,
+/// broken statements are left out, so tools can report all problems at once
+pub fn parse_with_recovery(data: String) -> (Box<FruStatement>, Vec<ParseError>) {
+    let tree = parse_tree_with_errors(&data);
+    let errors = RefCell::new(collect_errors(tree.root_node()));
+
+    let root = NodeWrapper {
+        errors: Some(&errors),
+        ..NodeWrapper::new(tree.root_node(), data.as_bytes())
+    };
+
+    let ast = match parse_statement(root) {
+        Ok(ast) => ast,
+
+        Err(err) => {
+            if !tree.root_node().has_error() {
+                errors.borrow_mut().push(err);
+            }
+
+            FruStatement::SourceCode { body: Vec::new() }
+        }
+    };
+
+    (Box::new(ast), errors.into_inner())
+}
 
+fn parse_tree_with_errors(data: &str) -> Tree {
     let mut parser = Parser::new();
 
     parser // TODO: load grammar one time
         .set_language(&tree_sitter_frugurt::language())
         .expect("Error loading Frugurt grammar");
 
-    let tree = parser.parse(source, None).unwrap();
+    parser.parse(data.as_bytes(), None).unwrap()
+}
 
-    let root = tree.root_node();
+// every ERROR and MISSING node, in order of appearance
+fn collect_errors(root: Node) -> Vec<ParseError> {
+    let mut errors = Vec::new();
 
-    if root.has_error() {
-        return Err(search_for_errors(root));
+    if !root.has_error() {
+        return errors;
     }
 
-    parse_statement(NodeWrapper::new(root, source)).map(Box::new)
-}
-
-fn search_for_errors(ast: Node) -> ParseError {
-    let mut cur = ast.walk();
+    let mut cursor = root.walk();
 
     loop {
-        if cur.node().is_error() || cur.node().is_missing() {
-            return ParseError::ParsingError {
-                position: cur.node().range(),
-            };
-        }
+        let node = cursor.node();
 
-        if cur.node().has_error() {
-            cur.goto_first_child();
-            continue;
+        let descend = if node.is_missing() {
+            errors.push(ParseError::Error {
+                position: node.range(),
+                error: format!("missing `{}`", node.kind()),
+            });
+            false
+        } else if node.is_error() {
+            errors.push(ParseError::ParsingError {
+                position: node.range(),
+            });
+            false
         } else {
-            cur.goto_next_sibling();
+            node.has_error()
+        };
+
+        if descend && cursor.goto_first_child() {
+            continue;
+        }
+
+        while !cursor.goto_next_sibling() {
+            if !cursor.goto_parent() {
+                return errors;
+            }
         }
     }
 }
 
+// parsing is recursive too, code nested deeper than the stack limit allows is rejected
+fn check_nesting(ast: &NodeWrapper) -> Result<(), ParseError> {
+    if stack_exhausted() {
+        return Err(ParseError::Error {
+            position: ast.range(),
+            error: "code is nested too deeply".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
 fn parse_statement(ast: NodeWrapper) -> Result<FruStatement, ParseError> {
+    check_nesting(&ast)?;
+
+    // statements for other platforms are left out when the code is loaded
+    if !ast.cfg_enabled()? {
+        return Ok(FruStatement::Block { body: Vec::new() });
+    }
+
     let result_statement = match ast.grammar_name() {
-        "source_file" => FruStatement::SourceCode {
-            body: ast.parse_children("body", parse_statement)?,
-        },
+        "source_file" => {
+            return Ok(FruStatement::SourceCode {
+                body: ast.parse_body()?,
+            });
+        }
 
         "block_statement" => FruStatement::Block {
-            body: ast.parse_children("body", parse_statement)?,
+            body: ast.parse_body()?,
         },
 
         "scope_modifier_statement" => FruStatement::ScopeModifier {
             what: ast.parse_child_expression("what")?.wrap_box(),
-            body: ast.parse_children("body", parse_statement)?,
+            body: ast.parse_body()?,
         },
 
         "expression_statement" => FruStatement::Expression {
             value: ast.parse_child_expression("value")?.wrap_box(),
         },
 
-        "let_statement" => FruStatement::Let {
-            ident: ast.get_child_ident("ident")?,
-            value: ast.parse_child_expression("value")?.wrap_box(),
-        },
+        "let_statement" => {
+            let ident = ast.get_child_ident("ident")?;
+            let mut value = ast.parse_child_expression("value")?;
+
+            // `let f = fn() {}` gives the function its name
+            if let FruExpression::Function {
+                name: name @ None,
+                doc,
+                ..
+            } = &mut value
+            {
+                *name = Some(ident);
+
+                if let Some(comment) = ast.doc_comment()? {
+                    *doc = Some(comment);
+                }
+            }
+
+            FruStatement::Let {
+                ident,
+                value: value.wrap_box(),
+            }
+        }
 
         "set_statement" => FruStatement::Set {
             ident: ast.get_child_ident("ident")?,
@@ -291,6 +517,7 @@ fn parse_statement(ast: NodeWrapper) -> Result<FruStatement, ParseError> {
                 right_ident: ast.get_child_ident("right_ident")?,
                 right_type_ident,
                 body: ast.parse_child("body", parse_function_body)?.wrap_rc(),
+                doc: ast.doc_comment()?,
             }
         }
 
@@ -358,6 +585,7 @@ fn parse_statement(ast: NodeWrapper) -> Result<FruStatement, ParseError> {
                 properties,
                 static_properties,
                 methods,
+                doc: ast.doc_comment()?,
             }
         }
 
@@ -369,21 +597,33 @@ fn parse_statement(ast: NodeWrapper) -> Result<FruStatement, ParseError> {
         }
     };
 
-    Ok(result_statement)
+    Ok(FruStatement::Located {
+        location: ast.location(),
+        end: ast.end_location(),
+        statement: result_statement.wrap_box(),
+    })
 }
 
 fn parse_expression(ast: NodeWrapper) -> Result<FruExpression, ParseError> {
+    check_nesting(&ast)?;
+
     let result_expression = match ast.grammar_name() {
         "nah_literal" => FruExpression::Literal {
             value: FruValue::Nah,
         },
 
         "number_literal" => FruExpression::Literal {
-            value: FruValue::Number(ast.text()?.parse().unwrap()),
+            value: FruValue::Number(ast.text()?.parse().map_err(|_| ParseError::InvalidAst {
+                position: ast.range(),
+                error: format!("invalid number `{}`", ast.text().unwrap_or_default()),
+            })?),
         },
 
         "bool_literal" => FruExpression::Literal {
-            value: FruValue::Bool(ast.text()?.parse().unwrap()),
+            value: FruValue::Bool(ast.text()?.parse().map_err(|_| ParseError::InvalidAst {
+                position: ast.range(),
+                error: format!("invalid bool `{}`", ast.text().unwrap_or_default()),
+            })?),
         },
 
         "string_literal" => match unescape(&ast.text()?.replace("\\\n", "\n")) {
@@ -405,21 +645,28 @@ fn parse_expression(ast: NodeWrapper) -> Result<FruExpression, ParseError> {
 
         "scope_expression" => FruExpression::ScopeAccessor,
 
-        "function_expression" => FruExpression::Function {
-            args: ast.parse_child("parameters", parse_formal_parameters)?,
-            body: ast.parse_child("body", parse_function_body)?.wrap_rc(),
-        },
+        "function_expression" => {
+            let body = ast.parse_child("body", parse_function_body)?;
+
+            FruExpression::Function {
+                args: ast.parse_child("parameters", parse_formal_parameters)?,
+                doc: leading_docstring(&body),
+                body: body.wrap_rc(),
+                name: None,
+                location: ast.location(),
+            }
+        }
 
         "parenthesized_expression" => ast.parse_child_expression("expr")?,
 
         "block_expression" => FruExpression::Block {
-            body: ast.parse_children("body", parse_statement)?,
+            body: ast.parse_body()?,
             expr: ast.parse_child_expression("expr")?.wrap_box(),
         },
 
         "scope_modifier_expression" => FruExpression::ScopeModifier {
             what: ast.parse_child_expression("what")?.wrap_box(),
-            body: ast.parse_children("body", parse_statement)?,
+            body: ast.parse_body()?,
             expr: ast.parse_child_expression("expr")?.wrap_box(),
         },
 
@@ -443,11 +690,7 @@ fn parse_expression(ast: NodeWrapper) -> Result<FruExpression, ParseError> {
             ident: ast.get_child_ident("ident")?,
         },
 
-        "binary_expression" => FruExpression::Binary {
-            operator: ast.get_child_ident("operator")?,
-            left: ast.parse_child_expression("left")?.wrap_box(),
-            right: ast.parse_child_expression("right")?.wrap_box(),
-        },
+        "binary_expression" => parse_binary_expression(ast)?,
 
         "if_expression" => FruExpression::If {
             condition: ast.parse_child_expression("condition")?.wrap_box(),
@@ -470,6 +713,85 @@ fn parse_expression(ast: NodeWrapper) -> Result<FruExpression, ParseError> {
     Ok(result_expression)
 }
 
+// `a < b < c` is parsed as `(a < b) < c`, but means `a < b && b < c`
+fn parse_binary_expression(ast: NodeWrapper) -> Result<FruExpression, ParseError> {
+    const CHAINED: [Identifier; 4] = [id::LESS, id::LESS_EQ, id::GREATER, id::GREATER_EQ];
+
+    let mut operators = vec![ast.get_child_ident("operator")?];
+    let mut operands = vec![ast.get_child("right")?];
+    let mut left = ast.get_child("left")?;
+
+    while CHAINED.contains(&operators[0])
+        && left.grammar_name() == "binary_expression"
+        && CHAINED.contains(&left.get_child_ident("operator")?)
+    {
+        operators.insert(0, left.get_child_ident("operator")?);
+        operands.insert(0, left.get_child("right")?);
+        left = left.get_child("left")?;
+    }
+
+    operands.insert(0, left);
+
+    if operators.len() == 1 {
+        return Ok(FruExpression::Binary {
+            operator: operators[0],
+            left: parse_expression(operands[0])?.wrap_box(),
+            right: parse_expression(operands[1])?.wrap_box(),
+        });
+    }
+
+    // every operand but the last is stored, so each one is evaluated once and in order,
+    // `a < b < c` is `{ let s0 = a; { let s1 = b; if s0 < s1 { s1 < c } else { false } } }`,
+    // so operands after a false comparison are not evaluated
+    let stored: Vec<_> =
+        (0..operators.len()).map(|k| Identifier::new(&format!("$chain{}", k))).collect();
+
+    let mut operands = operands.into_iter().map(parse_expression).collect::<Result<Vec<_>, _>>()?;
+
+    let store = |k: usize, operand: FruExpression, expr: FruExpression| FruExpression::Block {
+        body: vec![FruStatement::Let {
+            ident: stored[k],
+            value: operand.wrap_box(),
+        }],
+        expr: expr.wrap_box(),
+    };
+
+    let variable = |k: usize| FruExpression::Variable { ident: stored[k] }.wrap_box();
+
+    let last = operators.len() - 1;
+
+    let mut expr = FruExpression::Binary {
+        operator: operators[last],
+        left: variable(last),
+        right: operands.pop().expect("chain has at least three operands").wrap_box(),
+    };
+
+    for k in (0..last).rev() {
+        let condition = FruExpression::Binary {
+            operator: operators[k],
+            left: variable(k),
+            right: variable(k + 1),
+        };
+
+        let chained = FruExpression::If {
+            condition: condition.wrap_box(),
+            then_body: expr.wrap_box(),
+            else_body: FruExpression::Literal {
+                value: FruValue::Bool(false),
+            }
+            .wrap_box(),
+        };
+
+        expr = store(
+            k + 1,
+            operands.pop().expect("one operand per comparison"),
+            chained,
+        );
+    }
+
+    Ok(store(0, operands.pop().expect("first operand"), expr))
+}
+
 fn parse_maybe_typed_ident(
     ast: NodeWrapper,
 ) -> Result<(Identifier, Option<Identifier>), ParseError> {
@@ -485,8 +807,14 @@ fn parse_function_body(ast: NodeWrapper) -> Result<FruStatement, ParseError> {
     Ok(match ast.grammar_name() {
         "block_statement" => parse_statement(ast)?,
 
-        "block_expression" => FruStatement::Return {
-            value: Some(parse_expression(ast)?.wrap_box()),
+        // located at its resulting expression, so errors in `fn(x) { x + y }` point at it
+        "block_expression" => FruStatement::Located {
+            location: ast.get_child("expr")?.location(),
+            end: ast.get_child("expr")?.end_location(),
+            statement: FruStatement::Return {
+                value: Some(parse_expression(ast)?.wrap_box()),
+            }
+            .wrap_box(),
         },
 
         unexpected => {
@@ -535,6 +863,7 @@ fn parse_field(ast: NodeWrapper) -> Result<TypeMember, ParseError> {
             is_public,
             ident,
             type_ident,
+            doc: ast.doc_comment()?,
         })
     })
 }
@@ -616,14 +945,43 @@ fn parse_impl(ast: NodeWrapper) -> Result<Vec<RawMethod>, ParseError> {
 }
 
 fn parse_method(ast: NodeWrapper) -> Result<RawMethod, ParseError> {
+    let body = ast.parse_child("body", parse_function_body)?;
+
     Ok(RawMethod {
         is_static: ast.get_child("static").is_ok(),
         ident: ast.get_child_ident("ident")?,
         parameters: ast.parse_child("parameters", parse_formal_parameters)?,
-        body: ast.parse_child("body", parse_function_body)?.wrap_rc(),
+        doc: ast.doc_comment()?.or_else(|| leading_docstring(&body)),
+        body: body.wrap_rc(),
+        location: ast.location(),
     })
 }
 
+/// String literal that starts a function body documents the function
+fn leading_docstring(body: &FruStatement) -> Option<Rc<str>> {
+    let first = match body.unlocated() {
+        FruStatement::Block { body } => body.first()?,
+
+        FruStatement::Return { value: Some(value) } => match &**value {
+            FruExpression::Block { body, .. } => body.first()?,
+            _ => return None,
+        },
+
+        _ => return None,
+    };
+
+    match first.unlocated() {
+        FruStatement::Expression { value } => match &**value {
+            FruExpression::Literal {
+                value: FruValue::String(doc),
+            } => Some(doc.as_str().into()),
+            _ => None,
+        },
+
+        _ => None,
+    }
+}
+
 fn parse_formal_parameters(ast: NodeWrapper) -> Result<FormalParameters, ParseError> {
     let mut args = ast.parse_children("args", |x| Ok((parse_formal_parameter(x)?, x)))?;
 
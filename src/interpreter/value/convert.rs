@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+
+use crate::interpreter::{error::FruError, value::fru_value::FruValue};
+use crate::stdlib::{
+    tree::fru_tree::FruTreeMap,
+    tuple::fru_tuple::{extract_tuple_items, FruTuple},
+};
+
+/// Rust value that can be passed to scripts, `Vec` becomes `Tuple` and `HashMap` becomes `TreeMap`
+pub trait IntoFruValue {
+    fn into_fru_value(self) -> FruValue;
+}
+
+/// Rust value that can be made of what scripts return
+pub trait FromFruValue: Sized {
+    fn from_fru_value(value: FruValue) -> Result<Self, FruError>;
+}
+
+/// Arguments of a call from Rust, a tuple of values that are `IntoFruValue`
+pub trait IntoFruArguments {
+    fn into_fru_arguments(self) -> Vec<FruValue>;
+}
+
+fn expected<T>(what: &str, value: &FruValue) -> Result<T, FruError> {
+    FruError::new_res(format!(
+        "expected {}, got `{}`",
+        what,
+        value.get_type_identifier()
+    ))
+}
+
+impl IntoFruValue for FruValue {
+    fn into_fru_value(self) -> FruValue {
+        self
+    }
+}
+
+impl FromFruValue for FruValue {
+    fn from_fru_value(value: FruValue) -> Result<Self, FruError> {
+        Ok(value)
+    }
+}
+
+impl IntoFruValue for () {
+    fn into_fru_value(self) -> FruValue {
+        FruValue::Nah
+    }
+}
+
+// the result is ignored, so functions that return anything can be called for their effects
+impl FromFruValue for () {
+    fn from_fru_value(_: FruValue) -> Result<Self, FruError> {
+        Ok(())
+    }
+}
+
+impl IntoFruValue for bool {
+    fn into_fru_value(self) -> FruValue {
+        FruValue::Bool(self)
+    }
+}
+
+impl FromFruValue for bool {
+    fn from_fru_value(value: FruValue) -> Result<Self, FruError> {
+        match value {
+            FruValue::Bool(b) => Ok(b),
+            other => expected("`Bool`", &other),
+        }
+    }
+}
+
+impl IntoFruValue for String {
+    fn into_fru_value(self) -> FruValue {
+        FruValue::String(self)
+    }
+}
+
+impl IntoFruValue for &str {
+    fn into_fru_value(self) -> FruValue {
+        FruValue::String(self.to_string())
+    }
+}
+
+impl FromFruValue for String {
+    fn from_fru_value(value: FruValue) -> Result<Self, FruError> {
+        match value {
+            FruValue::String(s) => Ok(s),
+            other => expected("`String`", &other),
+        }
+    }
+}
+
+impl IntoFruValue for f64 {
+    fn into_fru_value(self) -> FruValue {
+        FruValue::Number(self)
+    }
+}
+
+impl FromFruValue for f64 {
+    fn from_fru_value(value: FruValue) -> Result<Self, FruError> {
+        match value {
+            FruValue::Number(n) => Ok(n),
+            other => expected("`Number`", &other),
+        }
+    }
+}
+
+impl IntoFruValue for f32 {
+    fn into_fru_value(self) -> FruValue {
+        FruValue::Number(self as f64)
+    }
+}
+
+impl FromFruValue for f32 {
+    fn from_fru_value(value: FruValue) -> Result<Self, FruError> {
+        f64::from_fru_value(value).map(|n| n as f32)
+    }
+}
+
+// numbers are floats, integers are converted only when they are whole and fit
+macro_rules! integer_conversions {
+    ($($t:ty),*) => {
+        $(
+            impl IntoFruValue for $t {
+                fn into_fru_value(self) -> FruValue {
+                    FruValue::Number(self as f64)
+                }
+            }
+
+            impl FromFruValue for $t {
+                fn from_fru_value(value: FruValue) -> Result<Self, FruError> {
+                    let n = f64::from_fru_value(value)?;
+
+                    // `MAX as f64` rounds up to the next power of two for 64-bit types,
+                    // so the upper bound is that power of two, excluded
+                    let signed = <$t>::MIN != 0;
+                    let end = 2f64.powi(<$t>::BITS as i32 - signed as i32);
+
+                    if n.fract() == 0.0 && n >= <$t>::MIN as f64 && n < end {
+                        Ok(n as $t)
+                    } else {
+                        FruError::new_res(format!(
+                            "expected an integer that fits into `{}`, got {}",
+                            stringify!($t),
+                            n
+                        ))
+                    }
+                }
+            }
+        )*
+    };
+}
+
+integer_conversions!(i32, i64, u32, u64, usize);
+
+impl<T: IntoFruValue> IntoFruValue for Option<T> {
+    fn into_fru_value(self) -> FruValue {
+        self.map_or(FruValue::Nah, T::into_fru_value)
+    }
+}
+
+impl<T: FromFruValue> FromFruValue for Option<T> {
+    fn from_fru_value(value: FruValue) -> Result<Self, FruError> {
+        match value {
+            FruValue::Nah => Ok(None),
+            other => T::from_fru_value(other).map(Some),
+        }
+    }
+}
+
+impl<T: IntoFruValue> IntoFruValue for Vec<T> {
+    fn into_fru_value(self) -> FruValue {
+        FruTuple::new_value(self.into_iter().map(T::into_fru_value).collect())
+    }
+}
+
+impl<T: FromFruValue> FromFruValue for Vec<T> {
+    fn from_fru_value(value: FruValue) -> Result<Self, FruError> {
+        match extract_tuple_items(&value) {
+            Some(items) => items.iter().cloned().map(T::from_fru_value).collect(),
+            None => expected("`Tuple`", &value),
+        }
+    }
+}
+
+impl<T: IntoFruValue> IntoFruValue for HashMap<String, T> {
+    fn into_fru_value(self) -> FruValue {
+        FruTreeMap::from_fields(self.into_iter().map(|(key, value)| (key, value.into_fru_value())))
+    }
+}
+
+impl<T: FromFruValue> FromFruValue for HashMap<String, T> {
+    fn from_fru_value(value: FruValue) -> Result<Self, FruError> {
+        match FruTreeMap::extract_fields(&value) {
+            Some(fields) => (fields.into_iter())
+                .map(|(key, value)| Ok((key, T::from_fru_value(value)?)))
+                .collect(),
+
+            None => expected("`TreeMap` with `String` keys", &value),
+        }
+    }
+}
+
+impl IntoFruArguments for Vec<FruValue> {
+    fn into_fru_arguments(self) -> Vec<FruValue> {
+        self
+    }
+}
+
+macro_rules! tuple_arguments {
+    ($($name:ident),*) => {
+        impl<$($name: IntoFruValue),*> IntoFruArguments for ($($name,)*) {
+            #[allow(non_snake_case)]
+            fn into_fru_arguments(self) -> Vec<FruValue> {
+                let ($($name,)*) = self;
+                vec![$($name.into_fru_value()),*]
+            }
+        }
+    };
+}
+
+tuple_arguments!();
+tuple_arguments!(A);
+tuple_arguments!(A, B);
+tuple_arguments!(A, B, C);
+tuple_arguments!(A, B, C, D);
+tuple_arguments!(A, B, C, D, E);
+tuple_arguments!(A, B, C, D, E, F);
@@ -1,16 +1,31 @@
-use std::{cell::RefCell, fmt::Debug, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    mem::{replace, size_of, take},
+    rc::{Rc, Weak},
+};
+
+use macros::static_ident;
 
 use crate::interpreter::{
     control::{returned, returned_nothing},
     error::FruError,
     identifier::Identifier,
+    runtime::{check_deprecated, schedule_finalizer, Allocation},
     scope::Scope,
     value::fru_type::FruType,
     value::fru_type::TypeType,
-    value::fru_value::FruValue,
-    value::function::FruFunction,
+    value::fru_value::{fmt_without_cycles, FruValue},
+    value::function::{EvaluatedArgumentList, FruFunction},
 };
 
+/// Called with the old and the new value after a field of an object is set
+pub type FieldObserver = Rc<dyn Fn(FruValue, FruValue) -> Result<(), FruError>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObserverId(u64);
+
 #[derive(Clone)]
 pub struct FruObject {
     internal: Rc<FruObjectInternal>,
@@ -19,14 +34,43 @@ pub struct FruObject {
 pub struct FruObjectInternal {
     type_: FruType,
     fields: RefCell<Vec<FruValue>>,
+    allocation: Allocation,
+    // set on the copy that `_drop` is called on, so it is not finalized twice
+    is_finalized: bool,
+    frozen: Cell<bool>,
+    // values of cached properties, forgotten when any field changes
+    cache: RefCell<HashMap<Identifier, FruValue>>,
+    observers: RefCell<Vec<(ObserverId, Identifier, FieldObserver)>>,
+    next_observer: Cell<u64>,
+}
+
+#[derive(Clone)]
+pub struct FruWeakObject {
+    internal: Weak<FruObjectInternal>,
 }
 
 impl FruObject {
     pub fn new(type_: FruType, fields: Vec<FruValue>) -> FruObject {
+        Self::new_internal(type_, fields, false)
+    }
+
+    fn new_internal(type_: FruType, fields: Vec<FruValue>, is_finalized: bool) -> FruObject {
+        let allocation = Allocation::new(
+            size_of::<FruObjectInternal>()
+                + fields.len() * size_of::<FruValue>()
+                + held_bytes(&fields),
+        );
+
         FruObject {
             internal: Rc::new(FruObjectInternal {
                 type_,
                 fields: RefCell::new(fields),
+                allocation,
+                is_finalized,
+                frozen: Cell::new(false),
+                cache: RefCell::new(HashMap::new()),
+                observers: RefCell::new(Vec::new()),
+                next_observer: Cell::new(0),
             }),
         }
     }
@@ -39,16 +83,96 @@ impl FruObject {
         self.internal.type_.clone()
     }
 
+    /// After this fields of the object can't be changed, setters of properties still run
+    pub fn freeze(&self) {
+        self.internal.frozen.set(true);
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.internal.frozen.get()
+    }
+
+    pub fn get_field_values(&self) -> Vec<FruValue> {
+        self.internal.fields.borrow().clone()
+    }
+
+    // for copies that are made before their fields, so cycles can refer to them
+    pub(crate) fn set_field_values(&self, fields: Vec<FruValue>) {
+        self.internal.allocation.grow(held_bytes(&fields));
+        let old = replace(&mut *self.internal.fields.borrow_mut(), fields);
+        self.internal.allocation.shrink(held_bytes(&old));
+    }
+
+    pub fn as_ptr(&self) -> *const () {
+        Rc::as_ptr(&self.internal) as *const ()
+    }
+
     fn get_kth_field(&self, i: usize) -> FruValue {
         self.internal.fields.borrow()[i].clone()
     }
 
-    fn set_kth_field(&self, i: usize, value: FruValue) {
-        self.internal.fields.borrow_mut()[i] = value
+    fn set_kth_field(&self, i: usize, value: FruValue) -> FruValue {
+        self.internal.allocation.grow(value.held_bytes());
+        let old = replace(&mut self.internal.fields.borrow_mut()[i], value);
+        self.internal.allocation.shrink(old.held_bytes());
+        old
+    }
+
+    /// `observer` is called every time `field` is set, until `unobserve` is called with the returned id
+    pub fn observe(
+        &self,
+        field: Identifier,
+        observer: FieldObserver,
+    ) -> Result<ObserverId, FruError> {
+        if self.get_type().get_field_k(field).is_none() {
+            return FruError::new_res(format!(
+                "`{}` has no field `{}`",
+                self.get_type().get_ident(),
+                field
+            ));
+        }
+
+        let id = ObserverId(self.internal.next_observer.get());
+        self.internal.next_observer.set(id.0 + 1);
+
+        self.internal.observers.borrow_mut().push((id, field, observer));
+
+        Ok(id)
+    }
+
+    /// Returns whether the observer was still registered
+    pub fn unobserve(&self, id: ObserverId) -> bool {
+        let mut observers = self.internal.observers.borrow_mut();
+        let before = observers.len();
+
+        observers.retain(|(observer, _, _)| *observer != id);
+
+        observers.len() != before
+    }
+
+    // observers are not borrowed while they run, they may set fields and subscribe too
+    fn notify(&self, field: Identifier, old: FruValue, new: FruValue) -> Result<(), FruError> {
+        let observers: Vec<FieldObserver> = (self.internal.observers.borrow().iter())
+            .filter(|(_, observed, _)| *observed == field)
+            .map(|(_, _, observer)| observer.clone())
+            .collect();
+
+        for observer in observers {
+            observer(old.clone(), new.clone()).map_err(|err| {
+                err.in_frame(format!(
+                    "observer of `{}.{}`",
+                    self.get_type().get_ident(),
+                    field
+                ))
+            })?;
+        }
+
+        Ok(())
     }
 
     pub fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
         if let Some(k) = self.get_type().get_field_k(ident) {
+            self.check_deprecated_field(k);
             return Ok(self.get_kth_field(k));
         }
 
@@ -56,22 +180,36 @@ impl FruObject {
             let new_scope = Scope::new_with_object(self.clone());
 
             return match property.getter {
-                Some(getter) => returned(getter.evaluate(new_scope)),
+                Some(getter) => returned(getter.evaluate(new_scope)).map_err(|err| {
+                    err.in_frame(format!(
+                        "getter of `{}.{}`",
+                        self.get_type().get_ident(),
+                        ident
+                    ))
+                }),
 
                 None => FruError::new_res(format!("property `{}` has no getter", ident)),
             };
         }
 
-        if let Some(FruFunction {
-            parameters: argument_idents,
-            body,
-            ..
-        }) = self.get_type().get_method(ident)
-        {
+        if let Some(method) = self.get_type().get_cached_property(ident) {
+            if let Some(value) = self.internal.cache.borrow().get(&ident) {
+                return Ok(value.clone());
+            }
+
+            let value = self.get_prop(method)?.call(EvaluatedArgumentList { args: Vec::new() })?;
+            self.internal.cache.borrow_mut().insert(ident, value.clone());
+
+            return Ok(value);
+        }
+
+        if let Some(method) = self.get_type().get_method(ident) {
+            let scope = Scope::new_with_object(self.clone());
+
             return Ok(FruFunction {
-                parameters: argument_idents,
-                body,
-                scope: Scope::new_with_object(self.clone()),
+                contract: method.contract.as_ref().map(|x| x.bind(&scope)),
+                scope,
+                ..method
             }
             .into());
         }
@@ -85,6 +223,8 @@ impl FruObject {
 
     pub fn set_prop(&self, ident: Identifier, value: FruValue) -> Result<(), FruError> {
         if let Some(field_k) = self.get_type().get_field_k(ident) {
+            self.check_deprecated_field(field_k);
+
             if self.get_type().get_type_type() == TypeType::Data {
                 return FruError::new_res(format!(
                     "cannot set field `{}` in 'data' type `{}`",
@@ -93,17 +233,33 @@ impl FruObject {
                 ));
             }
 
-            self.set_kth_field(field_k, value);
-            return Ok(());
+            if self.is_frozen() {
+                return FruError::new_res(format!(
+                    "object of `{}` is frozen, field `{}` cannot be changed",
+                    self.get_type().get_ident(),
+                    ident
+                ));
+            }
+
+            let old = self.set_kth_field(field_k, value.clone());
+            self.internal.cache.borrow_mut().clear();
+
+            return self.notify(ident, old, value);
         }
 
         if let Some(property) = self.get_type().get_property(ident) {
-            return if let Some((ident, setter)) = property.setter {
+            return if let Some((value_ident, setter)) = property.setter {
                 let new_scope = Scope::new_with_object(self.clone());
 
-                new_scope.let_variable(ident, value)?;
+                new_scope.let_variable(value_ident, value)?;
 
-                returned_nothing(setter.execute(new_scope))
+                returned_nothing(setter.execute(new_scope)).map_err(|err| {
+                    err.in_frame(format!(
+                        "setter of `{}.{}`",
+                        self.get_type().get_ident(),
+                        ident
+                    ))
+                })
             } else {
                 FruError::new_res(format!("property `{}` has no setter", ident))
             };
@@ -120,50 +276,131 @@ impl FruObject {
         ))
     }
 
+    fn check_deprecated_field(&self, k: usize) {
+        let type_ = self.get_type();
+        let field = &type_.get_fields()[k];
+
+        check_deprecated(field.doc.as_deref(), || {
+            format!("field `{}.{}`", type_.get_ident(), field.ident)
+        });
+    }
+
+    pub fn downgrade(&self) -> FruWeakObject {
+        FruWeakObject {
+            internal: Rc::downgrade(&self.internal),
+        }
+    }
+
+    pub fn finalize(&self) -> Result<(), FruError> {
+        self.get_prop(static_ident!("_drop"))?
+            .call(EvaluatedArgumentList { args: Vec::new() })
+            .map_err(|err| {
+                FruError::new(format!(
+                    "error in `_drop` of `{}`: {}",
+                    self.get_type().get_ident(),
+                    err
+                ))
+            })?;
+
+        Ok(())
+    }
+
     pub fn fru_clone(&self) -> FruValue {
         let tt = self.get_type().get_type_type();
 
         match tt {
-            TypeType::Struct => FruObject::new_object(
-                self.get_type(),
-                self.internal.fields.borrow().iter().map(FruValue::fru_clone).collect(),
-            ),
+            // copies of a frozen struct are frozen too
+            TypeType::Struct => {
+                let copy = FruObject::new(
+                    self.get_type(),
+                    self.internal.fields.borrow().iter().map(FruValue::fru_clone).collect(),
+                );
+
+                if self.is_frozen() {
+                    copy.freeze();
+                }
+
+                FruValue::Object(copy)
+            }
 
             TypeType::Class | TypeType::Data => FruValue::Object(self.clone()),
         }
     }
 }
 
-impl PartialEq for FruObject {
-    fn eq(&self, other: &Self) -> bool {
-        if self.get_type() != other.get_type() {
-            return false;
+fn held_bytes(fields: &[FruValue]) -> usize {
+    fields.iter().map(FruValue::held_bytes).sum()
+}
+
+impl FruWeakObject {
+    pub fn upgrade(&self) -> Option<FruObject> {
+        self.internal.upgrade().map(|internal| FruObject { internal })
+    }
+}
+
+impl Drop for FruObjectInternal {
+    // the object is already gone, so `_drop` gets a new one that owns its fields
+    fn drop(&mut self) {
+        if self.is_finalized || self.type_.get_method(static_ident!("_drop")).is_none() {
+            return;
         }
 
-        self.internal.fields == other.internal.fields
+        let fields = take(self.fields.get_mut());
+
+        schedule_finalizer(FruObject::new_internal(self.type_.clone(), fields, true));
     }
 }
 
-impl Debug for FruObject {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}{{", self.get_type())?;
+impl PartialEq for FruObject {
+    // without recursion, so neither cycles nor long chains of objects overflow the stack
+    fn eq(&self, other: &Self) -> bool {
+        let mut visited = HashSet::new();
+        let mut pending = vec![(self.clone(), other.clone())];
 
-        let fields = self.get_type().get_fields().len();
+        while let Some((left, right)) = pending.pop() {
+            if !visited.insert((left.as_ptr(), right.as_ptr())) {
+                continue;
+            }
 
-        for (k, (field, value)) in self
-            .get_type()
-            .get_fields()
-            .iter()
-            .zip(self.internal.fields.borrow().iter())
-            .enumerate()
-        {
-            write!(f, "{:?}={:?}", field, value)?;
+            if left.get_type() != right.get_type() {
+                return false;
+            }
 
-            if k + 1 < fields {
-                write!(f, ", ")?;
+            for (l, r) in left.get_field_values().into_iter().zip(right.get_field_values()) {
+                match (l, r) {
+                    (FruValue::Object(l), FruValue::Object(r)) => pending.push((l, r)),
+                    (l, r) if l != r => return false,
+                    _ => {}
+                }
             }
         }
 
-        write!(f, "}}")
+        true
+    }
+}
+
+impl Debug for FruObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_without_cycles(f, self.as_ptr(), |f| {
+            write!(f, "{:?}{{", self.get_type())?;
+
+            let fields = self.get_type().get_fields().len();
+
+            for (k, (field, value)) in self
+                .get_type()
+                .get_fields()
+                .iter()
+                .zip(self.internal.fields.borrow().iter())
+                .enumerate()
+            {
+                write!(f, "{:?}={:?}", field, value)?;
+
+                if k + 1 < fields {
+                    write!(f, ", ")?;
+                }
+            }
+
+            write!(f, "}}")
+        })
     }
 }
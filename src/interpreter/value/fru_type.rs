@@ -6,12 +6,14 @@ use crate::interpreter::{
     expression::FruExpression,
     helpers::WrappingExtension,
     identifier::Identifier,
+    runtime::{check_deprecated, check_memory},
     scope::Scope,
     statement::FruStatement,
     value::fru_object::FruObject,
     value::fru_value::FruValue,
     value::function::{EvaluatedArgumentList, FruFunction},
 };
+use crate::stdlib::lazy::fru_lazy::FruLazy;
 
 #[derive(Clone)]
 pub struct FruType {
@@ -29,7 +31,10 @@ pub struct FruTypeInternal {
     static_properties: HashMap<Identifier, Property>,
     methods: HashMap<Identifier, FruFunction>,
     static_methods: HashMap<Identifier, FruFunction>,
+    // property name to the `_cached_` method that computes it
+    cached_properties: HashMap<Identifier, Identifier>,
     scope: Rc<Scope>,
+    doc: Option<Rc<str>>,
 }
 
 #[derive(Clone)]
@@ -37,6 +42,7 @@ pub struct FruField {
     pub is_public: bool,
     pub ident: Identifier,
     pub type_ident: Option<Identifier>, // useless for now
+    pub doc: Option<Rc<str>>,
 }
 
 #[derive(Debug, Clone)]
@@ -53,29 +59,58 @@ pub enum TypeType {
     Data,
 }
 
+/// Everything a type is made of, methods are already bound to the scope of the declaration
+pub struct TypeParts {
+    pub ident: Identifier,
+    pub type_type: TypeType,
+    pub fields: Vec<FruField>,
+    pub static_fields: HashMap<Identifier, FruValue>,
+    pub properties: HashMap<Identifier, Property>,
+    pub static_properties: HashMap<Identifier, Property>,
+    pub methods: HashMap<Identifier, FruFunction>,
+    pub static_methods: HashMap<Identifier, FruFunction>,
+    pub scope: Rc<Scope>,
+    pub doc: Option<Rc<str>>,
+}
+
 impl FruType {
-    pub fn new_value(
-        ident: Identifier,
-        type_type: TypeType,
-        fields: Vec<FruField>,
-        static_fields: RefCell<HashMap<Identifier, FruValue>>,
-        properties: HashMap<Identifier, Property>,
-        static_properties: HashMap<Identifier, Property>,
-        methods: HashMap<Identifier, FruFunction>,
-        static_methods: HashMap<Identifier, FruFunction>,
-        scope: Rc<Scope>,
-    ) -> FruValue {
+    pub fn new_value(parts: TypeParts) -> FruValue {
+        let TypeParts {
+            ident,
+            type_type,
+            fields,
+            static_fields,
+            properties,
+            static_properties,
+            methods,
+            static_methods,
+            scope,
+            doc,
+        } = parts;
+
+        let cached_properties = methods
+            .keys()
+            .filter_map(|method| {
+                let name = method.to_string();
+                let property = name.strip_prefix("_cached_")?;
+
+                Some((Identifier::new(property), *method))
+            })
+            .collect();
+
         FruValue::Type(Self {
             internal: FruTypeInternal {
                 ident,
                 type_type,
                 fields,
-                static_fields,
+                static_fields: RefCell::new(static_fields),
                 properties,
                 methods,
+                cached_properties,
                 static_methods,
                 static_properties,
                 scope,
+                doc,
             }
             .wrap_rc(),
         })
@@ -85,10 +120,18 @@ impl FruType {
         self.internal.ident
     }
 
+    pub fn as_ptr(&self) -> *const () {
+        Rc::as_ptr(&self.internal) as *const ()
+    }
+
     pub fn get_type_type(&self) -> TypeType {
         self.internal.type_type
     }
 
+    pub fn get_doc(&self) -> Option<Rc<str>> {
+        self.internal.doc.clone()
+    }
+
     pub fn get_scope(&self) -> Rc<Scope> {
         self.internal.scope.clone()
     }
@@ -106,6 +149,10 @@ impl FruType {
         None
     }
 
+    pub fn get_static_field_idents(&self) -> Vec<Identifier> {
+        self.internal.static_fields.borrow().keys().copied().collect()
+    }
+
     pub fn get_property(&self, ident: Identifier) -> Option<Property> {
         self.internal.properties.get(&ident).cloned()
     }
@@ -114,27 +161,55 @@ impl FruType {
         self.internal.methods.get(&ident).cloned()
     }
 
+    /// `_cached_area` method makes `area` a property computed once per object
+    pub fn get_cached_property(&self, ident: Identifier) -> Option<Identifier> {
+        self.internal.cached_properties.get(&ident).copied()
+    }
+
     /// In this case means static field of method
     pub fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
-        if let Some(field) = self.internal.static_fields.borrow().get(&ident) {
-            return Ok(field.clone());
+        let field = self.internal.static_fields.borrow().get(&ident).cloned();
+
+        if let Some(field) = field {
+            let lazy = match &field {
+                FruValue::NativeObject(obj) => obj.downcast::<FruLazy>().cloned(),
+                _ => None,
+            };
+
+            // not borrowed while the initializer runs, it may use other static fields
+            return match lazy {
+                Some(lazy) => {
+                    let value = lazy.force().map_err(|err| {
+                        err.in_frame(format!("initializer of `{}.{}`", self.get_ident(), ident))
+                    })?;
+                    self.internal.static_fields.borrow_mut().insert(ident, value.clone());
+
+                    Ok(value)
+                }
+
+                None => Ok(field),
+            };
         }
 
         if let Some(property) = self.internal.static_properties.get(&ident) {
             let new_scope = Scope::new_with_type(self.clone());
 
             return match &property.getter {
-                Some(getter) => returned(getter.evaluate(new_scope)),
+                Some(getter) => returned(getter.evaluate(new_scope)).map_err(|err| {
+                    err.in_frame(format!("static getter of `{}.{}`", self.get_ident(), ident))
+                }),
 
                 None => FruError::new_res(format!("static property `{}` has no getter", ident)),
             };
         }
 
         if let Some(static_method) = self.internal.static_methods.get(&ident) {
+            let scope = Scope::new_with_type(self.clone());
+
             return Ok(FruFunction {
-                parameters: static_method.parameters.clone(),
-                body: static_method.body.clone(),
-                scope: Scope::new_with_type(self.clone()),
+                contract: static_method.contract.as_ref().map(|x| x.bind(&scope)),
+                scope,
+                ..static_method.clone()
             }
             .into());
         }
@@ -150,12 +225,14 @@ impl FruType {
 
         if let Some(property) = self.internal.static_properties.get(&ident) {
             return match &property.setter {
-                Some((ident, setter)) => {
+                Some((value_ident, setter)) => {
                     let new_scope = Scope::new_with_type(self.clone());
 
-                    new_scope.let_variable(*ident, value)?;
+                    new_scope.let_variable(*value_ident, value)?;
 
-                    returned_nothing(setter.execute(new_scope))
+                    returned_nothing(setter.execute(new_scope)).map_err(|err| {
+                        err.in_frame(format!("static setter of `{}.{}`", self.get_ident(), ident))
+                    })
                 }
 
                 None => FruError::new_res(format!("static property `{}` has no setter", ident)),
@@ -165,7 +242,16 @@ impl FruType {
         FruError::new_res(format!("static prop `{}` not found", ident))
     }
 
-    pub fn instantiate(&self, mut args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    pub fn instantiate(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+        check_deprecated(self.internal.doc.as_deref(), || {
+            format!("type `{}`", self.get_ident())
+        });
+
+        self.instantiate_fields(args)
+            .map_err(|err| err.in_frame(format!("instantiation of `{}`", self.get_ident())))
+    }
+
+    fn instantiate_fields(&self, mut args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
         let mut obj_fields = HashMap::new();
 
         let fields = self.get_fields();
@@ -194,7 +280,11 @@ impl FruType {
             return FruError::new_res(format!("field `{}` does not exist", *ident));
         }
 
-        Ok(FruObject::new_object(self.clone(), args))
+        let object = FruObject::new_object(self.clone(), args);
+
+        check_memory()?;
+
+        Ok(object)
     }
 }
 
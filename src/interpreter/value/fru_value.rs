@@ -1,5 +1,11 @@
-use std::{fmt::Debug, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    fmt::{self, Debug},
+    rc::Rc,
+};
 
+use crate::interpreter::{builtins::types::builtin_types, runtime::Runtime};
 use crate::interpreter::{
     error::FruError,
     identifier::id,
@@ -7,10 +13,18 @@ use crate::interpreter::{
     value::{
         fru_object::FruObject,
         fru_type::FruType,
-        function::{AnyFunction, CurriedFunction, EvaluatedArgumentList, FruFunction},
+        function::{
+            AnyFunction, BuiltinFunction, CurriedFunction, EvaluatedArgumentList, FruFunction,
+        },
         native::object::NativeObject,
     },
 };
+use crate::stdlib::{primitive::fru_primitive::FruNativeType, string::fru_string::string_prop};
+
+thread_local! {
+    // values that are being formatted, met again inside of themselves they are cycles
+    static FORMATTING: RefCell<HashSet<*const ()>> = RefCell::new(HashSet::new());
+}
 
 pub type TFnBuiltin = fn(EvaluatedArgumentList) -> Result<FruValue, FruError>;
 pub type TOpBuiltin = fn(FruValue, FruValue) -> Result<FruValue, FruError>;
@@ -46,6 +60,14 @@ impl FruValue {
         }
     }
 
+    /// Value of the type, builtin types are taken from the prelude of the running interpreter
+    pub fn get_type(&self) -> FruValue {
+        match self {
+            FruValue::Object(obj) => FruValue::Type(obj.get_type()),
+            other => builtin_type(other.get_type_identifier()),
+        }
+    }
+
     pub fn call(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
         match self {
             FruValue::Function(fun) => fun.call(args),
@@ -106,6 +128,10 @@ impl FruValue {
 
             FruValue::NativeObject(obj) => obj.get_prop(ident),
 
+            FruValue::Function(func) => func.get_prop(ident),
+
+            FruValue::String(s) => string_prop(s, ident),
+
             _ => FruError::new_res(format!(
                 "cannot access prop of `{}`",
                 self.get_type_identifier()
@@ -128,6 +154,14 @@ impl FruValue {
         }
     }
 
+    /// Bytes that the value keeps besides itself, accounted while it is stored in a scope or an object
+    pub fn held_bytes(&self) -> usize {
+        match self {
+            FruValue::String(s) => s.len(),
+            _ => 0,
+        }
+    }
+
     pub fn fru_clone(&self) -> FruValue {
         match self {
             FruValue::Object(obj) => obj.fru_clone(),
@@ -145,6 +179,12 @@ impl From<FruFunction> for FruValue {
     }
 }
 
+impl From<BuiltinFunction> for FruValue {
+    fn from(func: BuiltinFunction) -> Self {
+        FruValue::Function(AnyFunction::BuiltinFunction(func))
+    }
+}
+
 impl PartialEq for FruValue {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
@@ -154,6 +194,7 @@ impl PartialEq for FruValue {
             (FruValue::String(v1), FruValue::String(v2)) => v1 == v2,
             (FruValue::Type(v1), FruValue::Type(v2)) => v1 == v2,
             (FruValue::Object(v1), FruValue::Object(v2)) => v1 == v2,
+            (FruValue::NativeObject(v1), FruValue::NativeObject(v2)) => v1.fru_eq(v2),
             _ => false,
         }
     }
@@ -169,7 +210,33 @@ impl Debug for FruValue {
             FruValue::Function(fun) => write!(f, "{:?}", fun),
             FruValue::Type(type_) => write!(f, "{:?}", type_),
             FruValue::Object(obj) => write!(f, "{:?}", obj),
-            FruValue::NativeObject(obj) => write!(f, "{}{{}}", obj.get_type_identifier()),
+            FruValue::NativeObject(obj) => write!(f, "{:?}", obj),
         }
     }
 }
+
+/// Formats a value that can contain itself, `<cycle>` is written where it is met again
+pub fn fmt_without_cycles(
+    f: &mut fmt::Formatter<'_>,
+    ptr: *const (),
+    fmt: impl FnOnce(&mut fmt::Formatter<'_>) -> fmt::Result,
+) -> fmt::Result {
+    if !FORMATTING.with(|formatting| formatting.borrow_mut().insert(ptr)) {
+        return write!(f, "<cycle>");
+    }
+
+    let result = fmt(f);
+    FORMATTING.with(|formatting| formatting.borrow_mut().remove(&ptr));
+
+    result
+}
+
+fn builtin_type(ident: Identifier) -> FruValue {
+    let from_prelude = Runtime::current()
+        .and_then(|runtime| runtime.global_scope())
+        .and_then(|scope| scope.get_builtin(ident));
+
+    from_prelude
+        .or_else(|| builtin_types().remove(&ident))
+        .unwrap_or_else(|| FruNativeType::new_value(ident))
+}
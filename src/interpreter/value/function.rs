@@ -1,24 +1,37 @@
-use std::{collections::HashSet, fmt::Debug, rc::Rc};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    rc::Rc,
+};
+
+use macros::static_ident;
+use thiserror::Error;
 
 use crate::interpreter::{
     control::{returned, returned_unit},
     error::FruError,
     expression::FruExpression,
     identifier::Identifier,
+    runtime::{check_deprecated, check_stack, contracts_enabled},
     scope::Scope,
     statement::FruStatement,
     value::fru_value::{FruValue, TFnBuiltin},
 };
+use crate::stdlib::tuple::fru_tuple::FruTuple;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Error)]
 pub enum ArgumentError {
+    #[error("too many arguments")]
     TooMany,
+    #[error("argument `{ident}` is set twice")]
     SameSetTwice {
         ident: Identifier,
     },
+    #[error("missing argument `{ident}`")]
     NotSetPositional {
         ident: Identifier,
     },
+    #[error("there is no parameter named `{ident}`")]
     DoesNotExist {
         ident: Identifier,
     },
@@ -36,6 +49,26 @@ pub struct FruFunction {
     pub parameters: FormalParameters,
     pub body: Rc<FruStatement>,
     pub scope: Rc<Scope>,
+    pub name: Option<Identifier>,
+    pub location: Option<SourceLocation>,
+    pub doc: Option<Rc<str>>,
+    pub contract: Option<Rc<Contract>>,
+}
+
+/// Methods `_requires_<name>` and `_ensures_<name>` of a type, checked around calls of `<name>`
+#[derive(Clone)]
+pub struct Contract {
+    // `Type.method`, for messages
+    pub method: String,
+    pub requires: Option<FruFunction>,
+    pub ensures: Option<FruFunction>,
+}
+
+/// Position of a definition in the source file, both are 1-based
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SourceLocation {
+    pub line: usize,
+    pub column: usize,
 }
 
 #[derive(Clone, Debug)]
@@ -55,7 +88,8 @@ pub struct EvaluatedArgumentList {
 
 #[derive(Clone)]
 pub struct BuiltinFunction {
-    function: TFnBuiltin,
+    function: Rc<dyn Fn(EvaluatedArgumentList) -> Result<FruValue, FruError>>,
+    name: Option<Identifier>,
 }
 
 pub struct CurriedFunction {
@@ -71,25 +105,253 @@ impl AnyFunction {
             AnyFunction::CurriedFunction(func) => func.call(args),
         }
     }
+
+    /// Identity comparison, functions are never compared structurally
+    pub fn ptr_eq(&self, other: &AnyFunction) -> bool {
+        match (self, other) {
+            (AnyFunction::Function(a), AnyFunction::Function(b)) => Rc::ptr_eq(a, b),
+            (AnyFunction::CurriedFunction(a), AnyFunction::CurriedFunction(b)) => Rc::ptr_eq(a, b),
+            (AnyFunction::BuiltinFunction(a), AnyFunction::BuiltinFunction(b)) => {
+                Rc::ptr_eq(&a.function, &b.function)
+            }
+            _ => false,
+        }
+    }
+
+    /// Introspection props, so callbacks can be described in messages
+    pub fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        match ident {
+            x if x == static_ident!("name") => {
+                Ok(self.name().map_or(FruValue::Nah, |name| FruValue::String(name.to_string())))
+            }
+
+            x if x == static_ident!("arity") => {
+                Ok(self.parameters().map_or(FruValue::Nah, |params| {
+                    FruValue::Number(params.len() as f64)
+                }))
+            }
+
+            x if x == static_ident!("parameters") => {
+                Ok(self.parameters().map_or(FruValue::Nah, |params| {
+                    FruTuple::new_value(
+                        params.iter().map(|ident| FruValue::String(ident.to_string())).collect(),
+                    )
+                }))
+            }
+
+            x if x == static_ident!("location") => {
+                Ok(self.location().map_or(FruValue::Nah, |location| {
+                    FruValue::String(format!("{}:{}", location.line, location.column))
+                }))
+            }
+
+            _ => FruError::new_res(format!("prop `{}` not found", ident)),
+        }
+    }
+
+    pub fn name(&self) -> Option<Identifier> {
+        match self {
+            AnyFunction::Function(func) => func.name,
+            AnyFunction::BuiltinFunction(func) => func.name,
+            AnyFunction::CurriedFunction(func) => func.function.name(),
+        }
+    }
+
+    pub fn doc(&self) -> Option<Rc<str>> {
+        match self {
+            AnyFunction::Function(func) => func.doc.clone(),
+            AnyFunction::BuiltinFunction(_) => None,
+            AnyFunction::CurriedFunction(func) => func.function.doc(),
+        }
+    }
+
+    pub fn location(&self) -> Option<SourceLocation> {
+        match self {
+            AnyFunction::Function(func) => func.location,
+            AnyFunction::BuiltinFunction(_) => None,
+            AnyFunction::CurriedFunction(func) => func.function.location(),
+        }
+    }
+
+    /// Parameters that are still to be passed, builtin functions do not declare them
+    pub fn parameters(&self) -> Option<Vec<Identifier>> {
+        match self {
+            AnyFunction::Function(func) => {
+                Some(func.parameters.args.iter().map(|(ident, _)| *ident).collect())
+            }
+
+            AnyFunction::BuiltinFunction(_) => None,
+
+            AnyFunction::CurriedFunction(func) => {
+                let params = func.function.parameters()?;
+
+                let positional = func.saved_args.args.iter().filter(|(x, _)| x.is_none()).count();
+                let named: HashSet<_> =
+                    func.saved_args.args.iter().filter_map(|(x, _)| *x).collect();
+
+                Some(
+                    params
+                        .into_iter()
+                        .skip(positional)
+                        .filter(|ident| !named.contains(ident))
+                        .collect(),
+                )
+            }
+        }
+    }
 }
 
 impl FruFunction {
     fn call(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+        check_stack()?;
+
+        check_deprecated(self.doc.as_deref(), || match self.name {
+            Some(name) => format!("function `{}`", name),
+            None => "anonymous function".to_string(),
+        });
+
         let new_scope = Scope::new_with_parent(self.scope.clone());
 
-        self.parameters.apply(args, new_scope.clone())?;
+        let contract = self.contract.as_ref().filter(|_| contracts_enabled());
+        let contract_args = contract.map(|_| args.clone());
+
+        self.parameters.apply(args, new_scope.clone(), self.name)?;
 
-        returned_unit(self.body.execute(new_scope))
+        if let (Some(contract), Some(args)) = (contract, &contract_args) {
+            contract.check_requires(args.clone(), self.location)?;
+        }
+
+        let result =
+            returned_unit(self.body.execute(new_scope)).map_err(|err| match self.name {
+                Some(name) => err.in_frame(format!("function `{}`", name)),
+                None => err.in_frame("anonymous function".to_string()),
+            })?;
+
+        if let (Some(contract), Some(args)) = (contract, contract_args) {
+            contract.check_ensures(result.clone(), args, self.location)?;
+        }
+
+        Ok(result)
+    }
+}
+
+impl Contract {
+    /// Gives methods that have `_requires_` or `_ensures_` counterparts their contracts
+    pub fn attach(type_ident: Identifier, methods: &mut HashMap<Identifier, FruFunction>) {
+        let contracts: Vec<_> = methods
+            .keys()
+            .filter_map(|ident| {
+                let requires = methods.get(&Identifier::new(&format!("_requires_{}", ident)));
+                let ensures = methods.get(&Identifier::new(&format!("_ensures_{}", ident)));
+
+                if requires.is_none() && ensures.is_none() {
+                    return None;
+                }
+
+                let contract = Contract {
+                    method: format!("{}.{}", type_ident, ident),
+                    requires: requires.cloned(),
+                    ensures: ensures.cloned(),
+                };
+
+                Some((*ident, Rc::new(contract)))
+            })
+            .collect();
+
+        for (ident, contract) in contracts {
+            if let Some(method) = methods.get_mut(&ident) {
+                method.contract = Some(contract);
+            }
+        }
+    }
+
+    /// Same contract with conditions evaluated in the scope of the object or type
+    pub fn bind(&self, scope: &Rc<Scope>) -> Rc<Contract> {
+        let bind = |function: &FruFunction| FruFunction {
+            scope: scope.clone(),
+            ..function.clone()
+        };
+
+        Rc::new(Contract {
+            method: self.method.clone(),
+            requires: self.requires.as_ref().map(bind),
+            ensures: self.ensures.as_ref().map(bind),
+        })
+    }
+
+    fn check_requires(
+        &self,
+        args: EvaluatedArgumentList,
+        location: Option<SourceLocation>,
+    ) -> Result<(), FruError> {
+        match &self.requires {
+            Some(requires) => self.check(requires, args, "precondition", location),
+            None => Ok(()),
+        }
+    }
+
+    // `_ensures_` gets the result before the arguments
+    fn check_ensures(
+        &self,
+        result: FruValue,
+        mut args: EvaluatedArgumentList,
+        location: Option<SourceLocation>,
+    ) -> Result<(), FruError> {
+        match &self.ensures {
+            Some(ensures) => {
+                args.args.insert(0, (None, result));
+                self.check(ensures, args, "postcondition", location)
+            }
+
+            None => Ok(()),
+        }
+    }
+
+    fn check(
+        &self,
+        condition: &FruFunction,
+        args: EvaluatedArgumentList,
+        what: &str,
+        location: Option<SourceLocation>,
+    ) -> Result<(), FruError> {
+        match condition.call(args)? {
+            FruValue::Bool(true) => Ok(()),
+
+            FruValue::Bool(false) => {
+                let defined = location.map_or_else(String::new, |x| {
+                    format!(" (defined at {}:{})", x.line, x.column)
+                });
+
+                FruError::new_res(format!(
+                    "{} of `{}` is violated{}",
+                    what, self.method, defined
+                ))
+            }
+
+            other => FruError::new_res(format!(
+                "{} of `{}` must return Bool, got `{}`",
+                what,
+                self.method,
+                other.get_type_identifier()
+            )),
+        }
     }
 }
 
 impl FormalParameters {
-    // scope is the scope of function being called
+    // scope is the scope of function being called,
+    // argument errors are prefixed with the function name, errors of default values are not
     pub fn apply(
         &self,
         evaluated: EvaluatedArgumentList,
         scope: Rc<Scope>,
+        function: Option<Identifier>,
     ) -> Result<(), FruError> {
+        let fail = |err: ArgumentError| match function {
+            Some(name) => FruError::from(err).in_context(format_args!("in function `{}`", name)),
+            None => FruError::from(err),
+        };
+
         let mut next_positional = 0;
 
         let acceptable: HashSet<_> = self.args.iter().map(|(x, _)| *x).collect();
@@ -98,13 +360,13 @@ impl FormalParameters {
             let ident = match ident {
                 Some(ident) => {
                     if !acceptable.contains(&ident) {
-                        return Err(ArgumentError::DoesNotExist { ident }.into());
+                        return Err(fail(ArgumentError::DoesNotExist { ident }));
                     }
                     ident
                 }
                 None => {
                     if next_positional >= self.args.len() {
-                        return Err(ArgumentError::TooMany.into());
+                        return Err(fail(ArgumentError::TooMany));
                     }
                     let r = self.args[next_positional].0;
                     next_positional += 1;
@@ -114,7 +376,7 @@ impl FormalParameters {
 
             scope
                 .let_variable(ident, value)
-                .map_err(|_| ArgumentError::SameSetTwice { ident })?;
+                .map_err(|_| fail(ArgumentError::SameSetTwice { ident }))?;
         }
 
         for (ident, value) in self.args.iter().skip(next_positional) {
@@ -127,7 +389,7 @@ impl FormalParameters {
 
                 scope.let_variable(*ident, default)?;
             } else {
-                return Err(ArgumentError::NotSetPositional { ident: *ident }.into());
+                return Err(fail(ArgumentError::NotSetPositional { ident: *ident }));
             }
         }
 
@@ -137,7 +399,25 @@ impl FormalParameters {
 
 impl BuiltinFunction {
     pub fn new(function: TFnBuiltin) -> Self {
-        Self { function }
+        Self {
+            function: Rc::new(function),
+            name: None,
+        }
+    }
+
+    pub fn with_name(mut self, name: Identifier) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    // used by native objects to expose methods bound to their state
+    pub fn new_closure(
+        function: impl Fn(EvaluatedArgumentList) -> Result<FruValue, FruError> + 'static,
+    ) -> Self {
+        Self {
+            function: Rc::new(function),
+            name: None,
+        }
     }
 
     fn call(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
@@ -152,7 +432,15 @@ impl CurriedFunction {
 
         match &*self.function {
             AnyFunction::Function(func) => func.call(new_args),
-            AnyFunction::BuiltinFunction(func) => func.call(new_args),
+
+            // builtins have no frame of their own, the curried one is named after them
+            AnyFunction::BuiltinFunction(func) => {
+                func.call(new_args).map_err(|err| match func.name {
+                    Some(name) => err.in_frame(format!("curried function `{}`", name)),
+                    None => err.in_frame("curried function".to_string()),
+                })
+            }
+
             AnyFunction::CurriedFunction(_) => {
                 unreachable!("CurriedFunction should never contain a CurriedFunction")
             }
@@ -1,6 +1,8 @@
+pub mod convert;
 pub mod fru_object;
 pub mod fru_type;
 pub mod fru_value;
 pub mod function;
 pub mod native;
 pub mod operator;
+pub mod sendable;
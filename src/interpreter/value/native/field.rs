@@ -0,0 +1,106 @@
+use std::cell::{Cell, RefCell};
+
+use crate::interpreter::{error::FruError, identifier::Identifier, value::fru_value::FruValue};
+
+/// Rust value that a prop generated by `derive_nat` holds
+pub trait NatValue: Sized {
+    fn into_value(self) -> FruValue;
+
+    fn from_value(value: FruValue, ident: Identifier) -> Result<Self, FruError>;
+}
+
+/// Field of a struct with `derive_nat`, plain fields can only be read,
+/// fields in a `Cell` or a `RefCell` can be set too
+pub trait NatField {
+    fn get_value(&self) -> FruValue;
+
+    fn set_value(&self, _value: FruValue, ident: Identifier) -> Result<(), FruError> {
+        FruError::new_res(format!("prop `{}` is read-only", ident))
+    }
+}
+
+impl<T: NatValue + Clone> NatField for T {
+    fn get_value(&self) -> FruValue {
+        self.clone().into_value()
+    }
+}
+
+impl<T: NatValue + Copy> NatField for Cell<T> {
+    fn get_value(&self) -> FruValue {
+        self.get().into_value()
+    }
+
+    fn set_value(&self, value: FruValue, ident: Identifier) -> Result<(), FruError> {
+        self.set(T::from_value(value, ident)?);
+        Ok(())
+    }
+}
+
+impl<T: NatValue + Clone> NatField for RefCell<T> {
+    fn get_value(&self) -> FruValue {
+        self.borrow().clone().into_value()
+    }
+
+    fn set_value(&self, value: FruValue, ident: Identifier) -> Result<(), FruError> {
+        *self.borrow_mut() = T::from_value(value, ident)?;
+        Ok(())
+    }
+}
+
+impl NatValue for FruValue {
+    fn into_value(self) -> FruValue {
+        self
+    }
+
+    fn from_value(value: FruValue, _: Identifier) -> Result<Self, FruError> {
+        Ok(value)
+    }
+}
+
+impl NatValue for f64 {
+    fn into_value(self) -> FruValue {
+        FruValue::Number(self)
+    }
+
+    fn from_value(value: FruValue, ident: Identifier) -> Result<Self, FruError> {
+        match value {
+            FruValue::Number(n) => Ok(n),
+            other => wrong_type("Number", &other, ident),
+        }
+    }
+}
+
+impl NatValue for bool {
+    fn into_value(self) -> FruValue {
+        FruValue::Bool(self)
+    }
+
+    fn from_value(value: FruValue, ident: Identifier) -> Result<Self, FruError> {
+        match value {
+            FruValue::Bool(b) => Ok(b),
+            other => wrong_type("Bool", &other, ident),
+        }
+    }
+}
+
+impl NatValue for String {
+    fn into_value(self) -> FruValue {
+        FruValue::String(self)
+    }
+
+    fn from_value(value: FruValue, ident: Identifier) -> Result<Self, FruError> {
+        match value {
+            FruValue::String(s) => Ok(s),
+            other => wrong_type("String", &other, ident),
+        }
+    }
+}
+
+fn wrong_type<T>(expected: &str, got: &FruValue, ident: Identifier) -> Result<T, FruError> {
+    FruError::new_res(format!(
+        "prop `{}` expects `{}`, got `{}`",
+        ident,
+        expected,
+        got.get_type_identifier()
+    ))
+}
@@ -1,12 +1,23 @@
-use std::{any::Any, rc::Rc};
+use std::{any::Any, cell::Cell, fmt, rc::Rc};
 
 use crate::interpreter::{
     error::FruError,
     identifier::id,
-    identifier::Identifier,
-    value::{fru_value::FruValue, function::EvaluatedArgumentList},
+    identifier::{Identifier, OperatorIdentifier},
+    value::{
+        fru_value::{fmt_without_cycles, FruValue},
+        function::EvaluatedArgumentList,
+        operator::AnyOperator,
+    },
 };
 
+/// Items of a collection as `inspect` shows them, keys are shown for map-like values
+pub struct Contents {
+    pub open: &'static str,
+    pub close: &'static str,
+    pub items: Vec<(Option<FruValue>, FruValue)>,
+}
+
 pub trait INativeObject {
     fn as_any(&self) -> &dyn Any;
 
@@ -46,6 +57,71 @@ pub trait INativeObject {
         ))
     }
 
+    /// Props that hold data, for printing and reflection, methods are not listed
+    fn list_props(&self) -> Vec<Identifier> {
+        Vec::new()
+    }
+
+    /// `x.ident(args)`, overridden to dispatch without making a bound function first
+    fn call_method(
+        &self,
+        ident: Identifier,
+        args: EvaluatedArgumentList,
+    ) -> Result<FruValue, FruError> {
+        self.get_prop(ident)?.call(args)
+    }
+
+    /// Operators of the values this type object makes, installed into a prelude with it
+    fn operators(&self) -> Vec<(OperatorIdentifier, AnyOperator)> {
+        Vec::new()
+    }
+
+    /// `None` if values of this type are compared by identity
+    fn fru_eq(&self, _other: &dyn INativeObject) -> Option<bool> {
+        None
+    }
+
+    fn fmt_debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{{", self.get_type_identifier())?;
+
+        for (k, ident) in self.list_props().into_iter().enumerate() {
+            if k > 0 {
+                write!(f, ", ")?;
+            }
+
+            match self.get_prop(ident) {
+                Ok(value) => write!(f, "{}: {:?}", ident, value)?,
+                Err(_) => write!(f, "{}: ?", ident)?,
+            }
+        }
+
+        write!(f, "}}")
+    }
+
+    /// `None` for values that are shown by `fmt_debug`
+    fn contents(&self) -> Option<Contents> {
+        None
+    }
+
+    /// Makes the value immutable and returns the values it holds, so they are frozen too.
+    /// `None` if values of this type can't be frozen
+    fn freeze(&self) -> Option<Vec<FruValue>> {
+        None
+    }
+
+    fn is_frozen(&self) -> bool {
+        false
+    }
+
+    /// Object with the same contents for `clone` and `deep_clone`, items go through `copy_item`.
+    /// `None` for values that are shared instead of copied, like channels
+    fn copy_with(
+        &self,
+        _copy_item: &mut dyn FnMut(FruValue) -> Result<FruValue, FruError>,
+    ) -> Option<Result<FruValue, FruError>> {
+        None
+    }
+
     fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject>;
 }
 
@@ -54,6 +130,18 @@ pub struct NativeObject {
     internal: Rc<dyn INativeObject>,
 }
 
+/// Fails for methods that change a builtin collection after it is frozen
+pub fn check_mutable(frozen: &Cell<bool>, type_ident: &str, method: &str) -> Result<(), FruError> {
+    if frozen.get() {
+        FruError::new_res(format!(
+            "`{}` is frozen, `{}` cannot be called",
+            type_ident, method
+        ))
+    } else {
+        Ok(())
+    }
+}
+
 impl NativeObject {
     pub fn new(internal: Rc<dyn INativeObject>) -> Self {
         Self { internal }
@@ -83,6 +171,51 @@ impl NativeObject {
         self.internal.set_prop(ident, value)
     }
 
+    pub fn list_props(&self) -> Vec<Identifier> {
+        self.internal.list_props()
+    }
+
+    pub fn call_method(
+        &self,
+        ident: Identifier,
+        args: EvaluatedArgumentList,
+    ) -> Result<FruValue, FruError> {
+        self.internal.call_method(ident, args)
+    }
+
+    pub fn operators(&self) -> Vec<(OperatorIdentifier, AnyOperator)> {
+        self.internal.operators()
+    }
+
+    pub fn fru_eq(&self, other: &NativeObject) -> bool {
+        self.internal
+            .fru_eq(other.internal.as_ref())
+            .unwrap_or_else(|| Rc::ptr_eq(&self.internal, &other.internal))
+    }
+
+    pub fn contents(&self) -> Option<Contents> {
+        self.internal.contents()
+    }
+
+    pub fn freeze(&self) -> Option<Vec<FruValue>> {
+        self.internal.freeze()
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.internal.is_frozen()
+    }
+
+    pub fn copy_with(
+        &self,
+        copy_item: &mut dyn FnMut(FruValue) -> Result<FruValue, FruError>,
+    ) -> Option<Result<FruValue, FruError>> {
+        self.internal.copy_with(copy_item)
+    }
+
+    pub fn as_ptr(&self) -> *const () {
+        Rc::as_ptr(&self.internal) as *const ()
+    }
+
     pub fn fru_clone(&self) -> FruValue {
         FruValue::NativeObject(NativeObject {
             internal: self.internal.clone().fru_clone(),
@@ -93,3 +226,9 @@ impl NativeObject {
         self.internal.as_any().downcast_ref::<T>()
     }
 }
+
+impl fmt::Debug for NativeObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_without_cycles(f, self.as_ptr(), |f| self.internal.fmt_debug(f))
+    }
+}
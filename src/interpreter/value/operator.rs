@@ -1,11 +1,14 @@
-use std::{fmt::Debug, rc::Rc};
+use std::{cmp::Ordering, collections::HashSet, fmt::Debug, rc::Rc};
 
 use crate::interpreter::{
     control::returned_unit,
     error::FruError,
-    identifier::Identifier,
+    identifier::{id, Identifier},
+    runtime::check_stack,
     scope::Scope,
     statement::FruStatement,
+    value::fru_object::FruObject,
+    value::fru_type::TypeType,
     value::fru_value::{FruValue, TOpBuiltin},
 };
 
@@ -35,6 +38,7 @@ impl AnyOperator {
                 new_scope.let_variable(*right_ident, right_val)?;
 
                 returned_unit(body.execute(new_scope))
+                    .map_err(|err| err.in_frame("operator".to_string()))
             }
 
             AnyOperator::BuiltinOperator(op) => op(left_val, right_val),
@@ -42,6 +46,136 @@ impl AnyOperator {
     }
 }
 
+/// Comparison of two objects of the same `struct` or `data` type that has no operator declared
+/// for them, `==` and `!=` compare fields deeply, ordering compares fields in the order of declaration.
+/// Types, and native objects by `fru_eq` or by identity, are compared only with `==` and `!=`
+pub fn structural_operator(
+    op: Identifier,
+    left: &FruValue,
+    right: &FruValue,
+) -> Option<Result<FruValue, FruError>> {
+    if let (FruValue::NativeObject(_), FruValue::NativeObject(_))
+    | (FruValue::Type(_), FruValue::Type(_)) = (left, right)
+    {
+        let eq = left == right;
+
+        return match op {
+            id::EQ => Some(Ok(FruValue::Bool(eq))),
+            id::NOT_EQ => Some(Ok(FruValue::Bool(!eq))),
+            _ => None,
+        };
+    }
+
+    let (FruValue::Object(l), FruValue::Object(r)) = (left, right) else {
+        return None;
+    };
+
+    // objects of classes are references, they have no value to compare
+    if l.get_type() != r.get_type() || !is_value_type(l) {
+        return None;
+    }
+
+    let ordering = |accept: fn(Ordering) -> bool| {
+        Some(compare(left, right).map(|ordering| FruValue::Bool(accept(ordering))))
+    };
+
+    match op {
+        id::EQ => Some(Structure::default().equal(left, right).map(FruValue::Bool)),
+        id::NOT_EQ => Some(Structure::default().equal(left, right).map(|eq| FruValue::Bool(!eq))),
+        id::LESS => ordering(Ordering::is_lt),
+        id::LESS_EQ => ordering(Ordering::is_le),
+        id::GREATER => ordering(Ordering::is_gt),
+        id::GREATER_EQ => ordering(Ordering::is_ge),
+        _ => None,
+    }
+}
+
+/// Natural order of values of the same type, objects of `struct` and `data` types
+/// are ordered lexicographically by fields, objects of classes only by identity
+pub fn compare(left: &FruValue, right: &FruValue) -> Result<Ordering, FruError> {
+    Structure::default().compare(left, right)
+}
+
+fn is_value_type(object: &FruObject) -> bool {
+    object.get_type().get_type_type() != TypeType::Class
+}
+
+// walks fields of two values side by side, pairs of objects met again are cycles
+#[derive(Default)]
+struct Structure {
+    visited: HashSet<(*const (), *const ())>,
+}
+
+impl Structure {
+    fn equal(&mut self, left: &FruValue, right: &FruValue) -> Result<bool, FruError> {
+        check_stack()?;
+
+        match (left, right) {
+            (FruValue::Object(l), FruValue::Object(r))
+                if l.get_type() == r.get_type() && is_value_type(l) =>
+            {
+                if !self.visited.insert((l.as_ptr(), r.as_ptr())) {
+                    return Ok(true);
+                }
+
+                for (l, r) in l.get_field_values().iter().zip(r.get_field_values().iter()) {
+                    if !self.equal(l, r)? {
+                        return Ok(false);
+                    }
+                }
+
+                Ok(true)
+            }
+
+            (FruValue::Object(l), FruValue::Object(r)) => Ok(l.as_ptr() == r.as_ptr()),
+
+            _ => Ok(left == right),
+        }
+    }
+
+    fn compare(&mut self, left: &FruValue, right: &FruValue) -> Result<Ordering, FruError> {
+        check_stack()?;
+
+        match (left, right) {
+            (FruValue::Nah, FruValue::Nah) => Ok(Ordering::Equal),
+            (FruValue::Bool(l), FruValue::Bool(r)) => Ok(l.cmp(r)),
+            (FruValue::String(l), FruValue::String(r)) => Ok(l.cmp(r)),
+
+            (FruValue::Number(l), FruValue::Number(r)) => match l.partial_cmp(r) {
+                Some(ordering) => Ok(ordering),
+                None => FruError::new_res("NaN cannot be ordered"),
+            },
+
+            (FruValue::Object(l), FruValue::Object(r)) if l.as_ptr() == r.as_ptr() => {
+                Ok(Ordering::Equal)
+            }
+
+            (FruValue::Object(l), FruValue::Object(r))
+                if l.get_type() == r.get_type() && is_value_type(l) =>
+            {
+                if !self.visited.insert((l.as_ptr(), r.as_ptr())) {
+                    return Ok(Ordering::Equal);
+                }
+
+                for (l, r) in l.get_field_values().iter().zip(r.get_field_values().iter()) {
+                    match self.compare(l, r)? {
+                        Ordering::Equal => {}
+                        ordering => return Ok(ordering),
+                    }
+                }
+
+                Ok(Ordering::Equal)
+            }
+
+            _ => FruError::new_res(format!(
+                "values of `{}` and `{}` cannot be ordered",
+                left.get_type_identifier(),
+                right.get_type_identifier()
+            )),
+        }
+    }
+}
+
 impl Debug for AnyOperator {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
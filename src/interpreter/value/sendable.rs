@@ -0,0 +1,118 @@
+use crate::interpreter::{
+    error::FruError, identifier::Identifier, runtime::check_stack, scope::Scope,
+    value::fru_type::TypeType, value::fru_value::FruValue, value::function::EvaluatedArgumentList,
+};
+use crate::stdlib::{
+    tree::fru_tree::FruTreeMap,
+    tuple::fru_tuple::{extract_tuple_items, FruTuple},
+};
+
+/// Copy of a value, that can be moved to another interpreter running on another thread.
+/// Objects of classes, functions, types and natives other than `Tuple` and `TreeMap` can't be copied
+#[derive(Debug, Clone, PartialEq)]
+pub enum SendableValue {
+    Nah,
+    Number(f64),
+    Bool(bool),
+    String(String),
+    Tuple(Vec<SendableValue>),
+    // entries of a `TreeMap`
+    Map(Vec<(SendableValue, SendableValue)>),
+    // object of a `struct` or `data` type, made again with the type of the same name where it is received
+    Object {
+        type_name: String,
+        fields: Vec<(String, SendableValue)>,
+    },
+}
+
+impl SendableValue {
+    /// Value in the interpreter that `global` belongs to, objects get the type of the same name from it
+    pub fn into_value(self, global: &Scope) -> Result<FruValue, FruError> {
+        Ok(match self {
+            SendableValue::Nah => FruValue::Nah,
+            SendableValue::Number(v) => FruValue::Number(v),
+            SendableValue::Bool(v) => FruValue::Bool(v),
+            SendableValue::String(v) => FruValue::String(v),
+
+            SendableValue::Tuple(items) => FruTuple::new_value(
+                (items.into_iter()).map(|x| x.into_value(global)).collect::<Result<_, _>>()?,
+            ),
+
+            SendableValue::Map(entries) => FruTreeMap::from_entries(
+                (entries.into_iter())
+                    .map(|(key, value)| Ok((key.into_value(global)?, value.into_value(global)?)))
+                    .collect::<Result<Vec<_>, FruError>>()?,
+            )?,
+
+            SendableValue::Object { type_name, fields } => {
+                let type_ = match global.get_variable(Identifier::new(&type_name)) {
+                    Ok(FruValue::Type(type_)) if type_.get_type_type() != TypeType::Class => type_,
+                    _ => {
+                        return FruError::new_res(format!(
+                            "object of `{}` is received, but there is no struct or data type with this name",
+                            type_name
+                        ))
+                    }
+                };
+
+                let args = (fields.into_iter())
+                    .map(|(name, value)| {
+                        Ok((Some(Identifier::new(&name)), value.into_value(global)?))
+                    })
+                    .collect::<Result<_, FruError>>()?;
+
+                type_.instantiate(EvaluatedArgumentList { args })?
+            }
+        })
+    }
+}
+
+impl TryFrom<&FruValue> for SendableValue {
+    type Error = FruError;
+
+    fn try_from(value: &FruValue) -> Result<Self, Self::Error> {
+        check_stack()?;
+
+        Ok(match value {
+            FruValue::Nah => SendableValue::Nah,
+            FruValue::Number(v) => SendableValue::Number(*v),
+            FruValue::Bool(v) => SendableValue::Bool(*v),
+            FruValue::String(v) => SendableValue::String(v.clone()),
+
+            FruValue::Object(object) if object.get_type().get_type_type() != TypeType::Class => {
+                let type_ = object.get_type();
+
+                SendableValue::Object {
+                    type_name: type_.get_ident().to_string(),
+                    fields: (type_.get_fields().iter())
+                        .zip(object.get_field_values())
+                        .map(|(field, value)| {
+                            Ok((field.ident.to_string(), Self::try_from(&value)?))
+                        })
+                        .collect::<Result<_, FruError>>()?,
+                }
+            }
+
+            other => {
+                if let Some(items) = extract_tuple_items(other) {
+                    return Ok(SendableValue::Tuple(
+                        items.iter().map(Self::try_from).collect::<Result<_, _>>()?,
+                    ));
+                }
+
+                if let Some(entries) = FruTreeMap::extract_entries(other) {
+                    return Ok(SendableValue::Map(
+                        (entries.iter())
+                            .map(|(key, value)| Ok((Self::try_from(key)?, Self::try_from(value)?)))
+                            .collect::<Result<_, FruError>>()?,
+                    ));
+                }
+
+                return FruError::new_res(format!(
+                    "`{}` cannot be sent to another interpreter",
+                    other.get_type_identifier()
+                ));
+            }
+        })
+    }
+}
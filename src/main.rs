@@ -1,32 +1,204 @@
-use std::{path::PathBuf, time::Instant};
+use std::{env::current_dir, fs::write, path::PathBuf, time::Instant};
 
-use crate::interpreter::runner::execute_file;
-use clap::Parser;
-
-mod interpreter;
-mod stdlib;
+use clap::{Parser, Subcommand};
+use frugurt::{
+    interpreter::{
+        error::FruError,
+        identifier::Identifier,
+        manifest::Manifest,
+        options::InterpreterOptions,
+        runner::{execute_ast_file_with_options, execute_file_with_options},
+    },
+    tooling::{
+        check::{check_path, default_globals},
+        doc::{self, DocFormat},
+        emit_js::emit_js_file,
+        highlight::highlight_file,
+        lint::{lint_path, LintConfig, Severity},
+        package,
+        repl::run_repl,
+    },
+};
 
 #[derive(Parser, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
 struct Args {
-    #[clap(required = true, help = "File to execute")]
-    filename: PathBuf,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[clap(help = "File to execute, entry point of the project by default")]
+    filename: Option<PathBuf>,
 
     #[clap(short, long, help = "Print execution time")]
     time: bool,
+
+    #[clap(long, help = "Don't check `_requires_` and `_ensures_` methods")]
+    no_contracts: bool,
+
+    #[clap(
+        long,
+        help = "Let `if` and `while` conditions be any value, converted like `Bool(x)`"
+    )]
+    truthy_conditions: bool,
+
+    #[clap(
+        long,
+        help = "Collect deprecations, implicit conversions and shadowed variables, print them at exit"
+    )]
+    warnings: bool,
+
+    #[clap(
+        long,
+        requires = "filename",
+        help = "The file is a JSON AST made by another frontend instead of source code"
+    )]
+    from_ast: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    #[clap(about = "Generate API documentation from declarations and docstrings")]
+    Doc {
+        #[clap(help = "File or directory to document")]
+        path: PathBuf,
+
+        #[clap(long, default_value = "markdown", value_parser = ["markdown", "html"])]
+        format: String,
+
+        #[clap(short, long, help = "Write documentation to a file instead of stdout")]
+        output: Option<PathBuf>,
+    },
+
+    #[clap(about = "Check code for likely mistakes, rules are configured in fruit.toml")]
+    Lint {
+        #[clap(help = "File or directory to check")]
+        path: PathBuf,
+    },
+
+    #[clap(about = "Start an interactive session, `:help` lists its commands")]
+    Repl,
+
+    #[clap(about = "Find undefined variables without running the program")]
+    Check {
+        #[clap(help = "File or directory to check")]
+        path: PathBuf,
+
+        #[clap(
+            long = "global",
+            help = "Name defined by the host, like `Parent` in workers"
+        )]
+        globals: Vec<String>,
+    },
+
+    #[clap(about = "Classify identifiers of a file for editors and list its declarations")]
+    Highlight {
+        #[clap(help = "File to highlight")]
+        path: PathBuf,
+
+        #[clap(long, help = "Print tokens and outline as JSON")]
+        json: bool,
+    },
+
+    #[clap(about = "Translate a file to a standalone JavaScript program")]
+    EmitJs {
+        #[clap(help = "File to translate")]
+        path: PathBuf,
+
+        #[clap(short, long, help = "Write JavaScript to a file instead of stdout")]
+        output: Option<PathBuf>,
+    },
+
+    #[clap(about = "Add a dependency to the project in the current directory and fetch it")]
+    Add {
+        #[clap(help = "Git url or path to a project, relative to the project root")]
+        source: String,
+
+        #[clap(
+            long,
+            help = "Name to import the dependency by, name of its package by default"
+        )]
+        name: Option<String>,
+    },
+
+    #[clap(about = "Fetch git dependencies at commits from fruit.lock")]
+    Fetch,
 }
 
 fn main() {
     let args: Args = Args::parse();
 
+    match args.command {
+        Some(Command::Doc {
+            path,
+            format,
+            output,
+        }) => run_doc(path, &format, output),
+
+        Some(Command::Lint { path }) => run_lint(path),
+
+        Some(Command::Repl) => run_interactive(),
+
+        Some(Command::Check { path, globals }) => run_check(path, globals),
+
+        Some(Command::Highlight { path, json }) => run_highlight(path, json),
+
+        Some(Command::EmitJs { path, output }) => run_emit_js(path, output),
+
+        Some(Command::Add { source, name }) => run_add(&source, name.as_deref()),
+
+        Some(Command::Fetch) => run_fetch(),
+
+        None => run_file(
+            args.filename,
+            args.time,
+            args.no_contracts,
+            args.truthy_conditions,
+            args.warnings,
+            args.from_ast,
+        ),
+    }
+}
+
+fn run_file(
+    filename: Option<PathBuf>,
+    time: bool,
+    no_contracts: bool,
+    truthy_conditions: bool,
+    warnings: bool,
+    from_ast: bool,
+) {
     let start = Instant::now();
 
-    let result = execute_file(args.filename.as_path());
+    let (filename, project) = match find_entry(filename) {
+        Ok(entry) => entry,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    };
 
-    if let Err(err) = &result {
-        eprintln!("{}", err);
+    let options = InterpreterOptions {
+        project,
+        skip_contracts: no_contracts,
+        truthy_conditions,
+        warnings,
+        ..Default::default()
+    };
+
+    let result = if from_ast {
+        execute_ast_file_with_options(&filename, options)
+    } else {
+        execute_file_with_options(&filename, options)
+    };
+
+    match &result {
+        // locations in the AST point at the source it was made from, which is unknown here
+        Err(err) if from_ast => eprintln!("error: {}", err),
+        Err(err) => eprint!("{}", err.render(&filename)),
+        Ok(_) => {}
     }
 
-    if args.time {
+    if time {
         println!("Program finished in {}ms", start.elapsed().as_millis());
     }
 
@@ -34,3 +206,190 @@ fn main() {
         std::process::exit(1);
     }
 }
+
+// without a file, the entry point of the project in the current directory is executed
+fn find_entry(filename: Option<PathBuf>) -> Result<(PathBuf, Option<Manifest>), FruError> {
+    match filename {
+        Some(filename) => {
+            let project = Manifest::find(&filename)?;
+            Ok((filename, project))
+        }
+
+        None => {
+            let dir = current_dir().map_err(|err| FruError::new(err.to_string()))?;
+
+            match Manifest::find(&dir)? {
+                Some(project) => Ok((project.entry_path(), Some(project))),
+                None => FruError::new_res("no file to execute and no fruit.toml found"),
+            }
+        }
+    }
+}
+
+fn run_doc(path: PathBuf, format: &str, output: Option<PathBuf>) {
+    let format = match format {
+        "html" => DocFormat::Html,
+        _ => DocFormat::Markdown,
+    };
+
+    let docs = match doc::generate(&path, format) {
+        Ok(docs) => docs,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    };
+
+    match output {
+        Some(output) => {
+            if let Err(err) = write(&output, docs) {
+                eprintln!("Error writing file {output:?} {err}");
+                std::process::exit(1);
+            }
+        }
+
+        None => print!("{}", docs),
+    }
+}
+
+fn run_lint(path: PathBuf) {
+    let result = LintConfig::find(&path).and_then(|config| lint_path(&path, &config));
+
+    let files = match result {
+        Ok(files) => files,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    };
+
+    let mut has_errors = false;
+
+    for (file, diagnostics) in files {
+        for diagnostic in diagnostics {
+            has_errors |= diagnostic.severity == Severity::Error;
+            println!("{}:{}", file.display(), diagnostic);
+        }
+    }
+
+    if has_errors {
+        std::process::exit(1);
+    }
+}
+
+fn run_interactive() {
+    let options = InterpreterOptions {
+        project: current_dir().ok().and_then(|dir| Manifest::find(&dir).ok().flatten()),
+        ..Default::default()
+    };
+
+    if let Err(err) = run_repl(options, std::io::stdin().lock(), std::io::stdout()) {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+}
+
+fn run_check(path: PathBuf, extra_globals: Vec<String>) {
+    let mut globals = default_globals();
+    globals.extend(extra_globals.iter().map(|x| Identifier::new(x)));
+
+    let files = match check_path(&path, &globals) {
+        Ok(files) => files,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    };
+
+    let mut has_errors = false;
+
+    for (file, diagnostics) in files {
+        for diagnostic in diagnostics {
+            has_errors = true;
+            println!("{}:{}", file.display(), diagnostic);
+        }
+    }
+
+    if has_errors {
+        std::process::exit(1);
+    }
+}
+
+fn run_highlight(path: PathBuf, json: bool) {
+    let highlights = match highlight_file(&path) {
+        Ok(highlights) => highlights,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    };
+
+    if json {
+        println!("{}", highlights.to_json());
+        return;
+    }
+
+    for token in highlights.tokens {
+        let declaration = if token.declaration {
+            " (declaration)"
+        } else {
+            ""
+        };
+
+        println!(
+            "{}:{}: {} {}{}",
+            token.line,
+            token.column,
+            token.kind.name(),
+            token.text,
+            declaration
+        );
+    }
+}
+
+fn run_emit_js(path: PathBuf, output: Option<PathBuf>) {
+    let js = match emit_js_file(&path) {
+        Ok(js) => js,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    };
+
+    match output {
+        Some(output) => {
+            if let Err(err) = write(&output, js) {
+                eprintln!("Error writing file {output:?} {err}");
+                std::process::exit(1);
+            }
+        }
+
+        None => print!("{}", js),
+    }
+}
+
+fn project_root() -> Result<PathBuf, FruError> {
+    let dir = current_dir().map_err(|err| FruError::new(err.to_string()))?;
+
+    match Manifest::find(&dir)? {
+        Some(project) => Ok(project.root),
+        None => FruError::new_res("no fruit.toml found"),
+    }
+}
+
+fn run_add(source: &str, name: Option<&str>) {
+    match project_root().and_then(|root| package::add(&root, source, name)) {
+        Ok(name) => println!("added dependency `{}`", name),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_fetch() {
+    if let Err(err) = project_root().and_then(|root| package::fetch(&root)) {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+}
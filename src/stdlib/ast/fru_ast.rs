@@ -0,0 +1,377 @@
+use std::{any::Any, fmt, rc::Rc};
+
+use macros::static_ident;
+
+use crate::interpreter::{
+    control::returned,
+    error::FruError,
+    expression::FruExpression,
+    identifier::Identifier,
+    value::fru_value::FruValue,
+    value::function::{ArgumentList, BuiltinFunction, EvaluatedArgumentList},
+    value::native::object::{INativeObject, NativeObject},
+};
+use crate::stdlib::scope::fru_scope::extract_scope_from_value;
+
+pub struct FruAstType;
+
+/// Quoted expression, what macros receive and return
+pub struct FruAst {
+    expression: Rc<FruExpression>,
+}
+
+impl FruAstType {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self)))
+    }
+}
+
+impl INativeObject for FruAstType {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("AstType")
+    }
+
+    // constructors take `Ast` values for children, other values become literals
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        let constructor = match ident {
+            x if x == static_ident!("literal") => BuiltinFunction::new_closure(|args| {
+                let [value] = positional::<1>(args, "literal")?;
+                Ok(FruAst::new_value(FruExpression::Literal { value }))
+            }),
+
+            x if x == static_ident!("variable") => BuiltinFunction::new_closure(|args| {
+                let [ident] = positional::<1>(args, "variable")?;
+                Ok(FruAst::new_value(FruExpression::Variable {
+                    ident: string_argument(&ident, "variable")?,
+                }))
+            }),
+
+            x if x == static_ident!("binary") => BuiltinFunction::new_closure(|args| {
+                let [operator, left, right] = positional::<3>(args, "binary")?;
+                Ok(FruAst::new_value(FruExpression::Binary {
+                    operator: string_argument(&operator, "binary")?,
+                    left: Box::new(value_to_expression(left)),
+                    right: Box::new(value_to_expression(right)),
+                }))
+            }),
+
+            x if x == static_ident!("prop") => BuiltinFunction::new_closure(|args| {
+                let [what, ident] = positional::<2>(args, "prop")?;
+                Ok(FruAst::new_value(FruExpression::PropAccess {
+                    what: Box::new(value_to_expression(what)),
+                    ident: string_argument(&ident, "prop")?,
+                }))
+            }),
+
+            x if x == static_ident!("condition") => BuiltinFunction::new_closure(|args| {
+                let [condition, then_body, else_body] = positional::<3>(args, "condition")?;
+                Ok(FruAst::new_value(FruExpression::If {
+                    condition: Box::new(value_to_expression(condition)),
+                    then_body: Box::new(value_to_expression(then_body)),
+                    else_body: Box::new(value_to_expression(else_body)),
+                }))
+            }),
+
+            // `Ast.call(f, a, b: c)` is `f(a, b: c)`
+            x if x == static_ident!("call") => BuiltinFunction::new_closure(|args| {
+                let mut args = args.args.into_iter();
+
+                let what = match args.next() {
+                    Some((None, what)) => what,
+                    _ => {
+                        return FruError::new_res("`call` expects a function as the first argument")
+                    }
+                };
+
+                Ok(FruAst::new_value(FruExpression::Call {
+                    what: Box::new(value_to_expression(what)),
+                    args: ArgumentList {
+                        args: args
+                            .map(|(ident, value)| (ident, value_to_expression(value)))
+                            .collect(),
+                    },
+                }))
+            }),
+
+            _ => return FruError::new_res(format!("prop `{}` not found", ident)),
+        };
+
+        Ok(constructor.into())
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+impl FruAst {
+    pub fn new_value(expression: FruExpression) -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self {
+            expression: Rc::new(expression),
+        })))
+    }
+
+    fn child(expression: &FruExpression) -> FruValue {
+        Self::new_value(expression.clone())
+    }
+
+    fn kind(&self) -> &'static str {
+        match self.expression.as_ref() {
+            FruExpression::Literal { .. } => "Literal",
+            FruExpression::Variable { .. } => "Variable",
+            FruExpression::ScopeAccessor => "ScopeAccessor",
+            FruExpression::Function { .. } => "Function",
+            FruExpression::Block { .. } => "Block",
+            FruExpression::ScopeModifier { .. } => "ScopeModifier",
+            FruExpression::Call { .. } => "Call",
+            FruExpression::CurryCall { .. } => "CurryCall",
+            FruExpression::Instantiation { .. } => "Instantiation",
+            FruExpression::PropAccess { .. } => "PropAccess",
+            FruExpression::Binary { .. } => "Binary",
+            FruExpression::If { .. } => "If",
+            FruExpression::Import { .. } => "Import",
+        }
+    }
+
+    fn node_prop(&self, ident: Identifier) -> Option<FruValue> {
+        let name = |ident: &Identifier| FruValue::String(ident.to_string());
+
+        Some(match (self.expression.as_ref(), ident) {
+            (FruExpression::Literal { value }, x) if x == static_ident!("value") => value.clone(),
+
+            (FruExpression::Variable { ident }, x) if x == static_ident!("ident") => name(ident),
+
+            (
+                FruExpression::Function {
+                    name: Some(ident), ..
+                },
+                x,
+            ) if x == static_ident!("name") => name(ident),
+
+            (FruExpression::Block { expr, .. } | FruExpression::ScopeModifier { expr, .. }, x)
+                if x == static_ident!("expr") =>
+            {
+                Self::child(expr)
+            }
+
+            (
+                FruExpression::Call { what, .. }
+                | FruExpression::CurryCall { what, .. }
+                | FruExpression::Instantiation { what, .. }
+                | FruExpression::PropAccess { what, .. },
+                x,
+            ) if x == static_ident!("what") => Self::child(what),
+
+            (
+                FruExpression::Call { args, .. }
+                | FruExpression::CurryCall { args, .. }
+                | FruExpression::Instantiation { args, .. },
+                x,
+            ) => return Self::argument_prop(args, x),
+
+            (FruExpression::PropAccess { ident, .. }, x) if x == static_ident!("ident") => {
+                name(ident)
+            }
+
+            (FruExpression::Binary { operator, .. }, x) if x == static_ident!("operator") => {
+                name(operator)
+            }
+
+            (FruExpression::Binary { left, .. }, x) if x == static_ident!("left") => {
+                Self::child(left)
+            }
+
+            (FruExpression::Binary { right, .. }, x) if x == static_ident!("right") => {
+                Self::child(right)
+            }
+
+            (FruExpression::If { condition, .. }, x) if x == static_ident!("condition") => {
+                Self::child(condition)
+            }
+
+            (FruExpression::If { then_body, .. }, x) if x == static_ident!("then_body") => {
+                Self::child(then_body)
+            }
+
+            (FruExpression::If { else_body, .. }, x) if x == static_ident!("else_body") => {
+                Self::child(else_body)
+            }
+
+            (FruExpression::Import { path }, x) if x == static_ident!("path") => Self::child(path),
+
+            _ => return None,
+        })
+    }
+
+    fn argument_prop(args: &ArgumentList, ident: Identifier) -> Option<FruValue> {
+        if ident == static_ident!("arg_count") {
+            return Some(FruValue::Number(args.args.len() as f64));
+        }
+
+        let args = Rc::new(args.args.clone());
+
+        let method = match ident {
+            x if x == static_ident!("arg") => BuiltinFunction::new_closure(move |call| {
+                let (_, value) = &args[index_argument(call, args.len(), "arg")?];
+                Ok(Self::child(value))
+            }),
+
+            // `nah` for positional arguments
+            x if x == static_ident!("arg_name") => BuiltinFunction::new_closure(move |call| {
+                let (ident, _) = &args[index_argument(call, args.len(), "arg_name")?];
+                Ok(ident.map_or(FruValue::Nah, |x| FruValue::String(x.to_string())))
+            }),
+
+            _ => return None,
+        };
+
+        Some(method.into())
+    }
+}
+
+impl INativeObject for FruAst {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("Ast")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        if ident == static_ident!("kind") {
+            return Ok(FruValue::String(self.kind().to_string()));
+        }
+
+        if ident == static_ident!("eval_in") {
+            let expression = self.expression.clone();
+
+            return Ok(BuiltinFunction::new_closure(move |args| {
+                let [scope] = positional::<1>(args, "eval_in")?;
+
+                let Some(scope) = extract_scope_from_value(&scope) else {
+                    return FruError::new_res(format!(
+                        "`eval_in` expects a scope, got `{}`",
+                        scope.get_type_identifier()
+                    ));
+                };
+
+                returned(expression.evaluate(scope))
+            })
+            .into());
+        }
+
+        self.node_prop(ident).map_or_else(
+            || {
+                FruError::new_res(format!(
+                    "prop `{}` not found in `{}` Ast",
+                    ident,
+                    self.kind()
+                ))
+            },
+            Ok,
+        )
+    }
+
+    fn fmt_debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Ast({})", self.kind())
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+pub fn extract_ast_from_value(v: &FruValue) -> Option<Rc<FruExpression>> {
+    if let FruValue::NativeObject(o) = v {
+        o.downcast::<FruAst>().map(|x| x.expression.clone())
+    } else {
+        None
+    }
+}
+
+/// `Ast` values are unwrapped, everything else becomes a literal
+pub fn value_to_expression(value: FruValue) -> FruExpression {
+    match extract_ast_from_value(&value) {
+        Some(expression) => Rc::unwrap_or_clone(expression),
+        None => FruExpression::Literal { value },
+    }
+}
+
+fn positional<const N: usize>(
+    args: EvaluatedArgumentList,
+    name: &str,
+) -> Result<[FruValue; N], FruError> {
+    if args.args.len() != N || args.args.iter().any(|(ident, _)| ident.is_some()) {
+        return FruError::new_res(format!("`{}` expects {} arguments without names", name, N));
+    }
+
+    Ok(args
+        .args
+        .into_iter()
+        .map(|(_, value)| value)
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap_or_else(|_| unreachable!()))
+}
+
+fn string_argument(value: &FruValue, name: &str) -> Result<Identifier, FruError> {
+    match value {
+        FruValue::String(s) => Ok(Identifier::new(s)),
+        other => FruError::new_res(format!(
+            "`{}` expects a name as `String`, got `{}`",
+            name,
+            other.get_type_identifier()
+        )),
+    }
+}
+
+fn index_argument(args: EvaluatedArgumentList, len: usize, name: &str) -> Result<usize, FruError> {
+    match positional::<1>(args, name)? {
+        [FruValue::Number(n)] if n >= 0.0 && n.fract() == 0.0 && (n as usize) < len => {
+            Ok(n as usize)
+        }
+
+        [other] => FruError::new_res(format!(
+            "`{}` expects an index below {}, got {:?}",
+            name, len, other
+        )),
+    }
+}
+
+// `macro`, `quote`, `unquote`, `op`, `when`, `case` and `fallthrough` are replaced before execution,
+// these only run when they are not
+
+pub fn b_macro(_: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    FruError::new_res(
+        "`macro` can only be used as `let name = macro(fn(...) {...});` at the top level",
+    )
+}
+
+pub fn b_quote(_: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    FruError::new_res("`quote` can only be called directly, as `quote(expression)`")
+}
+
+pub fn b_unquote(_: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    FruError::new_res("`unquote` can only be used inside of `quote`")
+}
+
+pub fn b_op(_: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    FruError::new_res("`op` expects an operator as a string literal, like `op(\"+\")`")
+}
+
+pub fn b_when(_: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    FruError::new_res("`when` can only be called directly, as `when(value, case(...), ...)`")
+}
+
+pub fn b_case(_: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    FruError::new_res("`case` can only be used inside of `when`")
+}
+
+pub fn b_fallthrough(_: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    FruError::new_res("`fallthrough` can only be the result of `case`")
+}
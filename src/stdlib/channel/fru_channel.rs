@@ -0,0 +1,167 @@
+use std::{any::Any, cell::RefCell, collections::VecDeque, rc::Rc};
+
+use macros::static_ident;
+
+use crate::interpreter::{
+    error::FruError,
+    identifier::Identifier,
+    runtime::current_runtime,
+    scheduler::Wait,
+    value::fru_value::FruValue,
+    value::function::{BuiltinFunction, EvaluatedArgumentList},
+    value::native::object::{INativeObject, NativeObject},
+};
+
+pub struct FruChannelType;
+
+pub struct FruChannel {
+    internal: Rc<ChannelInternal>,
+}
+
+struct ChannelInternal {
+    buffer: RefCell<VecDeque<FruValue>>,
+    capacity: Option<usize>,
+}
+
+impl FruChannelType {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self)))
+    }
+}
+
+impl INativeObject for FruChannelType {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("ChannelType")
+    }
+
+    fn instantiate(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+        let capacity = match args.args.as_slice() {
+            [] => None,
+
+            [(None, FruValue::Number(n))] => Some(*n),
+
+            [(Some(ident), FruValue::Number(n))] if *ident == static_ident!("capacity") => Some(*n),
+
+            _ => {
+                return FruError::new_res("`Channel` expects optional `capacity` argument");
+            }
+        };
+
+        let capacity = match capacity {
+            Some(n) if n.fract() != 0.0 || n < 1.0 => {
+                return FruError::new_res("channel capacity must be a positive integer");
+            }
+
+            capacity => capacity.map(|n| n as usize),
+        };
+
+        Ok(FruChannel::new_value(capacity))
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+impl FruChannel {
+    pub fn new_value(capacity: Option<usize>) -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self {
+            internal: Rc::new(ChannelInternal {
+                buffer: RefCell::new(VecDeque::new()),
+                capacity,
+            }),
+        })))
+    }
+}
+
+impl ChannelInternal {
+    fn is_full(&self) -> bool {
+        self.capacity.is_some_and(|capacity| self.buffer.borrow().len() >= capacity)
+    }
+
+    // while channel is full other tasks get a chance to receive from it
+    fn send(self: &Rc<Self>, value: FruValue) -> Result<(), FruError> {
+        let channel = self.clone();
+        let wait = Wait::new(
+            "channel is full and no task can receive from it",
+            move || !channel.is_full(),
+        );
+
+        current_runtime()?
+            .scheduler()
+            .wait_until(wait, || Ok((!self.is_full()).then_some(())))?;
+
+        self.buffer.borrow_mut().push_back(value);
+
+        Ok(())
+    }
+
+    // while channel is empty other tasks get a chance to send to it
+    fn recv(self: &Rc<Self>) -> Result<FruValue, FruError> {
+        let channel = self.clone();
+        let wait = Wait::new("channel is empty and no task can send to it", move || {
+            !channel.buffer.borrow().is_empty()
+        });
+
+        current_runtime()?.scheduler().wait_until(wait, || Ok(self.try_recv()))
+    }
+
+    fn try_recv(&self) -> Option<FruValue> {
+        self.buffer.borrow_mut().pop_front()
+    }
+}
+
+impl INativeObject for FruChannel {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("Channel")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        let internal = self.internal.clone();
+
+        let method = match ident {
+            x if x == static_ident!("send") => {
+                BuiltinFunction::new_closure(move |args| match args.args.as_slice() {
+                    [(None, value)] => internal.send(value.clone()).map(|_| FruValue::Nah),
+
+                    _ => FruError::new_res("`send` expects 1 argument"),
+                })
+            }
+
+            x if x == static_ident!("recv") => {
+                BuiltinFunction::new_closure(move |_| internal.recv())
+            }
+
+            x if x == static_ident!("try_recv") => BuiltinFunction::new_closure(move |_| {
+                Ok(internal.try_recv().unwrap_or(FruValue::Nah))
+            }),
+
+            x if x == static_ident!("len") => {
+                return Ok(FruValue::Number(self.internal.buffer.borrow().len() as f64));
+            }
+
+            x if x == static_ident!("capacity") => {
+                return Ok(match self.internal.capacity {
+                    Some(capacity) => FruValue::Number(capacity as f64),
+                    None => FruValue::Nah,
+                });
+            }
+
+            _ => return FruError::new_res(format!("prop `{}` not found", ident)),
+        };
+
+        Ok(method.into())
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::{HashMap, HashSet};
+
+use macros::static_ident;
+
+use crate::interpreter::{
+    error::FruError, identifier::Identifier, value::fru_object::FruObject,
+    value::fru_value::FruValue, value::function::EvaluatedArgumentList,
+    value::native::object::NativeObject,
+};
+
+/// Copies values, shared values like functions and channels are kept as they are
+struct Cloner {
+    deep: bool,
+    // copies by the address of the original, so shared parts stay shared and cycles are kept
+    copies: HashMap<*const (), FruValue>,
+    // builtin collections are made after their items, so they can't be part of a cycle
+    in_progress: HashSet<*const ()>,
+}
+
+impl Cloner {
+    fn new(deep: bool) -> Self {
+        Self {
+            deep,
+            copies: HashMap::new(),
+            in_progress: HashSet::new(),
+        }
+    }
+
+    fn copy(&mut self, value: FruValue) -> Result<FruValue, FruError> {
+        match value {
+            FruValue::Object(object) => self.copy_object(object),
+            FruValue::NativeObject(object) => self.copy_native(object),
+            other => Ok(other),
+        }
+    }
+
+    fn copy_item(&mut self, value: FruValue) -> Result<FruValue, FruError> {
+        if self.deep {
+            self.copy(value)
+        } else {
+            Ok(value)
+        }
+    }
+
+    fn copy_object(&mut self, object: FruObject) -> Result<FruValue, FruError> {
+        if let Some(copy) = self.copies.get(&object.as_ptr()) {
+            return Ok(copy.clone());
+        }
+
+        if object.get_type().get_method(static_ident!("_clone")).is_some() {
+            let copy = object
+                .get_prop(static_ident!("_clone"))?
+                .call(EvaluatedArgumentList { args: Vec::new() })?;
+
+            self.copies.insert(object.as_ptr(), copy.clone());
+
+            return Ok(copy);
+        }
+
+        let copy = FruObject::new(object.get_type(), object.get_field_values());
+        self.copies.insert(object.as_ptr(), FruValue::Object(copy.clone()));
+
+        if self.deep {
+            let fields = (object.get_field_values().into_iter())
+                .map(|value| self.copy(value))
+                .collect::<Result<_, _>>()?;
+
+            copy.set_field_values(fields);
+        }
+
+        Ok(FruValue::Object(copy))
+    }
+
+    fn copy_native(&mut self, object: NativeObject) -> Result<FruValue, FruError> {
+        let ptr = object.as_ptr();
+
+        if let Some(copy) = self.copies.get(&ptr) {
+            return Ok(copy.clone());
+        }
+
+        if !self.in_progress.insert(ptr) {
+            return FruError::new_res(format!(
+                "cannot clone `{}` that contains itself",
+                object.get_type_identifier()
+            ));
+        }
+
+        let copy = object
+            .copy_with(&mut |item| self.copy_item(item))
+            .unwrap_or_else(|| Ok(FruValue::NativeObject(object.clone())))?;
+
+        self.in_progress.remove(&ptr);
+        self.copies.insert(ptr, copy.clone());
+
+        Ok(copy)
+    }
+}
+
+fn clone_with(name: &str, args: EvaluatedArgumentList, deep: bool) -> Result<FruValue, FruError> {
+    match <[_; 1]>::try_from(args.args) {
+        Ok([(None, value)]) => Cloner::new(deep).copy(value),
+        _ => FruError::new_res(format!("`{}` expects 1 argument", name)),
+    }
+}
+
+/// New object with the same fields, or collection with the same items
+pub fn b_clone(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    clone_with("clone", args, false)
+}
+
+/// Copies everything reachable from the value, objects with `_clone` are copied by it
+pub fn b_deep_clone(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    clone_with("deep_clone", args, true)
+}
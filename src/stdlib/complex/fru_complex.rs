@@ -0,0 +1,249 @@
+use std::{any::Any, fmt, rc::Rc};
+
+use macros::{derive_nat, static_ident};
+
+use crate::interpreter::{
+    builtins::operators::unexpected_operand,
+    error::FruError,
+    identifier::{id, Identifier, OperatorIdentifier},
+    value::fru_value::{FruValue, TOpBuiltin},
+    value::function::{BuiltinFunction, EvaluatedArgumentList},
+    value::native::object::{INativeObject, NativeObject},
+    value::operator::AnyOperator,
+};
+
+const COMPLEX: Identifier = static_ident!("Complex");
+
+pub struct FruComplexType;
+
+#[derive_nat("Complex", immutable, methods = Self::method, debug = Self::fmt_complex)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct FruComplex {
+    #[fru(readonly)]
+    pub re: f64,
+    #[fru(readonly)]
+    pub im: f64,
+}
+
+impl FruComplexType {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self)))
+    }
+}
+
+impl INativeObject for FruComplexType {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("ComplexType")
+    }
+
+    fn instantiate(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+        let mut re = None;
+        let mut im = None;
+
+        for (k, (ident, value)) in args.args.iter().enumerate() {
+            let slot = match (ident, k) {
+                (None, 0) => &mut re,
+                (None, 1) => &mut im,
+                (Some(x), _) if *x == static_ident!("re") => &mut re,
+                (Some(x), _) if *x == static_ident!("im") => &mut im,
+                _ => return FruError::new_res("`Complex` expects `re` and `im` arguments"),
+            };
+
+            *slot = Some(number(value)?);
+        }
+
+        match (re, im) {
+            (Some(re), Some(im)) => Ok(FruComplex::new_value(re, im)),
+            _ => FruError::new_res("`Complex` expects `re` and `im` arguments"),
+        }
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        match ident {
+            x if x == static_ident!("i") => Ok(FruComplex::new_value(0.0, 1.0)),
+
+            x if x == static_ident!("polar") => {
+                Ok(BuiltinFunction::new(|args| match args.args.as_slice() {
+                    [(None, r), (None, phi)] => {
+                        let (r, phi) = (number(r)?, number(phi)?);
+                        Ok(FruComplex::new_value(r * phi.cos(), r * phi.sin()))
+                    }
+
+                    _ => FruError::new_res("`polar` expects 2 arguments"),
+                })
+                .into())
+            }
+
+            _ => FruError::new_res(format!("prop `{}` not found", ident)),
+        }
+    }
+
+    fn operators(&self) -> Vec<(OperatorIdentifier, AnyOperator)> {
+        complex_operators()
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+impl FruComplex {
+    pub fn new_value(re: f64, im: f64) -> FruValue {
+        Self { re, im }.into_value()
+    }
+
+    fn abs(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    fn arg(self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    fn conj(self) -> Self {
+        Self {
+            re: self.re,
+            im: -self.im,
+        }
+    }
+
+    fn method(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        let this = *self;
+
+        let method = match ident {
+            x if x == static_ident!("abs") => {
+                BuiltinFunction::new_closure(move |_| Ok(FruValue::Number(this.abs())))
+            }
+
+            x if x == static_ident!("arg") => {
+                BuiltinFunction::new_closure(move |_| Ok(FruValue::Number(this.arg())))
+            }
+
+            x if x == static_ident!("conj") => {
+                BuiltinFunction::new_closure(move |_| Ok(this.conj().into_value()))
+            }
+
+            _ => return FruError::new_res(format!("prop `{}` not found", ident)),
+        };
+
+        Ok(method.into())
+    }
+
+    fn fmt_complex(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.im.is_sign_negative() {
+            write!(f, "{}-{}i", self.re, -self.im)
+        } else {
+            write!(f, "{}+{}i", self.re, self.im)
+        }
+    }
+}
+
+/// Arithmetic between complex numbers and with real numbers on either side
+fn complex_operators() -> Vec<(OperatorIdentifier, AnyOperator)> {
+    let arithmetic: [(Identifier, TOpBuiltin); 4] = [
+        (id::PLUS, complex_plus),
+        (id::MINUS, complex_minus),
+        (id::MULTIPLY, complex_mul),
+        (id::DIVIDE, complex_div),
+    ];
+
+    let mut operators = Vec::new();
+
+    for (op, function) in arithmetic {
+        for (left, right) in [
+            (COMPLEX, COMPLEX),
+            (COMPLEX, id::NUMBER),
+            (id::NUMBER, COMPLEX),
+        ] {
+            operators.push((
+                OperatorIdentifier::new(op, left, right),
+                AnyOperator::BuiltinOperator(function),
+            ));
+        }
+    }
+
+    operators.push((
+        OperatorIdentifier::new(id::EQ, COMPLEX, COMPLEX),
+        AnyOperator::BuiltinOperator(complex_eq),
+    ));
+
+    operators.push((
+        OperatorIdentifier::new(id::NOT_EQ, COMPLEX, COMPLEX),
+        AnyOperator::BuiltinOperator(complex_not_eq),
+    ));
+
+    operators
+}
+
+// real numbers are complex numbers with zero imaginary part
+fn operands(left: FruValue, right: FruValue) -> Result<(FruComplex, FruComplex), FruError> {
+    let convert = |value: FruValue| match &value {
+        FruValue::Number(re) => Ok(FruComplex { re: *re, im: 0.0 }),
+
+        FruValue::NativeObject(obj) => match obj.downcast::<FruComplex>() {
+            Some(complex) => Ok(*complex),
+            None => unexpected_operand(&value),
+        },
+
+        _ => unexpected_operand(&value),
+    };
+
+    Ok((convert(left)?, convert(right)?))
+}
+
+fn complex_plus(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    let (l, r) = operands(left, right)?;
+    Ok(FruComplex::new_value(l.re + r.re, l.im + r.im))
+}
+
+fn complex_minus(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    let (l, r) = operands(left, right)?;
+    Ok(FruComplex::new_value(l.re - r.re, l.im - r.im))
+}
+
+fn complex_mul(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    let (l, r) = operands(left, right)?;
+
+    Ok(FruComplex::new_value(
+        l.re * r.re - l.im * r.im,
+        l.re * r.im + l.im * r.re,
+    ))
+}
+
+fn complex_div(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    let (l, r) = operands(left, right)?;
+    let denominator = r.re * r.re + r.im * r.im;
+
+    if denominator == 0.0 {
+        return FruError::new_res("division by zero");
+    }
+
+    Ok(FruComplex::new_value(
+        (l.re * r.re + l.im * r.im) / denominator,
+        (l.im * r.re - l.re * r.im) / denominator,
+    ))
+}
+
+fn complex_eq(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    let (l, r) = operands(left, right)?;
+    Ok(FruValue::Bool(l == r))
+}
+
+fn complex_not_eq(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    let (l, r) = operands(left, right)?;
+    Ok(FruValue::Bool(l != r))
+}
+
+fn number(value: &FruValue) -> Result<f64, FruError> {
+    match value {
+        FruValue::Number(n) => Ok(*n),
+        other => FruError::new_res(format!(
+            "expected `Number`, got `{}`",
+            other.get_type_identifier()
+        )),
+    }
+}
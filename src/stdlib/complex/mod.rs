@@ -0,0 +1 @@
+pub mod fru_complex;
@@ -0,0 +1,301 @@
+use std::{
+    any::Any,
+    cell::RefCell,
+    fmt,
+    fs::File,
+    io::{self, Read, Write},
+    rc::Rc,
+};
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use macros::static_ident;
+use zip::ZipArchive;
+
+use crate::interpreter::{
+    error::FruError,
+    identifier::Identifier,
+    options::Capability,
+    runtime::{check_allocation, require_capability},
+    value::fru_value::FruValue,
+    value::function::{BuiltinFunction, EvaluatedArgumentList},
+    value::native::object::{INativeObject, NativeObject},
+};
+use crate::stdlib::{
+    crypto::fru_crypto::{from_hex, to_hex},
+    fs::fru_fs::write_atomic,
+    tuple::fru_tuple::FruTuple,
+};
+
+/// `Gzip` namespace, compressed data is passed around as hex, like other bytes
+pub struct FruGzip;
+
+/// `Zip` namespace, only reading archives is supported
+pub struct FruZip;
+
+/// Archive opened with `Zip.open`, entries are decompressed only when they are read
+pub struct FruZipArchive {
+    path: String,
+    // `None` once the archive is closed
+    archive: Rc<ArchiveCell>,
+}
+
+type ArchiveCell = RefCell<Option<ZipArchive<File>>>;
+
+impl FruGzip {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self)))
+    }
+}
+
+impl FruZip {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self)))
+    }
+}
+
+fn string_arguments<const N: usize>(
+    args: EvaluatedArgumentList,
+    name: &str,
+) -> Result<[String; N], FruError> {
+    let strings: Option<Vec<String>> = (args.args.into_iter())
+        .map(|arg| match arg {
+            (None, FruValue::String(s)) => Some(s),
+            _ => None,
+        })
+        .collect();
+
+    match strings.map(<[String; N]>::try_from) {
+        Some(Ok(strings)) => Ok(strings),
+        _ => FruError::new_res(format!("`{}` expects {} `String` arguments", name, N)),
+    }
+}
+
+fn compress(text: &str) -> Result<Vec<u8>, io::Error> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(text.as_bytes())?;
+    encoder.finish()
+}
+
+// checked against the memory limit while reading, a small input may decompress to a lot
+fn decompress(reader: impl Read, what: &str) -> Result<String, FruError> {
+    let error =
+        |err: &dyn fmt::Display| FruError::new(format!("cannot decompress {}: {}", what, err));
+
+    let mut bytes = Vec::new();
+    let mut chunk = [0; 64 * 1024];
+    let mut reader = GzDecoder::new(reader);
+
+    loop {
+        let n = reader.read(&mut chunk).map_err(|err| error(&err))?;
+
+        if n == 0 {
+            break;
+        }
+
+        check_allocation(bytes.len() + n)?;
+        bytes.extend_from_slice(&chunk[..n]);
+    }
+
+    String::from_utf8(bytes).map_err(|_| error(&"it is not UTF-8 text"))
+}
+
+impl INativeObject for FruGzip {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("Gzip")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        let function = match ident {
+            x if x == static_ident!("compress") => BuiltinFunction::new_closure(|args| {
+                let [text] = string_arguments(args, "compress")?;
+
+                let compressed = compress(&text)
+                    .map_err(|err| FruError::new(format!("cannot compress: {}", err)))?;
+
+                Ok(FruValue::String(to_hex(&compressed)))
+            }),
+
+            x if x == static_ident!("decompress") => BuiltinFunction::new_closure(|args| {
+                let [hex] = string_arguments(args, "decompress")?;
+
+                decompress(from_hex(&hex)?.as_slice(), "data").map(FruValue::String)
+            }),
+
+            // text of a `.gz` file
+            x if x == static_ident!("read") => BuiltinFunction::new_closure(|args| {
+                require_capability(Capability::Fs)?;
+                let [path] = string_arguments(args, "read")?;
+
+                let file = File::open(&path)
+                    .map_err(|err| FruError::new(format!("cannot read {:?}: {}", path, err)))?;
+
+                decompress(file, &format!("{:?}", path)).map(FruValue::String)
+            }),
+
+            x if x == static_ident!("write") => BuiltinFunction::new_closure(|args| {
+                require_capability(Capability::Fs)?;
+                let [path, text] = string_arguments(args, "write")?;
+
+                let compressed = compress(&text)
+                    .map_err(|err| FruError::new(format!("cannot write {:?}: {}", path, err)))?;
+
+                std::fs::write(&path, compressed)
+                    .map_err(|err| FruError::new(format!("cannot write {:?}: {}", path, err)))?;
+
+                Ok(FruValue::Nah)
+            }),
+
+            _ => return FruError::new_res(format!("prop `{}` not found", ident)),
+        };
+
+        Ok(function.into())
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+impl INativeObject for FruZip {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("Zip")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        if ident == static_ident!("open") {
+            return Ok(BuiltinFunction::new_closure(|args| {
+                require_capability(Capability::Fs)?;
+                let [path] = string_arguments(args, "open")?;
+
+                FruZipArchive::new_value(path)
+            })
+            .into());
+        }
+
+        FruError::new_res(format!("prop `{}` not found", ident))
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+impl FruZipArchive {
+    fn new_value(path: String) -> Result<FruValue, FruError> {
+        let error =
+            |err: &dyn fmt::Display| FruError::new(format!("cannot open {:?}: {}", path, err));
+
+        let file = File::open(&path).map_err(|err| error(&err))?;
+        let archive = ZipArchive::new(file).map_err(|err| error(&err))?;
+
+        Ok(FruValue::NativeObject(NativeObject::new(Rc::new(Self {
+            path,
+            archive: Rc::new(RefCell::new(Some(archive))),
+        }))))
+    }
+}
+
+fn open_archive<'a>(
+    archive: &'a mut Option<ZipArchive<File>>,
+    path: &str,
+) -> Result<&'a mut ZipArchive<File>, FruError> {
+    archive
+        .as_mut()
+        .ok_or_else(|| FruError::new(format!("archive {:?} is closed", path)))
+}
+
+fn read_entry(archive: &ArchiveCell, path: &str, name: &str) -> Result<String, FruError> {
+    let error = |err: &dyn fmt::Display| {
+        FruError::new(format!("cannot read `{}` from {:?}: {}", name, path, err))
+    };
+
+    let mut archive = archive.borrow_mut();
+    let archive = open_archive(&mut archive, path)?;
+    let mut entry = archive.by_name(name).map_err(|err| error(&err))?;
+
+    check_allocation(entry.size() as usize)?;
+
+    let mut text = String::new();
+    entry.read_to_string(&mut text).map_err(|err| error(&err))?;
+
+    Ok(text)
+}
+
+impl INativeObject for FruZipArchive {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("ZipArchive")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        if ident == static_ident!("path") {
+            return Ok(FruValue::String(self.path.clone()));
+        }
+
+        let path = self.path.clone();
+        let archive = self.archive.clone();
+
+        let method = match ident {
+            // names of files and directories in the order they are stored, directories end with `/`
+            x if x == static_ident!("entries") => BuiltinFunction::new_closure(move |_| {
+                let mut archive = archive.borrow_mut();
+                let archive = open_archive(&mut archive, &path)?;
+
+                // raw entries are not decompressed, only their headers are read
+                let names = (0..archive.len())
+                    .map(|k| match archive.by_index_raw(k) {
+                        Ok(entry) => Ok(FruValue::String(entry.name().to_string())),
+                        Err(err) => FruError::new_res(format!("cannot read {:?}: {}", path, err)),
+                    })
+                    .collect::<Result<_, _>>()?;
+
+                Ok(FruTuple::new_value(names))
+            }),
+
+            x if x == static_ident!("read") => BuiltinFunction::new_closure(move |args| {
+                let [name] = string_arguments(args, "read")?;
+
+                read_entry(&archive, &path, &name).map(FruValue::String)
+            }),
+
+            // the entry is written to `to`, never to a path from the archive,
+            // so names like `../../etc/passwd` can't write outside of where they are asked to
+            x if x == static_ident!("extract") => BuiltinFunction::new_closure(move |args| {
+                require_capability(Capability::Fs)?;
+                let [name, to] = string_arguments(args, "extract")?;
+
+                write_atomic(&to, &read_entry(&archive, &path, &name)?)?;
+                Ok(FruValue::Nah)
+            }),
+
+            // lets `with` close the file of the archive
+            x if x == static_ident!("_close") => BuiltinFunction::new_closure(move |_| {
+                archive.borrow_mut().take();
+                Ok(FruValue::Nah)
+            }),
+
+            _ => return FruError::new_res(format!("prop `{}` not found", ident)),
+        };
+
+        Ok(method.into())
+    }
+
+    fn fmt_debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ZipArchive({})", self.path)
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
@@ -0,0 +1,156 @@
+use std::{any::Any, rc::Rc};
+
+use hmac::{Hmac, Mac};
+use macros::static_ident;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+use crate::interpreter::{
+    error::FruError,
+    identifier::Identifier,
+    options::Capability,
+    runtime::{check_allocation, require_capability},
+    value::fru_value::FruValue,
+    value::function::BuiltinFunction,
+    value::native::object::{INativeObject, NativeObject},
+};
+
+/// `Random` namespace, randomness from the operating system that is fit for secrets
+pub struct FruRandom;
+
+/// `Hash` namespace, digests and comparisons for verifying signatures
+pub struct FruHash;
+
+impl FruRandom {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self)))
+    }
+}
+
+impl FruHash {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self)))
+    }
+}
+
+// there are no byte strings, bytes are passed around as lowercase hex
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+pub fn from_hex(hex: &str) -> Result<Vec<u8>, FruError> {
+    let error = || FruError::new(format!("{:?} is not a hex string of bytes", hex));
+
+    if !hex.len().is_multiple_of(2) || !hex.bytes().all(|x| x.is_ascii_hexdigit()) {
+        return Err(error());
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|k| hex.get(k..k + 2).and_then(|x| u8::from_str_radix(x, 16).ok()).ok_or_else(error))
+        .collect()
+}
+
+impl INativeObject for FruRandom {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("Random")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        let function = match ident {
+            // `n` random bytes as `2 * n` hex digits
+            x if x == static_ident!("secure_bytes") => BuiltinFunction::new_closure(|args| {
+                let n = match args.args.as_slice() {
+                    [(None, FruValue::Number(n))] if n.fract() == 0.0 && *n >= 0.0 => *n as usize,
+                    _ => {
+                        return FruError::new_res(
+                            "`secure_bytes` expects a non-negative integer `Number`",
+                        )
+                    }
+                };
+
+                require_capability(Capability::Random)?;
+                check_allocation(n.saturating_mul(2))?;
+
+                let mut bytes = vec![0; n];
+                getrandom::getrandom(&mut bytes)
+                    .map_err(|err| FruError::new(format!("failed to get random bytes: {}", err)))?;
+
+                Ok(FruValue::String(to_hex(&bytes)))
+            }),
+
+            _ => return FruError::new_res(format!("prop `{}` not found", ident)),
+        };
+
+        Ok(function.into())
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+impl INativeObject for FruHash {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("Hash")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        let function = match ident {
+            x if x == static_ident!("sha256") => {
+                BuiltinFunction::new_closure(|args| match args.args.as_slice() {
+                    [(None, FruValue::String(message))] => Ok(FruValue::String(to_hex(
+                        &Sha256::digest(message.as_bytes()),
+                    ))),
+
+                    _ => FruError::new_res("`sha256` expects 1 `String`"),
+                })
+            }
+
+            x if x == static_ident!("hmac_sha256") => BuiltinFunction::new_closure(|args| {
+                match args.args.as_slice() {
+                    [(None, FruValue::String(key)), (None, FruValue::String(message))] => {
+                        // keys of any length are accepted, long ones are hashed first
+                        let mut mac = Hmac::<Sha256>::new_from_slice(key.as_bytes())
+                            .expect("HMAC accepts keys of any length");
+                        mac.update(message.as_bytes());
+
+                        Ok(FruValue::String(to_hex(&mac.finalize().into_bytes())))
+                    }
+
+                    _ => {
+                        FruError::new_res("`hmac_sha256` expects 2 `String`s, a key and a message")
+                    }
+                }
+            }),
+
+            // takes as long for strings that differ in the first character as in the last,
+            // only their lengths can be told apart by timing
+            x if x == static_ident!("equals_constant_time") => {
+                BuiltinFunction::new_closure(|args| match args.args.as_slice() {
+                    [(None, FruValue::String(a)), (None, FruValue::String(b))] => {
+                        Ok(FruValue::Bool(a.as_bytes().ct_eq(b.as_bytes()).into()))
+                    }
+
+                    _ => FruError::new_res("`equals_constant_time` expects 2 `String`s"),
+                })
+            }
+
+            _ => return FruError::new_res(format!("prop `{}` not found", ident)),
+        };
+
+        Ok(function.into())
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
@@ -0,0 +1,414 @@
+use std::{any::Any, cmp::Ordering, fmt, rc::Rc};
+
+use macros::static_ident;
+
+use crate::interpreter::{
+    builtins::operators::unexpected_operand,
+    error::FruError,
+    identifier::{id, Identifier, OperatorIdentifier},
+    value::fru_value::{FruValue, TOpBuiltin},
+    value::function::{BuiltinFunction, EvaluatedArgumentList},
+    value::native::object::{INativeObject, NativeObject},
+    value::operator::AnyOperator,
+};
+
+const DECIMAL: Identifier = static_ident!("Decimal");
+
+// i128 holds 38 digits, so this leaves room for the integer part
+const MAX_SCALE: u32 = 28;
+
+// digits after the point that division is rounded to
+const DIVISION_SCALE: u32 = 20;
+
+pub struct FruDecimalType;
+
+/// Exact decimal number, `value / 10^scale`
+#[derive(Clone, Copy)]
+pub struct FruDecimal {
+    value: i128,
+    scale: u32,
+}
+
+impl FruDecimalType {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self)))
+    }
+}
+
+impl INativeObject for FruDecimalType {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("DecimalType")
+    }
+
+    // both `Decimal("0.1")` and `Decimal:{ "0.1" }` work
+    fn call(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+        let decimal = match args.args.as_slice() {
+            [(None, FruValue::String(text))] => FruDecimal::parse(text)?,
+            [(None, FruValue::Number(n))] => FruDecimal::from_integer(*n)?,
+            [(None, other)] => {
+                return FruError::new_res(format!(
+                    "`Decimal` expects `String` or integer `Number`, got `{}`",
+                    other.get_type_identifier()
+                ))
+            }
+            _ => return FruError::new_res("`Decimal` expects 1 argument"),
+        };
+
+        Ok(decimal.into_value())
+    }
+
+    fn instantiate(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+        self.call(args)
+    }
+
+    fn operators(&self) -> Vec<(OperatorIdentifier, AnyOperator)> {
+        decimal_operators()
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+impl FruDecimal {
+    fn parse(text: &str) -> Result<Self, FruError> {
+        let invalid = || FruError::new_res(format!("invalid decimal \"{}\"", text));
+
+        let trimmed = text.trim();
+
+        let (negative, digits) = match trimmed.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+        };
+
+        let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+
+        if integer.is_empty() && fraction.is_empty()
+            || !integer.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit())
+        {
+            return invalid();
+        }
+
+        if fraction.len() > MAX_SCALE as usize {
+            return FruError::new_res(format!(
+                "decimal \"{}\" has more than {} digits after the point",
+                text, MAX_SCALE
+            ));
+        }
+
+        let value: i128 = match format!("{}{}", integer, fraction).parse() {
+            Ok(value) => value,
+            Err(_) => return invalid(),
+        };
+
+        Ok(Self {
+            value: if negative { -value } else { value },
+            scale: fraction.len() as u32,
+        })
+    }
+
+    fn from_integer(n: f64) -> Result<Self, FruError> {
+        if n.fract() != 0.0 || n.abs() >= 1e38 {
+            return FruError::new_res(format!(
+                "cannot convert {} to `Decimal` exactly, use a string like Decimal(\"{}\")",
+                n, n
+            ));
+        }
+
+        Ok(Self {
+            value: n as i128,
+            scale: 0,
+        })
+    }
+
+    fn into_value(self) -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(self)))
+    }
+
+    fn to_f64(self) -> f64 {
+        // through the string, so the result is the closest number to the decimal
+        self.to_string().parse().expect("decimal is a valid number")
+    }
+
+    fn rescale(self, scale: u32) -> Result<i128, FruError> {
+        10i128
+            .checked_pow(scale - self.scale)
+            .and_then(|k| self.value.checked_mul(k))
+            .ok_or_else(overflow)
+    }
+
+    fn align(self, other: Self) -> Result<(i128, i128, u32), FruError> {
+        let scale = self.scale.max(other.scale);
+        Ok((self.rescale(scale)?, other.rescale(scale)?, scale))
+    }
+
+    fn add(self, other: Self) -> Result<Self, FruError> {
+        let (l, r, scale) = self.align(other)?;
+        let value = l.checked_add(r).ok_or_else(overflow)?;
+        Ok(Self { value, scale })
+    }
+
+    fn sub(self, other: Self) -> Result<Self, FruError> {
+        let (l, r, scale) = self.align(other)?;
+        let value = l.checked_sub(r).ok_or_else(overflow)?;
+        Ok(Self { value, scale })
+    }
+
+    fn mul(self, other: Self) -> Result<Self, FruError> {
+        let value = self.value.checked_mul(other.value).ok_or_else(overflow)?;
+        let product = Self {
+            value,
+            scale: self.scale + other.scale,
+        };
+
+        if product.scale > MAX_SCALE {
+            return Ok(product.round(MAX_SCALE));
+        }
+
+        Ok(product)
+    }
+
+    fn div(self, other: Self) -> Result<Self, FruError> {
+        if other.value == 0 {
+            return FruError::new_res("division by zero");
+        }
+
+        // (l / 10^ls) / (r / 10^rs) * 10^scale = l * 10^(rs + scale - ls) / r
+        let scale = DIVISION_SCALE.max(self.scale);
+        let numerator = 10i128
+            .checked_pow(other.scale + scale - self.scale)
+            .and_then(|k| self.value.checked_mul(k))
+            .ok_or_else(overflow)?;
+
+        Ok(Self {
+            value: divide_half_even(numerator, other.value),
+            scale,
+        }
+        .normalize(self.scale.max(other.scale)))
+    }
+
+    fn cmp(self, other: Self) -> Result<Ordering, FruError> {
+        let (l, r, _) = self.align(other)?;
+        Ok(l.cmp(&r))
+    }
+
+    /// Rounds half to even, so sums of many rounded values are not biased
+    fn round(self, digits: u32) -> Self {
+        if digits >= self.scale {
+            return self;
+        }
+
+        Self {
+            value: divide_half_even(self.value, 10i128.pow(self.scale - digits)),
+            scale: digits,
+        }
+    }
+
+    // trailing zeros of division are noise, but `1.50 / 1` keeps its scale
+    fn normalize(mut self, min_scale: u32) -> Self {
+        while self.scale > min_scale && self.value % 10 == 0 {
+            self.value /= 10;
+            self.scale -= 1;
+        }
+
+        self
+    }
+}
+
+fn divide_half_even(numerator: i128, denominator: i128) -> i128 {
+    let quotient = numerator / denominator;
+    let remainder = (numerator % denominator).unsigned_abs() * 2;
+    let sign = if (numerator < 0) != (denominator < 0) {
+        -1
+    } else {
+        1
+    };
+
+    match remainder.cmp(&denominator.unsigned_abs()) {
+        Ordering::Less => quotient,
+        Ordering::Greater => quotient + sign,
+        Ordering::Equal if quotient % 2 == 0 => quotient,
+        Ordering::Equal => quotient + sign,
+    }
+}
+
+fn overflow() -> FruError {
+    FruError::new("`Decimal` overflow".to_string())
+}
+
+impl fmt::Display for FruDecimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.value.unsigned_abs().to_string();
+        let scale = self.scale as usize;
+        let sign = if self.value < 0 { "-" } else { "" };
+
+        if scale == 0 {
+            return write!(f, "{}{}", sign, digits);
+        }
+
+        let digits = format!("{:0>width$}", digits, width = scale + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - scale);
+
+        write!(f, "{}{}.{}", sign, integer, fraction)
+    }
+}
+
+impl INativeObject for FruDecimal {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        DECIMAL
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        let this = *self;
+
+        let method = match ident {
+            x if x == static_ident!("round") => {
+                BuiltinFunction::new_closure(move |args| match args.args.as_slice() {
+                    [(None, FruValue::Number(n))] if n.fract() == 0.0 && *n >= 0.0 => {
+                        Ok(this.round(*n as u32).into_value())
+                    }
+                    _ => FruError::new_res("`round` expects a non-negative integer"),
+                })
+            }
+
+            // `_to_number` lets `Number(d)` work
+            x if x == static_ident!("to_number") || x == static_ident!("_to_number") => {
+                BuiltinFunction::new_closure(move |_| Ok(FruValue::Number(this.to_f64())))
+            }
+
+            x if x == static_ident!("_to_bool") => {
+                BuiltinFunction::new_closure(move |_| Ok(FruValue::Bool(this.value != 0)))
+            }
+
+            _ => return FruError::new_res(format!("prop `{}` not found", ident)),
+        };
+
+        Ok(method.into())
+    }
+
+    fn fmt_debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+
+    fn freeze(&self) -> Option<Vec<FruValue>> {
+        Some(Vec::new())
+    }
+
+    fn is_frozen(&self) -> bool {
+        true
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+/// Arithmetic and comparison of decimals with each other and with integer numbers
+fn decimal_operators() -> Vec<(OperatorIdentifier, AnyOperator)> {
+    let operators: [(Identifier, TOpBuiltin); 10] = [
+        (id::PLUS, decimal_plus),
+        (id::MINUS, decimal_minus),
+        (id::MULTIPLY, decimal_mul),
+        (id::DIVIDE, decimal_div),
+        (id::LESS, decimal_less),
+        (id::LESS_EQ, decimal_less_eq),
+        (id::GREATER, decimal_greater),
+        (id::GREATER_EQ, decimal_greater_eq),
+        (id::EQ, decimal_eq),
+        (id::NOT_EQ, decimal_not_eq),
+    ];
+
+    let mut result = Vec::new();
+
+    for (op, function) in operators {
+        for (left, right) in [
+            (DECIMAL, DECIMAL),
+            (DECIMAL, id::NUMBER),
+            (id::NUMBER, DECIMAL),
+        ] {
+            result.push((
+                OperatorIdentifier::new(op, left, right),
+                AnyOperator::BuiltinOperator(function),
+            ));
+        }
+    }
+
+    result
+}
+
+// numbers must be integers, `Decimal + 0.1` would bring the float error back
+fn operands(left: FruValue, right: FruValue) -> Result<(FruDecimal, FruDecimal), FruError> {
+    let convert = |value: FruValue| match &value {
+        FruValue::Number(n) => FruDecimal::from_integer(*n),
+
+        FruValue::NativeObject(obj) => match obj.downcast::<FruDecimal>() {
+            Some(decimal) => Ok(*decimal),
+            None => unexpected_operand(&value),
+        },
+
+        _ => unexpected_operand(&value),
+    };
+
+    Ok((convert(left)?, convert(right)?))
+}
+
+fn decimal_plus(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    let (l, r) = operands(left, right)?;
+    Ok(l.add(r)?.into_value())
+}
+
+fn decimal_minus(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    let (l, r) = operands(left, right)?;
+    Ok(l.sub(r)?.into_value())
+}
+
+fn decimal_mul(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    let (l, r) = operands(left, right)?;
+    Ok(l.mul(r)?.into_value())
+}
+
+fn decimal_div(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    let (l, r) = operands(left, right)?;
+    Ok(l.div(r)?.into_value())
+}
+
+fn compare(
+    left: FruValue,
+    right: FruValue,
+    accept: fn(Ordering) -> bool,
+) -> Result<FruValue, FruError> {
+    let (l, r) = operands(left, right)?;
+    Ok(FruValue::Bool(accept(l.cmp(r)?)))
+}
+
+fn decimal_less(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    compare(left, right, Ordering::is_lt)
+}
+
+fn decimal_less_eq(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    compare(left, right, Ordering::is_le)
+}
+
+fn decimal_greater(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    compare(left, right, Ordering::is_gt)
+}
+
+fn decimal_greater_eq(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    compare(left, right, Ordering::is_ge)
+}
+
+fn decimal_eq(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    compare(left, right, Ordering::is_eq)
+}
+
+fn decimal_not_eq(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    compare(left, right, Ordering::is_ne)
+}
@@ -0,0 +1,268 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, HashMap},
+    hash::{Hash, Hasher},
+    time::Instant,
+};
+
+use macros::static_ident;
+
+use crate::interpreter::{
+    error::FruError,
+    identifier::Identifier,
+    options::Capability,
+    runtime::{check_stack, log_to_host, require_capability, warn, warnings_enabled, WarningKind},
+    value::fru_type::{FruType, TypeType},
+    value::fru_value::FruValue,
+    value::function::{BuiltinFunction, EvaluatedArgumentList},
+};
+use crate::stdlib::tuple::fru_tuple::extract_tuple_items;
+
+// arguments are remembered by value, tuples and objects of structs and data types by their items,
+// objects of classes and functions have no value, so calls with them are an error
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum MemoKey {
+    Nah,
+    Number(u64),
+    Bool(bool),
+    String(String),
+    Tuple(Vec<MemoKey>),
+    Object(MemoType, Vec<MemoKey>),
+}
+
+// compared by identity, holding the type keeps its address from being reused
+#[derive(Clone)]
+struct MemoType(FruType);
+
+impl PartialEq for MemoType {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for MemoType {}
+
+impl Hash for MemoType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.as_ptr().hash(state)
+    }
+}
+
+type MemoArgs = Vec<(Option<Identifier>, MemoKey)>;
+
+/// Evicts the least recently used result when `max_entries` is reached
+struct MemoCache {
+    entries: HashMap<MemoArgs, (FruValue, u64)>,
+    // keys by the time they were last used, the first one is evicted
+    recent: BTreeMap<u64, MemoArgs>,
+    max_entries: Option<usize>,
+    clock: u64,
+}
+
+impl MemoCache {
+    fn new(max_entries: Option<usize>) -> Self {
+        Self {
+            entries: HashMap::new(),
+            recent: BTreeMap::new(),
+            max_entries,
+            clock: 0,
+        }
+    }
+
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    fn get(&mut self, key: &MemoArgs) -> Option<FruValue> {
+        let now = self.tick();
+
+        let (value, last_used) = self.entries.get_mut(key)?;
+        let key = self.recent.remove(last_used).expect("every entry is in `recent`");
+
+        *last_used = now;
+        let value = value.clone();
+        self.recent.insert(now, key);
+
+        Some(value)
+    }
+
+    fn insert(&mut self, key: MemoArgs, value: FruValue) {
+        if let Some(max_entries) = self.max_entries {
+            if self.entries.len() >= max_entries && !self.entries.contains_key(&key) {
+                if let Some((_, oldest)) = self.recent.pop_first() {
+                    self.entries.remove(&oldest);
+                }
+            }
+        }
+
+        let now = self.tick();
+
+        if let Some((_, last_used)) = self.entries.insert(key.clone(), (value, now)) {
+            self.recent.remove(&last_used);
+        }
+
+        self.recent.insert(now, key);
+    }
+}
+
+fn memo_key(args: &EvaluatedArgumentList) -> Result<MemoArgs, FruError> {
+    args.args.iter().map(|(ident, value)| Ok((*ident, value_key(value)?))).collect()
+}
+
+fn value_key(value: &FruValue) -> Result<MemoKey, FruError> {
+    check_stack()?;
+
+    let items_key = |items: &[FruValue]| items.iter().map(value_key).collect::<Result<_, _>>();
+
+    Ok(match value {
+        FruValue::Nah => MemoKey::Nah,
+        // adding zero turns -0 into 0
+        FruValue::Number(n) => MemoKey::Number((n + 0.0).to_bits()),
+        FruValue::Bool(b) => MemoKey::Bool(*b),
+        FruValue::String(s) => MemoKey::String(s.clone()),
+
+        FruValue::Object(object) if object.get_type().get_type_type() != TypeType::Class => {
+            MemoKey::Object(
+                MemoType(object.get_type()),
+                items_key(&object.get_field_values())?,
+            )
+        }
+
+        other => match extract_tuple_items(other) {
+            Some(items) => MemoKey::Tuple(items_key(&items)?),
+            None => {
+                return FruError::new_res(format!(
+                    "`memoize` can't remember calls with `{}` arguments, \
+                     only `nah`, numbers, bools, strings, tuples and objects of structs and data types",
+                    other.get_type_identifier()
+                ))
+            }
+        },
+    })
+}
+
+pub(crate) fn expect_function(name: &str, value: &FruValue) -> Result<FruValue, FruError> {
+    match value {
+        FruValue::Function(_) => Ok(value.clone()),
+        other => FruError::new_res(format!(
+            "`{}` expects a function, got `{}`",
+            name,
+            other.get_type_identifier()
+        )),
+    }
+}
+
+fn expect_string(name: &str, value: &FruValue) -> Result<String, FruError> {
+    match value {
+        FruValue::String(s) => Ok(s.clone()),
+        other => FruError::new_res(format!(
+            "`{}` expects a `String` label, got `{}`",
+            name,
+            other.get_type_identifier()
+        )),
+    }
+}
+
+pub fn b_memoize(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    let (function, max_entries) = match args.args.as_slice() {
+        [(None, function)] => (expect_function("memoize", function)?, None),
+
+        [(None, function), (ident, max_entries)]
+            if ident.is_none() || *ident == Some(static_ident!("max_entries")) =>
+        {
+            (
+                expect_function("memoize", function)?,
+                Some(expect_max_entries(max_entries)?),
+            )
+        }
+
+        _ => return FruError::new_res("`memoize` expects 1 or 2 arguments"),
+    };
+
+    let cache = RefCell::new(MemoCache::new(max_entries));
+
+    Ok(BuiltinFunction::new_closure(move |args| {
+        let key = memo_key(&args)?;
+
+        if let Some(result) = cache.borrow_mut().get(&key) {
+            return Ok(result);
+        }
+
+        // not borrowed during the call, so recursive calls are cached too
+        let result = function.call(args)?;
+        cache.borrow_mut().insert(key, result.clone());
+
+        Ok(result)
+    })
+    .into())
+}
+
+fn expect_max_entries(value: &FruValue) -> Result<usize, FruError> {
+    match value {
+        FruValue::Number(n) if *n >= 1.0 && n.fract() == 0.0 => Ok(*n as usize),
+        _ => FruError::new_res("`max_entries` must be a positive integer"),
+    }
+}
+
+pub fn b_timed(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    require_capability(Capability::Time)?;
+
+    let (function, label) = match args.args.as_slice() {
+        [(None, function)] => (expect_function("timed", function)?, "function".to_string()),
+        [(None, function), (None, label)] => (
+            expect_function("timed", function)?,
+            expect_string("timed", label)?,
+        ),
+        _ => return FruError::new_res("`timed` expects 1 or 2 arguments"),
+    };
+
+    Ok(BuiltinFunction::new_closure(move |args| {
+        let start = Instant::now();
+        let result = function.call(args);
+
+        let report = format!(
+            "{} took {:.3} ms",
+            label,
+            start.elapsed().as_secs_f64() * 1000.0
+        );
+
+        if !log_to_host(&report) {
+            println!("{}", report);
+        }
+
+        result
+    })
+    .into())
+}
+
+pub fn b_deprecated(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    let (function, message) = match args.args.as_slice() {
+        [(None, function)] => (
+            expect_function("deprecated", function)?,
+            "function is deprecated".to_string(),
+        ),
+        [(None, function), (None, message)] => (
+            expect_function("deprecated", function)?,
+            expect_string("deprecated", message)?,
+        ),
+        _ => return FruError::new_res("`deprecated` expects 1 or 2 arguments"),
+    };
+
+    let warned = Cell::new(false);
+
+    Ok(BuiltinFunction::new_closure(move |args| {
+        if warnings_enabled() {
+            warn(WarningKind::Deprecation, message.clone());
+        } else if !warned.replace(true) {
+            let line = format!("warning: {}", message);
+
+            if !log_to_host(&line) {
+                eprintln!("{}", line);
+            }
+        }
+
+        function.call(args)
+    })
+    .into())
+}
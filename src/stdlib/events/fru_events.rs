@@ -0,0 +1,196 @@
+use std::{any::Any, cell::RefCell, collections::HashMap, rc::Rc};
+
+use macros::static_ident;
+
+use crate::interpreter::{
+    error::FruError,
+    identifier::Identifier,
+    value::fru_value::FruValue,
+    value::function::{BuiltinFunction, EvaluatedArgumentList},
+    value::native::object::{INativeObject, NativeObject},
+};
+
+pub struct FruEventsType;
+
+pub struct FruEvents {
+    handlers: Rc<Handlers>,
+}
+
+type Handlers = RefCell<HashMap<String, Vec<FruValue>>>;
+
+impl FruEventsType {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self)))
+    }
+}
+
+impl INativeObject for FruEventsType {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("EventsType")
+    }
+
+    fn instantiate(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+        if !args.args.is_empty() {
+            return FruError::new_res("`Events` does not take arguments");
+        }
+
+        Ok(FruEvents::new_value())
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+impl FruEvents {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self {
+            handlers: Default::default(),
+        })))
+    }
+}
+
+fn event_name(value: &FruValue) -> Result<String, FruError> {
+    match value {
+        FruValue::String(name) => Ok(name.clone()),
+
+        other => FruError::new_res(format!(
+            "event name must be `String`, got `{}`",
+            other.get_type_identifier()
+        )),
+    }
+}
+
+// handlers may subscribe or unsubscribe while the event is dispatched
+fn emit(handlers: &Handlers, name: &str, args: EvaluatedArgumentList) -> Result<usize, FruError> {
+    let list = handlers.borrow().get(name).cloned().unwrap_or_default();
+
+    for handler in &list {
+        handler.call(args.clone())?;
+    }
+
+    Ok(list.len())
+}
+
+fn is_same_handler(a: &FruValue, b: &FruValue) -> bool {
+    match (a, b) {
+        (FruValue::Function(a), FruValue::Function(b)) => a.ptr_eq(b),
+        (a, b) => a == b,
+    }
+}
+
+impl INativeObject for FruEvents {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("Events")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        let handlers = self.handlers.clone();
+
+        let method = match ident {
+            x if x == static_ident!("on") => BuiltinFunction::new_closure(move |args| {
+                let (name, handler) = match args.args.as_slice() {
+                    [(None, name), (None, handler)] => (event_name(name)?, handler.clone()),
+                    _ => return FruError::new_res("`on` expects 2 arguments"),
+                };
+
+                handlers.borrow_mut().entry(name).or_default().push(handler.clone());
+
+                Ok(handler)
+            }),
+
+            x if x == static_ident!("off") => BuiltinFunction::new_closure(move |args| {
+                match args.args.as_slice() {
+                    [(None, name)] => {
+                        handlers.borrow_mut().remove(&event_name(name)?);
+                    }
+
+                    [(None, name), (None, handler)] => {
+                        if let Some(list) = handlers.borrow_mut().get_mut(&event_name(name)?) {
+                            list.retain(|x| !is_same_handler(x, handler));
+                        }
+                    }
+
+                    _ => return FruError::new_res("`off` expects 1 or 2 arguments"),
+                }
+
+                Ok(FruValue::Nah)
+            }),
+
+            x if x == static_ident!("emit") => BuiltinFunction::new_closure(move |mut args| {
+                let name = match args.args.first() {
+                    Some((None, name)) => event_name(name)?,
+                    _ => return FruError::new_res("`emit` expects event name"),
+                };
+
+                args.args.remove(0);
+
+                Ok(FruValue::Number(emit(&handlers, &name, args)? as f64))
+            }),
+
+            // changes of an object field are emitted as event with old and new values
+            x if x == static_ident!("watch") => {
+                BuiltinFunction::new_closure(move |args| {
+                    let (object, field, name) = match args.args.as_slice() {
+                        [(None, FruValue::Object(object)), (None, FruValue::String(field))] => {
+                            (object.clone(), field.clone(), field.clone())
+                        }
+
+                        [(None, FruValue::Object(object)), (None, FruValue::String(field)), (None, name)] => {
+                            (object.clone(), field.clone(), event_name(name)?)
+                        }
+
+                        _ => return FruError::new_res(
+                            "`watch` expects an object, a field name and optionally an event name",
+                        ),
+                    };
+
+                    let handlers = handlers.clone();
+
+                    let id = object.observe(
+                        Identifier::new(&field),
+                        Rc::new(move |old, new| {
+                            emit(
+                                &handlers,
+                                &name,
+                                EvaluatedArgumentList {
+                                    args: vec![(None, old), (None, new)],
+                                },
+                            )?;
+
+                            Ok(())
+                        }),
+                    )?;
+
+                    // the returned function doesn't keep the object alive, like the one of `observe`
+                    let object = object.downgrade();
+
+                    Ok(BuiltinFunction::new_closure(move |_| {
+                        if let Some(object) = object.upgrade() {
+                            object.unobserve(id);
+                        }
+
+                        Ok(FruValue::Nah)
+                    })
+                    .into())
+                })
+            }
+
+            _ => return FruError::new_res(format!("prop `{}` not found", ident)),
+        };
+
+        Ok(method.into())
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
@@ -0,0 +1,72 @@
+use std::collections::HashSet;
+
+use crate::interpreter::{
+    error::FruError, value::fru_value::FruValue, value::function::EvaluatedArgumentList,
+};
+
+// visited values by address, so shared parts and cycles are frozen once
+fn freeze_deep(value: &FruValue, visited: &mut HashSet<*const ()>) -> Result<(), FruError> {
+    match value {
+        FruValue::Object(object) => {
+            if !visited.insert(object.as_ptr()) {
+                return Ok(());
+            }
+
+            object.freeze();
+
+            for field in object.get_field_values() {
+                freeze_deep(&field, visited)?;
+            }
+        }
+
+        FruValue::NativeObject(object) => {
+            if !visited.insert(object.as_ptr()) {
+                return Ok(());
+            }
+
+            let Some(items) = object.freeze() else {
+                return FruError::new_res(format!(
+                    "`{}` can't be frozen",
+                    object.get_type_identifier()
+                ));
+            };
+
+            for item in items {
+                freeze_deep(&item, visited)?;
+            }
+        }
+
+        // functions and types are shared, primitives can't be changed anyway
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Freezes the value and everything reachable from it, then returns it,
+/// so `let config = freeze(Config:{ ... });` also works. Scopes are frozen shallowly
+pub fn b_freeze(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    match <[_; 1]>::try_from(args.args) {
+        Ok([(None, value)]) => {
+            freeze_deep(&value, &mut HashSet::new())?;
+            Ok(value)
+        }
+
+        _ => FruError::new_res("`freeze` expects 1 argument"),
+    }
+}
+
+/// Values that can't be changed, like numbers and strings, are always frozen
+pub fn b_is_frozen(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    let value = match args.args.as_slice() {
+        [(None, value)] => value,
+        _ => return FruError::new_res("`is_frozen` expects 1 argument"),
+    };
+
+    Ok(FruValue::Bool(match value {
+        FruValue::Object(object) => object.is_frozen(),
+        FruValue::NativeObject(object) => object.is_frozen(),
+        FruValue::Type(_) => false,
+        _ => true,
+    }))
+}
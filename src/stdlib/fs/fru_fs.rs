@@ -0,0 +1,714 @@
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::BTreeMap,
+    fmt,
+    fs::{read_dir, read_to_string, write, File},
+    io::{BufRead, BufReader, Lines, Write},
+    path::{Component, Path, PathBuf},
+    rc::{Rc, Weak},
+    time::{Duration, SystemTime},
+};
+
+use macros::static_ident;
+use tempfile::{NamedTempFile, TempDir, TempPath};
+
+use crate::interpreter::{
+    error::FruError,
+    identifier::Identifier,
+    options::Capability,
+    runtime::{current_runtime, require_capability},
+    value::fru_value::FruValue,
+    value::function::{BuiltinFunction, EvaluatedArgumentList},
+    value::native::object::{INativeObject, NativeObject},
+};
+use crate::stdlib::timer::fru_timer::FruTimer;
+
+// how often `Fs.watch` looks for changes by default, in milliseconds
+const WATCH_INTERVAL: f64 = 100.0;
+
+/// `Fs` namespace, every function requires the `fs` capability
+pub struct FruFs;
+
+/// Lazy sequence of paths, directories are read only when the walk reaches them
+pub struct FruPaths {
+    walk: Rc<RefCell<Walk>>,
+}
+
+/// File opened with `Fs.open`, its contents are read only on demand
+pub struct FruFile {
+    path: String,
+    // readers made by `lines`, closing the file closes them too, `None` once it is closed
+    readers: Rc<RefCell<Option<Vec<Weak<LinesReader>>>>>,
+}
+
+/// Lazy sequence of the lines of a file, only the current line is kept in memory
+pub struct FruLines {
+    path: String,
+    lines: Rc<LinesReader>,
+}
+
+// `None` once the file is closed
+type LinesReader = RefCell<Option<Lines<BufReader<File>>>>;
+
+/// File or directory that is deleted when the value is no longer used, or when it is closed
+pub struct FruTempPath {
+    path: PathBuf,
+    is_dir: bool,
+    // `None` once it is closed
+    temp: Rc<RefCell<Option<TempKind>>>,
+}
+
+enum TempKind {
+    File(TempPath),
+    Dir(TempDir),
+}
+
+struct Walk {
+    root: PathBuf,
+    // components of a glob pattern after `root`, `None` for plain walks
+    pattern: Option<Vec<String>>,
+    // entries that are not yielded yet, the next one is at the end
+    pending: Vec<PathBuf>,
+}
+
+// modification time and size of every file and directory under a watched path
+type Snapshot = BTreeMap<PathBuf, (Option<SystemTime>, u64)>;
+
+impl FruFs {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self)))
+    }
+}
+
+fn path_argument(args: EvaluatedArgumentList, name: &str) -> Result<String, FruError> {
+    match args.args.as_slice() {
+        [(None, FruValue::String(path))] => Ok(path.clone()),
+        _ => FruError::new_res(format!("`{}` expects 1 `String`", name)),
+    }
+}
+
+fn path_and_contents(
+    args: EvaluatedArgumentList,
+    name: &str,
+) -> Result<(String, String), FruError> {
+    match args.args.as_slice() {
+        [(None, FruValue::String(path)), (None, FruValue::String(contents))] => {
+            Ok((path.clone(), contents.clone()))
+        }
+        _ => FruError::new_res(format!(
+            "`{}` expects a path and contents as `String`",
+            name
+        )),
+    }
+}
+
+fn io_error<'a>(action: &'a str, path: &'a str) -> impl Fn(std::io::Error) -> FruError + 'a {
+    move |err| FruError::new(format!("cannot {} {:?}: {}", action, path, err))
+}
+
+// the temporary file is created next to the target, so the rename doesn't cross file systems
+pub fn write_atomic(path: &str, contents: &str) -> Result<(), FruError> {
+    let error = io_error("write", path);
+
+    let dir = match Path::new(path).parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+
+    let mut file = NamedTempFile::new_in(dir).map_err(&error)?;
+    file.write_all(contents.as_bytes()).map_err(&error)?;
+    file.as_file().sync_all().map_err(&error)?;
+    file.persist(path).map_err(|err| error(err.error))?;
+
+    Ok(())
+}
+
+impl INativeObject for FruFs {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("Fs")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        let function = match ident {
+            // every file and directory under `dir`, depth first, in name order
+            x if x == static_ident!("walk") => BuiltinFunction::new_closure(|args| {
+                require_capability(Capability::Fs)?;
+                let dir = path_argument(args, "walk")?;
+
+                FruPaths::new_value(Walk::new(PathBuf::from(dir), None)?)
+            }),
+
+            // `*` and `?` match within one path component, `**` matches any number of them
+            x if x == static_ident!("glob") => BuiltinFunction::new_closure(|args| {
+                require_capability(Capability::Fs)?;
+                let pattern = path_argument(args, "glob")?;
+
+                let mut components = pattern.split('/').filter(|x| !x.is_empty()).peekable();
+                let mut root = PathBuf::new();
+
+                if pattern.starts_with('/') {
+                    root.push("/");
+                }
+
+                while let Some(component) = components.next_if(|x| !is_wildcard(x)) {
+                    root.push(component);
+                }
+
+                let rest: Vec<String> = components.map(String::from).collect();
+
+                if rest.is_empty() {
+                    let found = root.exists().then(|| root.clone()).into_iter().collect();
+                    return FruPaths::new_value(Walk::of(root, Some(rest), found));
+                }
+
+                if root.as_os_str().is_empty() {
+                    root.push(".");
+                }
+
+                FruPaths::new_value(Walk::new(root, Some(rest))?)
+            }),
+
+            x if x == static_ident!("read") => BuiltinFunction::new_closure(|args| {
+                require_capability(Capability::Fs)?;
+                let path = path_argument(args, "read")?;
+
+                read_to_string(&path).map(FruValue::String).map_err(io_error("read", &path))
+            }),
+
+            x if x == static_ident!("open") => BuiltinFunction::new_closure(|args| {
+                require_capability(Capability::Fs)?;
+                let path = path_argument(args, "open")?;
+
+                File::open(&path).map_err(io_error("open", &path))?;
+                Ok(FruFile::new_value(path))
+            }),
+
+            x if x == static_ident!("write") => BuiltinFunction::new_closure(|args| {
+                require_capability(Capability::Fs)?;
+                let (path, contents) = path_and_contents(args, "write")?;
+
+                write(&path, contents).map_err(io_error("write", &path))?;
+                Ok(FruValue::Nah)
+            }),
+
+            // readers see either the old contents or the new ones, never a half-written file
+            x if x == static_ident!("write_atomic") => BuiltinFunction::new_closure(|args| {
+                require_capability(Capability::Fs)?;
+                let (path, contents) = path_and_contents(args, "write_atomic")?;
+
+                write_atomic(&path, &contents)?;
+                Ok(FruValue::Nah)
+            }),
+
+            // `handler(event, path)` is called from the scheduler for every path under `path`
+            // that was `created`, `modified` or `removed`, until the returned timer is cancelled
+            x if x == static_ident!("watch") => BuiltinFunction::new_closure(|args| {
+                require_capability(Capability::Fs)?;
+
+                let (path, handler, interval) = match args.args.as_slice() {
+                    [(None, FruValue::String(path)), (None, handler)] => {
+                        (path.clone(), handler.clone(), WATCH_INTERVAL)
+                    }
+
+                    [(None, FruValue::String(path)), (None, handler), (None, FruValue::Number(ms))]
+                        if *ms > 0.0 && ms.is_finite() =>
+                    {
+                        (path.clone(), handler.clone(), *ms)
+                    }
+
+                    _ => {
+                        return FruError::new_res(
+                            "`watch` expects a path, a handler and an optional positive interval",
+                        )
+                    }
+                };
+
+                let root = PathBuf::from(path);
+                let last = RefCell::new(snapshot(&root));
+
+                let poll = BuiltinFunction::new_closure(move |_| {
+                    let current = snapshot(&root);
+                    let previous = last.replace(current.clone());
+
+                    for (event, path) in changes(&previous, &current) {
+                        handler.call(EvaluatedArgumentList {
+                            args: vec![
+                                (None, FruValue::String(event.to_string())),
+                                (None, FruValue::String(path.to_string_lossy().into_owned())),
+                            ],
+                        })?;
+                    }
+
+                    Ok(FruValue::Nah)
+                });
+
+                let interval = Duration::from_secs_f64(interval / 1000.0);
+                let handle =
+                    current_runtime()?.scheduler().add_timer(interval, Some(interval), poll.into());
+
+                Ok(FruTimer::new_value(handle))
+            }),
+
+            x if x == static_ident!("temp_file") => BuiltinFunction::new_closure(|_| {
+                require_capability(Capability::Fs)?;
+
+                let file = NamedTempFile::new().map_err(|err| {
+                    FruError::new(format!("cannot create a temporary file: {}", err))
+                })?;
+
+                Ok(FruTempPath::new_value(TempKind::File(
+                    file.into_temp_path(),
+                )))
+            }),
+
+            x if x == static_ident!("temp_dir") => BuiltinFunction::new_closure(|_| {
+                require_capability(Capability::Fs)?;
+
+                let dir = TempDir::new().map_err(|err| {
+                    FruError::new(format!("cannot create a temporary directory: {}", err))
+                })?;
+
+                Ok(FruTempPath::new_value(TempKind::Dir(dir)))
+            }),
+
+            _ => return FruError::new_res(format!("prop `{}` not found", ident)),
+        };
+
+        Ok(function.into())
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+// paths that can't be read are left out, as if they were removed
+fn snapshot(root: &Path) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    let mut pending = vec![root.to_path_buf()];
+
+    while let Some(path) = pending.pop() {
+        let Ok(metadata) = path.symlink_metadata() else {
+            continue;
+        };
+
+        // directories change with every file in them, only their files are reported as modified
+        if metadata.is_dir() {
+            if let Ok(entries) = read_dir(&path) {
+                pending.extend(entries.filter_map(|entry| entry.ok().map(|x| x.path())));
+            }
+
+            snapshot.insert(path, (None, 0));
+        } else {
+            snapshot.insert(path, (metadata.modified().ok(), metadata.len()));
+        }
+    }
+
+    snapshot
+}
+
+// in path order, so handlers see the same events on every platform
+fn changes<'a>(previous: &'a Snapshot, current: &'a Snapshot) -> Vec<(&'static str, &'a Path)> {
+    let mut changes: Vec<_> = (current.iter())
+        .filter_map(|(path, state)| match previous.get(path) {
+            None => Some(("created", path.as_path())),
+            Some(old) if old != state => Some(("modified", path.as_path())),
+            Some(_) => None,
+        })
+        .chain(
+            (previous.keys())
+                .filter(|path| !current.contains_key(*path))
+                .map(|path| ("removed", path.as_path())),
+        )
+        .collect();
+
+    changes.sort_by_key(|(_, path)| *path);
+    changes
+}
+
+fn is_wildcard(component: &str) -> bool {
+    component.contains(['*', '?'])
+}
+
+impl Walk {
+    fn new(root: PathBuf, pattern: Option<Vec<String>>) -> Result<Walk, FruError> {
+        let mut walk = Walk::of(root.clone(), pattern, Vec::new());
+        walk.enter(&root)?;
+        Ok(walk)
+    }
+
+    fn of(root: PathBuf, pattern: Option<Vec<String>>, pending: Vec<PathBuf>) -> Walk {
+        Walk {
+            root,
+            pattern,
+            pending,
+        }
+    }
+
+    fn enter(&mut self, dir: &Path) -> Result<(), FruError> {
+        let error = |err| FruError::new(format!("cannot read directory {:?}: {}", dir, err));
+
+        let mut entries = read_dir(dir)
+            .map_err(error)?
+            .map(|entry| entry.map(|x| x.path()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(error)?;
+
+        entries.sort_unstable_by(|a, b| b.cmp(a));
+        self.pending.extend(entries);
+
+        Ok(())
+    }
+
+    // symlinks to directories are yielded but not followed, so cycles are not possible
+    fn next_entry(&mut self) -> Result<Option<PathBuf>, FruError> {
+        let Some(path) = self.pending.pop() else {
+            return Ok(None);
+        };
+
+        if path.symlink_metadata().is_ok_and(|x| x.is_dir()) {
+            self.enter(&path)?;
+        }
+
+        Ok(Some(path))
+    }
+}
+
+fn matches_component(pattern: &[char], name: &[char]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, _) => name.is_empty(),
+        (Some('*'), _) => {
+            matches_component(&pattern[1..], name)
+                || (!name.is_empty() && matches_component(pattern, &name[1..]))
+        }
+        (Some('?'), Some(_)) => matches_component(&pattern[1..], &name[1..]),
+        (Some(p), Some(n)) => p == n && matches_component(&pattern[1..], &name[1..]),
+        (Some(_), None) => false,
+    }
+}
+
+fn matches_path(pattern: &[String], path: &[String]) -> bool {
+    match pattern.first() {
+        None => path.is_empty(),
+
+        Some(x) if x == "**" => {
+            matches_path(&pattern[1..], path)
+                || (!path.is_empty() && matches_path(pattern, &path[1..]))
+        }
+
+        Some(x) => {
+            !path.is_empty()
+                && matches_component(
+                    &x.chars().collect::<Vec<_>>(),
+                    &path[0].chars().collect::<Vec<_>>(),
+                )
+                && matches_path(&pattern[1..], &path[1..])
+        }
+    }
+}
+
+impl Walk {
+    fn next_path(&mut self) -> Result<Option<String>, FruError> {
+        while let Some(path) = self.next_entry()? {
+            let relative = path.strip_prefix(&self.root).unwrap_or(&path);
+
+            if let Some(pattern) = &self.pattern {
+                let components: Vec<String> = relative
+                    .components()
+                    .filter_map(|x| match x {
+                        Component::Normal(x) => Some(x.to_string_lossy().into_owned()),
+                        _ => None,
+                    })
+                    .collect();
+
+                if !components.is_empty() && !matches_path(pattern, &components) {
+                    continue;
+                }
+            }
+
+            // paths found from the current directory are printed without `./`
+            let path = match path.strip_prefix(".") {
+                Ok(x) if self.root == Path::new(".") => x.to_path_buf(),
+                _ => path,
+            };
+
+            return Ok(Some(path.to_string_lossy().into_owned()));
+        }
+
+        Ok(None)
+    }
+}
+
+impl FruPaths {
+    fn new_value(walk: Walk) -> Result<FruValue, FruError> {
+        Ok(FruValue::NativeObject(NativeObject::new(Rc::new(Self {
+            walk: Rc::new(RefCell::new(walk)),
+        }))))
+    }
+}
+
+impl INativeObject for FruPaths {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("Paths")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        let walk = self.walk.clone();
+
+        let method = match ident {
+            // the next path, or `nah` when there are no more
+            x if x == static_ident!("next") => BuiltinFunction::new_closure(move |_| {
+                let path = walk.borrow_mut().next_path()?;
+                Ok(path.map_or(FruValue::Nah, FruValue::String))
+            }),
+
+            // the borrow is not held during the call, so `f` may take paths too
+            x if x == static_ident!("each") => BuiltinFunction::new_closure(move |args| {
+                let [(None, function)] = args.args.as_slice() else {
+                    return FruError::new_res("`each` expects 1 argument");
+                };
+
+                loop {
+                    let path = walk.borrow_mut().next_path()?;
+
+                    let Some(path) = path else {
+                        return Ok(FruValue::Nah);
+                    };
+
+                    function.call(EvaluatedArgumentList {
+                        args: vec![(None, FruValue::String(path))],
+                    })?;
+                }
+            }),
+
+            _ => return FruError::new_res(format!("prop `{}` not found", ident)),
+        };
+
+        Ok(method.into())
+    }
+
+    fn fmt_debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Paths({})", self.walk.borrow().root.display())
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+impl FruFile {
+    fn new_value(path: String) -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self {
+            path,
+            readers: Rc::new(RefCell::new(Some(Vec::new()))),
+        })))
+    }
+}
+
+fn file_closed_error(path: &str) -> FruError {
+    FruError::new(format!("file {:?} is closed", path))
+}
+
+impl INativeObject for FruFile {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("File")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        match ident {
+            x if x == static_ident!("path") => Ok(FruValue::String(self.path.clone())),
+
+            // every call reads the file from the start
+            x if x == static_ident!("lines") => {
+                let path = self.path.clone();
+                let readers = self.readers.clone();
+
+                Ok(BuiltinFunction::new_closure(move |_| {
+                    require_capability(Capability::Fs)?;
+
+                    let mut readers = readers.borrow_mut();
+                    let readers = readers.as_mut().ok_or_else(|| file_closed_error(&path))?;
+
+                    let file = File::open(&path).map_err(io_error("open", &path))?;
+                    let lines = Rc::new(RefCell::new(Some(BufReader::new(file).lines())));
+
+                    readers.retain(|reader| reader.strong_count() > 0);
+                    readers.push(Rc::downgrade(&lines));
+
+                    Ok(FruLines::new_value(path.clone(), lines))
+                })
+                .into())
+            }
+
+            // lets `with` close the readers of the file, it can't be read after that
+            x if x == static_ident!("_close") => {
+                let readers = self.readers.clone();
+
+                Ok(BuiltinFunction::new_closure(move |_| {
+                    for reader in readers.borrow_mut().take().into_iter().flatten() {
+                        if let Some(reader) = reader.upgrade() {
+                            reader.borrow_mut().take();
+                        }
+                    }
+
+                    Ok(FruValue::Nah)
+                })
+                .into())
+            }
+
+            _ => FruError::new_res(format!("prop `{}` not found", ident)),
+        }
+    }
+
+    fn fmt_debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "File({})", self.path)
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+impl FruLines {
+    fn new_value(path: String, lines: Rc<LinesReader>) -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self { path, lines })))
+    }
+}
+
+// line endings, `\n` or `\r\n`, are not a part of lines
+fn next_line(lines: &LinesReader, path: &str) -> Result<Option<String>, FruError> {
+    let mut lines = lines.borrow_mut();
+    let lines = lines.as_mut().ok_or_else(|| file_closed_error(path))?;
+
+    lines.next().transpose().map_err(io_error("read", path))
+}
+
+impl INativeObject for FruLines {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("Lines")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        let path = self.path.clone();
+        let lines = self.lines.clone();
+
+        let method = match ident {
+            // the next line, or `nah` at the end of the file
+            x if x == static_ident!("next") => BuiltinFunction::new_closure(move |_| {
+                let line = next_line(&lines, &path)?;
+                Ok(line.map_or(FruValue::Nah, FruValue::String))
+            }),
+
+            x if x == static_ident!("each") => BuiltinFunction::new_closure(move |args| {
+                let [(None, function)] = args.args.as_slice() else {
+                    return FruError::new_res("`each` expects 1 argument");
+                };
+
+                while let Some(line) = next_line(&lines, &path)? {
+                    function.call(EvaluatedArgumentList {
+                        args: vec![(None, FruValue::String(line))],
+                    })?;
+                }
+
+                Ok(FruValue::Nah)
+            }),
+
+            _ => return FruError::new_res(format!("prop `{}` not found", ident)),
+        };
+
+        Ok(method.into())
+    }
+
+    fn fmt_debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Lines({})", self.path)
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+impl FruTempPath {
+    fn new_value(temp: TempKind) -> FruValue {
+        let (path, is_dir) = match &temp {
+            TempKind::File(path) => (path.to_path_buf(), false),
+            TempKind::Dir(dir) => (dir.path().to_path_buf(), true),
+        };
+
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self {
+            path,
+            is_dir,
+            temp: Rc::new(RefCell::new(Some(temp))),
+        })))
+    }
+}
+
+// deletes the path right away, unlike dropping it reports errors
+fn close_temp(temp: &RefCell<Option<TempKind>>, path: &Path) -> Result<(), FruError> {
+    let result = match temp.borrow_mut().take() {
+        Some(TempKind::File(path)) => path.close(),
+        Some(TempKind::Dir(dir)) => dir.close(),
+        None => Ok(()),
+    };
+
+    result.map_err(|err| FruError::new(format!("cannot delete {}: {}", path.display(), err)))
+}
+
+impl INativeObject for FruTempPath {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        if self.is_dir {
+            static_ident!("TempDir")
+        } else {
+            static_ident!("TempFile")
+        }
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        if ident == static_ident!("path") {
+            return Ok(FruValue::String(self.path.to_string_lossy().into_owned()));
+        }
+
+        // lets `with` delete the path when it is done with it
+        if ident == static_ident!("_close") {
+            let path = self.path.clone();
+            let temp = self.temp.clone();
+
+            return Ok(BuiltinFunction::new_closure(move |_| {
+                close_temp(&temp, &path)?;
+                Ok(FruValue::Nah)
+            })
+            .into());
+        }
+
+        FruError::new_res(format!("prop `{}` not found", ident))
+    }
+
+    fn fmt_debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path.display())
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
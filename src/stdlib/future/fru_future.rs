@@ -0,0 +1,165 @@
+use std::{
+    any::Any,
+    cell::RefCell,
+    future::Future,
+    mem,
+    pin::Pin,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+};
+
+use macros::static_ident;
+
+use crate::interpreter::{
+    error::FruError,
+    identifier::Identifier,
+    runtime::current_runtime,
+    scheduler::Wait,
+    value::fru_value::FruValue,
+    value::function::EvaluatedArgumentList,
+    value::native::object::{INativeObject, NativeObject},
+};
+
+type HostFuture = Pin<Box<dyn Future<Output = Result<FruValue, FruError>>>>;
+
+/// Value produced by the host asynchronously, scripts wait for it with `await`
+pub struct FruFuture {
+    state: RefCell<FutureState>,
+}
+
+enum FutureState {
+    Pending(HostFuture),
+    // taken out while the host polls it, so the host may look at the future meanwhile
+    Polling,
+    Ready(FruValue),
+    Failed,
+}
+
+// remembers being woken, a task waiting for the future is resumed by the scheduler after that
+struct FlagWaker {
+    woken: AtomicBool,
+    thread: Thread,
+}
+
+impl FruFuture {
+    pub fn new_value(
+        future: impl Future<Output = Result<FruValue, FruError>> + 'static,
+    ) -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self {
+            state: RefCell::new(FutureState::Pending(Box::pin(future))),
+        })))
+    }
+
+    fn is_ready(&self) -> bool {
+        matches!(
+            *self.state.borrow(),
+            FutureState::Ready(_) | FutureState::Failed
+        )
+    }
+
+    fn poll(&self, cx: &mut Context) -> Poll<Result<FruValue, FruError>> {
+        let state = mem::replace(&mut *self.state.borrow_mut(), FutureState::Polling);
+
+        let (state, poll) = match state {
+            FutureState::Pending(mut future) => match future.as_mut().poll(cx) {
+                Poll::Ready(Ok(value)) => {
+                    (FutureState::Ready(value.clone()), Poll::Ready(Ok(value)))
+                }
+                Poll::Ready(Err(err)) => (FutureState::Failed, Poll::Ready(Err(err))),
+                Poll::Pending => (FutureState::Pending(future), Poll::Pending),
+            },
+
+            FutureState::Polling => {
+                return Poll::Ready(FruError::new_res("future is awaited from its own poll"));
+            }
+
+            FutureState::Ready(value) => {
+                (FutureState::Ready(value.clone()), Poll::Ready(Ok(value)))
+            }
+
+            FutureState::Failed => (
+                FutureState::Failed,
+                Poll::Ready(FruError::new_res("future has failed")),
+            ),
+        };
+
+        *self.state.borrow_mut() = state;
+
+        poll
+    }
+
+    // a task is suspended until the host wakes the future, other code runs tasks and timers meanwhile
+    fn wait(&self) -> Result<FruValue, FruError> {
+        let waker = Arc::new(FlagWaker {
+            woken: AtomicBool::new(false),
+            thread: thread::current(),
+        });
+        let wait = Wait::external({
+            let waker = waker.clone();
+            move || waker.woken.load(Ordering::Acquire)
+        });
+
+        let poll_waker = Waker::from(waker.clone());
+        let mut cx = Context::from_waker(&poll_waker);
+
+        current_runtime()?.scheduler().wait_until(wait, || {
+            waker.woken.store(false, Ordering::Release);
+
+            match self.poll(&mut cx) {
+                Poll::Ready(result) => result.map(Some),
+                Poll::Pending => Ok(None),
+            }
+        })
+    }
+}
+
+impl Wake for FlagWaker {
+    fn wake(self: Arc<Self>) {
+        self.woken.store(true, Ordering::Release);
+        self.thread.unpark();
+    }
+}
+
+impl INativeObject for FruFuture {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("Future")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        if ident == static_ident!("is_ready") {
+            return Ok(FruValue::Bool(self.is_ready()));
+        }
+
+        FruError::new_res(format!("prop `{}` not found", ident))
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+pub fn b_await(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    let value = match args.args.as_slice() {
+        [(None, value)] => value,
+        _ => return FruError::new_res("`await` expects 1 argument"),
+    };
+
+    match value {
+        FruValue::NativeObject(obj) => match obj.downcast::<FruFuture>() {
+            Some(future) => future.wait(),
+            None => FruError::new_res(format!("cannot await `{}`", value.get_type_identifier())),
+        },
+
+        // awaiting a ready value is allowed, so that functions can return either
+        ready => Ok(ready.clone()),
+    }
+}
@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+
+use crate::interpreter::{
+    error::FruError, value::fru_value::FruValue, value::function::EvaluatedArgumentList,
+};
+
+pub const DEFAULT_DEPTH: usize = 6;
+
+pub const DEFAULT_WIDTH: usize = 80;
+
+const INDENT: usize = 4;
+
+enum Node {
+    Leaf(String),
+
+    // shown in full elsewhere, refers to it by uid
+    Repeat(*const ()),
+
+    Group {
+        ptr: *const (),
+        open: String,
+        close: &'static str,
+        items: Vec<(Option<String>, Node)>,
+    },
+}
+
+struct Inspector {
+    depth: usize,
+    width: usize,
+    // values in the order they are first shown, with whether they are shown again
+    order: Vec<*const ()>,
+    repeated: HashMap<*const (), bool>,
+    uids: HashMap<*const (), usize>,
+}
+
+impl Inspector {
+    fn build(&mut self, value: &FruValue, level: usize) -> Node {
+        let (ptr, open, close, items) = match value {
+            FruValue::Object(object) => {
+                let type_ = object.get_type();
+                let items: Vec<_> = (type_.get_fields().iter().zip(object.get_field_values()))
+                    .map(|(field, value)| (Some(field.ident.to_string()), value))
+                    .collect();
+
+                (
+                    object.as_ptr(),
+                    format!("{}{{", object.get_type().get_ident()),
+                    "}",
+                    items,
+                )
+            }
+
+            FruValue::NativeObject(object) => match object.contents() {
+                Some(contents) => {
+                    let items: Vec<_> = (contents.items.into_iter())
+                        .map(|(key, value)| (key.map(|key| leaf(&key)), value))
+                        .collect();
+
+                    (
+                        object.as_ptr(),
+                        contents.open.to_string(),
+                        contents.close,
+                        items,
+                    )
+                }
+
+                None => return Node::Leaf(leaf(value)),
+            },
+
+            other => return Node::Leaf(leaf(other)),
+        };
+
+        if let Some(repeated) = self.repeated.get_mut(&ptr) {
+            *repeated = true;
+            return Node::Repeat(ptr);
+        }
+
+        if level == self.depth {
+            return Node::Leaf(format!("{}...{}", open, close));
+        }
+
+        self.order.push(ptr);
+        self.repeated.insert(ptr, false);
+
+        let items = (items.into_iter())
+            .map(|(label, value)| (label, self.build(&value, level + 1)))
+            .collect();
+
+        Node::Group {
+            ptr,
+            open,
+            close,
+            items,
+        }
+    }
+
+    // values that are shown more than once are numbered in the order they appear
+    fn assign_uids(&mut self) {
+        for ptr in &self.order {
+            if self.repeated[ptr] {
+                self.uids.insert(*ptr, self.uids.len() + 1);
+            }
+        }
+    }
+
+    fn flat(&self, node: &Node, out: &mut String) {
+        match node {
+            Node::Leaf(text) => out.push_str(text),
+
+            Node::Repeat(ptr) => out.push_str(&format!("*{}", self.uids[ptr])),
+
+            Node::Group {
+                ptr,
+                open,
+                close,
+                items,
+            } => {
+                self.open(*ptr, open, out);
+
+                for (k, (label, item)) in items.iter().enumerate() {
+                    if k > 0 {
+                        out.push_str(", ");
+                    }
+
+                    push_label(label, out);
+                    self.flat(item, out);
+                }
+
+                out.push_str(close);
+            }
+        }
+    }
+
+    // `column` is where the node starts on the current line
+    fn render(&self, node: &Node, indent: usize, column: usize, out: &mut String) {
+        let mut line = String::new();
+        self.flat(node, &mut line);
+
+        let (ptr, open, close, items) = match node {
+            Node::Group {
+                ptr,
+                open,
+                close,
+                items,
+            } if column + line.chars().count() > self.width && !items.is_empty() => {
+                (ptr, open, close, items)
+            }
+
+            _ => {
+                out.push_str(&line);
+                return;
+            }
+        };
+
+        self.open(*ptr, open, out);
+
+        for (label, item) in items {
+            out.push('\n');
+            out.push_str(&" ".repeat(indent + INDENT));
+
+            let start = out.len();
+            push_label(label, out);
+            let label_width = out[start..].chars().count();
+
+            self.render(item, indent + INDENT, indent + INDENT + label_width, out);
+            out.push(',');
+        }
+
+        out.push('\n');
+        out.push_str(&" ".repeat(indent));
+        out.push_str(close);
+    }
+
+    fn open(&self, ptr: *const (), open: &str, out: &mut String) {
+        if let Some(uid) = self.uids.get(&ptr) {
+            out.push_str(&format!("&{} ", uid));
+        }
+
+        out.push_str(open);
+    }
+}
+
+fn push_label(label: &Option<String>, out: &mut String) {
+    if let Some(label) = label {
+        out.push_str(label);
+        out.push_str(": ");
+    }
+}
+
+// strings are quoted, so `"1"` and `1` look different
+fn leaf(value: &FruValue) -> String {
+    match value {
+        FruValue::String(s) => format!("{:?}", s),
+        other => format!("{:?}", other),
+    }
+}
+
+/// Objects and collections `depth` levels deep, one item per line when they don't fit into `width`.
+/// Values that are reached more than once are shown once as `&1 ...` and referred to as `*1`,
+/// so cyclic values can be inspected too
+pub fn inspect(value: &FruValue, depth: usize, width: usize) -> String {
+    let mut inspector = Inspector {
+        depth,
+        width,
+        order: Vec::new(),
+        repeated: HashMap::new(),
+        uids: HashMap::new(),
+    };
+
+    let node = inspector.build(value, 0);
+    inspector.assign_uids();
+
+    let mut out = String::new();
+    inspector.render(&node, 0, 0, &mut out);
+
+    out
+}
+
+fn limit(value: &FruValue, what: &str) -> Result<usize, FruError> {
+    match value {
+        FruValue::Number(n) if n.fract() == 0.0 && *n >= 0.0 => Ok(*n as usize),
+
+        _ => FruError::new_res(format!(
+            "{} of `inspect` must be a non-negative integer",
+            what
+        )),
+    }
+}
+
+/// `inspect(value, depth, width)`, depth and width are optional
+pub fn b_inspect(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    let (value, depth, width) = match args.args.as_slice() {
+        [(None, value)] => (value, DEFAULT_DEPTH, DEFAULT_WIDTH),
+
+        [(None, value), (None, depth)] => (value, limit(depth, "depth")?, DEFAULT_WIDTH),
+
+        [(None, value), (None, depth), (None, width)] => {
+            (value, limit(depth, "depth")?, limit(width, "width")?)
+        }
+
+        _ => return FruError::new_res("`inspect` expects a value, an optional depth and width"),
+    };
+
+    Ok(FruValue::String(inspect(value, depth, width)))
+}
@@ -0,0 +1,97 @@
+use crate::interpreter::{
+    error::FruError,
+    identifier::{Identifier, OperatorIdentifier},
+    value::fru_value::FruValue,
+    value::function::EvaluatedArgumentList,
+};
+use crate::stdlib::{
+    prelude::fru_prelude::Prelude, scope::fru_scope::extract_scope_from_value,
+    tuple::fru_tuple::FruTuple,
+};
+
+/// Docstring of a function or a type, objects are documented by their type
+pub fn b_doc_of(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    let doc = match args.args.as_slice() {
+        [(None, FruValue::Function(func))] => func.doc(),
+        [(None, FruValue::Type(type_))] => type_.get_doc(),
+        [(None, FruValue::Object(obj))] => obj.get_type().get_doc(),
+        [(None, _)] => None,
+        _ => return FruError::new_res("`doc_of` expects 1 argument"),
+    };
+
+    Ok(doc.map_or(FruValue::Nah, |doc| FruValue::String(doc.to_string())))
+}
+
+/// Signatures of operators with the type on either side, one per line.
+/// Operators are looked up from the scope of the type declaration or from the given scope,
+/// builtin types are given by name
+pub fn b_operators_of(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    let (what, scope) = match args.args.as_slice() {
+        [(None, what)] => (what, None),
+
+        [(None, what), (None, scope)] => match extract_scope_from_value(scope) {
+            Some(scope) => (what, Some(scope)),
+            None => return FruError::new_res("second argument of `operators_of` must be a scope"),
+        },
+
+        _ => return FruError::new_res("`operators_of` expects a type and an optional scope"),
+    };
+
+    let (ident, scope) = match what {
+        FruValue::Type(type_) => (type_.get_ident(), scope.or_else(|| Some(type_.get_scope()))),
+        FruValue::String(name) => (Identifier::new(name), scope),
+        other => {
+            return FruError::new_res(format!(
+                "`operators_of` expects a type or a name of a type, got `{}`",
+                other.get_type_identifier()
+            ))
+        }
+    };
+
+    let operators: Vec<OperatorIdentifier> = match scope {
+        Some(scope) => scope.operators().into_iter().map(|(op, _)| op).collect(),
+        None => Prelude::standard().into_parts().1.into_keys().collect(),
+    };
+
+    let mut signatures: Vec<_> = operators
+        .into_iter()
+        .filter(|op| op.left() == ident || op.right() == ident)
+        .map(|op| op.to_string())
+        .collect();
+
+    signatures.sort();
+
+    Ok(FruValue::String(signatures.join("\n")))
+}
+
+/// Names of fields of an object, or of data props of a native object, in a `Tuple`
+pub fn b_props_of(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    let idents: Vec<Identifier> = match args.args.as_slice() {
+        [(None, FruValue::Object(obj))] => {
+            obj.get_type().get_fields().iter().map(|field| field.ident).collect()
+        }
+
+        [(None, FruValue::NativeObject(obj))] => obj.list_props(),
+
+        [(None, other)] => {
+            return FruError::new_res(format!(
+                "`props_of` expects an object, got `{}`",
+                other.get_type_identifier()
+            ))
+        }
+
+        _ => return FruError::new_res("`props_of` expects 1 argument"),
+    };
+
+    Ok(FruTuple::new_value(
+        idents.into_iter().map(|ident| FruValue::String(ident.to_string())).collect(),
+    ))
+}
+
+/// Type of any value as a value, `type_of(1) == Number`
+pub fn b_type_of(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    match args.args.as_slice() {
+        [(None, value)] => Ok(value.get_type()),
+        _ => FruError::new_res("`type_of` expects 1 argument"),
+    }
+}
@@ -0,0 +1,2 @@
+pub mod fru_inspect;
+pub mod fru_introspection;
@@ -0,0 +1,58 @@
+use std::{cell::RefCell, rc::Rc};
+
+use macros::{derive_nat, static_ident};
+
+use crate::interpreter::{
+    error::FruError,
+    identifier::Identifier,
+    value::fru_value::FruValue,
+    value::function::{BuiltinFunction, EvaluatedArgumentList},
+};
+use crate::stdlib::decorators::fru_decorators::expect_function;
+
+/// Value computed by calling `init` on first use, static fields holding one are replaced by the result
+#[derive_nat("Lazy", methods = Self::method)]
+#[derive(Clone)]
+pub struct FruLazy {
+    #[fru(skip)]
+    init: FruValue,
+    #[fru(skip)]
+    value: Rc<RefCell<Option<FruValue>>>,
+}
+
+impl FruLazy {
+    pub fn force(&self) -> Result<FruValue, FruError> {
+        if let Some(value) = self.value.borrow().as_ref() {
+            return Ok(value.clone());
+        }
+
+        let value = self.init.call(EvaluatedArgumentList { args: Vec::new() })?;
+        *self.value.borrow_mut() = Some(value.clone());
+
+        Ok(value)
+    }
+
+    fn method(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        match ident {
+            x if x == static_ident!("get") => {
+                let this = self.clone();
+
+                Ok(BuiltinFunction::new_closure(move |_| this.force()).into())
+            }
+
+            _ => FruError::new_res(format!("prop `{}` not found", ident)),
+        }
+    }
+}
+
+pub fn b_lazy(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    match args.args.as_slice() {
+        [(None, init)] => Ok(FruLazy {
+            init: expect_function("lazy", init)?,
+            value: Rc::new(RefCell::new(None)),
+        }
+        .into_value()),
+
+        _ => FruError::new_res("`lazy` expects 1 argument"),
+    }
+}
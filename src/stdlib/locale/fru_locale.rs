@@ -0,0 +1,415 @@
+use std::{any::Any, rc::Rc};
+
+use macros::static_ident;
+
+use crate::interpreter::{
+    error::FruError,
+    identifier::Identifier,
+    value::fru_value::FruValue,
+    value::function::BuiltinFunction,
+    value::native::object::{INativeObject, NativeObject},
+};
+use crate::stdlib::number::fru_number::{digits_count, group_digits, number};
+
+/// `Locale` namespace, numbers and dates written the way people of a region expect
+pub struct FruLocale;
+
+struct LocaleData {
+    tag: &'static str,
+    separator: &'static str,
+    point: &'static str,
+    // integer digits beyond 3 that a number needs before it is grouped, 2 means `1234` but `12 345`
+    min_grouping: usize,
+    months: [&'static str; 12],
+    short_months: [&'static str; 12],
+    // `{d}` is the day, `{M}` the month and `{y}` the year, doubled for two digits,
+    // `{MMM}` and `{MMMM}` are the short and full names of the month
+    short: &'static str,
+    medium: &'static str,
+    long: &'static str,
+}
+
+const ENGLISH_MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const ENGLISH_SHORT_MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+const NUMERIC_MONTHS: [&str; 12] = [
+    "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12",
+];
+
+// the first locale of a language is used when only the language is given
+const LOCALES: [LocaleData; 9] = [
+    LocaleData {
+        tag: "en-US",
+        separator: ",",
+        point: ".",
+        min_grouping: 1,
+        months: ENGLISH_MONTHS,
+        short_months: ENGLISH_SHORT_MONTHS,
+        short: "{M}/{d}/{yy}",
+        medium: "{MMM} {d}, {y}",
+        long: "{MMMM} {d}, {y}",
+    },
+    LocaleData {
+        tag: "en-GB",
+        separator: ",",
+        point: ".",
+        min_grouping: 1,
+        months: ENGLISH_MONTHS,
+        short_months: ENGLISH_SHORT_MONTHS,
+        short: "{dd}/{MM}/{y}",
+        medium: "{d} {MMM} {y}",
+        long: "{d} {MMMM} {y}",
+    },
+    LocaleData {
+        tag: "de-DE",
+        separator: ".",
+        point: ",",
+        min_grouping: 1,
+        months: [
+            "Januar",
+            "Februar",
+            "März",
+            "April",
+            "Mai",
+            "Juni",
+            "Juli",
+            "August",
+            "September",
+            "Oktober",
+            "November",
+            "Dezember",
+        ],
+        short_months: [
+            "Jan.", "Feb.", "März", "Apr.", "Mai", "Juni", "Juli", "Aug.", "Sept.", "Okt.", "Nov.",
+            "Dez.",
+        ],
+        short: "{dd}.{MM}.{yy}",
+        medium: "{dd}.{MM}.{y}",
+        long: "{d}. {MMMM} {y}",
+    },
+    LocaleData {
+        tag: "fr-FR",
+        separator: "\u{202f}",
+        point: ",",
+        min_grouping: 1,
+        months: [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ],
+        short_months: [
+            "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.",
+            "nov.", "déc.",
+        ],
+        short: "{dd}/{MM}/{y}",
+        medium: "{d} {MMM} {y}",
+        long: "{d} {MMMM} {y}",
+    },
+    LocaleData {
+        tag: "es-ES",
+        separator: ".",
+        point: ",",
+        min_grouping: 2,
+        months: [
+            "enero",
+            "febrero",
+            "marzo",
+            "abril",
+            "mayo",
+            "junio",
+            "julio",
+            "agosto",
+            "septiembre",
+            "octubre",
+            "noviembre",
+            "diciembre",
+        ],
+        short_months: [
+            "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic",
+        ],
+        short: "{d}/{M}/{yy}",
+        medium: "{d} {MMM} {y}",
+        long: "{d} de {MMMM} de {y}",
+    },
+    LocaleData {
+        tag: "it-IT",
+        separator: ".",
+        point: ",",
+        min_grouping: 1,
+        months: [
+            "gennaio",
+            "febbraio",
+            "marzo",
+            "aprile",
+            "maggio",
+            "giugno",
+            "luglio",
+            "agosto",
+            "settembre",
+            "ottobre",
+            "novembre",
+            "dicembre",
+        ],
+        short_months: [
+            "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+        ],
+        short: "{dd}/{MM}/{yy}",
+        medium: "{d} {MMM} {y}",
+        long: "{d} {MMMM} {y}",
+    },
+    LocaleData {
+        tag: "pt-BR",
+        separator: ".",
+        point: ",",
+        min_grouping: 1,
+        months: [
+            "janeiro",
+            "fevereiro",
+            "março",
+            "abril",
+            "maio",
+            "junho",
+            "julho",
+            "agosto",
+            "setembro",
+            "outubro",
+            "novembro",
+            "dezembro",
+        ],
+        short_months: [
+            "jan.", "fev.", "mar.", "abr.", "mai.", "jun.", "jul.", "ago.", "set.", "out.", "nov.",
+            "dez.",
+        ],
+        short: "{dd}/{MM}/{y}",
+        medium: "{d} de {MMM} de {y}",
+        long: "{d} de {MMMM} de {y}",
+    },
+    LocaleData {
+        tag: "ru-RU",
+        separator: "\u{a0}",
+        point: ",",
+        min_grouping: 1,
+        // dates use the genitive case
+        months: [
+            "января",
+            "февраля",
+            "марта",
+            "апреля",
+            "мая",
+            "июня",
+            "июля",
+            "августа",
+            "сентября",
+            "октября",
+            "ноября",
+            "декабря",
+        ],
+        short_months: [
+            "янв.",
+            "февр.",
+            "мар.",
+            "апр.",
+            "мая",
+            "июн.",
+            "июл.",
+            "авг.",
+            "сент.",
+            "окт.",
+            "нояб.",
+            "дек.",
+        ],
+        short: "{dd}.{MM}.{y}",
+        medium: "{d} {MMM} {y} г.",
+        long: "{d} {MMMM} {y} г.",
+    },
+    LocaleData {
+        tag: "ja-JP",
+        separator: ",",
+        point: ".",
+        min_grouping: 1,
+        months: NUMERIC_MONTHS,
+        short_months: NUMERIC_MONTHS,
+        short: "{y}/{MM}/{dd}",
+        medium: "{y}/{MM}/{dd}",
+        long: "{y}年{M}月{d}日",
+    },
+];
+
+impl FruLocale {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self)))
+    }
+}
+
+// `de-DE`, `de_de` and `de` are the same locale
+fn find_locale(value: &FruValue) -> Result<&'static LocaleData, FruError> {
+    let FruValue::String(tag) = value else {
+        return FruError::new_res(format!(
+            "locale must be a `String` like \"en-US\", got `{}`",
+            value.get_type_identifier()
+        ));
+    };
+
+    let normalized = tag.replace('_', "-");
+
+    let found = (LOCALES.iter().find(|x| x.tag.eq_ignore_ascii_case(&normalized))).or_else(|| {
+        LOCALES.iter().find(|x| {
+            x.tag
+                .split('-')
+                .next()
+                .is_some_and(|language| language.eq_ignore_ascii_case(&normalized))
+        })
+    });
+
+    match found {
+        Some(locale) => Ok(locale),
+        None => FruError::new_res(format!(
+            "unknown locale \"{}\", known ones are {}",
+            tag,
+            LOCALES.map(|x| x.tag).join(", ")
+        )),
+    }
+}
+
+/// Year, month and day in UTC of a Unix timestamp in seconds
+fn civil_date(timestamp: f64) -> (i64, usize, usize) {
+    // days since 0000-03-01, so leap days are at the end of a year
+    let days = (timestamp / 86400.0).floor() as i64 + 719468;
+
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year, month as usize, day as usize)
+}
+
+fn format_date(timestamp: f64, locale: &LocaleData, style: &str) -> Result<String, FruError> {
+    let pattern = match style {
+        "short" => locale.short,
+        "medium" => locale.medium,
+        "long" => locale.long,
+        _ => {
+            return FruError::new_res(format!(
+                "unknown date style \"{}\", expected \"short\", \"medium\" or \"long\"",
+                style
+            ))
+        }
+    };
+
+    let (year, month, day) = civil_date(timestamp);
+
+    Ok(pattern
+        .replace("{MMMM}", locale.months[month - 1])
+        .replace("{MMM}", locale.short_months[month - 1])
+        .replace("{MM}", &format!("{:02}", month))
+        .replace("{M}", &month.to_string())
+        .replace("{dd}", &format!("{:02}", day))
+        .replace("{d}", &day.to_string())
+        .replace("{yy}", &format!("{:02}", year.rem_euclid(100)))
+        .replace("{y}", &year.to_string()))
+}
+
+impl INativeObject for FruLocale {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("Locale")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        let function = match ident {
+            // `Locale.format_number(n, locale, digits)`, digits are optional
+            x if x == static_ident!("format_number") => BuiltinFunction::new_closure(|args| {
+                let (n, locale, digits) = match args.args.as_slice() {
+                    [(None, n), (None, locale)] => (number(n)?, find_locale(locale)?, None),
+
+                    [(None, n), (None, locale), (None, digits)] => (
+                        number(n)?,
+                        find_locale(locale)?,
+                        Some(digits_count(digits, 0.0)?),
+                    ),
+
+                    _ => return FruError::new_res("`format_number` expects 2 or 3 arguments"),
+                };
+
+                Ok(FruValue::String(group_digits(
+                    n,
+                    digits,
+                    locale.separator,
+                    locale.point,
+                    locale.min_grouping,
+                )))
+            }),
+
+            // `Locale.format_date(t, locale, style)`, `t` is a Unix timestamp in seconds,
+            // the style is `"medium"` unless given
+            x if x == static_ident!("format_date") => BuiltinFunction::new_closure(|args| {
+                let (timestamp, locale, style) = match args.args.as_slice() {
+                    [(None, t), (None, locale)] => (number(t)?, find_locale(locale)?, "medium"),
+
+                    [(None, t), (None, locale), (None, FruValue::String(style))] => {
+                        (number(t)?, find_locale(locale)?, style.as_str())
+                    }
+
+                    _ => {
+                        return FruError::new_res(
+                            "`format_date` expects a timestamp, a locale and an optional style",
+                        )
+                    }
+                };
+
+                if !timestamp.is_finite() {
+                    return FruError::new_res("timestamp must be a finite number");
+                }
+
+                Ok(FruValue::String(format_date(timestamp, locale, style)?))
+            }),
+
+            _ => return FruError::new_res(format!("prop `{}` not found", ident)),
+        };
+
+        Ok(function.into())
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
@@ -1 +1,38 @@
+pub mod ast;
+pub mod channel;
+pub mod clone;
+pub mod complex;
+#[cfg(feature = "compression")]
+pub mod compression;
+pub mod crypto;
+pub mod decimal;
+pub mod decorators;
+pub mod events;
+pub mod freeze;
+pub mod fs;
+pub mod future;
+pub mod introspection;
+pub mod lazy;
+#[cfg(feature = "locale")]
+pub mod locale;
+pub mod number;
+pub mod observe;
+pub mod prelude;
+pub mod primitive;
+pub mod queue;
+pub mod regex;
 pub mod scope;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod store;
+pub mod string;
+pub mod sys;
+pub mod task;
+pub mod timer;
+pub mod tree;
+pub mod tuple;
+pub mod units;
+pub mod vector;
+pub mod weak;
+pub mod with;
+pub mod worker;
@@ -0,0 +1,148 @@
+use crate::interpreter::{
+    error::FruError, value::fru_value::FruValue, value::function::EvaluatedArgumentList,
+};
+
+// formatting more digits than this only shows the error of f64
+const MAX_DIGITS: f64 = 100.0;
+
+/// Number written in the string, `nah` if there is none.
+/// Unlike `Number(x)` it is meant for user input, so it never fails
+pub fn b_parse_number(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    let text = match args.args.as_slice() {
+        [(None, FruValue::String(text))] => text,
+        [(None, other)] => return expected_string(other),
+        _ => return FruError::new_res("`parse_number` expects 1 argument"),
+    };
+
+    Ok(match text.trim().parse::<f64>() {
+        Ok(n) if n.is_finite() => FruValue::Number(n),
+        _ => FruValue::Nah,
+    })
+}
+
+/// `n` with exactly `digits` digits after the point
+pub fn b_to_fixed(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    let (n, digits) = match args.args.as_slice() {
+        [(None, n), (None, digits)] => (number(n)?, digits_count(digits, 0.0)?),
+        _ => return FruError::new_res("`to_fixed` expects 2 arguments"),
+    };
+
+    Ok(FruValue::String(format!("{:.*}", digits, n)))
+}
+
+/// `n` with `digits` significant digits, very big and very small numbers use exponent
+pub fn b_to_precision(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    let (n, digits) = match args.args.as_slice() {
+        [(None, n), (None, digits)] => (number(n)?, digits_count(digits, 1.0)?),
+        _ => return FruError::new_res("`to_precision` expects 2 arguments"),
+    };
+
+    Ok(FruValue::String(to_precision(n, digits)))
+}
+
+/// `n` with digits grouped by thousands, like `1,234,567.5`,
+/// the output is the same on every machine, whatever its locale is
+pub fn b_format_number(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    let (n, digits) = match args.args.as_slice() {
+        [(None, n)] => (number(n)?, None),
+        [(None, n), (None, digits)] => (number(n)?, Some(digits_count(digits, 0.0)?)),
+        _ => return FruError::new_res("`format_number` expects 1 or 2 arguments"),
+    };
+
+    Ok(FruValue::String(group_digits(n, digits, ",", ".", 1)))
+}
+
+/// `n` with `separator` between thousands and `point` before the fraction,
+/// numbers with fewer than `3 + min_grouping` integer digits are not grouped
+pub fn group_digits(
+    n: f64,
+    digits: Option<usize>,
+    separator: &str,
+    point: &str,
+    min_grouping: usize,
+) -> String {
+    if !n.is_finite() {
+        return n.to_string();
+    }
+
+    let text = match digits {
+        Some(digits) => format!("{:.*}", digits, n.abs()),
+        None => n.abs().to_string(),
+    };
+
+    let (integer, fraction) = match text.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (text.as_str(), None),
+    };
+
+    let grouped = integer.len() >= 3 + min_grouping;
+    let mut result = String::new();
+
+    if n.is_sign_negative() && text.chars().any(|c| c.is_ascii_digit() && c != '0') {
+        result.push('-');
+    }
+
+    for (k, c) in integer.chars().enumerate() {
+        if grouped && k > 0 && (integer.len() - k) % 3 == 0 {
+            result.push_str(separator);
+        }
+
+        result.push(c);
+    }
+
+    if let Some(fraction) = fraction {
+        result.push_str(point);
+        result.push_str(fraction);
+    }
+
+    result
+}
+
+fn to_precision(n: f64, digits: usize) -> String {
+    if !n.is_finite() {
+        return n.to_string();
+    }
+
+    // rounding to the precision can change the exponent, like 9.99 -> 1.0e1
+    let exponential = format!("{:.*e}", digits - 1, n);
+    let (mantissa, exponent) =
+        exponential.split_once('e').expect("exponential format always has an exponent");
+    let exponent: i32 = exponent.parse().expect("exponent is an integer");
+
+    if exponent < -6 || exponent >= digits as i32 {
+        let sign = if exponent < 0 { '-' } else { '+' };
+        return format!("{}e{}{}", mantissa, sign, exponent.abs());
+    }
+
+    format!("{:.*}", (digits as i32 - 1 - exponent) as usize, n)
+}
+
+pub fn number(value: &FruValue) -> Result<f64, FruError> {
+    match value {
+        FruValue::Number(n) => Ok(*n),
+        other => FruError::new_res(format!(
+            "expected `Number`, got `{}`",
+            other.get_type_identifier()
+        )),
+    }
+}
+
+pub fn digits_count(value: &FruValue, min: f64) -> Result<usize, FruError> {
+    match value {
+        FruValue::Number(n) if n.fract() == 0.0 && (min..=MAX_DIGITS).contains(n) => {
+            Ok(*n as usize)
+        }
+
+        _ => FruError::new_res(format!(
+            "number of digits must be an integer from {} to {}",
+            min, MAX_DIGITS
+        )),
+    }
+}
+
+fn expected_string<T>(value: &FruValue) -> Result<T, FruError> {
+    FruError::new_res(format!(
+        "expected `String`, got `{}`",
+        value.get_type_identifier()
+    ))
+}
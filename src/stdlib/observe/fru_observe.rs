@@ -0,0 +1,49 @@
+use std::rc::Rc;
+
+use crate::interpreter::{
+    error::FruError,
+    identifier::Identifier,
+    value::fru_value::FruValue,
+    value::function::{BuiltinFunction, EvaluatedArgumentList},
+};
+
+/// `observe(obj, "field", fn(old, new) {...})` returns a function that stops observing
+pub fn b_observe(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    let (object, field, callback) = match args.args.as_slice() {
+        [(None, FruValue::Object(object)), (None, FruValue::String(field)), (None, callback @ FruValue::Function(_))] => {
+            (object, Identifier::new(field), callback.clone())
+        }
+
+        [(None, value), _, _] if !matches!(value, FruValue::Object(_)) => {
+            return FruError::new_res(format!(
+                "`observe` expects an object, got `{}`",
+                value.get_type_identifier()
+            ))
+        }
+
+        _ => return FruError::new_res("`observe` expects an object, a field name and a function"),
+    };
+
+    let id = object.observe(
+        field,
+        Rc::new(move |old, new| {
+            callback.call(EvaluatedArgumentList {
+                args: vec![(None, old), (None, new)],
+            })?;
+
+            Ok(())
+        }),
+    )?;
+
+    // the returned function doesn't keep the object alive
+    let object = object.downgrade();
+
+    Ok(BuiltinFunction::new_closure(move |_| {
+        if let Some(object) = object.upgrade() {
+            object.unobserve(id);
+        }
+
+        Ok(FruValue::Nah)
+    })
+    .into())
+}
@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+
+use crate::interpreter::{
+    builtins::{functions::builtin_functions, operators::builtin_operators, types::builtin_types},
+    error::FruError,
+    identifier::{Identifier, OperatorIdentifier},
+    value::fru_value::FruValue,
+    value::function::{AnyFunction, BuiltinFunction, EvaluatedArgumentList},
+    value::operator::AnyOperator,
+};
+
+/// Values and operators that the root scope of an interpreter starts with
+#[derive(Clone)]
+pub struct Prelude {
+    variables: HashMap<Identifier, FruValue>,
+    operators: HashMap<OperatorIdentifier, AnyOperator>,
+}
+
+/// Group of builtins that can be installed into a prelude together
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StdModule {
+    Core,
+    Ast,
+    Collections,
+    Decorators,
+    Fs,
+    Introspection,
+    Math,
+    Tasks,
+    Text,
+}
+
+impl StdModule {
+    pub const ALL: [StdModule; 9] = [
+        StdModule::Core,
+        StdModule::Ast,
+        StdModule::Collections,
+        StdModule::Decorators,
+        StdModule::Fs,
+        StdModule::Introspection,
+        StdModule::Math,
+        StdModule::Tasks,
+        StdModule::Text,
+    ];
+
+    // names that are not built in, like `Sqlite` without its feature, are skipped
+    pub fn names(self) -> &'static [&'static str] {
+        match self {
+            StdModule::Core => &[
+                "print",
+                "input",
+                "assert_eq",
+                "global",
+                "freeze",
+                "is_frozen",
+                "observe",
+                "clone",
+                "deep_clone",
+                "weak",
+                "with",
+                "op",
+                "when",
+                "case",
+                "fallthrough",
+                "has_feature",
+                "Bool",
+                "Function",
+                "Nah",
+                "Number",
+                "String",
+                "Sys",
+                "Tuple",
+                "Type",
+            ],
+            StdModule::Ast => &["Ast", "macro", "quote", "unquote"],
+            StdModule::Collections => &["Deque", "Heap", "TreeMap", "TreeSet"],
+            StdModule::Decorators => &["memoize", "timed", "deprecated", "lazy"],
+            StdModule::Fs => &["Fs", "Gzip", "Store", "Sqlite", "Zip"],
+            StdModule::Introspection => {
+                &["doc_of", "inspect", "operators_of", "props_of", "type_of"]
+            }
+            StdModule::Math => &[
+                "Complex",
+                "Decimal",
+                "Vec2",
+                "Vec3",
+                "Mat3",
+                "Unit",
+                "parse_number",
+                "to_fixed",
+                "to_precision",
+                "format_number",
+                "Random",
+            ],
+            StdModule::Tasks => &[
+                "spawn",
+                "yield_now",
+                "await",
+                "sleep",
+                "Channel",
+                "Events",
+                "Timer",
+                "Worker",
+            ],
+            StdModule::Text => &["Char", "Hash", "Locale", "Regex", "Template", "dedent"],
+        }
+    }
+}
+
+impl Prelude {
+    /// Every builtin, what interpreters get by default
+    pub fn standard() -> Self {
+        let mut variables = builtin_functions();
+        variables.extend(builtin_types());
+
+        variables.into_iter().fold(Self::empty(), |prelude, (ident, value)| {
+            prelude.with_value_ident(ident, value)
+        })
+    }
+
+    /// Only the operators of primitive types, values are added with `with_module` and `with_value`
+    pub fn empty() -> Self {
+        Self {
+            variables: HashMap::new(),
+            operators: builtin_operators(),
+        }
+    }
+
+    pub fn with_module(mut self, module: StdModule) -> Self {
+        let mut builtins = builtin_functions();
+        builtins.extend(builtin_types());
+
+        for name in module.names() {
+            if let Some(value) = builtins.remove(&Identifier::new(name)) {
+                self = self.with_value_ident(Identifier::new(name), value);
+            }
+        }
+
+        self
+    }
+
+    pub fn without_module(mut self, module: StdModule) -> Self {
+        for name in module.names() {
+            self.variables.remove(&Identifier::new(name));
+        }
+
+        self
+    }
+
+    /// Native type objects bring the operators of their values with them
+    pub fn with_value(self, ident: &str, value: FruValue) -> Self {
+        self.with_value_ident(Identifier::new(ident), value)
+    }
+
+    pub fn with_function(
+        self,
+        ident: &str,
+        function: impl Fn(EvaluatedArgumentList) -> Result<FruValue, FruError> + 'static,
+    ) -> Self {
+        let function = BuiltinFunction::new_closure(function).with_name(Identifier::new(ident));
+
+        self.with_value(
+            ident,
+            FruValue::Function(AnyFunction::BuiltinFunction(function)),
+        )
+    }
+
+    pub fn without(mut self, ident: &str) -> Self {
+        self.variables.remove(&Identifier::new(ident));
+        self
+    }
+
+    pub fn has(&self, ident: &str) -> bool {
+        self.variables.contains_key(&Identifier::new(ident))
+    }
+
+    fn with_value_ident(mut self, ident: Identifier, value: FruValue) -> Self {
+        if let FruValue::NativeObject(object) = &value {
+            self.operators.extend(object.operators());
+        }
+
+        self.variables.insert(ident, value);
+        self
+    }
+
+    pub fn into_parts(
+        self,
+    ) -> (
+        HashMap<Identifier, FruValue>,
+        HashMap<OperatorIdentifier, AnyOperator>,
+    ) {
+        (self.variables, self.operators)
+    }
+}
+
+impl Default for Prelude {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
@@ -0,0 +1,278 @@
+use std::fmt;
+
+use macros::{derive_nat, static_ident};
+
+use crate::interpreter::{
+    error::FruError,
+    identifier::Identifier,
+    value::fru_value::FruValue,
+    value::function::{BuiltinFunction, EvaluatedArgumentList},
+    value::native::object::INativeObject,
+};
+use crate::stdlib::{
+    number::fru_number::b_parse_number, string::fru_char::extract_char,
+    tuple::fru_tuple::extract_tuple_items,
+};
+
+/// `Number` as a value, calling it converts to a number
+#[derive_nat("NumberType", methods = Self::method, call = Self::convert, debug = Self::fmt_name)]
+pub struct FruNumberType;
+
+/// `String` as a value, calling it converts anything to its printed form
+#[derive_nat("StringType", methods = Self::method, call = Self::convert, debug = Self::fmt_name)]
+pub struct FruStringType;
+
+/// `Bool` as a value, calling it gives the truthiness of a value
+#[derive_nat("BoolType", methods = Self::method, call = Self::convert, debug = Self::fmt_name)]
+pub struct FruBoolType;
+
+/// What `type_of(nah)` returns
+#[derive_nat("NahType", debug = Self::fmt_name)]
+pub struct FruNahType;
+
+/// What `type_of` returns for functions, both builtin and declared
+#[derive_nat("FunctionType", debug = Self::fmt_name)]
+pub struct FruFunctionType;
+
+/// What `type_of` returns for types
+#[derive_nat("TypeType", debug = Self::fmt_name)]
+pub struct FruTypeType;
+
+/// Type of a native object that has no type value in the prelude, known only by its name
+#[derive_nat("NativeType", eq = Self::eq, debug = Self::fmt_name)]
+pub struct FruNativeType {
+    #[fru(skip)]
+    ident: Identifier,
+}
+
+impl FruNahType {
+    pub fn new_value() -> FruValue {
+        Self.into_value()
+    }
+
+    fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Nah")
+    }
+}
+
+impl FruFunctionType {
+    pub fn new_value() -> FruValue {
+        Self.into_value()
+    }
+
+    fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Function")
+    }
+}
+
+impl FruTypeType {
+    pub fn new_value() -> FruValue {
+        Self.into_value()
+    }
+
+    fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Type")
+    }
+}
+
+impl FruNativeType {
+    pub fn new_value(ident: Identifier) -> FruValue {
+        Self { ident }.into_value()
+    }
+
+    fn eq(&self, other: &dyn INativeObject) -> Option<bool> {
+        let other = other.as_any().downcast_ref::<FruNativeType>()?;
+        Some(self.ident == other.ident)
+    }
+
+    fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.ident)
+    }
+}
+
+impl FruNumberType {
+    pub fn new_value() -> FruValue {
+        Self.into_value()
+    }
+
+    fn convert(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+        Ok(FruValue::Number(to_number(single_argument(
+            args, "Number",
+        )?)?))
+    }
+
+    fn method(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        match ident {
+            // `nah` instead of an error, for input that is expected to be wrong sometimes
+            x if x == static_ident!("parse") => Ok(BuiltinFunction::new(b_parse_number).into()),
+            _ => FruError::new_res(format!("prop `{}` not found", ident)),
+        }
+    }
+
+    fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Number")
+    }
+}
+
+impl FruStringType {
+    pub fn new_value() -> FruValue {
+        Self.into_value()
+    }
+
+    fn convert(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+        Ok(FruValue::String(to_string(single_argument(
+            args, "String",
+        )?)?))
+    }
+
+    fn method(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        match ident {
+            x if x == static_ident!("from_chars") => Ok(BuiltinFunction::new(from_chars).into()),
+            _ => FruError::new_res(format!("prop `{}` not found", ident)),
+        }
+    }
+
+    fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "String")
+    }
+}
+
+impl FruBoolType {
+    pub fn new_value() -> FruValue {
+        Self.into_value()
+    }
+
+    fn convert(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+        Ok(FruValue::Bool(to_bool(single_argument(args, "Bool")?)?))
+    }
+
+    fn method(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        match ident {
+            x if x == static_ident!("from") => {
+                Ok(BuiltinFunction::new_closure(|args| FruBoolType.convert(args)).into())
+            }
+            _ => FruError::new_res(format!("prop `{}` not found", ident)),
+        }
+    }
+
+    fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Bool")
+    }
+}
+
+/// What `Number(x)` gives, objects convert themselves with a `_to_number` method
+pub fn to_number(value: FruValue) -> Result<f64, FruError> {
+    match value {
+        FruValue::Number(n) => Ok(n),
+        FruValue::Bool(b) => Ok(if b { 1.0 } else { 0.0 }),
+
+        FruValue::String(s) => match s.trim().parse() {
+            Ok(n) => Ok(n),
+            Err(_) => FruError::new_res(format!("cannot convert \"{}\" to `Number`", s)),
+        },
+
+        other => match convert_with_method(&other, static_ident!("_to_number"))? {
+            Some(FruValue::Number(n)) => Ok(n),
+            Some(result) => wrong_result("_to_number", &other, "Number", &result),
+            None => FruError::new_res(format!(
+                "cannot convert `{}` to `Number`",
+                other.get_type_identifier()
+            )),
+        },
+    }
+}
+
+/// What `String(x)` and templates give, objects convert themselves with a `_to_string` method
+pub fn to_string(value: FruValue) -> Result<String, FruError> {
+    match convert_with_method(&value, static_ident!("_to_string"))? {
+        Some(FruValue::String(s)) => Ok(s),
+        Some(result) => wrong_result("_to_string", &value, "String", &result),
+        None => Ok(format!("{:?}", value)),
+    }
+}
+
+/// `nah`, `0`, NaN and `""` are false, objects decide with a `_to_bool` method,
+/// everything else is true
+pub fn to_bool(value: FruValue) -> Result<bool, FruError> {
+    Ok(match value {
+        FruValue::Nah => false,
+        FruValue::Bool(b) => b,
+        FruValue::Number(n) => n != 0.0 && !n.is_nan(),
+        FruValue::String(s) => !s.is_empty(),
+
+        other => match convert_with_method(&other, static_ident!("_to_bool"))? {
+            Some(FruValue::Bool(b)) => b,
+            Some(result) => return wrong_result("_to_bool", &other, "Bool", &result),
+            None => true,
+        },
+    })
+}
+
+// `None` if the value has no such method
+fn convert_with_method(value: &FruValue, ident: Identifier) -> Result<Option<FruValue>, FruError> {
+    let has_method = match value {
+        FruValue::Object(obj) => obj.get_type().get_method(ident).is_some(),
+        FruValue::NativeObject(obj) => obj.get_prop(ident).is_ok(),
+        _ => false,
+    };
+
+    if !has_method {
+        return Ok(None);
+    }
+
+    value
+        .get_prop(ident)?
+        .call(EvaluatedArgumentList { args: Vec::new() })
+        .map(Some)
+}
+
+fn wrong_result<T>(
+    method: &str,
+    value: &FruValue,
+    expected: &str,
+    result: &FruValue,
+) -> Result<T, FruError> {
+    FruError::new_res(format!(
+        "`{}` of `{}` must return `{}`, got `{}`",
+        method,
+        value.get_type_identifier(),
+        expected,
+        result.get_type_identifier()
+    ))
+}
+
+// `String.from_chars(t)` joins a `Tuple` of characters and strings
+fn from_chars(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    let value = single_argument(args, "from_chars")?;
+
+    let Some(items) = extract_tuple_items(&value) else {
+        return FruError::new_res(format!(
+            "`from_chars` expects a `Tuple`, got `{}`",
+            value.get_type_identifier()
+        ));
+    };
+
+    let mut result = String::new();
+
+    for item in items.iter() {
+        match (item, extract_char(item)) {
+            (_, Some(c)) => result.push(c),
+            (FruValue::String(s), None) => result.push_str(s),
+            (other, None) => {
+                return FruError::new_res(format!(
+                    "`from_chars` expects characters, got `{}`",
+                    other.get_type_identifier()
+                ))
+            }
+        }
+    }
+
+    Ok(FruValue::String(result))
+}
+
+fn single_argument(args: EvaluatedArgumentList, name: &str) -> Result<FruValue, FruError> {
+    match <[_; 1]>::try_from(args.args) {
+        Ok([(None, value)]) => Ok(value),
+        _ => FruError::new_res(format!("`{}` expects 1 argument", name)),
+    }
+}
@@ -0,0 +1,408 @@
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    cmp::Ordering,
+    collections::VecDeque,
+    fmt,
+    rc::Rc,
+};
+
+use macros::static_ident;
+
+use crate::interpreter::{
+    error::FruError,
+    identifier::Identifier,
+    value::fru_value::FruValue,
+    value::function::{BuiltinFunction, EvaluatedArgumentList},
+    value::native::object::{check_mutable, Contents, INativeObject, NativeObject},
+    value::operator::compare,
+};
+
+pub struct FruDequeType;
+
+pub struct FruHeapType;
+
+/// Double-ended queue, also serves as a stack
+pub struct FruDeque {
+    items: Rc<RefCell<VecDeque<FruValue>>>,
+    frozen: Rc<Cell<bool>>,
+}
+
+/// Priority queue, `pop` returns the smallest item
+pub struct FruHeap {
+    internal: Rc<HeapInternal>,
+}
+
+struct HeapInternal {
+    items: RefCell<Vec<FruValue>>,
+    frozen: Cell<bool>,
+    // `less(a, b)` is true when `a` must be popped before `b`
+    less: Option<FruValue>,
+}
+
+impl FruDequeType {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self)))
+    }
+}
+
+impl INativeObject for FruDequeType {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("DequeType")
+    }
+
+    fn instantiate(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+        if args.args.iter().any(|(ident, _)| ident.is_some()) {
+            return FruError::new_res("`Deque` expects items without names");
+        }
+
+        Ok(FruDeque::new_value(
+            args.args.into_iter().map(|(_, value)| value).collect(),
+        ))
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+impl FruDeque {
+    fn new_value(items: VecDeque<FruValue>) -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self {
+            items: Rc::new(RefCell::new(items)),
+            frozen: Rc::new(Cell::new(false)),
+        })))
+    }
+}
+
+fn single_argument(args: EvaluatedArgumentList, name: &str) -> Result<FruValue, FruError> {
+    match <[_; 1]>::try_from(args.args) {
+        Ok([(None, value)]) => Ok(value),
+        _ => FruError::new_res(format!("`{}` expects 1 argument", name)),
+    }
+}
+
+impl INativeObject for FruDeque {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("Deque")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        let items = self.items.clone();
+        let frozen = self.frozen.clone();
+
+        let method = match ident {
+            x if x == static_ident!("len") => {
+                return Ok(FruValue::Number(self.items.borrow().len() as f64));
+            }
+
+            x if x == static_ident!("push_back") => BuiltinFunction::new_closure(move |args| {
+                check_mutable(&frozen, "Deque", "push_back")?;
+                items.borrow_mut().push_back(single_argument(args, "push_back")?);
+                Ok(FruValue::Nah)
+            }),
+
+            x if x == static_ident!("push_front") => BuiltinFunction::new_closure(move |args| {
+                check_mutable(&frozen, "Deque", "push_front")?;
+                items.borrow_mut().push_front(single_argument(args, "push_front")?);
+                Ok(FruValue::Nah)
+            }),
+
+            x if x == static_ident!("pop_back") => BuiltinFunction::new_closure(move |_| {
+                check_mutable(&frozen, "Deque", "pop_back")?;
+                Ok(items.borrow_mut().pop_back().unwrap_or(FruValue::Nah))
+            }),
+
+            x if x == static_ident!("pop_front") => BuiltinFunction::new_closure(move |_| {
+                check_mutable(&frozen, "Deque", "pop_front")?;
+                Ok(items.borrow_mut().pop_front().unwrap_or(FruValue::Nah))
+            }),
+
+            x if x == static_ident!("back") => BuiltinFunction::new_closure(move |_| {
+                Ok(items.borrow().back().cloned().unwrap_or(FruValue::Nah))
+            }),
+
+            x if x == static_ident!("front") => BuiltinFunction::new_closure(move |_| {
+                Ok(items.borrow().front().cloned().unwrap_or(FruValue::Nah))
+            }),
+
+            x if x == static_ident!("each") => BuiltinFunction::new_closure(move |args| {
+                let function = single_argument(args, "each")?;
+
+                // the callback may change the deque, so it iterates over a snapshot
+                let snapshot: Vec<_> = items.borrow().iter().cloned().collect();
+
+                for item in snapshot {
+                    function.call(EvaluatedArgumentList {
+                        args: vec![(None, item)],
+                    })?;
+                }
+
+                Ok(FruValue::Nah)
+            }),
+
+            _ => return FruError::new_res(format!("prop `{}` not found", ident)),
+        };
+
+        Ok(method.into())
+    }
+
+    fn fmt_debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Deque[")?;
+
+        for (k, item) in self.items.borrow().iter().enumerate() {
+            if k > 0 {
+                write!(f, ", ")?;
+            }
+
+            write!(f, "{:?}", item)?;
+        }
+
+        write!(f, "]")
+    }
+
+    fn contents(&self) -> Option<Contents> {
+        Some(Contents {
+            open: "Deque[",
+            close: "]",
+            items: self.items.borrow().iter().map(|item| (None, item.clone())).collect(),
+        })
+    }
+
+    fn freeze(&self) -> Option<Vec<FruValue>> {
+        self.frozen.set(true);
+        Some(self.items.borrow().iter().cloned().collect())
+    }
+
+    fn is_frozen(&self) -> bool {
+        self.frozen.get()
+    }
+
+    fn copy_with(
+        &self,
+        copy_item: &mut dyn FnMut(FruValue) -> Result<FruValue, FruError>,
+    ) -> Option<Result<FruValue, FruError>> {
+        let items: Vec<_> = self.items.borrow().iter().cloned().collect();
+
+        Some(
+            (items.into_iter().map(copy_item))
+                .collect::<Result<_, _>>()
+                .map(FruDeque::new_value),
+        )
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+impl FruHeapType {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self)))
+    }
+}
+
+impl INativeObject for FruHeapType {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("HeapType")
+    }
+
+    fn instantiate(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+        let less = match args.args.as_slice() {
+            [] => None,
+            [(None, less)] => Some(less.clone()),
+            [(Some(ident), less)] if *ident == static_ident!("less") => Some(less.clone()),
+            _ => return FruError::new_res("`Heap` expects optional `less` argument"),
+        };
+
+        Ok(FruValue::NativeObject(NativeObject::new(Rc::new(
+            FruHeap {
+                internal: Rc::new(HeapInternal {
+                    items: RefCell::new(Vec::new()),
+                    frozen: Cell::new(false),
+                    less,
+                }),
+            },
+        ))))
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+impl HeapInternal {
+    fn less(&self, a: &FruValue, b: &FruValue) -> Result<bool, FruError> {
+        let Some(less) = &self.less else {
+            return Ok(compare(a, b)? == Ordering::Less);
+        };
+
+        match less.call(EvaluatedArgumentList {
+            args: vec![(None, a.clone()), (None, b.clone())],
+        })? {
+            FruValue::Bool(b) => Ok(b),
+            other => FruError::new_res(format!(
+                "`less` of `Heap` must return `Bool`, got `{}`",
+                other.get_type_identifier()
+            )),
+        }
+    }
+
+    // items are cloned out of the cell, so `less` can look at the heap while it runs
+    fn item(&self, k: usize) -> FruValue {
+        self.items.borrow()[k].clone()
+    }
+
+    fn push(&self, value: FruValue) -> Result<(), FruError> {
+        self.items.borrow_mut().push(value);
+
+        let mut k = self.items.borrow().len() - 1;
+
+        while k > 0 {
+            let parent = (k - 1) / 2;
+
+            if !self.less(&self.item(k), &self.item(parent))? {
+                break;
+            }
+
+            self.items.borrow_mut().swap(k, parent);
+            k = parent;
+        }
+
+        Ok(())
+    }
+
+    fn pop(&self) -> Result<FruValue, FruError> {
+        let top = {
+            let mut items = self.items.borrow_mut();
+
+            if items.is_empty() {
+                return Ok(FruValue::Nah);
+            }
+
+            items.swap_remove(0)
+        };
+
+        let len = self.items.borrow().len();
+        let mut k = 0;
+
+        loop {
+            let mut smallest = k;
+
+            for child in [2 * k + 1, 2 * k + 2] {
+                if child < len && self.less(&self.item(child), &self.item(smallest))? {
+                    smallest = child;
+                }
+            }
+
+            if smallest == k {
+                break;
+            }
+
+            self.items.borrow_mut().swap(k, smallest);
+            k = smallest;
+        }
+
+        Ok(top)
+    }
+}
+
+impl INativeObject for FruHeap {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("Heap")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        let internal = self.internal.clone();
+
+        let method = match ident {
+            x if x == static_ident!("len") => {
+                return Ok(FruValue::Number(self.internal.items.borrow().len() as f64));
+            }
+
+            x if x == static_ident!("push") => BuiltinFunction::new_closure(move |args| {
+                check_mutable(&internal.frozen, "Heap", "push")?;
+                internal.push(single_argument(args, "push")?)?;
+                Ok(FruValue::Nah)
+            }),
+
+            x if x == static_ident!("pop") => BuiltinFunction::new_closure(move |_| {
+                check_mutable(&internal.frozen, "Heap", "pop")?;
+                internal.pop()
+            }),
+
+            x if x == static_ident!("peek") => BuiltinFunction::new_closure(move |_| {
+                Ok(internal.items.borrow().first().cloned().unwrap_or(FruValue::Nah))
+            }),
+
+            _ => return FruError::new_res(format!("prop `{}` not found", ident)),
+        };
+
+        Ok(method.into())
+    }
+
+    fn fmt_debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Heap(len={})", self.internal.items.borrow().len())
+    }
+
+    // in the order they are stored, only the first one is the smallest
+    fn contents(&self) -> Option<Contents> {
+        Some(Contents {
+            open: "Heap[",
+            close: "]",
+            items: (self.internal.items.borrow().iter()).map(|item| (None, item.clone())).collect(),
+        })
+    }
+
+    fn freeze(&self) -> Option<Vec<FruValue>> {
+        self.internal.frozen.set(true);
+        Some(self.internal.items.borrow().clone())
+    }
+
+    fn is_frozen(&self) -> bool {
+        self.internal.frozen.get()
+    }
+
+    // copies keep their order, so the heap stays valid
+    fn copy_with(
+        &self,
+        copy_item: &mut dyn FnMut(FruValue) -> Result<FruValue, FruError>,
+    ) -> Option<Result<FruValue, FruError>> {
+        let items: Vec<_> = self.internal.items.borrow().clone();
+
+        let items = match items.into_iter().map(copy_item).collect() {
+            Ok(items) => items,
+            Err(err) => return Some(Err(err)),
+        };
+
+        Some(Ok(FruValue::NativeObject(NativeObject::new(Rc::new(
+            FruHeap {
+                internal: Rc::new(HeapInternal {
+                    items: RefCell::new(items),
+                    frozen: Cell::new(false),
+                    less: self.internal.less.clone(),
+                }),
+            },
+        )))))
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
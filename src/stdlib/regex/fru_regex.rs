@@ -0,0 +1,199 @@
+use std::{any::Any, fmt, rc::Rc};
+
+use macros::static_ident;
+use regex::{Regex, RegexBuilder};
+
+use crate::interpreter::{
+    error::FruError,
+    identifier::Identifier,
+    value::fru_value::FruValue,
+    value::function::{BuiltinFunction, EvaluatedArgumentList},
+    value::native::object::{INativeObject, NativeObject},
+};
+use crate::stdlib::tuple::fru_tuple::FruTuple;
+
+pub struct FruRegexType;
+
+/// Compiled regular expression, `Regex("pattern", "flags")`
+pub struct FruRegex {
+    regex: Rc<Regex>,
+    flags: String,
+}
+
+impl FruRegexType {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self)))
+    }
+}
+
+impl INativeObject for FruRegexType {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("RegexType")
+    }
+
+    fn call(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+        match args.args.as_slice() {
+            [(None, FruValue::String(pattern))] => FruRegex::compile(pattern, ""),
+
+            [(None, FruValue::String(pattern)), (None, FruValue::String(flags))] => {
+                FruRegex::compile(pattern, flags)
+            }
+
+            _ => FruError::new_res("`Regex` expects a pattern and optional flags as `String`"),
+        }
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+impl FruRegex {
+    /// Flags are `i` (ignore case), `m` (multi-line), `s` (`.` matches `\n`) and `x` (verbose)
+    pub fn compile(pattern: &str, flags: &str) -> Result<FruValue, FruError> {
+        let mut builder = RegexBuilder::new(pattern);
+
+        for flag in flags.chars() {
+            match flag {
+                'i' => builder.case_insensitive(true),
+                'm' => builder.multi_line(true),
+                's' => builder.dot_matches_new_line(true),
+                'x' => builder.ignore_whitespace(true),
+                other => return FruError::new_res(format!("unknown `Regex` flag `{}`", other)),
+            };
+        }
+
+        let regex = builder
+            .build()
+            .map_err(|err| FruError::new(format!("invalid regex /{}/: {}", pattern, err)))?;
+
+        Ok(FruValue::NativeObject(NativeObject::new(Rc::new(Self {
+            regex: Rc::new(regex),
+            flags: flags.to_string(),
+        }))))
+    }
+}
+
+fn text_argument<const N: usize>(
+    args: &EvaluatedArgumentList,
+    name: &str,
+) -> Result<(String, Vec<FruValue>), FruError> {
+    match args.args.as_slice() {
+        [(None, FruValue::String(text)), rest @ ..]
+            if rest.len() == N - 1 && rest.iter().all(|(ident, _)| ident.is_none()) =>
+        {
+            Ok((
+                text.clone(),
+                rest.iter().map(|(_, value)| value.clone()).collect(),
+            ))
+        }
+
+        _ => FruError::new_res(format!(
+            "`{}` expects a `String` and {} more arguments",
+            name,
+            N - 1
+        )),
+    }
+}
+
+impl INativeObject for FruRegex {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("Regex")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        let regex = self.regex.clone();
+
+        let method = match ident {
+            x if x == static_ident!("source") => {
+                return Ok(FruValue::String(self.regex.as_str().to_string()));
+            }
+
+            x if x == static_ident!("is_match") => BuiltinFunction::new_closure(move |args| {
+                let (text, _) = text_argument::<1>(&args, "is_match")?;
+                Ok(FruValue::Bool(regex.is_match(&text)))
+            }),
+
+            // first match or `nah`
+            x if x == static_ident!("find") => BuiltinFunction::new_closure(move |args| {
+                let (text, _) = text_argument::<1>(&args, "find")?;
+                Ok(regex
+                    .find(&text)
+                    .map_or(FruValue::Nah, |m| FruValue::String(m.as_str().to_string())))
+            }),
+
+            // `Tuple` of the whole match and the groups, groups that did not match are `nah`
+            x if x == static_ident!("captures") => BuiltinFunction::new_closure(move |args| {
+                let (text, _) = text_argument::<1>(&args, "captures")?;
+                Ok(regex.captures(&text).map_or(FruValue::Nah, |captures| {
+                    FruTuple::new_value(
+                        captures
+                            .iter()
+                            .map(|group| {
+                                group.map_or(FruValue::Nah, |m| {
+                                    FruValue::String(m.as_str().to_string())
+                                })
+                            })
+                            .collect(),
+                    )
+                }))
+            }),
+
+            x if x == static_ident!("each_match") => BuiltinFunction::new_closure(move |args| {
+                let (text, rest) = text_argument::<2>(&args, "each_match")?;
+
+                for m in regex.find_iter(&text) {
+                    rest[0].call(EvaluatedArgumentList {
+                        args: vec![(None, FruValue::String(m.as_str().to_string()))],
+                    })?;
+                }
+
+                Ok(FruValue::Nah)
+            }),
+
+            // replaces all matches, `$1` and `$name` in the replacement refer to groups
+            x if x == static_ident!("replace") => BuiltinFunction::new_closure(move |args| {
+                let (text, rest) = text_argument::<2>(&args, "replace")?;
+
+                let FruValue::String(replacement) = &rest[0] else {
+                    return FruError::new_res(format!(
+                        "`replace` expects a `String` replacement, got `{}`",
+                        rest[0].get_type_identifier()
+                    ));
+                };
+
+                Ok(FruValue::String(
+                    regex.replace_all(&text, replacement.as_str()).into_owned(),
+                ))
+            }),
+
+            _ => return FruError::new_res(format!("prop `{}` not found", ident)),
+        };
+
+        Ok(method.into())
+    }
+
+    fn fmt_debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "/{}/{}", self.regex.as_str(), self.flags)
+    }
+
+    fn freeze(&self) -> Option<Vec<FruValue>> {
+        Some(Vec::new())
+    }
+
+    fn is_frozen(&self) -> bool {
+        true
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
@@ -1,12 +1,14 @@
-use std::{any::Any, rc::Rc};
+use std::{any::Any, fmt, rc::Rc};
 
 use macros::static_ident;
 
 use crate::interpreter::{
     error::FruError,
     identifier::Identifier,
+    runtime::current_runtime,
     scope::Scope,
     value::fru_value::FruValue,
+    value::function::EvaluatedArgumentList,
     value::native::object::{INativeObject, NativeObject},
 };
 
@@ -34,13 +36,43 @@ impl INativeObject for FruScope {
     }
 
     fn set_prop(&self, ident: Identifier, value: FruValue) -> Result<(), FruError> {
+        self.scope.check_not_frozen(ident)?;
         self.scope.let_set_variable(ident, value);
         Ok(())
     }
 
+    fn list_props(&self) -> Vec<Identifier> {
+        let mut idents: Vec<_> =
+            self.scope.variables().into_iter().map(|(ident, _)| ident).collect();
+        idents.sort_by_key(|ident| ident.to_string());
+        idents
+    }
+
+    // names only, a scope can hold itself
+    fn fmt_debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let idents: Vec<_> = self.list_props().iter().map(|ident| ident.to_string()).collect();
+        write!(f, "Scope{{{}}}", idents.join(", "))
+    }
+
+    // the same scope, captured twice
+    fn fru_eq(&self, other: &dyn INativeObject) -> Option<bool> {
+        let other = other.as_any().downcast_ref::<FruScope>()?;
+        Some(Rc::ptr_eq(&self.scope, &other.scope))
+    }
+
     fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
         self
     }
+
+    // variables of a frozen scope can still be changed through other references
+    fn freeze(&self) -> Option<Vec<FruValue>> {
+        self.scope.freeze();
+        Some(Vec::new())
+    }
+
+    fn is_frozen(&self) -> bool {
+        self.scope.is_frozen()
+    }
 }
 
 pub fn extract_scope_from_value(v: &FruValue) -> Option<Rc<Scope>> {
@@ -50,3 +82,15 @@ pub fn extract_scope_from_value(v: &FruValue) -> Option<Rc<Scope>> {
         None
     }
 }
+
+/// Global scope of the interpreter, `global().x = 1;` declares or assigns a global variable from anywhere
+pub fn b_global(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    if !args.args.is_empty() {
+        return FruError::new_res("`global` expects no arguments");
+    }
+
+    match current_runtime()?.global_scope() {
+        Some(scope) => Ok(FruScope::new_value(scope)),
+        None => FruError::new_res("the global scope of the interpreter no longer exists"),
+    }
+}
@@ -0,0 +1,262 @@
+use std::{any::Any, cell::RefCell, fmt, rc::Rc};
+
+use macros::static_ident;
+use rusqlite::{params_from_iter, types::Value, Connection, Statement, ToSql};
+
+use crate::interpreter::{
+    error::FruError,
+    identifier::Identifier,
+    options::Capability,
+    runtime::require_capability,
+    value::fru_value::FruValue,
+    value::function::{BuiltinFunction, EvaluatedArgumentList},
+    value::native::object::{INativeObject, NativeObject},
+};
+use crate::stdlib::{tree::fru_tree::FruTreeMap, tuple::fru_tuple::FruTuple};
+
+pub struct FruSqliteType;
+
+/// Open database connection, closed when the value is no longer used, or by `with`
+pub struct FruSqlite {
+    path: String,
+    // `None` once the connection is closed
+    connection: Rc<RefCell<Option<Connection>>>,
+}
+
+// `?1, ?2` are filled by arguments without names, `:name` by `name: value`
+enum SqlParams {
+    Positional(Vec<Value>),
+    Named(Vec<(String, Value)>),
+}
+
+fn sqlite_error(err: rusqlite::Error) -> FruError {
+    FruError::new(format!("sqlite: {}", err))
+}
+
+impl FruSqliteType {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self)))
+    }
+}
+
+impl INativeObject for FruSqliteType {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("SqliteType")
+    }
+
+    // `":memory:"` opens a database that lives only as long as the connection
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        if ident == static_ident!("open") {
+            return Ok(BuiltinFunction::new_closure(|args| {
+                require_capability(Capability::Fs)?;
+
+                let [(None, FruValue::String(path))] = args.args.as_slice() else {
+                    return FruError::new_res("`open` expects a path as `String`");
+                };
+
+                let connection = Connection::open(path).map_err(sqlite_error)?;
+
+                Ok(FruValue::NativeObject(NativeObject::new(Rc::new(
+                    FruSqlite {
+                        path: path.clone(),
+                        connection: Rc::new(RefCell::new(Some(connection))),
+                    },
+                ))))
+            })
+            .into());
+        }
+
+        FruError::new_res(format!("prop `{}` not found", ident))
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+fn to_sql(value: FruValue) -> Result<Value, FruError> {
+    Ok(match value {
+        FruValue::Nah => Value::Null,
+        FruValue::Bool(b) => Value::Integer(b as i64),
+        FruValue::Number(n) if n.fract() == 0.0 && n.abs() < i64::MAX as f64 => {
+            Value::Integer(n as i64)
+        }
+        FruValue::Number(n) => Value::Real(n),
+        FruValue::String(s) => Value::Text(s),
+        other => {
+            return FruError::new_res(format!(
+                "`{}` cannot be stored in sqlite",
+                other.get_type_identifier()
+            ))
+        }
+    })
+}
+
+fn from_sql(value: Value) -> Result<FruValue, FruError> {
+    Ok(match value {
+        Value::Null => FruValue::Nah,
+        Value::Integer(n) => FruValue::Number(n as f64),
+        Value::Real(n) => FruValue::Number(n),
+        Value::Text(s) => FruValue::String(s),
+        Value::Blob(_) => return FruError::new_res("sqlite: BLOB columns are not supported"),
+    })
+}
+
+// the first argument is the sql, the rest are parameters
+fn sql_and_params(
+    args: EvaluatedArgumentList,
+    name: &str,
+) -> Result<(String, SqlParams), FruError> {
+    let mut args = args.args.into_iter();
+
+    let Some((None, FruValue::String(sql))) = args.next() else {
+        return FruError::new_res(format!("`{}` expects sql as the first argument", name));
+    };
+
+    let (named, positional): (Vec<_>, Vec<_>) = args.partition(|(ident, _)| ident.is_some());
+
+    let params = match (named.is_empty(), positional.is_empty()) {
+        (true, _) => SqlParams::Positional(
+            positional
+                .into_iter()
+                .map(|(_, value)| to_sql(value))
+                .collect::<Result<_, _>>()?,
+        ),
+
+        (false, true) => SqlParams::Named(
+            named
+                .into_iter()
+                .map(|(ident, value)| Ok((format!(":{}", ident.unwrap()), to_sql(value)?)))
+                .collect::<Result<_, FruError>>()?,
+        ),
+
+        (false, false) => {
+            return FruError::new_res(format!(
+                "`{}` expects parameters either all with names or all without",
+                name
+            ))
+        }
+    };
+
+    Ok((sql, params))
+}
+
+fn execute(statement: &mut Statement, params: &SqlParams) -> rusqlite::Result<usize> {
+    match params {
+        SqlParams::Positional(values) => statement.execute(params_from_iter(values)),
+
+        SqlParams::Named(values) => {
+            let values: Vec<(&str, &dyn ToSql)> = values
+                .iter()
+                .map(|(name, value)| (name.as_str(), value as &dyn ToSql))
+                .collect();
+            statement.execute(values.as_slice())
+        }
+    }
+}
+
+// rows are `TreeMap`s from column names to values, collected in a `Tuple`
+fn query(statement: &mut Statement, params: &SqlParams) -> Result<FruValue, FruError> {
+    let columns: Vec<String> = statement.column_names().into_iter().map(String::from).collect();
+
+    let mut rows = match params {
+        SqlParams::Positional(values) => statement.query(params_from_iter(values)),
+
+        SqlParams::Named(values) => {
+            let values: Vec<(&str, &dyn ToSql)> = values
+                .iter()
+                .map(|(name, value)| (name.as_str(), value as &dyn ToSql))
+                .collect();
+            statement.query(values.as_slice())
+        }
+    }
+    .map_err(sqlite_error)?;
+
+    let mut result = Vec::new();
+
+    while let Some(row) = rows.next().map_err(sqlite_error)? {
+        let mut fields = Vec::new();
+
+        for (k, column) in columns.iter().enumerate() {
+            let value: Value = row.get(k).map_err(sqlite_error)?;
+            fields.push((column.clone(), from_sql(value)?));
+        }
+
+        result.push(FruTreeMap::from_fields(fields));
+    }
+
+    Ok(FruTuple::new_value(result))
+}
+
+fn open_connection<'a>(
+    connection: &'a Option<Connection>,
+    path: &str,
+) -> Result<&'a Connection, FruError> {
+    connection
+        .as_ref()
+        .ok_or_else(|| FruError::new(format!("sqlite: {:?} is closed", path)))
+}
+
+impl INativeObject for FruSqlite {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("Sqlite")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        let path = self.path.clone();
+        let connection = self.connection.clone();
+
+        let method = match ident {
+            x if x == static_ident!("query") => BuiltinFunction::new_closure(move |args| {
+                let (sql, params) = sql_and_params(args, "query")?;
+                let connection = connection.borrow();
+                let connection = open_connection(&connection, &path)?;
+                let mut statement = connection.prepare(&sql).map_err(sqlite_error)?;
+
+                query(&mut statement, &params)
+            }),
+
+            // returns the number of changed rows
+            x if x == static_ident!("execute") => BuiltinFunction::new_closure(move |args| {
+                let (sql, params) = sql_and_params(args, "execute")?;
+                let connection = connection.borrow();
+                let connection = open_connection(&connection, &path)?;
+                let mut statement = connection.prepare(&sql).map_err(sqlite_error)?;
+
+                let changed = execute(&mut statement, &params).map_err(sqlite_error)?;
+                Ok(FruValue::Number(changed as f64))
+            }),
+
+            // lets `with` close the connection, unlike dropping it reports errors
+            x if x == static_ident!("_close") => BuiltinFunction::new_closure(move |_| {
+                let connection = connection.borrow_mut().take();
+
+                if let Some(connection) = connection {
+                    connection.close().map_err(|(_, err)| sqlite_error(err))?;
+                }
+
+                Ok(FruValue::Nah)
+            }),
+
+            _ => return FruError::new_res(format!("prop `{}` not found", ident)),
+        };
+
+        Ok(method.into())
+    }
+
+    fn fmt_debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Sqlite({})", self.path)
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
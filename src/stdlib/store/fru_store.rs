@@ -0,0 +1,223 @@
+use std::{
+    any::Any, cell::RefCell, collections::BTreeMap, fmt, fs::read_to_string, io::ErrorKind, rc::Rc,
+};
+
+use macros::static_ident;
+use serde_json::Value;
+
+use crate::interpreter::{
+    error::FruError,
+    identifier::Identifier,
+    options::Capability,
+    runtime::require_capability,
+    value::fru_value::FruValue,
+    value::function::{BuiltinFunction, EvaluatedArgumentList},
+    value::native::object::{INativeObject, NativeObject},
+};
+use crate::stdlib::{
+    fs::fru_fs::write_atomic,
+    tuple::fru_tuple::{extract_tuple_items, FruTuple},
+};
+
+pub struct FruStoreType;
+
+/// Persistent map from strings to values, saved as a JSON object after every change
+pub struct FruStore {
+    path: Rc<str>,
+    // `None` once the store is closed
+    entries: Rc<RefCell<Option<BTreeMap<String, Value>>>>,
+}
+
+impl FruStoreType {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self)))
+    }
+}
+
+impl INativeObject for FruStoreType {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("StoreType")
+    }
+
+    // a missing file is an empty store, it is created on the first change
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        if ident == static_ident!("open") {
+            return Ok(BuiltinFunction::new_closure(|args| {
+                require_capability(Capability::Fs)?;
+
+                let [(None, FruValue::String(path))] = args.args.as_slice() else {
+                    return FruError::new_res("`open` expects a path as `String`");
+                };
+
+                let entries = match read_to_string(path) {
+                    Ok(text) => serde_json::from_str(&text).map_err(|err| {
+                        FruError::new(format!("cannot load store {:?}: {}", path, err))
+                    })?,
+                    Err(err) if err.kind() == ErrorKind::NotFound => BTreeMap::new(),
+                    Err(err) => {
+                        return FruError::new_res(format!("cannot load store {:?}: {}", path, err))
+                    }
+                };
+
+                Ok(FruValue::NativeObject(NativeObject::new(Rc::new(
+                    FruStore {
+                        path: path.as_str().into(),
+                        entries: Rc::new(RefCell::new(Some(entries))),
+                    },
+                ))))
+            })
+            .into());
+        }
+
+        FruError::new_res(format!("prop `{}` not found", ident))
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+fn to_json(value: &FruValue) -> Result<Value, FruError> {
+    Ok(match value {
+        FruValue::Nah => Value::Null,
+        FruValue::Bool(b) => Value::Bool(*b),
+        // integers are written without `.0`, for people reading the file
+        FruValue::Number(n) if n.fract() == 0.0 && n.abs() < 2f64.powi(53) => Value::from(*n as i64),
+        FruValue::Number(n) => match serde_json::Number::from_f64(*n) {
+            Some(n) => Value::Number(n),
+            None => return FruError::new_res(format!("{} cannot be saved in `Store`", n)),
+        },
+        FruValue::String(s) => Value::String(s.clone()),
+
+        other => match extract_tuple_items(other) {
+            Some(items) => Value::Array(items.iter().map(to_json).collect::<Result<_, _>>()?),
+            None => {
+                return FruError::new_res(format!(
+                    "`{}` cannot be saved in `Store`, only `nah`, `Bool`, `Number`, `String` and `Tuple` can",
+                    other.get_type_identifier()
+                ))
+            }
+        },
+    })
+}
+
+// objects written to the file by hand are not turned into values
+fn from_json(value: &Value) -> Result<FruValue, FruError> {
+    Ok(match value {
+        Value::Null => FruValue::Nah,
+        Value::Bool(b) => FruValue::Bool(*b),
+        Value::Number(n) => FruValue::Number(n.as_f64().unwrap_or(f64::NAN)),
+        Value::String(s) => FruValue::String(s.clone()),
+        Value::Array(items) => {
+            FruTuple::new_value(items.iter().map(from_json).collect::<Result<_, _>>()?)
+        }
+        Value::Object(_) => return FruError::new_res("`Store` values cannot be JSON objects"),
+    })
+}
+
+fn key_argument<'a>(
+    args: &'a EvaluatedArgumentList,
+    name: &str,
+    count: usize,
+) -> Result<&'a str, FruError> {
+    match args.args.as_slice() {
+        [(None, FruValue::String(key)), rest @ ..] if rest.len() + 1 == count => Ok(key),
+        _ => FruError::new_res(format!("`{}` expects a key as `String`", name)),
+    }
+}
+
+fn open_entries<'a>(
+    entries: &'a mut Option<BTreeMap<String, Value>>,
+    path: &str,
+) -> Result<&'a mut BTreeMap<String, Value>, FruError> {
+    entries
+        .as_mut()
+        .ok_or_else(|| FruError::new(format!("store {:?} is closed", path)))
+}
+
+fn save(path: &str, entries: &BTreeMap<String, Value>) -> Result<(), FruError> {
+    let text = serde_json::to_string_pretty(entries).expect("JSON values are always serializable");
+    write_atomic(path, &text)
+}
+
+impl INativeObject for FruStore {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("Store")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        let path = self.path.clone();
+        let entries = self.entries.clone();
+
+        let method = match ident {
+            // `nah` for missing keys
+            x if x == static_ident!("get") => BuiltinFunction::new_closure(move |args| {
+                let key = key_argument(&args, "get", 1)?;
+                let mut entries = entries.borrow_mut();
+                open_entries(&mut entries, &path)?.get(key).map_or(Ok(FruValue::Nah), from_json)
+            }),
+
+            x if x == static_ident!("set") => BuiltinFunction::new_closure(move |args| {
+                let key = key_argument(&args, "set", 2)?;
+                let value = to_json(&args.args[1].1)?;
+
+                let mut entries = entries.borrow_mut();
+                let entries = open_entries(&mut entries, &path)?;
+                entries.insert(key.to_string(), value);
+                save(&path, entries)?;
+
+                Ok(FruValue::Nah)
+            }),
+
+            // returns whether the key was there
+            x if x == static_ident!("delete") => BuiltinFunction::new_closure(move |args| {
+                let key = key_argument(&args, "delete", 1)?;
+
+                let mut entries = entries.borrow_mut();
+                let entries = open_entries(&mut entries, &path)?;
+                let removed = entries.remove(key).is_some();
+
+                if removed {
+                    save(&path, entries)?;
+                }
+
+                Ok(FruValue::Bool(removed))
+            }),
+
+            x if x == static_ident!("keys") => BuiltinFunction::new_closure(move |_| {
+                let mut entries = entries.borrow_mut();
+                let keys = open_entries(&mut entries, &path)?.keys();
+
+                Ok(FruTuple::new_value(
+                    keys.map(|key| FruValue::String(key.clone())).collect(),
+                ))
+            }),
+
+            // every change is already saved, the store just can't be used after that
+            x if x == static_ident!("_close") => BuiltinFunction::new_closure(move |_| {
+                entries.borrow_mut().take();
+                Ok(FruValue::Nah)
+            }),
+
+            _ => return FruError::new_res(format!("prop `{}` not found", ident)),
+        };
+
+        Ok(method.into())
+    }
+
+    fn fmt_debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Store({})", self.path)
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
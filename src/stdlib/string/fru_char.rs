@@ -0,0 +1,231 @@
+use std::{any::Any, cmp::Ordering, fmt, rc::Rc};
+
+use macros::static_ident;
+
+use crate::interpreter::{
+    builtins::operators::unexpected_operand,
+    error::FruError,
+    identifier::{id, Identifier, OperatorIdentifier},
+    value::fru_value::{FruValue, TOpBuiltin},
+    value::function::{BuiltinFunction, EvaluatedArgumentList},
+    value::native::object::{INativeObject, NativeObject},
+    value::operator::AnyOperator,
+};
+
+const CHAR: Identifier = static_ident!("Char");
+
+pub struct FruCharType;
+
+/// Single unicode scalar value, what indexing and iterating a `String` gives
+#[derive(Clone, Copy)]
+pub struct FruChar {
+    value: char,
+}
+
+impl FruCharType {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self)))
+    }
+}
+
+impl INativeObject for FruCharType {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("CharType")
+    }
+
+    fn call(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+        match args.args.as_slice() {
+            [(None, FruValue::String(s))] => {
+                let mut chars = s.chars();
+
+                match (chars.next(), chars.next()) {
+                    (Some(value), None) => Ok(FruChar::new_value(value)),
+                    _ => FruError::new_res(format!(
+                        "`Char` expects a string of 1 character, got \"{}\"",
+                        s
+                    )),
+                }
+            }
+
+            _ => FruError::new_res("`Char` expects 1 `String`"),
+        }
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        if ident == static_ident!("from_code") {
+            return Ok(
+                BuiltinFunction::new_closure(|args| match args.args.as_slice() {
+                    [(None, FruValue::Number(n))] if n.fract() == 0.0 && *n >= 0.0 => {
+                        match char::from_u32(*n as u32) {
+                            Some(value) => Ok(FruChar::new_value(value)),
+                            None => {
+                                FruError::new_res(format!("{} is not a unicode scalar value", n))
+                            }
+                        }
+                    }
+
+                    _ => FruError::new_res("`from_code` expects a non-negative integer"),
+                })
+                .into(),
+            );
+        }
+
+        FruError::new_res(format!("prop `{}` not found", ident))
+    }
+
+    fn operators(&self) -> Vec<(OperatorIdentifier, AnyOperator)> {
+        char_operators()
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+impl FruChar {
+    pub fn new_value(value: char) -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self { value })))
+    }
+}
+
+impl INativeObject for FruChar {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        CHAR
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        let value = self.value;
+
+        let method = match ident {
+            x if x == static_ident!("code") => {
+                BuiltinFunction::new_closure(move |_| Ok(FruValue::Number(value as u32 as f64)))
+            }
+
+            // any unicode digit or letter, not only ascii
+            x if x == static_ident!("is_digit") => {
+                BuiltinFunction::new_closure(move |_| Ok(FruValue::Bool(value.is_numeric())))
+            }
+
+            x if x == static_ident!("is_alpha") => {
+                BuiltinFunction::new_closure(move |_| Ok(FruValue::Bool(value.is_alphabetic())))
+            }
+
+            _ => return FruError::new_res(format!("prop `{}` not found", ident)),
+        };
+
+        Ok(method.into())
+    }
+
+    fn fmt_debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+
+    fn freeze(&self) -> Option<Vec<FruValue>> {
+        Some(Vec::new())
+    }
+
+    fn is_frozen(&self) -> bool {
+        true
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+/// Comparison of chars by code, and `<>` with strings
+fn char_operators() -> Vec<(OperatorIdentifier, AnyOperator)> {
+    let comparisons: [(Identifier, TOpBuiltin); 6] = [
+        (id::LESS, char_less),
+        (id::LESS_EQ, char_less_eq),
+        (id::GREATER, char_greater),
+        (id::GREATER_EQ, char_greater_eq),
+        (id::EQ, char_eq),
+        (id::NOT_EQ, char_not_eq),
+    ];
+
+    let mut result: Vec<_> = comparisons
+        .into_iter()
+        .map(|(op, function)| {
+            (
+                OperatorIdentifier::new(op, CHAR, CHAR),
+                AnyOperator::BuiltinOperator(function),
+            )
+        })
+        .collect();
+
+    for (left, right) in [(CHAR, CHAR), (CHAR, id::STRING), (id::STRING, CHAR)] {
+        result.push((
+            OperatorIdentifier::new(id::COMBINE, left, right),
+            AnyOperator::BuiltinOperator(char_combine),
+        ));
+    }
+
+    result
+}
+
+fn text(value: FruValue) -> Result<String, FruError> {
+    match value {
+        FruValue::String(s) => Ok(s),
+        other => Ok(code(other)?.to_string()),
+    }
+}
+
+fn code(value: FruValue) -> Result<char, FruError> {
+    match extract_char(&value) {
+        Some(c) => Ok(c),
+        None => unexpected_operand(&value),
+    }
+}
+
+fn compare(
+    left: FruValue,
+    right: FruValue,
+    predicate: fn(Ordering) -> bool,
+) -> Result<FruValue, FruError> {
+    Ok(FruValue::Bool(predicate(code(left)?.cmp(&code(right)?))))
+}
+
+fn char_less(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    compare(left, right, Ordering::is_lt)
+}
+
+fn char_less_eq(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    compare(left, right, Ordering::is_le)
+}
+
+fn char_greater(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    compare(left, right, Ordering::is_gt)
+}
+
+fn char_greater_eq(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    compare(left, right, Ordering::is_ge)
+}
+
+fn char_eq(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    compare(left, right, Ordering::is_eq)
+}
+
+fn char_not_eq(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    compare(left, right, Ordering::is_ne)
+}
+
+fn char_combine(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    Ok(FruValue::String(text(left)? + &text(right)?))
+}
+
+pub fn extract_char(v: &FruValue) -> Option<char> {
+    if let FruValue::NativeObject(o) = v {
+        o.downcast::<FruChar>().map(|x| x.value)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,153 @@
+use std::rc::Rc;
+
+use caseless::{canonical_caseless_match_str, default_case_fold_str};
+use macros::static_ident;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::interpreter::{
+    error::FruError,
+    identifier::Identifier,
+    value::fru_value::FruValue,
+    value::function::{BuiltinFunction, EvaluatedArgumentList},
+};
+use crate::stdlib::string::fru_char::FruChar;
+
+/// Props of `String` values, strings are indexed by characters, not bytes
+pub fn string_prop(s: &str, ident: Identifier) -> Result<FruValue, FruError> {
+    if ident == static_ident!("len") {
+        return Ok(FruValue::Number(s.chars().count() as f64));
+    }
+
+    let s: Rc<str> = s.into();
+
+    let method = match ident {
+        x if x == static_ident!("get") => {
+            BuiltinFunction::new_closure(move |args| match args.args.as_slice() {
+                [(None, FruValue::Number(k))] if *k >= 0.0 && k.fract() == 0.0 => {
+                    match s.chars().nth(*k as usize) {
+                        Some(c) => Ok(FruChar::new_value(c)),
+                        None => FruError::new_res(format!(
+                            "index {} is out of `String` of length {}",
+                            k,
+                            s.chars().count()
+                        )),
+                    }
+                }
+
+                [(None, other)] => FruError::new_res(format!(
+                    "index of `String` must be an integer from 0, got {:?}",
+                    other
+                )),
+
+                _ => FruError::new_res("`get` expects 1 argument"),
+            })
+        }
+
+        x if x == static_ident!("each") => {
+            BuiltinFunction::new_closure(move |args| match args.args.as_slice() {
+                [(None, function)] => {
+                    for c in s.chars() {
+                        function.call(EvaluatedArgumentList {
+                            args: vec![(None, FruChar::new_value(c))],
+                        })?;
+                    }
+
+                    Ok(FruValue::Nah)
+                }
+
+                _ => FruError::new_res("`each` expects 1 argument"),
+            })
+        }
+
+        // `"NFC"`, `"NFD"`, `"NFKC"` or `"NFKD"`, so the same text is made of the same characters
+        x if x == static_ident!("normalize") => BuiltinFunction::new_closure(move |args| {
+            match args.args.as_slice() {
+                [(None, FruValue::String(form))] => Ok(FruValue::String(match form.as_str() {
+                    "NFC" => s.nfc().collect(),
+                    "NFD" => s.nfd().collect(),
+                    "NFKC" => s.nfkc().collect(),
+                    "NFKD" => s.nfkd().collect(),
+                    other => {
+                        return FruError::new_res(format!(
+                            "unknown normalization form \"{}\", expected \"NFC\", \"NFD\", \"NFKC\" or \"NFKD\"",
+                            other
+                        ))
+                    }
+                })),
+
+                _ => FruError::new_res("`normalize` expects a form as `String`"),
+            }
+        }),
+
+        // lowercase for comparisons, not for display: `ß` becomes `ss`
+        x if x == static_ident!("case_fold") => {
+            BuiltinFunction::new_closure(move |args| match args.args.as_slice() {
+                [] => Ok(FruValue::String(default_case_fold_str(&s))),
+                _ => FruError::new_res("`case_fold` expects no arguments"),
+            })
+        }
+
+        // the same rules in every locale, composed and decomposed accents are equal too
+        x if x == static_ident!("equals_ignore_case") => {
+            BuiltinFunction::new_closure(move |args| match args.args.as_slice() {
+                [(None, FruValue::String(other))] => {
+                    Ok(FruValue::Bool(canonical_caseless_match_str(&s, other)))
+                }
+
+                _ => FruError::new_res("`equals_ignore_case` expects 1 `String`"),
+            })
+        }
+
+        _ => return FruError::new_res(format!("prop `{}` not found in `String`", ident)),
+    };
+
+    Ok(method.into())
+}
+
+/// Text of a multi-line literal without the indentation of the code around it:
+/// the common leading whitespace of non-blank lines is removed, along with the line break
+/// right after the opening quote and the indentation before the closing one
+pub fn b_dedent(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    let text = match args.args.as_slice() {
+        [(None, FruValue::String(text))] => text,
+
+        [(None, other)] => {
+            return FruError::new_res(format!(
+                "`dedent` expects `String`, got `{}`",
+                other.get_type_identifier()
+            ))
+        }
+
+        _ => return FruError::new_res("`dedent` expects 1 argument"),
+    };
+
+    let text = text.strip_prefix('\n').unwrap_or(text);
+
+    let mut lines: Vec<&str> = text.split('\n').collect();
+
+    if let Some(last) = lines.last_mut() {
+        if last.trim().is_empty() {
+            *last = "";
+        }
+    }
+
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start_matches([' ', '\t']).len())
+        .min()
+        .unwrap_or(0);
+
+    let lines: Vec<&str> = lines
+        .into_iter()
+        .map(|line| {
+            if line.trim().is_empty() {
+                ""
+            } else {
+                &line[indent..]
+            }
+        })
+        .collect();
+
+    Ok(FruValue::String(lines.join("\n")))
+}
@@ -0,0 +1,205 @@
+use std::{any::Any, fmt, rc::Rc};
+
+use macros::static_ident;
+
+use crate::interpreter::{
+    error::FruError,
+    identifier::Identifier,
+    value::fru_value::FruValue,
+    value::function::{BuiltinFunction, EvaluatedArgumentList},
+    value::native::object::{INativeObject, NativeObject},
+};
+use crate::stdlib::primitive::fru_primitive::to_string;
+
+pub struct FruTemplateType;
+
+/// Text with `{name}` placeholders, parsed once and rendered any number of times
+pub struct FruTemplate {
+    source: Rc<str>,
+    parts: Rc<[Part]>,
+}
+
+enum Part {
+    Text(String),
+    Value(Identifier),
+}
+
+impl FruTemplateType {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self)))
+    }
+}
+
+impl INativeObject for FruTemplateType {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("TemplateType")
+    }
+
+    fn call(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+        match args.args.as_slice() {
+            [(None, FruValue::String(source))] => Ok(FruValue::NativeObject(NativeObject::new(
+                Rc::new(FruTemplate {
+                    source: source.as_str().into(),
+                    parts: parse(source)?.into(),
+                }),
+            ))),
+
+            _ => FruError::new_res("`Template` expects 1 `String`"),
+        }
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+// `{{` and `}}` are literal braces
+fn parse(source: &str) -> Result<Vec<Part>, FruError> {
+    let mut parts = Vec::new();
+    let mut text = String::new();
+    let mut chars = source.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                text.push('{');
+            }
+
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                text.push('}');
+            }
+
+            '{' => {
+                let mut name = String::new();
+                let mut is_closed = false;
+
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        is_closed = true;
+                        break;
+                    }
+                    name.push(c);
+                }
+
+                if !is_closed {
+                    return FruError::new_res(format!(
+                        "invalid template: `{{{}` is not closed, write `{{{{` for a brace",
+                        name
+                    ));
+                }
+
+                let name = name.trim();
+
+                if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                    return FruError::new_res(format!(
+                        "invalid template: `{{{}}}` is not a placeholder, write a name like `{{name}}`",
+                        name
+                    ));
+                }
+
+                if !text.is_empty() {
+                    parts.push(Part::Text(std::mem::take(&mut text)));
+                }
+                parts.push(Part::Value(Identifier::new(name)));
+            }
+
+            '}' => {
+                return FruError::new_res("invalid template: unmatched `}`, write `}}` for a brace")
+            }
+
+            c => text.push(c),
+        }
+    }
+
+    if !text.is_empty() {
+        parts.push(Part::Text(text));
+    }
+
+    Ok(parts)
+}
+
+impl FruTemplate {
+    // values are given by name, or as one value that has them as props, like an object or a scope
+    fn render(parts: &[Part], args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+        let source = match args.args.as_slice() {
+            [(None, source)] => Some(source.clone()),
+            args if args.iter().all(|(ident, _)| ident.is_some()) => None,
+            _ => return FruError::new_res("`render` expects named values or 1 value with props"),
+        };
+
+        let mut result = String::new();
+
+        for part in parts {
+            match part {
+                Part::Text(text) => result.push_str(text),
+
+                Part::Value(ident) => {
+                    let value = match &source {
+                        Some(source) => source.get_prop(*ident),
+                        None => args
+                            .args
+                            .iter()
+                            .find(|(name, _)| *name == Some(*ident))
+                            .map(|(_, value)| value.clone())
+                            .ok_or_else(|| FruError::new(String::new())),
+                    };
+
+                    let value = value.map_err(|_| {
+                        FruError::new(format!("value of `{{{}}}` is not given", ident))
+                    })?;
+
+                    result.push_str(&to_string(value)?);
+                }
+            }
+        }
+
+        Ok(FruValue::String(result))
+    }
+}
+
+impl INativeObject for FruTemplate {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("Template")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        if ident == static_ident!("render") {
+            let parts = self.parts.clone();
+            return Ok(
+                BuiltinFunction::new_closure(move |args| FruTemplate::render(&parts, args)).into(),
+            );
+        }
+
+        if ident == static_ident!("source") {
+            return Ok(FruValue::String(self.source.to_string()));
+        }
+
+        FruError::new_res(format!("prop `{}` not found", ident))
+    }
+
+    fn fmt_debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Template({:?})", self.source)
+    }
+
+    fn freeze(&self) -> Option<Vec<FruValue>> {
+        Some(Vec::new())
+    }
+
+    fn is_frozen(&self) -> bool {
+        true
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
@@ -0,0 +1,3 @@
+pub mod fru_char;
+pub mod fru_string;
+pub mod fru_template;
@@ -0,0 +1,171 @@
+use std::{
+    any::Any,
+    env::consts::{FAMILY, OS},
+    rc::Rc,
+};
+
+use macros::static_ident;
+
+use crate::interpreter::{
+    error::FruError,
+    identifier::Identifier,
+    options::Capability,
+    runtime::{require_capability, Runtime},
+    value::fru_value::FruValue,
+    value::function::{BuiltinFunction, EvaluatedArgumentList},
+    value::native::object::{INativeObject, NativeObject},
+};
+use crate::stdlib::prelude::fru_prelude::Prelude;
+
+/// `Sys` namespace, facts about the platform and the interpreter
+pub struct FruSys;
+
+impl FruSys {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self)))
+    }
+}
+
+fn no_arguments(args: EvaluatedArgumentList, name: &str) -> Result<(), FruError> {
+    if args.args.is_empty() {
+        Ok(())
+    } else {
+        FruError::new_res(format!("`{}` expects no arguments", name))
+    }
+}
+
+impl INativeObject for FruSys {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("Sys")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        let function = match ident {
+            // `linux`, `macos`, `windows` and so on
+            x if x == static_ident!("platform") => BuiltinFunction::new_closure(|args| {
+                no_arguments(args, "platform")?;
+                Ok(FruValue::String(OS.to_string()))
+            }),
+
+            x if x == static_ident!("version") => BuiltinFunction::new_closure(|args| {
+                no_arguments(args, "version")?;
+                Ok(FruValue::String(env!("CARGO_PKG_VERSION").to_string()))
+            }),
+
+            _ => return FruError::new_res(format!("prop `{}` not found", ident)),
+        };
+
+        Ok(function.into())
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+// builtins that a feature needs, embedders may leave them out of the prelude
+const FEATURES: [(&str, &[&str]); 7] = [
+    ("compression", &["Gzip", "Zip"]),
+    ("coroutines", &["spawn", "yield_now", "await"]),
+    ("locale", &["Locale"]),
+    ("macros", &["macro", "quote", "unquote"]),
+    ("regex", &["Regex"]),
+    ("sqlite", &["Sqlite"]),
+    ("workers", &["Worker"]),
+];
+
+const CAPABILITIES: [(&str, Capability); 7] = [
+    ("fs", Capability::Fs),
+    ("net", Capability::Net),
+    ("env", Capability::Env),
+    ("process", Capability::Process),
+    ("time", Capability::Time),
+    ("random", Capability::Random),
+    ("stdin", Capability::Stdin),
+];
+
+/// `has_feature(name)`, unknown features are reported as missing, so scripts can check for anything
+pub fn b_has_feature(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    let name = match args.args.as_slice() {
+        [(None, FruValue::String(name))] => name.as_str(),
+        _ => return FruError::new_res("`has_feature` expects 1 `String`"),
+    };
+
+    if let Some((_, capability)) = CAPABILITIES.iter().find(|(x, _)| *x == name) {
+        return Ok(FruValue::Bool(require_capability(*capability).is_ok()));
+    }
+
+    let Some((_, builtins)) = FEATURES.iter().find(|(x, _)| *x == name) else {
+        return Ok(FruValue::Bool(false));
+    };
+
+    // without a running interpreter the standard prelude is assumed
+    let scope = Runtime::current().and_then(|runtime| runtime.global_scope());
+
+    let available = builtins.iter().all(|builtin| match &scope {
+        Some(scope) => scope.get_builtin(Identifier::new(builtin)).is_some(),
+        None => Prelude::standard().has(builtin),
+    });
+
+    Ok(FruValue::Bool(available))
+}
+
+/// Whether a `@cfg(...)` condition holds on this platform: a platform like `windows` or `linux`,
+/// a family like `unix`, or `not(x)`, `any(x, y, ...)` and `all(x, y, ...)` of them
+pub fn cfg_matches(condition: &str) -> Result<bool, String> {
+    let condition = condition.trim();
+
+    if let Some((operator, rest)) = condition.split_once('(') {
+        let inner = rest
+            .strip_suffix(')')
+            .ok_or_else(|| format!("unclosed parenthesis in `{}`", condition))?;
+
+        let operands = split_operands(inner)
+            .into_iter()
+            .map(cfg_matches)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        return match (operator.trim(), operands.as_slice()) {
+            ("not", [operand]) => Ok(!operand),
+            ("not", _) => Err("`not` expects 1 condition".to_string()),
+            ("any", _) => Ok(operands.contains(&true)),
+            ("all", _) => Ok(!operands.contains(&false)),
+            (other, _) => Err(format!("unknown `@cfg` operator `{}`", other)),
+        };
+    }
+
+    if condition.is_empty() || !condition.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return Err(format!("invalid `@cfg` condition `{}`", condition));
+    }
+
+    Ok(condition == OS || condition == FAMILY)
+}
+
+// commas that are not inside of nested parentheses
+fn split_operands(inner: &str) -> Vec<&str> {
+    let mut operands = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (k, c) in inner.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                operands.push(&inner[start..k]);
+                start = k + 1;
+            }
+            _ => {}
+        }
+    }
+
+    if !inner[start..].trim().is_empty() {
+        operands.push(&inner[start..]);
+    }
+
+    operands
+}
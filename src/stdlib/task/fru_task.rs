@@ -0,0 +1,74 @@
+use std::{any::Any, rc::Rc};
+
+use macros::static_ident;
+
+use crate::interpreter::{
+    error::FruError,
+    identifier::Identifier,
+    runtime::current_runtime,
+    scheduler::Task,
+    value::fru_value::FruValue,
+    value::function::{BuiltinFunction, EvaluatedArgumentList},
+    value::native::object::{INativeObject, NativeObject},
+};
+
+pub struct FruTask {
+    task: Rc<Task>,
+}
+
+impl FruTask {
+    pub fn new_value(task: Rc<Task>) -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self { task })))
+    }
+}
+
+impl INativeObject for FruTask {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("Task")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        if ident == static_ident!("join") {
+            let task = self.task.clone();
+            return Ok(BuiltinFunction::new_closure(move |_| {
+                task.join(current_runtime()?.scheduler())
+            })
+            .into());
+        }
+
+        if ident == static_ident!("is_done") {
+            return Ok(FruValue::Bool(self.task.is_done()));
+        }
+
+        FruError::new_res(format!("prop `{}` not found", ident))
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+pub fn b_spawn(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    if args.args.len() != 1 {
+        return FruError::new_res(format!(
+            "`spawn` expects 1 argument, got {}",
+            args.args.len()
+        ));
+    }
+
+    let function = args.args[0].1.clone();
+
+    Ok(FruTask::new_value(
+        current_runtime()?.scheduler().spawn(function),
+    ))
+}
+
+pub fn b_yield_now(_: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    current_runtime()?.scheduler().yield_now()?;
+
+    Ok(FruValue::Nah)
+}
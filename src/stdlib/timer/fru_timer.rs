@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use macros::{derive_nat, static_ident};
+
+use crate::interpreter::{
+    error::FruError,
+    identifier::Identifier,
+    options::Capability,
+    runtime::{current_runtime, require_capability},
+    scheduler::TimerHandle,
+    value::fru_value::FruValue,
+    value::function::{BuiltinFunction, EvaluatedArgumentList},
+};
+
+#[derive_nat("TimerType", methods = Self::method)]
+pub struct FruTimerType;
+
+#[derive_nat("Timer", methods = Self::method)]
+pub struct FruTimer {
+    #[fru(skip)]
+    handle: TimerHandle,
+}
+
+impl FruTimerType {
+    pub fn new_value() -> FruValue {
+        Self.into_value()
+    }
+
+    fn method(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        if ident == static_ident!("after") {
+            return Ok(BuiltinFunction::new(b_timer_after).into());
+        }
+
+        if ident == static_ident!("every") {
+            return Ok(BuiltinFunction::new(b_timer_every).into());
+        }
+
+        FruError::new_res(format!("prop `{}` not found", ident))
+    }
+}
+
+impl FruTimer {
+    pub fn new_value(handle: TimerHandle) -> FruValue {
+        Self { handle }.into_value()
+    }
+
+    fn method(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        // `_close` lets `with` cancel the timer
+        if ident == static_ident!("cancel") || ident == static_ident!("_close") {
+            let handle = self.handle.clone();
+            return Ok(BuiltinFunction::new_closure(move |_| {
+                handle.cancel();
+                Ok(FruValue::Nah)
+            })
+            .into());
+        }
+
+        if ident == static_ident!("is_active") {
+            return Ok(FruValue::Bool(self.handle.is_active()));
+        }
+
+        FruError::new_res(format!("prop `{}` not found", ident))
+    }
+}
+
+fn to_duration(value: &FruValue) -> Result<Duration, FruError> {
+    match value {
+        FruValue::Number(ms) if *ms >= 0.0 && ms.is_finite() => {
+            Ok(Duration::from_secs_f64(ms / 1000.0))
+        }
+
+        FruValue::Number(_) => FruError::new_res("duration must be a non-negative number"),
+
+        other => FruError::new_res(format!(
+            "expected `Number` of milliseconds, got `{}`",
+            other.get_type_identifier()
+        )),
+    }
+}
+
+fn add_timer(name: &str, args: EvaluatedArgumentList, repeat: bool) -> Result<FruValue, FruError> {
+    require_capability(Capability::Time)?;
+
+    let (delay, function) = match args.args.as_slice() {
+        [(None, delay), (None, function)] => (to_duration(delay)?, function.clone()),
+        _ => return FruError::new_res(format!("`{}` expects 2 arguments", name)),
+    };
+
+    let interval = if repeat {
+        if delay.is_zero() {
+            return FruError::new_res("`Timer.every` interval must be positive");
+        }
+        Some(delay)
+    } else {
+        None
+    };
+
+    let handle = current_runtime()?.scheduler().add_timer(delay, interval, function);
+
+    Ok(FruTimer::new_value(handle))
+}
+
+fn b_timer_after(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    add_timer("Timer.after", args, false)
+}
+
+fn b_timer_every(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    add_timer("Timer.every", args, true)
+}
+
+pub fn b_sleep(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    require_capability(Capability::Time)?;
+
+    let duration = match args.args.as_slice() {
+        [(None, ms)] => to_duration(ms)?,
+        _ => return FruError::new_res("`sleep` expects 1 argument"),
+    };
+
+    current_runtime()?.scheduler().sleep(duration)?;
+
+    Ok(FruValue::Nah)
+}
@@ -0,0 +1,513 @@
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    ops::Bound,
+    rc::Rc,
+};
+
+use macros::static_ident;
+
+use crate::interpreter::{
+    error::FruError,
+    identifier::Identifier,
+    value::fru_value::FruValue,
+    value::function::{BuiltinFunction, EvaluatedArgumentList},
+    value::native::object::{check_mutable, Contents, INativeObject, NativeObject},
+};
+
+pub struct FruTreeMapType;
+
+pub struct FruTreeSetType;
+
+/// Map with keys kept in order
+pub struct FruTreeMap {
+    entries: Rc<RefCell<BTreeMap<TreeKey, FruValue>>>,
+    frozen: Rc<Cell<bool>>,
+}
+
+/// Set with items kept in order
+pub struct FruTreeSet {
+    items: Rc<RefCell<BTreeSet<TreeKey>>>,
+    frozen: Rc<Cell<bool>>,
+}
+
+/// Key that can be ordered: bools go before numbers and numbers go before strings
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum TreeKey {
+    Bool(bool),
+    Number(OrderedNumber),
+    String(String),
+}
+
+// NaN is never a key, so numbers are totally ordered
+#[derive(Clone, Copy, PartialEq)]
+struct OrderedNumber(f64);
+
+impl Eq for OrderedNumber {}
+
+impl PartialOrd for OrderedNumber {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrderedNumber {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.partial_cmp(&other.0).expect("NaN is never a key")
+    }
+}
+
+impl TreeKey {
+    fn new(value: &FruValue) -> Result<TreeKey, FruError> {
+        match value {
+            FruValue::Bool(b) => Ok(TreeKey::Bool(*b)),
+            FruValue::Number(n) if n.is_nan() => FruError::new_res("NaN cannot be a key"),
+            // -0 and 0 are the same key
+            FruValue::Number(n) => Ok(TreeKey::Number(OrderedNumber(*n + 0.0))),
+            FruValue::String(s) => Ok(TreeKey::String(s.clone())),
+            other => FruError::new_res(format!(
+                "key must be `Bool`, `Number` or `String`, got `{}`",
+                other.get_type_identifier()
+            )),
+        }
+    }
+
+    fn to_value(&self) -> FruValue {
+        match self {
+            TreeKey::Bool(b) => FruValue::Bool(*b),
+            TreeKey::Number(n) => FruValue::Number(n.0),
+            TreeKey::String(s) => FruValue::String(s.clone()),
+        }
+    }
+}
+
+// `nah` bound means the range is not limited on that side
+fn range_bounds(
+    args: &EvaluatedArgumentList,
+) -> Result<(Bound<TreeKey>, Bound<TreeKey>), FruError> {
+    let bound = |value: &FruValue, inclusive: bool| match value {
+        FruValue::Nah => Ok(Bound::Unbounded),
+        value if inclusive => TreeKey::new(value).map(Bound::Included),
+        value => TreeKey::new(value).map(Bound::Excluded),
+    };
+
+    match args.args.as_slice() {
+        [(None, from), (None, to)] => {
+            let bounds = (bound(from, true)?, bound(to, false)?);
+
+            // BTreeMap::range panics on reversed bounds
+            if let (Bound::Included(from), Bound::Excluded(to)) = &bounds {
+                if from > to {
+                    return Ok((Bound::Included(to.clone()), Bound::Excluded(to.clone())));
+                }
+            }
+
+            Ok(bounds)
+        }
+
+        _ => FruError::new_res("`range` expects 2 arguments"),
+    }
+}
+
+fn single_key(args: &EvaluatedArgumentList, name: &str) -> Result<TreeKey, FruError> {
+    match args.args.as_slice() {
+        [(None, key)] => TreeKey::new(key),
+        _ => FruError::new_res(format!("`{}` expects 1 argument", name)),
+    }
+}
+
+fn callback(args: &EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    match args.args.as_slice() {
+        [(None, function)] => Ok(function.clone()),
+        _ => FruError::new_res("`each` expects 1 argument"),
+    }
+}
+
+fn key_or_nah(key: Option<&TreeKey>) -> FruValue {
+    key.map_or(FruValue::Nah, TreeKey::to_value)
+}
+
+impl FruTreeMapType {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self)))
+    }
+}
+
+impl INativeObject for FruTreeMapType {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("TreeMapType")
+    }
+
+    fn instantiate(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+        if !args.args.is_empty() {
+            return FruError::new_res("`TreeMap` does not take arguments");
+        }
+
+        Ok(FruTreeMap::new_value(BTreeMap::new()))
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+impl FruTreeMap {
+    fn new_value(entries: BTreeMap<TreeKey, FruValue>) -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self {
+            entries: Rc::new(RefCell::new(entries)),
+            frozen: Rc::new(Cell::new(false)),
+        })))
+    }
+
+    /// Map with string keys, for natives that return records
+    pub fn from_fields(fields: impl IntoIterator<Item = (String, FruValue)>) -> FruValue {
+        Self::new_value(
+            fields.into_iter().map(|(key, value)| (TreeKey::String(key), value)).collect(),
+        )
+    }
+
+    /// Map with the given entries, keys must be `Bool`, `Number` or `String`
+    pub fn from_entries(
+        entries: impl IntoIterator<Item = (FruValue, FruValue)>,
+    ) -> Result<FruValue, FruError> {
+        let entries = (entries.into_iter())
+            .map(|(key, value)| Ok((TreeKey::new(&key)?, value)))
+            .collect::<Result<_, FruError>>()?;
+
+        Ok(Self::new_value(entries))
+    }
+
+    /// Entries of a map in the order of keys, `None` for other values
+    pub fn extract_entries(value: &FruValue) -> Option<Vec<(FruValue, FruValue)>> {
+        let FruValue::NativeObject(object) = value else {
+            return None;
+        };
+
+        let entries = object.downcast::<FruTreeMap>()?.entries.borrow();
+
+        Some(entries.iter().map(|(key, value)| (key.to_value(), value.clone())).collect())
+    }
+
+    /// Entries of a map with only string keys, `None` for other values
+    pub fn extract_fields(value: &FruValue) -> Option<Vec<(String, FruValue)>> {
+        let FruValue::NativeObject(object) = value else {
+            return None;
+        };
+
+        (object.downcast::<FruTreeMap>()?.entries.borrow().iter())
+            .map(|(key, value)| match key {
+                TreeKey::String(key) => Some((key.clone(), value.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl INativeObject for FruTreeMap {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("TreeMap")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        let entries = self.entries.clone();
+        let frozen = self.frozen.clone();
+
+        let method = match ident {
+            x if x == static_ident!("len") => {
+                return Ok(FruValue::Number(self.entries.borrow().len() as f64));
+            }
+
+            x if x == static_ident!("set") => {
+                BuiltinFunction::new_closure(move |args| match args.args.as_slice() {
+                    [(None, key), (None, value)] => {
+                        check_mutable(&frozen, "TreeMap", "set")?;
+                        entries.borrow_mut().insert(TreeKey::new(key)?, value.clone());
+                        Ok(FruValue::Nah)
+                    }
+
+                    _ => FruError::new_res("`set` expects 2 arguments"),
+                })
+            }
+
+            x if x == static_ident!("get") => BuiltinFunction::new_closure(move |args| {
+                let key = single_key(&args, "get")?;
+                Ok(entries.borrow().get(&key).cloned().unwrap_or(FruValue::Nah))
+            }),
+
+            x if x == static_ident!("has") => BuiltinFunction::new_closure(move |args| {
+                let key = single_key(&args, "has")?;
+                Ok(FruValue::Bool(entries.borrow().contains_key(&key)))
+            }),
+
+            x if x == static_ident!("remove") => BuiltinFunction::new_closure(move |args| {
+                check_mutable(&frozen, "TreeMap", "remove")?;
+                let key = single_key(&args, "remove")?;
+                Ok(entries.borrow_mut().remove(&key).unwrap_or(FruValue::Nah))
+            }),
+
+            x if x == static_ident!("first") => BuiltinFunction::new_closure(move |_| {
+                Ok(key_or_nah(entries.borrow().keys().next()))
+            }),
+
+            x if x == static_ident!("last") => BuiltinFunction::new_closure(move |_| {
+                Ok(key_or_nah(entries.borrow().keys().next_back()))
+            }),
+
+            x if x == static_ident!("range") => BuiltinFunction::new_closure(move |args| {
+                let bounds = range_bounds(&args)?;
+
+                let entries = entries
+                    .borrow()
+                    .range(bounds)
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect();
+
+                Ok(FruTreeMap::new_value(entries))
+            }),
+
+            x if x == static_ident!("each") => BuiltinFunction::new_closure(move |args| {
+                let function = callback(&args)?;
+
+                // the callback may change the map, so it iterates over a snapshot
+                let snapshot: Vec<_> = entries
+                    .borrow()
+                    .iter()
+                    .map(|(key, value)| (key.to_value(), value.clone()))
+                    .collect();
+
+                for (key, value) in snapshot {
+                    function.call(EvaluatedArgumentList {
+                        args: vec![(None, key), (None, value)],
+                    })?;
+                }
+
+                Ok(FruValue::Nah)
+            }),
+
+            _ => return FruError::new_res(format!("prop `{}` not found", ident)),
+        };
+
+        Ok(method.into())
+    }
+
+    fn fmt_debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TreeMap{{")?;
+
+        for (k, (key, value)) in self.entries.borrow().iter().enumerate() {
+            if k > 0 {
+                write!(f, ", ")?;
+            }
+
+            write!(f, "{:?}: {:?}", key.to_value(), value)?;
+        }
+
+        write!(f, "}}")
+    }
+
+    fn contents(&self) -> Option<Contents> {
+        Some(Contents {
+            open: "TreeMap{",
+            close: "}",
+            items: (self.entries.borrow().iter())
+                .map(|(key, value)| (Some(key.to_value()), value.clone()))
+                .collect(),
+        })
+    }
+
+    // keys are primitive, only values need freezing
+    fn freeze(&self) -> Option<Vec<FruValue>> {
+        self.frozen.set(true);
+        Some(self.entries.borrow().values().cloned().collect())
+    }
+
+    fn is_frozen(&self) -> bool {
+        self.frozen.get()
+    }
+
+    // keys are primitive, only values are copied
+    fn copy_with(
+        &self,
+        copy_item: &mut dyn FnMut(FruValue) -> Result<FruValue, FruError>,
+    ) -> Option<Result<FruValue, FruError>> {
+        let entries = self.entries.borrow().clone();
+
+        Some(
+            (entries.into_iter())
+                .map(|(key, value)| Ok((key, copy_item(value)?)))
+                .collect::<Result<_, _>>()
+                .map(FruTreeMap::new_value),
+        )
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+impl FruTreeSetType {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self)))
+    }
+}
+
+impl INativeObject for FruTreeSetType {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("TreeSetType")
+    }
+
+    fn instantiate(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+        if args.args.iter().any(|(ident, _)| ident.is_some()) {
+            return FruError::new_res("`TreeSet` expects items without names");
+        }
+
+        let items = args
+            .args
+            .iter()
+            .map(|(_, value)| TreeKey::new(value))
+            .collect::<Result<_, _>>()?;
+
+        Ok(FruTreeSet::new_value(items))
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+impl FruTreeSet {
+    fn new_value(items: BTreeSet<TreeKey>) -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self {
+            items: Rc::new(RefCell::new(items)),
+            frozen: Rc::new(Cell::new(false)),
+        })))
+    }
+}
+
+impl INativeObject for FruTreeSet {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("TreeSet")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        let items = self.items.clone();
+        let frozen = self.frozen.clone();
+
+        let method = match ident {
+            x if x == static_ident!("len") => {
+                return Ok(FruValue::Number(self.items.borrow().len() as f64));
+            }
+
+            x if x == static_ident!("add") => BuiltinFunction::new_closure(move |args| {
+                check_mutable(&frozen, "TreeSet", "add")?;
+                let item = single_key(&args, "add")?;
+                Ok(FruValue::Bool(items.borrow_mut().insert(item)))
+            }),
+
+            x if x == static_ident!("has") => BuiltinFunction::new_closure(move |args| {
+                let item = single_key(&args, "has")?;
+                Ok(FruValue::Bool(items.borrow().contains(&item)))
+            }),
+
+            x if x == static_ident!("remove") => BuiltinFunction::new_closure(move |args| {
+                check_mutable(&frozen, "TreeSet", "remove")?;
+                let item = single_key(&args, "remove")?;
+                Ok(FruValue::Bool(items.borrow_mut().remove(&item)))
+            }),
+
+            x if x == static_ident!("first") => {
+                BuiltinFunction::new_closure(move |_| Ok(key_or_nah(items.borrow().first())))
+            }
+
+            x if x == static_ident!("last") => {
+                BuiltinFunction::new_closure(move |_| Ok(key_or_nah(items.borrow().last())))
+            }
+
+            x if x == static_ident!("range") => BuiltinFunction::new_closure(move |args| {
+                let bounds = range_bounds(&args)?;
+                Ok(FruTreeSet::new_value(
+                    items.borrow().range(bounds).cloned().collect(),
+                ))
+            }),
+
+            x if x == static_ident!("each") => BuiltinFunction::new_closure(move |args| {
+                let function = callback(&args)?;
+
+                // the callback may change the set, so it iterates over a snapshot
+                let snapshot: Vec<_> = items.borrow().iter().map(TreeKey::to_value).collect();
+
+                for item in snapshot {
+                    function.call(EvaluatedArgumentList {
+                        args: vec![(None, item)],
+                    })?;
+                }
+
+                Ok(FruValue::Nah)
+            }),
+
+            _ => return FruError::new_res(format!("prop `{}` not found", ident)),
+        };
+
+        Ok(method.into())
+    }
+
+    fn fmt_debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TreeSet{{")?;
+
+        for (k, item) in self.items.borrow().iter().enumerate() {
+            if k > 0 {
+                write!(f, ", ")?;
+            }
+
+            write!(f, "{:?}", item.to_value())?;
+        }
+
+        write!(f, "}}")
+    }
+
+    fn contents(&self) -> Option<Contents> {
+        Some(Contents {
+            open: "TreeSet{",
+            close: "}",
+            items: self.items.borrow().iter().map(|item| (None, item.to_value())).collect(),
+        })
+    }
+
+    fn freeze(&self) -> Option<Vec<FruValue>> {
+        self.frozen.set(true);
+        Some(Vec::new())
+    }
+
+    fn is_frozen(&self) -> bool {
+        self.frozen.get()
+    }
+
+    fn copy_with(
+        &self,
+        _copy_item: &mut dyn FnMut(FruValue) -> Result<FruValue, FruError>,
+    ) -> Option<Result<FruValue, FruError>> {
+        Some(Ok(FruTreeSet::new_value(self.items.borrow().clone())))
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
@@ -0,0 +1,180 @@
+use std::{any::Any, fmt, rc::Rc};
+
+use macros::static_ident;
+
+use crate::interpreter::{
+    error::FruError,
+    identifier::Identifier,
+    value::fru_value::FruValue,
+    value::function::{BuiltinFunction, EvaluatedArgumentList},
+    value::native::object::{Contents, INativeObject, NativeObject},
+};
+
+pub struct FruTupleType;
+
+/// Fixed group of values, a way to return several values without declaring a type
+pub struct FruTuple {
+    items: Rc<[FruValue]>,
+}
+
+impl FruTupleType {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self)))
+    }
+
+    fn make(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+        if args.args.iter().any(|(ident, _)| ident.is_some()) {
+            return FruError::new_res("`Tuple` expects items without names");
+        }
+
+        Ok(FruTuple::new_value(
+            args.args.into_iter().map(|(_, value)| value).collect(),
+        ))
+    }
+}
+
+impl FruTuple {
+    pub fn new_value(items: Vec<FruValue>) -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self {
+            items: items.into(),
+        })))
+    }
+}
+
+impl INativeObject for FruTupleType {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("TupleType")
+    }
+
+    // both `Tuple(1, 2)` and `Tuple:{ 1, 2 }` work
+    fn call(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+        Self::make(args)
+    }
+
+    fn instantiate(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+        Self::make(args)
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+impl INativeObject for FruTuple {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("Tuple")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        let items = self.items.clone();
+
+        let method = match ident {
+            x if x == static_ident!("len") => {
+                return Ok(FruValue::Number(self.items.len() as f64));
+            }
+
+            x if x == static_ident!("get") => {
+                BuiltinFunction::new_closure(move |args| match args.args.as_slice() {
+                    [(None, FruValue::Number(k))]
+                        if *k >= 0.0 && k.fract() == 0.0 && (*k as usize) < items.len() =>
+                    {
+                        Ok(items[*k as usize].clone())
+                    }
+
+                    [(None, other)] => FruError::new_res(format!(
+                        "index of `Tuple` of length {} must be an integer from 0, got {:?}",
+                        items.len(),
+                        other
+                    )),
+
+                    _ => FruError::new_res("`get` expects 1 argument"),
+                })
+            }
+
+            // `t.unpack(fn(a, b) { ... })` passes the items as arguments
+            x if x == static_ident!("unpack") => {
+                BuiltinFunction::new_closure(move |args| match args.args.as_slice() {
+                    [(None, function)] => function.call(EvaluatedArgumentList {
+                        args: items.iter().map(|item| (None, item.clone())).collect(),
+                    }),
+
+                    _ => FruError::new_res("`unpack` expects 1 argument"),
+                })
+            }
+
+            _ => return FruError::new_res(format!("prop `{}` not found", ident)),
+        };
+
+        Ok(method.into())
+    }
+
+    fn list_props(&self) -> Vec<Identifier> {
+        vec![static_ident!("len")]
+    }
+
+    fn fru_eq(&self, other: &dyn INativeObject) -> Option<bool> {
+        let other = other.as_any().downcast_ref::<FruTuple>()?;
+        Some(self.items == other.items)
+    }
+
+    fn fmt_debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(")?;
+
+        for (k, item) in self.items.iter().enumerate() {
+            if k > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{:?}", item)?;
+        }
+
+        write!(f, ")")
+    }
+
+    fn contents(&self) -> Option<Contents> {
+        Some(Contents {
+            open: "(",
+            close: ")",
+            items: self.items.iter().map(|item| (None, item.clone())).collect(),
+        })
+    }
+
+    // items are frozen, the tuple itself can't be changed anyway
+    fn freeze(&self) -> Option<Vec<FruValue>> {
+        Some(self.items.to_vec())
+    }
+
+    fn is_frozen(&self) -> bool {
+        true
+    }
+
+    fn copy_with(
+        &self,
+        copy_item: &mut dyn FnMut(FruValue) -> Result<FruValue, FruError>,
+    ) -> Option<Result<FruValue, FruError>> {
+        Some(
+            (self.items.iter().cloned().map(copy_item))
+                .collect::<Result<_, _>>()
+                .map(FruTuple::new_value),
+        )
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+pub fn extract_tuple_items(v: &FruValue) -> Option<Rc<[FruValue]>> {
+    if let FruValue::NativeObject(o) = v {
+        o.downcast::<FruTuple>().map(|x| x.items.clone())
+    } else {
+        None
+    }
+}
@@ -0,0 +1,397 @@
+use std::{any::Any, fmt, rc::Rc};
+
+use macros::{derive_nat, static_ident};
+
+use crate::interpreter::{
+    builtins::operators::unexpected_operand,
+    error::FruError,
+    identifier::{id, Identifier, OperatorIdentifier},
+    value::fru_value::{FruValue, TOpBuiltin},
+    value::function::{BuiltinFunction, EvaluatedArgumentList},
+    value::native::object::{INativeObject, NativeObject},
+    value::operator::AnyOperator,
+};
+
+const QUANTITY: Identifier = static_ident!("Quantity");
+
+// exponents of length, mass, time, current and temperature
+type Dimension = [i8; 5];
+
+// symbol, name, size in SI units, dimension
+const STANDARD_UNITS: [(&str, &str, f64, Dimension); 18] = [
+    ("m", "metre", 1.0, [1, 0, 0, 0, 0]),
+    ("mm", "millimetre", 0.001, [1, 0, 0, 0, 0]),
+    ("cm", "centimetre", 0.01, [1, 0, 0, 0, 0]),
+    ("km", "kilometre", 1000.0, [1, 0, 0, 0, 0]),
+    ("g", "gram", 0.001, [0, 1, 0, 0, 0]),
+    ("kg", "kilogram", 1.0, [0, 1, 0, 0, 0]),
+    ("t", "tonne", 1000.0, [0, 1, 0, 0, 0]),
+    ("s", "second", 1.0, [0, 0, 1, 0, 0]),
+    ("ms", "millisecond", 0.001, [0, 0, 1, 0, 0]),
+    ("min", "minute", 60.0, [0, 0, 1, 0, 0]),
+    ("h", "hour", 3600.0, [0, 0, 1, 0, 0]),
+    ("A", "ampere", 1.0, [0, 0, 0, 1, 0]),
+    ("K", "kelvin", 1.0, [0, 0, 0, 0, 1]),
+    ("Hz", "hertz", 1.0, [0, 0, -1, 0, 0]),
+    ("N", "newton", 1.0, [1, 1, -2, 0, 0]),
+    ("J", "joule", 1.0, [2, 1, -2, 0, 0]),
+    ("W", "watt", 1.0, [2, 1, -3, 0, 0]),
+    ("V", "volt", 1.0, [2, 1, -3, -1, 0]),
+];
+
+pub struct FruUnitType;
+
+struct NamedUnit {
+    symbol: Rc<str>,
+    size: f64,
+    dimension: Dimension,
+}
+
+/// Product of named units in integer powers, like `km/h`
+#[derive(Clone)]
+struct Unit {
+    parts: Vec<(Rc<NamedUnit>, i32)>,
+}
+
+#[derive_nat("Quantity", immutable, methods = Self::method, eq = Self::same_quantity, debug = Self::fmt_quantity)]
+#[derive(Clone)]
+pub struct FruQuantity {
+    #[fru(readonly)]
+    amount: f64,
+    #[fru(skip)]
+    unit: Unit,
+}
+
+impl FruUnitType {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self)))
+    }
+}
+
+impl INativeObject for FruUnitType {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("UnitType")
+    }
+
+    // `Unit:{ "in", 2.54 * Unit.cm }` defines a new unit
+    fn instantiate(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+        match args.args.as_slice() {
+            [(None, FruValue::String(symbol)), (None, FruValue::NativeObject(definition))] => {
+                let definition = match definition.downcast::<FruQuantity>() {
+                    Some(definition) => definition,
+                    None => return FruError::new_res("`Unit` expects a symbol and a quantity"),
+                };
+
+                let unit = NamedUnit {
+                    symbol: Rc::from(symbol.as_str()),
+                    size: definition.si_amount(),
+                    dimension: definition.unit.dimension(),
+                };
+
+                Ok(FruQuantity::new_value(1.0, Unit::named(Rc::new(unit))))
+            }
+
+            _ => FruError::new_res("`Unit` expects a symbol and a quantity"),
+        }
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        for (symbol, name, size, dimension) in STANDARD_UNITS {
+            if ident == Identifier::new(symbol) || ident == Identifier::new(name) {
+                let unit = NamedUnit {
+                    symbol: Rc::from(symbol),
+                    size,
+                    dimension,
+                };
+
+                return Ok(FruQuantity::new_value(1.0, Unit::named(Rc::new(unit))));
+            }
+        }
+
+        FruError::new_res(format!("prop `{}` not found", ident))
+    }
+
+    fn operators(&self) -> Vec<(OperatorIdentifier, AnyOperator)> {
+        quantity_operators()
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+impl Unit {
+    fn named(unit: Rc<NamedUnit>) -> Self {
+        Self {
+            parts: vec![(unit, 1)],
+        }
+    }
+
+    fn size(&self) -> f64 {
+        self.parts.iter().map(|(unit, power)| unit.size.powi(*power)).product()
+    }
+
+    fn dimension(&self) -> Dimension {
+        let mut dimension = [0; 5];
+
+        for (unit, power) in &self.parts {
+            for (total, own) in dimension.iter_mut().zip(unit.dimension) {
+                *total += own * *power as i8;
+            }
+        }
+
+        dimension
+    }
+
+    // parts with the same symbol are merged, so `m * m` is `m^2` and `m / m` is nothing
+    fn combine(&self, other: &Unit, sign: i32) -> Unit {
+        let mut parts = self.parts.clone();
+
+        for (unit, power) in &other.parts {
+            match parts.iter_mut().find(|(own, _)| own.symbol == unit.symbol) {
+                Some((_, own)) => *own += sign * power,
+                None => parts.push((unit.clone(), sign * power)),
+            }
+        }
+
+        parts.retain(|(_, power)| *power != 0);
+
+        Unit { parts }
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let part = |unit: &NamedUnit, power: i32| match power {
+            1 => unit.symbol.to_string(),
+            _ => format!("{}^{}", unit.symbol, power),
+        };
+
+        let numerator: Vec<String> = (self.parts.iter())
+            .filter(|(_, power)| *power > 0)
+            .map(|(unit, power)| part(unit, *power))
+            .collect();
+
+        let denominator: Vec<String> = (self.parts.iter())
+            .filter(|(_, power)| *power < 0)
+            .map(|(unit, power)| part(unit, -power))
+            .collect();
+
+        match (numerator.as_slice(), denominator.as_slice()) {
+            (numerator, []) => write!(f, "{}", numerator.join("*")),
+            ([], [single]) => write!(f, "1/{}", single),
+            ([], denominator) => write!(f, "1/({})", denominator.join("*")),
+            (numerator, [single]) => write!(f, "{}/{}", numerator.join("*"), single),
+            (numerator, denominator) => {
+                write!(f, "{}/({})", numerator.join("*"), denominator.join("*"))
+            }
+        }
+    }
+}
+
+impl FruQuantity {
+    // quantities without dimension are plain numbers
+    fn new_value(amount: f64, unit: Unit) -> FruValue {
+        if unit.dimension() == [0; 5] {
+            FruValue::Number(amount * unit.size())
+        } else {
+            Self { amount, unit }.into_value()
+        }
+    }
+
+    fn si_amount(&self) -> f64 {
+        self.amount * self.unit.size()
+    }
+
+    // amount of the same quantity in another unit
+    fn amount_in(&self, unit: &Unit) -> Option<f64> {
+        if self.unit.dimension() == unit.dimension() {
+            Some(self.si_amount() / unit.size())
+        } else {
+            None
+        }
+    }
+
+    fn method(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        let this = self.clone();
+
+        match ident {
+            x if x == static_ident!("unit") => Ok(Self::new_value(1.0, this.unit)),
+
+            x if x == static_ident!("to") => Ok(BuiltinFunction::new_closure(
+                move |args| match args.args.as_slice() {
+                    [(None, FruValue::NativeObject(target))] => match target.downcast::<Self>() {
+                        Some(target) => {
+                            let amount = this.amount_in(&target.unit).ok_or_else(|| {
+                                FruError::new(format!(
+                                    "cannot convert `{}` to `{}`",
+                                    this.unit, target.unit
+                                ))
+                            })?;
+
+                            Ok(Self::new_value(amount / target.amount, target.unit.clone()))
+                        }
+
+                        None => FruError::new_res("`to` expects a unit"),
+                    },
+
+                    _ => FruError::new_res("`to` expects a unit"),
+                },
+            )
+            .into()),
+
+            _ => FruError::new_res(format!("prop `{}` not found", ident)),
+        }
+    }
+
+    fn same_quantity(&self, other: &dyn INativeObject) -> Option<bool> {
+        let other = other.as_any().downcast_ref::<Self>()?;
+
+        Some(self.equals(other))
+    }
+
+    // quantities of different dimensions are never equal
+    fn equals(&self, other: &Self) -> bool {
+        self.unit.dimension() == other.unit.dimension() && self.si_amount() == other.si_amount()
+    }
+
+    fn fmt_quantity(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.amount, self.unit)
+    }
+}
+
+/// Arithmetic and comparison of quantities, `+`, `-`, `<` and others need operands of the same dimension
+fn quantity_operators() -> Vec<(OperatorIdentifier, AnyOperator)> {
+    let mut operators: Vec<(Identifier, Identifier, Identifier, TOpBuiltin)> = vec![
+        (id::PLUS, QUANTITY, QUANTITY, quantity_plus),
+        (id::MINUS, QUANTITY, QUANTITY, quantity_minus),
+        (id::EQ, QUANTITY, QUANTITY, quantity_eq),
+        (id::NOT_EQ, QUANTITY, QUANTITY, quantity_not_eq),
+        (id::LESS, QUANTITY, QUANTITY, quantity_less),
+        (id::LESS_EQ, QUANTITY, QUANTITY, quantity_less_eq),
+        (id::GREATER, QUANTITY, QUANTITY, quantity_greater),
+        (id::GREATER_EQ, QUANTITY, QUANTITY, quantity_greater_eq),
+    ];
+
+    for (left, right) in [
+        (QUANTITY, QUANTITY),
+        (QUANTITY, id::NUMBER),
+        (id::NUMBER, QUANTITY),
+    ] {
+        operators.push((id::MULTIPLY, left, right, quantity_mul));
+        operators.push((id::DIVIDE, left, right, quantity_div));
+    }
+
+    operators
+        .into_iter()
+        .map(|(op, left, right, function)| {
+            (
+                OperatorIdentifier::new(op, left, right),
+                AnyOperator::BuiltinOperator(function),
+            )
+        })
+        .collect()
+}
+
+// numbers are quantities without unit
+fn quantity(value: FruValue) -> Result<FruQuantity, FruError> {
+    match &value {
+        FruValue::Number(amount) => Ok(FruQuantity {
+            amount: *amount,
+            unit: Unit { parts: Vec::new() },
+        }),
+
+        FruValue::NativeObject(obj) => match obj.downcast::<FruQuantity>() {
+            Some(quantity) => Ok(quantity.clone()),
+            None => unexpected_operand(&value),
+        },
+
+        _ => unexpected_operand(&value),
+    }
+}
+
+// amount of the right operand in the unit of the left one
+fn right_amount(l: &FruQuantity, r: &FruQuantity, action: &str) -> Result<f64, FruError> {
+    r.amount_in(&l.unit).ok_or_else(|| {
+        FruError::new(format!(
+            "cannot {} `{}` and `{}`, their dimensions differ",
+            action, l.unit, r.unit
+        ))
+    })
+}
+
+// the result is in the unit of the left operand
+fn quantity_plus(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    let (l, r) = (quantity(left)?, quantity(right)?);
+    let amount = l.amount + right_amount(&l, &r, "add")?;
+
+    Ok(FruQuantity::new_value(amount, l.unit))
+}
+
+fn quantity_minus(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    let (l, r) = (quantity(left)?, quantity(right)?);
+    let amount = l.amount - right_amount(&l, &r, "subtract")?;
+
+    Ok(FruQuantity::new_value(amount, l.unit))
+}
+
+fn quantity_mul(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    let (l, r) = (quantity(left)?, quantity(right)?);
+
+    Ok(FruQuantity::new_value(
+        l.amount * r.amount,
+        l.unit.combine(&r.unit, 1),
+    ))
+}
+
+fn quantity_div(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    let (l, r) = (quantity(left)?, quantity(right)?);
+
+    if r.amount == 0.0 {
+        return FruError::new_res("division by zero");
+    }
+
+    Ok(FruQuantity::new_value(
+        l.amount / r.amount,
+        l.unit.combine(&r.unit, -1),
+    ))
+}
+
+fn compare(
+    left: FruValue,
+    right: FruValue,
+    check: fn(f64, f64) -> bool,
+) -> Result<FruValue, FruError> {
+    let (l, r) = (quantity(left)?, quantity(right)?);
+    let right_amount = right_amount(&l, &r, "compare")?;
+
+    Ok(FruValue::Bool(check(l.amount, right_amount)))
+}
+
+fn quantity_eq(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    Ok(FruValue::Bool(quantity(left)?.equals(&quantity(right)?)))
+}
+
+fn quantity_not_eq(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    Ok(FruValue::Bool(!quantity(left)?.equals(&quantity(right)?)))
+}
+
+fn quantity_less(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    compare(left, right, |l, r| l < r)
+}
+
+fn quantity_less_eq(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    compare(left, right, |l, r| l <= r)
+}
+
+fn quantity_greater(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    compare(left, right, |l, r| l > r)
+}
+
+fn quantity_greater_eq(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    compare(left, right, |l, r| l >= r)
+}
@@ -0,0 +1,653 @@
+use std::{any::Any, fmt, rc::Rc};
+
+use macros::static_ident;
+
+use crate::interpreter::{
+    builtins::operators::unexpected_operand,
+    error::FruError,
+    identifier::{id, Identifier, OperatorIdentifier},
+    value::fru_value::{FruValue, TOpBuiltin},
+    value::function::{BuiltinFunction, EvaluatedArgumentList},
+    value::native::object::{INativeObject, NativeObject},
+    value::operator::AnyOperator,
+};
+
+const VEC2: Identifier = static_ident!("Vec2");
+const VEC3: Identifier = static_ident!("Vec3");
+const MAT3: Identifier = static_ident!("Mat3");
+
+/// Type object of `Vec2` or `Vec3`
+pub struct FruVecType<const N: usize>;
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct FruVec<const N: usize> {
+    items: [f64; N],
+}
+
+pub struct FruMat3Type;
+
+/// 3x3 matrix, rows are stored one after another
+#[derive(Clone, Copy, PartialEq)]
+pub struct FruMat3 {
+    items: [[f64; 3]; 3],
+}
+
+impl<const N: usize> FruVecType<N> {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self)))
+    }
+}
+
+impl<const N: usize> INativeObject for FruVecType<N> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        match N {
+            2 => static_ident!("Vec2Type"),
+            _ => static_ident!("Vec3Type"),
+        }
+    }
+
+    fn instantiate(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+        let mut items = [None; N];
+
+        for (k, (ident, value)) in args.args.iter().enumerate() {
+            let index = match ident {
+                None => k,
+                Some(ident) => COMPONENTS[..N].iter().position(|x| x == ident).unwrap_or(N),
+            };
+
+            match items.get_mut(index) {
+                Some(slot) => *slot = Some(number(value)?),
+                None => return self.wrong_arguments(),
+            }
+        }
+
+        match items.iter().copied().collect::<Option<Vec<_>>>() {
+            Some(items) => Ok(FruVec::<N>::new_value(
+                items.try_into().expect("there are N components"),
+            )),
+            None => self.wrong_arguments(),
+        }
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        match ident {
+            x if x == static_ident!("zero") => Ok(FruVec::new_value([0.0; N])),
+            _ => FruError::new_res(format!("prop `{}` not found", ident)),
+        }
+    }
+
+    fn operators(&self) -> Vec<(OperatorIdentifier, AnyOperator)> {
+        operators_with(vec_ident::<N>())
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+impl<const N: usize> FruVecType<N> {
+    fn wrong_arguments<T>(&self) -> Result<T, FruError> {
+        let names: Vec<_> = COMPONENTS[..N].iter().map(|x| format!("`{}`", x)).collect();
+
+        FruError::new_res(format!(
+            "`{}` expects {} arguments",
+            vec_ident::<N>(),
+            names.join(", ")
+        ))
+    }
+}
+
+const COMPONENTS: [Identifier; 3] = [static_ident!("x"), static_ident!("y"), static_ident!("z")];
+
+fn vec_ident<const N: usize>() -> Identifier {
+    match N {
+        2 => VEC2,
+        _ => VEC3,
+    }
+}
+
+impl<const N: usize> FruVec<N> {
+    pub fn new_value(items: [f64; N]) -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self { items })))
+    }
+
+    fn map(self, f: impl Fn(f64) -> f64) -> Self {
+        Self {
+            items: self.items.map(f),
+        }
+    }
+
+    fn zip(self, other: Self, f: impl Fn(f64, f64) -> f64) -> Self {
+        let mut items = self.items;
+
+        for (item, other) in items.iter_mut().zip(other.items) {
+            *item = f(*item, other);
+        }
+
+        Self { items }
+    }
+
+    fn dot(self, other: Self) -> f64 {
+        self.items.iter().zip(other.items).map(|(a, b)| a * b).sum()
+    }
+
+    fn length(self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    fn normalize(self) -> Result<Self, FruError> {
+        let length = self.length();
+
+        if length == 0.0 {
+            return FruError::new_res("cannot normalize a zero vector");
+        }
+
+        Ok(self.map(|x| x / length))
+    }
+
+    fn lerp(self, other: Self, t: f64) -> Self {
+        self.zip(other, |a, b| a + (b - a) * t)
+    }
+
+    fn into_value(self) -> FruValue {
+        Self::new_value(self.items)
+    }
+}
+
+impl<const N: usize> INativeObject for FruVec<N> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        vec_ident::<N>()
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        if let Some(k) = COMPONENTS[..N].iter().position(|x| *x == ident) {
+            return Ok(FruValue::Number(self.items[k]));
+        }
+
+        let this = *self;
+
+        let method = match ident {
+            x if x == static_ident!("dot") => {
+                BuiltinFunction::new_closure(move |args| match args.args.as_slice() {
+                    [(None, other)] => Ok(FruValue::Number(this.dot(vec::<N>(other)?))),
+                    _ => FruError::new_res("`dot` expects 1 argument"),
+                })
+            }
+
+            x if x == static_ident!("cross") => {
+                BuiltinFunction::new_closure(move |args| match args.args.as_slice() {
+                    [(None, other)] => this.cross(vec::<N>(other)?),
+                    _ => FruError::new_res("`cross` expects 1 argument"),
+                })
+            }
+
+            x if x == static_ident!("length") => {
+                BuiltinFunction::new_closure(move |_| Ok(FruValue::Number(this.length())))
+            }
+
+            x if x == static_ident!("normalize") => {
+                BuiltinFunction::new_closure(move |_| Ok(this.normalize()?.into_value()))
+            }
+
+            x if x == static_ident!("lerp") => {
+                BuiltinFunction::new_closure(move |args| match args.args.as_slice() {
+                    [(None, other), (None, t)] => {
+                        Ok(this.lerp(vec::<N>(other)?, number(t)?).into_value())
+                    }
+                    _ => FruError::new_res("`lerp` expects 2 arguments"),
+                })
+            }
+
+            _ => return FruError::new_res(format!("prop `{}` not found", ident)),
+        };
+
+        Ok(method.into())
+    }
+
+    fn fmt_debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}(", vec_ident::<N>())?;
+
+        for (k, item) in self.items.iter().enumerate() {
+            if k > 0 {
+                write!(f, ", ")?;
+            }
+
+            write!(f, "{}", item)?;
+        }
+
+        write!(f, ")")
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+impl<const N: usize> FruVec<N> {
+    // cross product of plane vectors is the z of the cross product of them in space
+    fn cross(self, other: Self) -> Result<FruValue, FruError> {
+        match (self.items.as_slice(), other.items.as_slice()) {
+            ([ax, ay], [bx, by]) => Ok(FruValue::Number(ax * by - ay * bx)),
+
+            ([ax, ay, az], [bx, by, bz]) => Ok(FruVec::new_value([
+                ay * bz - az * by,
+                az * bx - ax * bz,
+                ax * by - ay * bx,
+            ])),
+
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl FruMat3Type {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self)))
+    }
+}
+
+impl INativeObject for FruMat3Type {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("Mat3Type")
+    }
+
+    fn instantiate(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+        if args.args.len() != 9 || args.args.iter().any(|(ident, _)| ident.is_some()) {
+            return FruError::new_res("`Mat3` expects 9 numbers, row by row");
+        }
+
+        let mut items = [[0.0; 3]; 3];
+
+        for (k, (_, value)) in args.args.iter().enumerate() {
+            items[k / 3][k % 3] = number(value)?;
+        }
+
+        Ok(FruMat3 { items }.into_value())
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        let function: fn(EvaluatedArgumentList) -> Result<FruValue, FruError> = match ident {
+            x if x == static_ident!("identity") => return Ok(FruMat3::IDENTITY.into_value()),
+
+            x if x == static_ident!("translation") => |args| {
+                let [x, y] = numbers(args, "translation")?;
+
+                Ok(FruMat3 {
+                    items: [[1.0, 0.0, x], [0.0, 1.0, y], [0.0, 0.0, 1.0]],
+                }
+                .into_value())
+            },
+
+            x if x == static_ident!("rotation") => |args| {
+                let [angle] = numbers(args, "rotation")?;
+                let (sin, cos) = angle.sin_cos();
+
+                Ok(FruMat3 {
+                    items: [[cos, -sin, 0.0], [sin, cos, 0.0], [0.0, 0.0, 1.0]],
+                }
+                .into_value())
+            },
+
+            x if x == static_ident!("scale") => |args| {
+                let [x, y] = numbers(args, "scale")?;
+
+                Ok(FruMat3 {
+                    items: [[x, 0.0, 0.0], [0.0, y, 0.0], [0.0, 0.0, 1.0]],
+                }
+                .into_value())
+            },
+
+            _ => return FruError::new_res(format!("prop `{}` not found", ident)),
+        };
+
+        Ok(BuiltinFunction::new(function).into())
+    }
+
+    fn operators(&self) -> Vec<(OperatorIdentifier, AnyOperator)> {
+        operators_with(MAT3)
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+impl FruMat3 {
+    const IDENTITY: FruMat3 = FruMat3 {
+        items: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+    };
+
+    fn into_value(self) -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(self)))
+    }
+
+    fn mul(self, other: Self) -> Self {
+        let mut items = [[0.0; 3]; 3];
+
+        for (i, row) in items.iter_mut().enumerate() {
+            for (j, item) in row.iter_mut().enumerate() {
+                *item = (0..3).map(|k| self.items[i][k] * other.items[k][j]).sum();
+            }
+        }
+
+        Self { items }
+    }
+
+    fn apply(self, v: [f64; 3]) -> [f64; 3] {
+        self.items.map(|row| row.iter().zip(v).map(|(a, b)| a * b).sum())
+    }
+
+    fn transpose(self) -> Self {
+        let mut items = self.items;
+
+        for (i, row) in items.iter_mut().enumerate() {
+            for (j, item) in row.iter_mut().enumerate() {
+                *item = self.items[j][i];
+            }
+        }
+
+        Self { items }
+    }
+
+    fn minor(self, i: usize, j: usize) -> f64 {
+        let rows: Vec<_> = (0..3).filter(|&r| r != i).collect();
+        let cols: Vec<_> = (0..3).filter(|&c| c != j).collect();
+
+        self.items[rows[0]][cols[0]] * self.items[rows[1]][cols[1]]
+            - self.items[rows[0]][cols[1]] * self.items[rows[1]][cols[0]]
+    }
+
+    fn determinant(self) -> f64 {
+        (0..3)
+            .map(|j| {
+                let sign = if j % 2 == 0 { 1.0 } else { -1.0 };
+                sign * self.items[0][j] * self.minor(0, j)
+            })
+            .sum()
+    }
+
+    fn inverse(self) -> Result<Self, FruError> {
+        let determinant = self.determinant();
+
+        if determinant == 0.0 {
+            return FruError::new_res("matrix is singular and has no inverse");
+        }
+
+        let mut items = [[0.0; 3]; 3];
+
+        // adjugate is the transposed matrix of cofactors
+        for (i, row) in items.iter_mut().enumerate() {
+            for (j, item) in row.iter_mut().enumerate() {
+                let sign = if (i + j) % 2 == 0 { 1.0 } else { -1.0 };
+                *item = sign * self.minor(j, i) / determinant;
+            }
+        }
+
+        Ok(Self { items })
+    }
+}
+
+impl INativeObject for FruMat3 {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        MAT3
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        let this = *self;
+
+        let method = match ident {
+            x if x == static_ident!("get") => {
+                BuiltinFunction::new_closure(move |args| match args.args.as_slice() {
+                    [(None, row), (None, column)] => {
+                        Ok(FruValue::Number(this.items[index(row)?][index(column)?]))
+                    }
+                    _ => FruError::new_res("`get` expects 2 arguments"),
+                })
+            }
+
+            x if x == static_ident!("transpose") => {
+                BuiltinFunction::new_closure(move |_| Ok(this.transpose().into_value()))
+            }
+
+            x if x == static_ident!("determinant") => {
+                BuiltinFunction::new_closure(move |_| Ok(FruValue::Number(this.determinant())))
+            }
+
+            x if x == static_ident!("inverse") => {
+                BuiltinFunction::new_closure(move |_| Ok(this.inverse()?.into_value()))
+            }
+
+            _ => return FruError::new_res(format!("prop `{}` not found", ident)),
+        };
+
+        Ok(method.into())
+    }
+
+    fn fmt_debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows: Vec<_> = self
+            .items
+            .iter()
+            .map(|row| format!("[{}, {}, {}]", row[0], row[1], row[2]))
+            .collect();
+
+        write!(f, "Mat3[{}]", rows.join(", "))
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+/// Arithmetic of vectors and matrices with each other and with numbers
+fn vector_operators() -> Vec<(OperatorIdentifier, AnyOperator)> {
+    let operators: [(Identifier, Identifier, Identifier, TOpBuiltin); 21] = [
+        (id::PLUS, VEC2, VEC2, vec_plus::<2>),
+        (id::MINUS, VEC2, VEC2, vec_minus::<2>),
+        (id::MULTIPLY, VEC2, id::NUMBER, vec_mul_num::<2>),
+        (id::MULTIPLY, id::NUMBER, VEC2, num_mul_vec::<2>),
+        (id::DIVIDE, VEC2, id::NUMBER, vec_div_num::<2>),
+        (id::EQ, VEC2, VEC2, vec_eq::<2>),
+        (id::NOT_EQ, VEC2, VEC2, vec_not_eq::<2>),
+        (id::PLUS, VEC3, VEC3, vec_plus::<3>),
+        (id::MINUS, VEC3, VEC3, vec_minus::<3>),
+        (id::MULTIPLY, VEC3, id::NUMBER, vec_mul_num::<3>),
+        (id::MULTIPLY, id::NUMBER, VEC3, num_mul_vec::<3>),
+        (id::DIVIDE, VEC3, id::NUMBER, vec_div_num::<3>),
+        (id::EQ, VEC3, VEC3, vec_eq::<3>),
+        (id::NOT_EQ, VEC3, VEC3, vec_not_eq::<3>),
+        (id::MULTIPLY, MAT3, MAT3, mat_mul_mat),
+        (id::MULTIPLY, MAT3, VEC3, mat_mul_vec3),
+        (id::MULTIPLY, MAT3, VEC2, mat_mul_vec2),
+        (id::MULTIPLY, MAT3, id::NUMBER, mat_mul_num),
+        (id::MULTIPLY, id::NUMBER, MAT3, num_mul_mat),
+        (id::EQ, MAT3, MAT3, mat_eq),
+        (id::NOT_EQ, MAT3, MAT3, mat_not_eq),
+    ];
+
+    operators
+        .into_iter()
+        .map(|(op, left, right, function)| {
+            (
+                OperatorIdentifier::new(op, left, right),
+                AnyOperator::BuiltinOperator(function),
+            )
+        })
+        .collect()
+}
+
+// operators of a type are installed with it, the ones between vectors and matrices with both
+fn operators_with(ident: Identifier) -> Vec<(OperatorIdentifier, AnyOperator)> {
+    vector_operators()
+        .into_iter()
+        .filter(|(op, _)| op.left() == ident || op.right() == ident)
+        .collect()
+}
+
+// operators are registered for these types, a type of the program with the same name can still get here
+fn as_vec<const N: usize>(value: FruValue) -> Result<FruVec<N>, FruError> {
+    match &value {
+        FruValue::NativeObject(obj) => match obj.downcast::<FruVec<N>>() {
+            Some(v) => Ok(*v),
+            None => unexpected_operand(&value),
+        },
+
+        _ => unexpected_operand(&value),
+    }
+}
+
+fn as_mat(value: FruValue) -> Result<FruMat3, FruError> {
+    match &value {
+        FruValue::NativeObject(obj) => match obj.downcast::<FruMat3>() {
+            Some(m) => Ok(*m),
+            None => unexpected_operand(&value),
+        },
+
+        _ => unexpected_operand(&value),
+    }
+}
+
+fn as_num(value: FruValue) -> Result<f64, FruError> {
+    match value {
+        FruValue::Number(n) => Ok(n),
+        other => unexpected_operand(&other),
+    }
+}
+
+fn vec_plus<const N: usize>(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    Ok(as_vec::<N>(left)?.zip(as_vec(right)?, |a, b| a + b).into_value())
+}
+
+fn vec_minus<const N: usize>(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    Ok(as_vec::<N>(left)?.zip(as_vec(right)?, |a, b| a - b).into_value())
+}
+
+fn vec_mul_num<const N: usize>(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    let k = as_num(right)?;
+    Ok(as_vec::<N>(left)?.map(|x| x * k).into_value())
+}
+
+fn num_mul_vec<const N: usize>(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    vec_mul_num::<N>(right, left)
+}
+
+fn vec_div_num<const N: usize>(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    let k = as_num(right)?;
+
+    if k == 0.0 {
+        return FruError::new_res("division by zero");
+    }
+
+    Ok(as_vec::<N>(left)?.map(|x| x / k).into_value())
+}
+
+fn vec_eq<const N: usize>(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    Ok(FruValue::Bool(as_vec::<N>(left)? == as_vec(right)?))
+}
+
+fn vec_not_eq<const N: usize>(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    Ok(FruValue::Bool(as_vec::<N>(left)? != as_vec(right)?))
+}
+
+fn mat_mul_mat(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    Ok(as_mat(left)?.mul(as_mat(right)?).into_value())
+}
+
+fn mat_mul_vec3(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    Ok(FruVec::new_value(
+        as_mat(left)?.apply(as_vec::<3>(right)?.items),
+    ))
+}
+
+// plane points are transformed in homogeneous coordinates, so translations apply to them
+fn mat_mul_vec2(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    let [x, y] = as_vec::<2>(right)?.items;
+    let [x, y, w] = as_mat(left)?.apply([x, y, 1.0]);
+
+    Ok(FruVec::new_value([x / w, y / w]))
+}
+
+fn mat_mul_num(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    let k = as_num(right)?;
+
+    Ok(FruMat3 {
+        items: as_mat(left)?.items.map(|row| row.map(|x| x * k)),
+    }
+    .into_value())
+}
+
+fn num_mul_mat(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    mat_mul_num(right, left)
+}
+
+fn mat_eq(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    Ok(FruValue::Bool(as_mat(left)? == as_mat(right)?))
+}
+
+fn mat_not_eq(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    Ok(FruValue::Bool(as_mat(left)? != as_mat(right)?))
+}
+
+fn vec<const N: usize>(value: &FruValue) -> Result<FruVec<N>, FruError> {
+    match value {
+        FruValue::NativeObject(obj) => match obj.downcast::<FruVec<N>>() {
+            Some(v) => Ok(*v),
+            None => expected(vec_ident::<N>(), value),
+        },
+
+        _ => expected(vec_ident::<N>(), value),
+    }
+}
+
+fn number(value: &FruValue) -> Result<f64, FruError> {
+    match value {
+        FruValue::Number(n) => Ok(*n),
+        _ => expected(id::NUMBER, value),
+    }
+}
+
+fn numbers<const N: usize>(args: EvaluatedArgumentList, name: &str) -> Result<[f64; N], FruError> {
+    match <[_; N]>::try_from(args.args) {
+        Ok(args) if args.iter().all(|(ident, _)| ident.is_none()) => {
+            let mut result = [0.0; N];
+
+            for (item, (_, value)) in result.iter_mut().zip(&args) {
+                *item = number(value)?;
+            }
+
+            Ok(result)
+        }
+
+        _ => FruError::new_res(format!("`{}` expects {} arguments", name, N)),
+    }
+}
+
+fn index(value: &FruValue) -> Result<usize, FruError> {
+    match value {
+        FruValue::Number(n) if n.fract() == 0.0 && (0.0..3.0).contains(n) => Ok(*n as usize),
+        _ => FruError::new_res("index of `Mat3` must be 0, 1 or 2"),
+    }
+}
+
+fn expected<T>(ident: Identifier, value: &FruValue) -> Result<T, FruError> {
+    FruError::new_res(format!(
+        "expected `{}`, got `{}`",
+        ident,
+        value.get_type_identifier()
+    ))
+}
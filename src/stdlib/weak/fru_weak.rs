@@ -0,0 +1,69 @@
+use std::{any::Any, rc::Rc};
+
+use macros::static_ident;
+
+use crate::interpreter::{
+    error::FruError,
+    identifier::Identifier,
+    value::fru_object::FruWeakObject,
+    value::fru_value::FruValue,
+    value::function::{BuiltinFunction, EvaluatedArgumentList},
+    value::native::object::{INativeObject, NativeObject},
+};
+
+/// Reference that does not keep the object alive
+pub struct FruWeak {
+    target: FruWeakObject,
+}
+
+impl FruWeak {
+    pub fn new_value(target: FruWeakObject) -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self { target })))
+    }
+}
+
+impl INativeObject for FruWeak {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("Weak")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        if ident == static_ident!("get") {
+            let target = self.target.clone();
+            return Ok(BuiltinFunction::new_closure(move |_| {
+                Ok(target.upgrade().map_or(FruValue::Nah, FruValue::Object))
+            })
+            .into());
+        }
+
+        if ident == static_ident!("is_alive") {
+            return Ok(FruValue::Bool(self.target.upgrade().is_some()));
+        }
+
+        FruError::new_res(format!("prop `{}` not found", ident))
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+pub fn b_weak(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    match args.args.as_slice() {
+        [(None, FruValue::Object(object))] => Ok(FruWeak::new_value(object.downgrade())),
+
+        [(None, value)] => FruError::new_res(format!(
+            "`weak` expects an object, got `{}`",
+            value.get_type_identifier()
+        )),
+
+        _ => FruError::new_res(format!(
+            "`weak` expects 1 argument, got {}",
+            args.args.len()
+        )),
+    }
+}
@@ -0,0 +1,47 @@
+use macros::static_ident;
+
+use crate::interpreter::{
+    error::FruError, identifier::Identifier, value::fru_value::FruValue,
+    value::function::EvaluatedArgumentList,
+};
+
+/// `with(resource, fn(r) { ... })` closes the resource however the function exits
+pub fn b_with(args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+    let (resource, body) = match args.args.as_slice() {
+        [(None, resource), (None, body)] => (resource.clone(), body.clone()),
+
+        _ => {
+            return FruError::new_res(format!(
+                "`with` expects 2 arguments, got {}",
+                args.args.len()
+            ))
+        }
+    };
+
+    let close = get_close_method(&resource)?;
+
+    let result = body.call(EvaluatedArgumentList {
+        args: vec![(None, resource)],
+    });
+
+    let closed = close.call(EvaluatedArgumentList { args: Vec::new() });
+
+    // error of the body is more important than the one of closing
+    let result = result?;
+    closed?;
+
+    Ok(result)
+}
+
+fn get_close_method(resource: &FruValue) -> Result<FruValue, FruError> {
+    for ident in [static_ident!("_close"), static_ident!("_exit")] {
+        if let Ok(method) = resource.get_prop(ident) {
+            return Ok(method);
+        }
+    }
+
+    FruError::new_res(format!(
+        "`{}` has neither `_close` nor `_exit` method",
+        resource.get_type_identifier()
+    ))
+}
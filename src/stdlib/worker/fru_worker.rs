@@ -0,0 +1,315 @@
+use std::{
+    any::Any,
+    cell::RefCell,
+    fs::read_to_string,
+    rc::Rc,
+    sync::mpsc::{channel, Receiver, Sender, TryRecvError},
+    thread::{self, JoinHandle, Thread},
+};
+
+use macros::static_ident;
+
+use crate::interpreter::{
+    error::FruError,
+    identifier::Identifier,
+    options::{Capability, InterpreterOptions},
+    runner::Interpreter,
+    runtime::{current_runtime, require_capability},
+    scheduler::Wait,
+    value::fru_value::FruValue,
+    value::function::{BuiltinFunction, EvaluatedArgumentList},
+    value::native::object::{INativeObject, NativeObject},
+    value::sendable::SendableValue,
+};
+
+pub struct FruWorkerType;
+
+/// Interpreter running on another thread, seen from the code that has spawned it
+pub struct FruWorker {
+    endpoint: Rc<Endpoint>,
+    thread: Rc<WorkerThread>,
+}
+
+type WorkerThread = RefCell<Option<JoinHandle<Result<(), FruError>>>>;
+
+/// Connection to the spawning interpreter, seen from the worker as `Parent`
+pub struct FruWorkerPort {
+    endpoint: Rc<Endpoint>,
+}
+
+struct Endpoint {
+    sender: Sender<SendableValue>,
+    receiver: Receiver<SendableValue>,
+    // message taken from `receiver` to check whether there is one, it is received first
+    peeked: RefCell<Option<SendableValue>>,
+    peer: Thread,
+}
+
+impl FruWorkerType {
+    pub fn new_value() -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self)))
+    }
+}
+
+impl INativeObject for FruWorkerType {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("WorkerType")
+    }
+
+    fn instantiate(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+        let source_code = match args.args.as_slice() {
+            [(None, FruValue::String(path))] => read_worker_file(path)?,
+
+            [(Some(ident), FruValue::String(path))] if *ident == static_ident!("path") => {
+                read_worker_file(path)?
+            }
+
+            [(Some(ident), FruValue::String(source))] if *ident == static_ident!("source") => {
+                source.clone()
+            }
+
+            _ => {
+                return FruError::new_res("`Worker` expects either `path` or `source` argument");
+            }
+        };
+
+        let options = current_runtime()?.options().clone();
+
+        Ok(FruWorker::spawn(source_code, options))
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+fn read_worker_file(path: &str) -> Result<String, FruError> {
+    require_capability(Capability::Fs)?;
+
+    read_to_string(path).map_err(|err| FruError::new(format!("Error reading file {path:?} {err}")))
+}
+
+impl FruWorker {
+    /// Worker gets its own global scope, the only way to communicate with it is messages
+    pub fn spawn(source_code: String, options: InterpreterOptions) -> FruValue {
+        let (to_worker, worker_inbox) = channel();
+        let (to_parent, parent_inbox) = channel();
+
+        let parent = thread::current();
+
+        let handle = thread::spawn(move || {
+            let interpreter = Interpreter::new(options);
+
+            interpreter.set_global(
+                "Parent",
+                FruWorkerPort::new_value(Endpoint {
+                    sender: to_parent,
+                    receiver: worker_inbox,
+                    peeked: RefCell::new(None),
+                    peer: parent.clone(),
+                }),
+            );
+
+            let result = interpreter.execute(source_code);
+
+            // parent may be waiting for this worker to finish
+            parent.unpark();
+
+            result
+        });
+
+        let endpoint = Endpoint {
+            sender: to_worker,
+            receiver: parent_inbox,
+            peeked: RefCell::new(None),
+            peer: handle.thread().clone(),
+        };
+
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self {
+            endpoint: Rc::new(endpoint),
+            thread: Rc::new(RefCell::new(Some(handle))),
+        })))
+    }
+
+    fn is_finished(&self) -> bool {
+        is_finished(&self.thread)
+    }
+
+    fn join(thread: &Rc<WorkerThread>) -> Result<FruValue, FruError> {
+        if thread.borrow().is_none() {
+            return FruError::new_res("worker is already joined");
+        }
+
+        let wait = Wait::external({
+            let thread = thread.clone();
+            move || is_finished(&thread)
+        });
+
+        current_runtime()?
+            .scheduler()
+            .wait_until(wait, || Ok(is_finished(thread).then_some(())))?;
+
+        let handle = match thread.borrow_mut().take() {
+            Some(handle) => handle,
+            None => return FruError::new_res("worker is already joined"),
+        };
+
+        match handle.join() {
+            Ok(Ok(())) => Ok(FruValue::Nah),
+            Ok(Err(err)) => FruError::new_res(format!("worker failed: {}", err)),
+            Err(_) => FruError::new_res("worker panicked"),
+        }
+    }
+}
+
+impl FruWorkerPort {
+    fn new_value(endpoint: Endpoint) -> FruValue {
+        FruValue::NativeObject(NativeObject::new(Rc::new(Self {
+            endpoint: Rc::new(endpoint),
+        })))
+    }
+}
+
+// objects get types from the global scope of the receiving interpreter
+fn receive(message: SendableValue) -> Result<FruValue, FruError> {
+    let global = (current_runtime()?.global_scope())
+        .ok_or_else(|| FruError::new("interpreter has no global scope".to_string()))?;
+
+    message.into_value(&global)
+}
+
+impl Endpoint {
+    fn send(&self, value: &FruValue) -> Result<(), FruError> {
+        let message = SendableValue::try_from(value)?;
+
+        if self.sender.send(message).is_err() {
+            return FruError::new_res("worker channel is closed");
+        }
+
+        self.peer.unpark();
+
+        Ok(())
+    }
+
+    fn try_recv(&self) -> Result<Option<FruValue>, FruError> {
+        if let Some(message) = self.peeked.borrow_mut().take() {
+            return receive(message).map(Some);
+        }
+
+        match self.receiver.try_recv() {
+            Ok(message) => receive(message).map(Some),
+            Err(TryRecvError::Empty) => Ok(None),
+            Err(TryRecvError::Disconnected) => FruError::new_res("worker channel is closed"),
+        }
+    }
+
+    // closed channel counts too, receiving from it fails
+    fn has_message(&self) -> bool {
+        let mut peeked = self.peeked.borrow_mut();
+
+        if peeked.is_none() {
+            match self.receiver.try_recv() {
+                Ok(message) => *peeked = Some(message),
+                Err(TryRecvError::Empty) => return false,
+                Err(TryRecvError::Disconnected) => return true,
+            }
+        }
+
+        true
+    }
+
+    // tasks and timers of this interpreter keep running while it waits
+    fn recv(self: &Rc<Self>) -> Result<FruValue, FruError> {
+        let wait = Wait::external({
+            let endpoint = self.clone();
+            move || endpoint.has_message()
+        });
+
+        current_runtime()?.scheduler().wait_until(wait, || self.try_recv())
+    }
+
+    fn get_method(self: &Rc<Self>, ident: Identifier) -> Option<FruValue> {
+        let endpoint = self.clone();
+
+        let method = match ident {
+            x if x == static_ident!("send") => {
+                BuiltinFunction::new_closure(move |args| match args.args.as_slice() {
+                    [(None, value)] => endpoint.send(value).map(|_| FruValue::Nah),
+                    _ => FruError::new_res("`send` expects 1 argument"),
+                })
+            }
+
+            x if x == static_ident!("recv") => {
+                BuiltinFunction::new_closure(move |_| endpoint.recv())
+            }
+
+            x if x == static_ident!("try_recv") => BuiltinFunction::new_closure(move |_| {
+                Ok(endpoint.try_recv()?.unwrap_or(FruValue::Nah))
+            }),
+
+            _ => return None,
+        };
+
+        Some(method.into())
+    }
+}
+
+impl INativeObject for FruWorker {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("Worker")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        if let Some(method) = self.endpoint.get_method(ident) {
+            return Ok(method);
+        }
+
+        match ident {
+            x if x == static_ident!("join") || x == static_ident!("_close") => {
+                let thread = self.thread.clone();
+                Ok(BuiltinFunction::new_closure(move |_| Self::join(&thread)).into())
+            }
+
+            x if x == static_ident!("is_finished") => Ok(FruValue::Bool(self.is_finished())),
+
+            _ => FruError::new_res(format!("prop `{}` not found", ident)),
+        }
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+impl INativeObject for FruWorkerPort {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        static_ident!("WorkerPort")
+    }
+
+    fn get_prop(&self, ident: Identifier) -> Result<FruValue, FruError> {
+        match self.endpoint.get_method(ident) {
+            Some(method) => Ok(method),
+            None => FruError::new_res(format!("prop `{}` not found", ident)),
+        }
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+fn is_finished(thread: &WorkerThread) -> bool {
+    thread.borrow().as_ref().is_none_or(|handle| handle.is_finished())
+}
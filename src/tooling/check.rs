@@ -0,0 +1,367 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use crate::interpreter::{
+    builtins::{functions::builtin_functions, types::builtin_types},
+    error::FruError,
+    expression::FruExpression,
+    identifier::Identifier,
+    runner::read_source_file,
+    statement::FruStatement,
+    value::function::{FormalParameters, SourceLocation},
+};
+use crate::tooling::{
+    collect_project_files,
+    lint::{Diagnostic, Severity},
+    parse_recovering,
+    visitor::{walk_expression, walk_statement, Visitor},
+};
+
+/// Names of the global scope of a fresh interpreter
+pub fn default_globals() -> HashSet<Identifier> {
+    let mut globals: HashSet<_> = builtin_functions().into_keys().collect();
+    globals.extend(builtin_types().into_keys());
+    globals
+}
+
+/// Reports variables that are never declared or used before their declaration,
+/// `globals` are names the program can use without declaring them
+pub fn check_source(
+    source: String,
+    globals: &HashSet<Identifier>,
+) -> Result<Vec<Diagnostic>, FruError> {
+    let (ast, diagnostics) = parse_recovering(source);
+
+    let mut assigned = GlobalAssignments(HashSet::new());
+    assigned.visit_statement(&ast);
+
+    let mut resolver = Resolver {
+        globals,
+        assigned_globals: assigned.0,
+        location: None,
+        scopes: Vec::new(),
+        function_depth: 0,
+        diagnostics,
+    };
+
+    resolver.visit_statement(&ast);
+
+    let mut diagnostics = resolver.diagnostics;
+    diagnostics.sort_by_key(|x| x.location.map(|x| (x.line, x.column)));
+
+    Ok(diagnostics)
+}
+
+pub fn check_path(
+    path: &Path,
+    globals: &HashSet<Identifier>,
+) -> Result<Vec<(PathBuf, Vec<Diagnostic>)>, FruError> {
+    collect_project_files(path)?
+        .into_iter()
+        .map(|file| {
+            let diagnostics = check_source(read_source_file(&file)?, globals)?;
+            Ok((file, diagnostics))
+        })
+        .collect()
+}
+
+// names declared anywhere in the program with `global().name = value`
+struct GlobalAssignments(HashSet<Identifier>);
+
+impl Visitor for GlobalAssignments {
+    fn visit_statement(&mut self, statement: &FruStatement) {
+        if let FruStatement::SetProp { what, ident, .. } = statement {
+            if let FruExpression::Call { what, args } = &**what {
+                let is_global = matches!(
+                    &**what,
+                    FruExpression::Variable { ident } if *ident == Identifier::new("global")
+                );
+
+                if is_global && args.args.is_empty() {
+                    self.0.insert(*ident);
+                }
+            }
+        }
+
+        walk_statement(self, statement)
+    }
+}
+
+struct Resolver<'a> {
+    globals: &'a HashSet<Identifier>,
+    assigned_globals: HashSet<Identifier>,
+    location: Option<SourceLocation>,
+    scopes: Vec<ResolverScope>,
+    function_depth: usize,
+    diagnostics: Vec<Diagnostic>,
+}
+
+struct ResolverScope {
+    declared: HashSet<Identifier>,
+    // declared later in the same block
+    pending: HashSet<Identifier>,
+    function_depth: usize,
+    // variables come from an object unknown before running, like in `scope obj { ... }`
+    is_open: bool,
+}
+
+enum Resolution {
+    Found,
+    Pending,
+    Missing,
+}
+
+impl Resolver<'_> {
+    fn in_scope(&mut self, names: HashSet<Identifier>, is_open: bool, f: impl FnOnce(&mut Self)) {
+        self.scopes.push(ResolverScope {
+            declared: names,
+            pending: HashSet::new(),
+            function_depth: self.function_depth,
+            is_open,
+        });
+
+        f(self);
+
+        self.scopes.pop();
+    }
+
+    // `let` and types of the block are known before it runs, so they can be used from functions
+    fn hoist(&mut self, body: &[FruStatement]) {
+        let scope = self.scopes.last_mut().expect("hoisting happens inside of a scope");
+
+        for statement in body {
+            match statement.unlocated() {
+                FruStatement::Let { ident, .. } | FruStatement::Type { ident, .. } => {
+                    scope.pending.insert(*ident);
+                }
+
+                _ => {}
+            }
+        }
+    }
+
+    fn declare(&mut self, ident: Identifier) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.declared.insert(ident);
+        }
+    }
+
+    fn resolve(&self, ident: Identifier) -> Resolution {
+        for scope in self.scopes.iter().rev() {
+            if scope.declared.contains(&ident) || scope.is_open {
+                return Resolution::Found;
+            }
+
+            if scope.pending.contains(&ident) {
+                // functions run after the rest of the block, when it is declared
+                return if scope.function_depth < self.function_depth {
+                    Resolution::Found
+                } else {
+                    Resolution::Pending
+                };
+            }
+        }
+
+        if self.globals.contains(&ident) || self.assigned_globals.contains(&ident) {
+            Resolution::Found
+        } else {
+            Resolution::Missing
+        }
+    }
+
+    fn use_variable(&mut self, ident: Identifier) {
+        let (rule, message) = match self.resolve(ident) {
+            Resolution::Found => return,
+
+            Resolution::Pending => (
+                "use-before-declaration",
+                format!("variable `{}` is used before its declaration", ident),
+            ),
+
+            Resolution::Missing => (
+                "undefined-variable",
+                format!("variable `{}` is not defined", ident),
+            ),
+        };
+
+        self.diagnostics.push(Diagnostic {
+            rule,
+            severity: Severity::Error,
+            message,
+            location: self.location,
+        });
+    }
+
+    fn visit_body(&mut self, body: &[FruStatement], is_open: bool) {
+        self.in_scope(HashSet::new(), is_open, |this| {
+            this.hoist(body);
+
+            for statement in body {
+                this.visit_statement(statement);
+            }
+        });
+    }
+
+    fn visit_type(&mut self, statement: &FruStatement) {
+        let FruStatement::Type {
+            fields,
+            static_fields,
+            properties,
+            static_properties,
+            methods,
+            ..
+        } = statement
+        else {
+            return;
+        };
+
+        // inside of methods and properties members of the type are variables
+        let members = fields
+            .iter()
+            .map(|x| x.ident)
+            .chain(static_fields.iter().map(|x| x.ident))
+            .chain(properties.keys().chain(static_properties.keys()).copied())
+            .chain(methods.iter().map(|x| x.ident))
+            .chain(methods.iter().filter_map(|x| {
+                let property = x.ident.to_string().strip_prefix("_cached_")?.to_string();
+                Some(Identifier::new(&property))
+            }))
+            .collect();
+
+        self.in_scope(members, false, |this| {
+            for field in static_fields {
+                if let Some(value) = &field.value {
+                    this.visit_expression(value);
+                }
+            }
+
+            for property in properties.values().chain(static_properties.values()) {
+                // getters run on access, like functions
+                if let Some(getter) = &property.getter {
+                    this.function_depth += 1;
+                    this.visit_expression(getter);
+                    this.function_depth -= 1;
+                }
+
+                if let Some((value, setter)) = &property.setter {
+                    this.visit_function_with(&[*value], setter);
+                }
+            }
+
+            for method in methods {
+                this.visit_function(Some(&method.parameters), &method.body);
+            }
+        });
+    }
+
+    fn visit_function_with(&mut self, parameters: &[Identifier], body: &FruStatement) {
+        self.function_depth += 1;
+
+        let names = parameters.iter().copied().collect();
+        self.in_scope(names, false, |this| this.visit_statement(body));
+
+        self.function_depth -= 1;
+    }
+}
+
+impl Visitor for Resolver<'_> {
+    fn visit_statement(&mut self, statement: &FruStatement) {
+        match statement {
+            FruStatement::Located {
+                location,
+                statement,
+                ..
+            } => {
+                let previous = self.location.replace(*location);
+                self.visit_statement(statement);
+                self.location = previous;
+            }
+
+            FruStatement::SourceCode { body } | FruStatement::Block { body } => {
+                self.visit_body(body, false)
+            }
+
+            FruStatement::ScopeModifier { what, body } => {
+                self.visit_expression(what);
+                self.visit_body(body, true);
+            }
+
+            FruStatement::Let { ident, value } => {
+                self.visit_expression(value);
+                self.declare(*ident);
+            }
+
+            FruStatement::Set { ident, value } => {
+                self.visit_expression(value);
+                self.use_variable(*ident);
+            }
+
+            FruStatement::Type { ident, .. } => {
+                self.declare(*ident);
+                self.visit_type(statement);
+            }
+
+            FruStatement::Operator {
+                left_ident,
+                right_ident,
+                body,
+                ..
+            } => self.visit_function_with(&[*left_ident, *right_ident], body),
+
+            _ => walk_statement(self, statement),
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &FruExpression) {
+        match expression {
+            FruExpression::Variable { ident } => self.use_variable(*ident),
+
+            FruExpression::Block { body, expr } => {
+                self.in_scope(HashSet::new(), false, |this| {
+                    this.hoist(body);
+
+                    for statement in body {
+                        this.visit_statement(statement);
+                    }
+
+                    this.visit_expression(expr);
+                });
+            }
+
+            FruExpression::ScopeModifier { what, body, expr } => {
+                self.visit_expression(what);
+
+                self.in_scope(HashSet::new(), true, |this| {
+                    for statement in body {
+                        this.visit_statement(statement);
+                    }
+
+                    this.visit_expression(expr);
+                });
+            }
+
+            _ => walk_expression(self, expression),
+        }
+    }
+
+    fn visit_function(&mut self, parameters: Option<&FormalParameters>, body: &FruStatement) {
+        self.function_depth += 1;
+
+        self.in_scope(HashSet::new(), false, |this| {
+            // defaults can use the parameters before them
+            for (ident, default) in parameters.iter().flat_map(|x| &x.args) {
+                if let Some(default) = default {
+                    this.visit_expression(default);
+                }
+
+                this.declare(*ident);
+            }
+
+            this.visit_statement(body);
+        });
+
+        self.function_depth -= 1;
+    }
+}
@@ -0,0 +1,318 @@
+use std::{fmt::Write, path::Path, rc::Rc};
+
+use crate::interpreter::{
+    ast_helpers::RawMethod,
+    error::FruError,
+    expression::FruExpression,
+    identifier::Identifier,
+    runner::read_source_file,
+    statement::FruStatement,
+    tree_sitter_parser,
+    value::{fru_type::TypeType, function::FormalParameters},
+};
+use crate::tooling::collect_project_files;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocFormat {
+    Markdown,
+    Html,
+}
+
+/// Declarations made at the top level of one file
+#[derive(Debug, Clone)]
+pub struct ModuleDoc {
+    pub path: String,
+    pub types: Vec<TypeDoc>,
+    pub functions: Vec<FunctionDoc>,
+    pub operators: Vec<OperatorDoc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TypeDoc {
+    pub type_type: TypeType,
+    pub ident: Identifier,
+    pub doc: Option<Rc<str>>,
+    pub fields: Vec<String>,
+    pub static_fields: Vec<String>,
+    pub properties: Vec<String>,
+    pub methods: Vec<FunctionDoc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FunctionDoc {
+    pub signature: String,
+    pub doc: Option<Rc<str>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct OperatorDoc {
+    pub signature: String,
+    pub doc: Option<Rc<str>>,
+}
+
+/// Documents a file, a project, or every `.fru` file in a directory
+pub fn generate(path: &Path, format: DocFormat) -> Result<String, FruError> {
+    let modules = collect_project_files(path)?
+        .iter()
+        .map(|file| document_source(&file.display().to_string(), read_source_file(file)?))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(match format {
+        DocFormat::Markdown => render_markdown(&modules),
+        DocFormat::Html => render_html(&modules),
+    })
+}
+
+pub fn document_source(path: &str, source: String) -> Result<ModuleDoc, FruError> {
+    let ast = tree_sitter_parser::parse(source).map_err(|err| FruError::new(err.to_string()))?;
+
+    let mut module = ModuleDoc {
+        path: path.to_string(),
+        types: Vec::new(),
+        functions: Vec::new(),
+        operators: Vec::new(),
+    };
+
+    let body = match &*ast {
+        FruStatement::SourceCode { body } => body.as_slice(),
+        _ => &[],
+    };
+
+    for statement in body {
+        match statement.unlocated() {
+            FruStatement::Let { ident, value } => {
+                if let FruExpression::Function { args, doc, .. } = &**value {
+                    module.functions.push(FunctionDoc {
+                        signature: format!("{}{}", ident, format_parameters(args)),
+                        doc: doc.clone(),
+                    });
+                }
+            }
+
+            FruStatement::Operator {
+                ident,
+                commutative,
+                left_type_ident,
+                right_type_ident,
+                doc,
+                ..
+            } => module.operators.push(OperatorDoc {
+                signature: format!(
+                    "{}{} {} {}",
+                    if *commutative { "commutative " } else { "" },
+                    left_type_ident,
+                    ident,
+                    right_type_ident
+                ),
+                doc: doc.clone(),
+            }),
+
+            FruStatement::Type {
+                type_type,
+                ident,
+                fields,
+                static_fields,
+                properties,
+                static_properties,
+                methods,
+                doc,
+            } => {
+                let mut property_names: Vec<_> = properties
+                    .keys()
+                    .map(|x| x.to_string())
+                    .chain(static_properties.keys().map(|x| format!("static {}", x)))
+                    .collect();
+                property_names.sort();
+
+                module.types.push(TypeDoc {
+                    type_type: *type_type,
+                    ident: *ident,
+                    doc: doc.clone(),
+                    fields: fields
+                        .iter()
+                        .map(|field| {
+                            format!(
+                                "{}{}",
+                                if field.is_public { "pub " } else { "" },
+                                field.ident
+                            )
+                        })
+                        .collect(),
+                    static_fields: static_fields.iter().map(|x| x.ident.to_string()).collect(),
+                    properties: property_names,
+                    methods: methods.iter().map(document_method).collect(),
+                })
+            }
+
+            _ => {}
+        }
+    }
+
+    Ok(module)
+}
+
+fn document_method(method: &RawMethod) -> FunctionDoc {
+    FunctionDoc {
+        signature: format!(
+            "{}{}{}",
+            if method.is_static { "static " } else { "" },
+            method.ident,
+            format_parameters(&method.parameters)
+        ),
+        doc: method.doc.clone(),
+    }
+}
+
+fn format_parameters(parameters: &FormalParameters) -> String {
+    let params: Vec<_> = parameters
+        .args
+        .iter()
+        .map(|(ident, default)| match default {
+            Some(_) => format!("{} = ...", ident),
+            None => ident.to_string(),
+        })
+        .collect();
+
+    format!("({})", params.join(", "))
+}
+
+fn type_keyword(type_type: TypeType) -> &'static str {
+    match type_type {
+        TypeType::Struct => "struct",
+        TypeType::Class => "class",
+        TypeType::Data => "data",
+    }
+}
+
+pub fn render_markdown(modules: &[ModuleDoc]) -> String {
+    let mut out = String::new();
+
+    for module in modules {
+        let _ = writeln!(out, "# {}\n", module.path);
+
+        for type_ in &module.types {
+            let _ = writeln!(
+                out,
+                "## {} {}\n",
+                type_keyword(type_.type_type),
+                type_.ident
+            );
+
+            if let Some(doc) = &type_.doc {
+                let _ = writeln!(out, "{}\n", doc);
+            }
+
+            for (title, items) in [
+                ("Fields", &type_.fields),
+                ("Static fields", &type_.static_fields),
+                ("Properties", &type_.properties),
+            ] {
+                if !items.is_empty() {
+                    let items: Vec<_> = items.iter().map(|x| format!("`{}`", x)).collect();
+                    let _ = writeln!(out, "{}: {}\n", title, items.join(", "));
+                }
+            }
+
+            for method in &type_.methods {
+                let _ = writeln!(out, "### {}\n", method.signature);
+                write_markdown_doc(&mut out, &method.doc);
+            }
+        }
+
+        if !module.functions.is_empty() {
+            let _ = writeln!(out, "## Functions\n");
+
+            for function in &module.functions {
+                let _ = writeln!(out, "### {}\n", function.signature);
+                write_markdown_doc(&mut out, &function.doc);
+            }
+        }
+
+        if !module.operators.is_empty() {
+            let _ = writeln!(out, "## Operators\n");
+
+            for operator in &module.operators {
+                let _ = writeln!(out, "### {}\n", operator.signature);
+                write_markdown_doc(&mut out, &operator.doc);
+            }
+        }
+    }
+
+    out
+}
+
+fn write_markdown_doc(out: &mut String, doc: &Option<Rc<str>>) {
+    if let Some(doc) = doc {
+        let _ = writeln!(out, "{}\n", doc);
+    }
+}
+
+pub fn render_html(modules: &[ModuleDoc]) -> String {
+    let mut out = String::from("<!DOCTYPE html>\n<html>\n<body>\n");
+
+    for module in modules {
+        let _ = writeln!(out, "<h1>{}</h1>", escape_html(&module.path));
+
+        for type_ in &module.types {
+            let _ = writeln!(
+                out,
+                "<h2>{} {}</h2>",
+                type_keyword(type_.type_type),
+                escape_html(&type_.ident.to_string())
+            );
+            write_html_doc(&mut out, &type_.doc);
+
+            for (title, items) in [
+                ("Fields", &type_.fields),
+                ("Static fields", &type_.static_fields),
+                ("Properties", &type_.properties),
+            ] {
+                if !items.is_empty() {
+                    let items: Vec<_> =
+                        items.iter().map(|x| format!("<code>{}</code>", escape_html(x))).collect();
+                    let _ = writeln!(out, "<p>{}: {}</p>", title, items.join(", "));
+                }
+            }
+
+            for method in &type_.methods {
+                let _ = writeln!(out, "<h3>{}</h3>", escape_html(&method.signature));
+                write_html_doc(&mut out, &method.doc);
+            }
+        }
+
+        if !module.functions.is_empty() {
+            let _ = writeln!(out, "<h2>Functions</h2>");
+
+            for function in &module.functions {
+                let _ = writeln!(out, "<h3>{}</h3>", escape_html(&function.signature));
+                write_html_doc(&mut out, &function.doc);
+            }
+        }
+
+        if !module.operators.is_empty() {
+            let _ = writeln!(out, "<h2>Operators</h2>");
+
+            for operator in &module.operators {
+                let _ = writeln!(out, "<h3>{}</h3>", escape_html(&operator.signature));
+                write_html_doc(&mut out, &operator.doc);
+            }
+        }
+    }
+
+    out.push_str("</body>\n</html>\n");
+
+    out
+}
+
+fn write_html_doc(out: &mut String, doc: &Option<Rc<str>>) {
+    if let Some(doc) = doc {
+        let _ = writeln!(out, "<p>{}</p>", escape_html(doc).replace('\n', "<br>"));
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,887 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+    path::Path,
+};
+
+use crate::interpreter::{
+    error::FruError,
+    expression::FruExpression,
+    identifier::Identifier,
+    macro_expansion::expand_macros,
+    runner::read_source_file,
+    statement::FruStatement,
+    tree_sitter_parser,
+    value::{
+        fru_type::{Property, TypeType},
+        fru_value::FruValue,
+        function::{ArgumentList, FormalParameters},
+    },
+};
+use crate::tooling::visitor::{walk_statement, Visitor};
+
+const RUNTIME: &str = include_str!("emit_js_runtime.js");
+
+// builtins of the interpreter that the runtime has too
+const BUILTINS: [&str; 6] = ["assert_eq", "Bool", "Number", "op", "print", "String"];
+
+const JS_RESERVED: [&str; 44] = [
+    "arguments",
+    "await",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "eval",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "yield",
+];
+
+const INDENT: &str = "    ";
+
+pub fn emit_js_file(path: &Path) -> Result<String, FruError> {
+    emit_js(read_source_file(path)?)
+}
+
+pub fn emit_js(source: String) -> Result<String, FruError> {
+    let ast = tree_sitter_parser::parse(source).map_err(|err| FruError::new(err.to_string()))?;
+
+    emit_js_ast(&ast)
+}
+
+/// Standalone JavaScript program with the runtime it needs, macros are expanded first.
+/// Imports and scope manipulation have no JavaScript counterpart and are errors
+pub fn emit_js_ast(ast: &FruStatement) -> Result<String, FruError> {
+    let ast = &expand_macros(ast)?;
+
+    let mut emitter = Emitter {
+        scopes: vec![HashSet::new()],
+        members: None,
+    };
+
+    let mut body = String::new();
+    emitter.statement(ast, &mut body, 1)?;
+
+    // builtins that the program declares itself would clash with its `let`
+    let declared = top_level_declarations(ast);
+    let builtins: Vec<_> = BUILTINS
+        .into_iter()
+        .filter(|x| !declared.contains(&Identifier::new(x)))
+        .collect();
+
+    let mut out = format!("{}\n{{\n", RUNTIME);
+
+    if !builtins.is_empty() {
+        let _ = writeln!(
+            out,
+            "{}const {{ {} }} = $fru.builtins;\n",
+            INDENT,
+            builtins.join(", ")
+        );
+    }
+
+    out.push_str(&body);
+    out.push_str("}\n");
+
+    Ok(out)
+}
+
+fn top_level_declarations(ast: &FruStatement) -> HashSet<Identifier> {
+    let body = match ast.unlocated() {
+        FruStatement::SourceCode { body } => body.as_slice(),
+        _ => &[],
+    };
+
+    body.iter()
+        .filter_map(|statement| match statement.unlocated() {
+            FruStatement::Let { ident, .. } | FruStatement::Type { ident, .. } => Some(*ident),
+            _ => None,
+        })
+        .collect()
+}
+
+fn unsupported<T>(what: &str) -> Result<T, FruError> {
+    FruError::new_res(format!("{} can't be translated to JavaScript", what))
+}
+
+fn js_name(ident: Identifier) -> String {
+    let name = ident.to_string();
+
+    if JS_RESERVED.contains(&name.as_str()) {
+        format!("{}$", name)
+    } else {
+        name
+    }
+}
+
+fn js_string(s: &str) -> String {
+    serde_json::to_string(s).expect("strings are always valid JSON")
+}
+
+fn line(out: &mut String, indent: usize, text: &str) {
+    let _ = writeln!(out, "{}{}", INDENT.repeat(indent), text);
+}
+
+#[derive(Clone, Copy)]
+enum MemberKind {
+    Field,
+    // properties and methods, read through `$fru.get`
+    Other,
+}
+
+// inside of methods, members of the type are variables
+#[derive(Clone)]
+struct Members {
+    type_name: String,
+    // empty in static methods
+    instance: HashMap<Identifier, MemberKind>,
+    statics: HashMap<Identifier, MemberKind>,
+    // scopes of the method start here, names declared in them hide members
+    depth: usize,
+}
+
+struct Emitter {
+    // names declared by `let` and parameters, innermost last
+    scopes: Vec<HashSet<Identifier>>,
+    members: Option<Members>,
+}
+
+impl Emitter {
+    fn declare(&mut self, ident: Identifier) {
+        self.scopes
+            .last_mut()
+            .expect("emitting happens inside of a scope")
+            .insert(ident);
+    }
+
+    fn in_scope<T>(
+        &mut self,
+        names: impl IntoIterator<Item = Identifier>,
+        f: impl FnOnce(&mut Self) -> T,
+    ) -> T {
+        self.scopes.push(names.into_iter().collect());
+        let result = f(self);
+        self.scopes.pop();
+        result
+    }
+
+    fn in_members<T>(&mut self, members: Members, f: impl FnOnce(&mut Self) -> T) -> T {
+        let previous = self.members.replace(Members {
+            depth: self.scopes.len(),
+            ..members
+        });
+        let result = f(self);
+        self.members = previous;
+        result
+    }
+
+    // the object, or the type for statics, and how the member is stored
+    fn member(&self, ident: Identifier) -> Option<(String, MemberKind)> {
+        let members = self.members.as_ref()?;
+
+        if self.scopes[members.depth..].iter().any(|x| x.contains(&ident)) {
+            return None;
+        }
+
+        if let Some(kind) = members.instance.get(&ident) {
+            return Some(("this".to_string(), *kind));
+        }
+
+        members.statics.get(&ident).map(|kind| (members.type_name.clone(), *kind))
+    }
+
+    fn variable(&self, ident: Identifier) -> String {
+        match self.member(ident) {
+            Some((target, MemberKind::Field)) if target == "this" => format!("this.{}", ident),
+            Some((target, MemberKind::Field)) => format!("{}.$statics.{}", target, ident),
+            Some((target, MemberKind::Other)) => {
+                format!("$fru.get({}, {})", target, js_string(&ident.to_string()))
+            }
+            None => js_name(ident),
+        }
+    }
+
+    fn body(
+        &mut self,
+        body: &[FruStatement],
+        out: &mut String,
+        indent: usize,
+    ) -> Result<(), FruError> {
+        for statement in body {
+            self.statement(statement, out, indent)?;
+        }
+
+        Ok(())
+    }
+
+    // contents of the statement without braces, blocks don't get nested braces
+    fn braced_contents(
+        &mut self,
+        statement: &FruStatement,
+        out: &mut String,
+        indent: usize,
+    ) -> Result<(), FruError> {
+        self.in_scope([], |this| match statement.unlocated() {
+            FruStatement::Block { body } => this.body(body, out, indent),
+            other => this.statement(other, out, indent),
+        })
+    }
+
+    // `fn(x) { x + 1 }` is parsed as returning a block, it becomes an ordinary function body
+    fn function_body(
+        &mut self,
+        body: &FruStatement,
+        out: &mut String,
+        indent: usize,
+    ) -> Result<(), FruError> {
+        match body.unlocated() {
+            FruStatement::Return { value: Some(value) } => self.expression_body(value, out, indent),
+            FruStatement::Block { body } => self.body(body, out, indent),
+            other => self.statement(other, out, indent),
+        }
+    }
+
+    fn expression_body(
+        &mut self,
+        expression: &FruExpression,
+        out: &mut String,
+        indent: usize,
+    ) -> Result<(), FruError> {
+        match expression {
+            FruExpression::Block { body, expr } => {
+                self.body(body, out, indent)?;
+                let expr = self.expression(expr, indent)?;
+                line(out, indent, &format!("return {};", expr));
+            }
+
+            other => {
+                let value = self.expression(other, indent)?;
+                line(out, indent, &format!("return {};", value));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn statement(
+        &mut self,
+        statement: &FruStatement,
+        out: &mut String,
+        indent: usize,
+    ) -> Result<(), FruError> {
+        match statement {
+            FruStatement::Located { statement, .. } => self.statement(statement, out, indent)?,
+
+            FruStatement::SourceCode { body } => self.body(body, out, indent)?,
+
+            FruStatement::Block { body } => {
+                line(out, indent, "{");
+                self.in_scope([], |this| this.body(body, out, indent + 1))?;
+                line(out, indent, "}");
+            }
+
+            FruStatement::ScopeModifier { .. } => return unsupported("`scope` statement"),
+
+            FruStatement::Expression { value } => match &**value {
+                FruExpression::Block { body, expr } => {
+                    line(out, indent, "{");
+
+                    self.in_scope([], |this| -> Result<(), FruError> {
+                        this.body(body, out, indent + 1)?;
+                        let expr = this.expression(expr, indent + 1)?;
+                        line(out, indent + 1, &format!("{};", expr));
+                        Ok(())
+                    })?;
+
+                    line(out, indent, "}");
+                }
+
+                value => {
+                    let value = self.expression(value, indent)?;
+                    line(out, indent, &format!("{};", value));
+                }
+            },
+
+            FruStatement::Let { ident, value } => {
+                let value = self.stored_value(value, indent)?;
+                self.declare(*ident);
+                line(
+                    out,
+                    indent,
+                    &format!("let {} = {};", js_name(*ident), value),
+                );
+            }
+
+            FruStatement::Set { ident, value } => {
+                let value = self.stored_value(value, indent)?;
+
+                let text = match self.member(*ident) {
+                    Some((target, _)) => format!(
+                        "$fru.set({}, {}, {});",
+                        target,
+                        js_string(&ident.to_string()),
+                        value
+                    ),
+                    None => format!("{} = {};", js_name(*ident), value),
+                };
+
+                line(out, indent, &text);
+            }
+
+            FruStatement::SetProp { what, ident, value } => {
+                let what = self.expression(what, indent)?;
+                let value = self.stored_value(value, indent)?;
+
+                line(
+                    out,
+                    indent,
+                    &format!(
+                        "$fru.set({}, {}, {});",
+                        what,
+                        js_string(&ident.to_string()),
+                        value
+                    ),
+                );
+            }
+
+            FruStatement::If { .. } => self.if_statement(statement, out, indent, "if")?,
+
+            FruStatement::While { condition, body } => {
+                let condition = self.expression(condition, indent)?;
+                line(
+                    out,
+                    indent,
+                    &format!("while ($fru.cond({}, \"while\")) {{", condition),
+                );
+                self.braced_contents(body, out, indent + 1)?;
+                line(out, indent, "}");
+            }
+
+            FruStatement::Return { value: None } => line(out, indent, "return;"),
+
+            FruStatement::Return { value: Some(value) } => match &**value {
+                FruExpression::Block { .. } => {
+                    line(out, indent, "{");
+                    self.in_scope([], |this| this.expression_body(value, out, indent + 1))?;
+                    line(out, indent, "}");
+                }
+
+                value => {
+                    let value = self.expression(value, indent)?;
+                    line(out, indent, &format!("return {};", value));
+                }
+            },
+
+            FruStatement::Break => line(out, indent, "break;"),
+
+            FruStatement::Continue => line(out, indent, "continue;"),
+
+            FruStatement::Operator {
+                ident,
+                commutative,
+                left_ident,
+                left_type_ident,
+                right_ident,
+                right_type_ident,
+                body,
+                ..
+            } => {
+                let mut body_out = String::new();
+
+                self.in_scope([*left_ident, *right_ident], |this| {
+                    this.function_body(body, &mut body_out, indent + 1)
+                })?;
+
+                line(
+                    out,
+                    indent,
+                    &format!(
+                        "$fru.operator({}, {}, {}, ({}, {}) => {{",
+                        js_string(&ident.to_string()),
+                        js_string(&left_type_ident.to_string()),
+                        js_string(&right_type_ident.to_string()),
+                        js_name(*left_ident),
+                        js_name(*right_ident)
+                    ),
+                );
+                out.push_str(&body_out);
+                line(out, indent, &format!("}}, {});", commutative));
+            }
+
+            FruStatement::Type { ident, .. } => {
+                self.declare(*ident);
+                self.type_statement(statement, out, indent)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn if_statement(
+        &mut self,
+        statement: &FruStatement,
+        out: &mut String,
+        indent: usize,
+        keyword: &str,
+    ) -> Result<(), FruError> {
+        let FruStatement::If {
+            condition,
+            then_body,
+            else_body,
+        } = statement
+        else {
+            unreachable!("only `if` statements are passed here");
+        };
+
+        let condition = self.expression(condition, indent)?;
+        line(
+            out,
+            indent,
+            &format!("{} ($fru.cond({}, \"if\")) {{", keyword, condition),
+        );
+        self.braced_contents(then_body, out, indent + 1)?;
+
+        match else_body.as_deref().map(FruStatement::unlocated) {
+            Some(else_if @ FruStatement::If { .. }) => {
+                self.if_statement(else_if, out, indent, "} else if")
+            }
+
+            Some(else_body) => {
+                line(out, indent, "} else {");
+                self.braced_contents(else_body, out, indent + 1)?;
+                line(out, indent, "}");
+                Ok(())
+            }
+
+            None => {
+                line(out, indent, "}");
+                Ok(())
+            }
+        }
+    }
+
+    fn type_statement(
+        &mut self,
+        statement: &FruStatement,
+        out: &mut String,
+        indent: usize,
+    ) -> Result<(), FruError> {
+        let FruStatement::Type {
+            type_type,
+            ident,
+            fields,
+            static_fields,
+            properties,
+            static_properties,
+            methods,
+            ..
+        } = statement
+        else {
+            unreachable!("only type statements are passed here");
+        };
+
+        let type_name = js_name(*ident);
+
+        let kind = match type_type {
+            TypeType::Struct => "struct",
+            TypeType::Class => "class",
+            TypeType::Data => "data",
+        };
+
+        let field_names: Vec<_> = fields.iter().map(|x| js_string(&x.ident.to_string())).collect();
+
+        line(
+            out,
+            indent,
+            &format!(
+                "let {} = $fru.type(\"{}\", {}, [{}]);",
+                type_name,
+                kind,
+                js_string(&ident.to_string()),
+                field_names.join(", ")
+            ),
+        );
+
+        let instance = fields
+            .iter()
+            .map(|x| (x.ident, MemberKind::Field))
+            .chain(properties.keys().map(|x| (*x, MemberKind::Other)))
+            .chain(methods.iter().filter(|x| !x.is_static).map(|x| (x.ident, MemberKind::Other)))
+            .collect();
+
+        let statics = static_fields
+            .iter()
+            .map(|x| (x.ident, MemberKind::Field))
+            .chain(static_properties.keys().map(|x| (*x, MemberKind::Other)))
+            .chain(methods.iter().filter(|x| x.is_static).map(|x| (x.ident, MemberKind::Other)))
+            .collect();
+
+        let static_members = Members {
+            type_name: type_name.clone(),
+            instance: HashMap::new(),
+            statics,
+            depth: 0,
+        };
+
+        let instance_members = Members {
+            instance,
+            ..static_members.clone()
+        };
+
+        for method in methods {
+            let (table, members) = if method.is_static {
+                ("$statics", &static_members)
+            } else {
+                ("$methods", &instance_members)
+            };
+
+            let function = self.in_members(members.clone(), |this| {
+                this.function(&method.parameters, &method.body, indent, "function ")
+            })?;
+
+            line(
+                out,
+                indent,
+                &format!("{}.{}.{} = {};", type_name, table, method.ident, function),
+            );
+        }
+
+        self.properties(
+            properties,
+            "$properties",
+            &instance_members,
+            out,
+            indent,
+            &type_name,
+        )?;
+
+        self.properties(
+            static_properties,
+            "$staticProperties",
+            &static_members,
+            out,
+            indent,
+            &type_name,
+        )?;
+
+        for field in static_fields {
+            let value = match &field.value {
+                Some(value) => self.in_members(static_members.clone(), |this| {
+                    this.stored_value(value, indent)
+                })?,
+                None => "null".to_string(),
+            };
+
+            line(
+                out,
+                indent,
+                &format!("{}.$statics.{} = {};", type_name, field.ident, value),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn properties(
+        &mut self,
+        properties: &HashMap<Identifier, Property>,
+        table: &str,
+        members: &Members,
+        out: &mut String,
+        indent: usize,
+        type_name: &str,
+    ) -> Result<(), FruError> {
+        // sorted, so the output doesn't change between runs
+        let mut properties: Vec<_> = properties.values().collect();
+        properties.sort_by_key(|x| x.ident.to_string());
+
+        for property in properties {
+            line(
+                out,
+                indent,
+                &format!("{}.{}.{} = {{", type_name, table, property.ident),
+            );
+
+            self.in_members(members.clone(), |this| -> Result<(), FruError> {
+                if let Some(getter) = &property.getter {
+                    line(out, indent + 1, "get: function () {");
+                    this.in_scope([], |this| this.expression_body(getter, out, indent + 2))?;
+                    line(out, indent + 1, "},");
+                }
+
+                if let Some((value, setter)) = &property.setter {
+                    line(
+                        out,
+                        indent + 1,
+                        &format!("set: function ({}) {{", js_name(*value)),
+                    );
+                    this.in_scope([*value], |this| this.function_body(setter, out, indent + 2))?;
+                    line(out, indent + 1, "},");
+                }
+
+                Ok(())
+            })?;
+
+            line(out, indent, "};");
+        }
+
+        Ok(())
+    }
+
+    // `$fru.fn(["x", "y"], (x, y = 1) => { ... })`, `prefix` is `function ` for methods
+    fn function(
+        &mut self,
+        parameters: &FormalParameters,
+        body: &FruStatement,
+        indent: usize,
+        prefix: &str,
+    ) -> Result<String, FruError> {
+        self.in_scope([], |this| {
+            let mut names = Vec::new();
+            let mut params = Vec::new();
+
+            for (ident, default) in &parameters.args {
+                names.push(js_string(&ident.to_string()));
+
+                // defaults can use the parameters before them
+                let param = match default {
+                    Some(default) => {
+                        format!(
+                            "{} = {}",
+                            js_name(*ident),
+                            this.expression(default, indent)?
+                        )
+                    }
+                    None => js_name(*ident),
+                };
+
+                this.declare(*ident);
+                params.push(param);
+            }
+
+            let mut body_out = String::new();
+            this.function_body(body, &mut body_out, indent + 1)?;
+
+            let arrow = if prefix.is_empty() { " =>" } else { "" };
+
+            Ok(format!(
+                "$fru.fn([{}], {}({}){} {{\n{}{}}})",
+                names.join(", "),
+                prefix,
+                params.join(", "),
+                arrow,
+                body_out,
+                INDENT.repeat(indent)
+            ))
+        })
+    }
+
+    // structs are copied when stored, fresh values don't need it
+    fn stored_value(&mut self, value: &FruExpression, indent: usize) -> Result<String, FruError> {
+        let text = self.expression(value, indent)?;
+
+        Ok(match value {
+            FruExpression::Literal { .. }
+            | FruExpression::Binary { .. }
+            | FruExpression::Function { .. }
+            | FruExpression::Instantiation { .. } => text,
+            _ => format!("$fru.copy({})", text),
+        })
+    }
+
+    fn arguments(&mut self, args: &ArgumentList, indent: usize) -> Result<String, FruError> {
+        let mut positional = Vec::new();
+        let mut named = Vec::new();
+
+        for (ident, value) in &args.args {
+            let value = self.expression(value, indent)?;
+
+            match ident {
+                Some(ident) => named.push(format!("{}: {}", ident, value)),
+                None => positional.push(value),
+            }
+        }
+
+        Ok(if named.is_empty() {
+            format!("[{}]", positional.join(", "))
+        } else {
+            format!("[{}], {{ {} }}", positional.join(", "), named.join(", "))
+        })
+    }
+
+    fn expression(
+        &mut self,
+        expression: &FruExpression,
+        indent: usize,
+    ) -> Result<String, FruError> {
+        Ok(match expression {
+            FruExpression::Literal { value } => match value {
+                FruValue::Nah => "null".to_string(),
+                FruValue::Bool(b) => b.to_string(),
+                FruValue::Number(n) if n.is_nan() => "NaN".to_string(),
+                FruValue::Number(n) if n.is_infinite() => {
+                    if *n > 0.0 { "Infinity" } else { "-Infinity" }.to_string()
+                }
+                FruValue::Number(n) => n.to_string(),
+                FruValue::String(s) => js_string(s),
+                other => {
+                    return unsupported(&format!(
+                        "literal of type `{}`",
+                        other.get_type_identifier()
+                    ))
+                }
+            },
+
+            FruExpression::Variable { ident } => self.variable(*ident),
+
+            FruExpression::ScopeAccessor => return unsupported("`scope()`"),
+
+            FruExpression::Function { args, body, .. } => self.function(args, body, indent, "")?,
+
+            FruExpression::Block { body, expr } => {
+                if has_jump(body) {
+                    return unsupported(
+                        "`return`, `break` or `continue` inside of a block expression",
+                    );
+                }
+
+                let mut body_out = String::new();
+
+                self.in_scope([], |this| -> Result<(), FruError> {
+                    this.body(body, &mut body_out, indent + 1)?;
+                    let expr = this.expression(expr, indent + 1)?;
+                    line(&mut body_out, indent + 1, &format!("return {};", expr));
+                    Ok(())
+                })?;
+
+                format!("(() => {{\n{}{}}})()", body_out, INDENT.repeat(indent))
+            }
+
+            FruExpression::ScopeModifier { .. } => return unsupported("`scope` expression"),
+
+            FruExpression::Call { what, args } => format!(
+                "$fru.call({}, {})",
+                self.expression(what, indent)?,
+                self.arguments(args, indent)?
+            ),
+
+            FruExpression::CurryCall { what, args } => format!(
+                "$fru.curry({}, {})",
+                self.expression(what, indent)?,
+                self.arguments(args, indent)?
+            ),
+
+            FruExpression::Instantiation { what, args } => format!(
+                "$fru.make({}, {})",
+                self.expression(what, indent)?,
+                self.arguments(args, indent)?
+            ),
+
+            FruExpression::PropAccess { what, ident } => format!(
+                "$fru.get({}, {})",
+                self.expression(what, indent)?,
+                js_string(&ident.to_string())
+            ),
+
+            FruExpression::Binary {
+                operator,
+                left,
+                right,
+            } => format!(
+                "$fru.op({}, {}, {})",
+                js_string(&operator.to_string()),
+                self.expression(left, indent)?,
+                self.expression(right, indent)?
+            ),
+
+            FruExpression::If {
+                condition,
+                then_body,
+                else_body,
+            } => format!(
+                "($fru.cond({}, \"if\") ? {} : {})",
+                self.expression(condition, indent)?,
+                self.expression(then_body, indent)?,
+                self.expression(else_body, indent)?
+            ),
+
+            FruExpression::Import { .. } => return unsupported("`import`"),
+        })
+    }
+}
+
+// a block expression becomes a function, so these would leave it instead of the enclosing code
+fn has_jump(body: &[FruStatement]) -> bool {
+    struct JumpFinder {
+        loops: usize,
+        found: bool,
+    }
+
+    impl Visitor for JumpFinder {
+        fn visit_statement(&mut self, statement: &FruStatement) {
+            match statement {
+                FruStatement::Return { .. } => self.found = true,
+
+                FruStatement::Break | FruStatement::Continue if self.loops == 0 => {
+                    self.found = true
+                }
+
+                FruStatement::While { .. } => {
+                    self.loops += 1;
+                    walk_statement(self, statement);
+                    self.loops -= 1;
+                }
+
+                _ => walk_statement(self, statement),
+            }
+        }
+
+        fn visit_function(&mut self, _: Option<&FormalParameters>, _: &FruStatement) {}
+    }
+
+    let mut finder = JumpFinder {
+        loops: 0,
+        found: false,
+    };
+
+    for statement in body {
+        finder.visit_statement(statement);
+    }
+
+    finder.found
+}
@@ -0,0 +1,367 @@
+// Runtime of programs translated by `frugurt emit-js`, `nah` is `null`
+const $fru = (() => {
+    "use strict";
+
+    class FruError extends Error {}
+
+    const fail = (message) => {
+        throw new FruError(message);
+    };
+
+    class FruType {
+        constructor(kind, name, fields) {
+            this.$kind = kind;
+            this.$name = name;
+            this.$fields = fields;
+            this.$methods = Object.create(null);
+            this.$properties = Object.create(null);
+            this.$statics = Object.create(null);
+            this.$staticProperties = Object.create(null);
+        }
+    }
+
+    // fields are own properties, names of fields never start with `$`
+    class FruObject {
+        constructor(type) {
+            Object.defineProperty(this, "$type", { value: type });
+        }
+    }
+
+    const nah = (value) => (value === undefined ? null : value);
+
+    const typeOf = (value) => {
+        if (value === null || value === undefined) return "Nah";
+        if (value instanceof FruType) return "Type";
+        if (value instanceof FruObject) return value.$type.$name;
+
+        switch (typeof value) {
+            case "number":
+                return "Number";
+            case "boolean":
+                return "Bool";
+            case "string":
+                return "String";
+            case "function":
+                return "Function";
+        }
+
+        return "NativeObject";
+    };
+
+    const show = (value) => {
+        if (value === null || value === undefined) return "nah";
+        if (value instanceof FruType) return value.$name;
+        if (typeof value === "function") return "Function";
+
+        if (value instanceof FruObject) {
+            const fields = value.$type.$fields.map((field) => `${field}=${show(value[field])}`);
+            return `${value.$type.$name}{${fields.join(", ")}}`;
+        }
+
+        return String(value);
+    };
+
+    const equals = (a, b) => {
+        if (a instanceof FruObject && b instanceof FruObject) {
+            return a.$type === b.$type && a.$type.$fields.every((field) => equals(a[field], b[field]));
+        }
+
+        return nah(a) === nah(b) && typeof a !== "function";
+    };
+
+    // objects are ordered lexicographically by fields
+    const compare = (a, b) => {
+        const [left, right] = [typeOf(a), typeOf(b)];
+
+        if (left !== right || !["Nah", "Bool", "Number", "String"].includes(left) && !(a instanceof FruObject)) {
+            fail(`values of \`${left}\` and \`${right}\` cannot be ordered`);
+        }
+
+        if (a instanceof FruObject) {
+            for (const field of a.$type.$fields) {
+                const ordering = compare(a[field], b[field]);
+                if (ordering !== 0) return ordering;
+            }
+
+            return 0;
+        }
+
+        if (Number.isNaN(a) || Number.isNaN(b)) fail("NaN cannot be ordered");
+
+        return a < b ? -1 : a > b ? 1 : 0;
+    };
+
+    const repeat = (s, n) => {
+        if (!Number.isInteger(n) || n < 0) fail("String * number must be a non-negative integer");
+        return s.repeat(n);
+    };
+
+    const divide = (a, b) => (b === 0 ? fail("division by zero") : a / b);
+
+    // like `rem_euclid`, the result is never negative
+    const remainder = (a, b) => {
+        if (b === 0) fail("division by zero");
+        const r = a % b;
+        return r < 0 ? r + Math.abs(b) : r;
+    };
+
+    const builtinOperators = new Map([
+        ["Number + Number", (a, b) => a + b],
+        ["Number - Number", (a, b) => a - b],
+        ["Number * Number", (a, b) => a * b],
+        ["Number / Number", divide],
+        ["Number % Number", remainder],
+        ["Number ** Number", (a, b) => a ** b],
+        ["Number < Number", (a, b) => a < b],
+        ["Number <= Number", (a, b) => a <= b],
+        ["Number > Number", (a, b) => a > b],
+        ["Number >= Number", (a, b) => a >= b],
+        ["Number == Number", (a, b) => a === b],
+        ["Number != Number", (a, b) => a !== b],
+        ["Bool && Bool", (a, b) => a && b],
+        ["Bool || Bool", (a, b) => a || b],
+        ["String <> String", (a, b) => a + b],
+        ["String < String", (a, b) => a < b],
+        ["String <= String", (a, b) => a <= b],
+        ["String > String", (a, b) => a > b],
+        ["String >= String", (a, b) => a >= b],
+        ["String == String", (a, b) => a === b],
+        ["String != String", (a, b) => a !== b],
+        ["String * Number", repeat],
+        ["Number * String", (a, b) => repeat(b, a)],
+    ]);
+
+    // declared operators are global here, unlike in the interpreter
+    const operators = new Map();
+
+    const operator = (op, left, right, body, commutative) => {
+        operators.set(`${left} ${op} ${right}`, body);
+
+        if (commutative) {
+            operators.set(`${right} ${op} ${left}`, (a, b) => body(b, a));
+        }
+    };
+
+    const structural = {
+        "==": (a, b) => equals(a, b),
+        "!=": (a, b) => !equals(a, b),
+        "<": (a, b) => compare(a, b) < 0,
+        "<=": (a, b) => compare(a, b) <= 0,
+        ">": (a, b) => compare(a, b) > 0,
+        ">=": (a, b) => compare(a, b) >= 0,
+    };
+
+    const op = (op, a, b) => {
+        const key = `${typeOf(a)} ${op} ${typeOf(b)}`;
+        const f = operators.get(key) ?? builtinOperators.get(key);
+
+        if (f) return nah(f(a, b));
+
+        if (a instanceof FruObject && b instanceof FruObject && a.$type === b.$type && op in structural) {
+            return structural[op](a, b);
+        }
+
+        fail(`operator \`Operator(${key})\` does not exist`);
+    };
+
+    const cond = (value, context) =>
+        typeof value === "boolean"
+            ? value
+            : fail(
+                  `Expected \`Bool\` in ${context} condition, got \`${typeOf(value)}\`, ` +
+                      "convert it with `Bool(x)` or compare it explicitly"
+              );
+
+    // `impl.length` is the number of parameters without defaults
+    const fn = (params, impl) => {
+        impl.$params = params;
+        return impl;
+    };
+
+    const bind = (method, target) => fn(method.$params, method.bind(target));
+
+    const call = (f, args, named = {}) => {
+        if (typeof f !== "function") fail(`\`${typeOf(f)}\` is not invokable`);
+
+        const names = Object.keys(named);
+
+        // builtins take positional arguments only
+        if (!f.$params) {
+            if (names.length > 0) fail("builtin functions take no named arguments");
+            return nah(f(...args));
+        }
+
+        const params = f.$params;
+
+        if (args.length > params.length) {
+            fail(`expected at most ${params.length} arguments, got ${args.length}`);
+        }
+
+        for (const name of names) {
+            const k = params.indexOf(name);
+            if (k === -1) fail(`unknown argument \`${name}\``);
+            if (k < args.length) fail(`argument \`${name}\` is set more than once`);
+        }
+
+        const full = params.map((param, k) => (k < args.length ? args[k] : named[param]));
+
+        for (let k = 0; k < f.length; k++) {
+            if (full[k] === undefined) fail(`missing argument \`${params[k]}\``);
+        }
+
+        return nah(f(...full));
+    };
+
+    const curry = (f, args, named = {}) => {
+        if (typeof f !== "function") fail(`\`${typeOf(f)}\` is not invokable`);
+
+        const rest = (f.$params ?? []).slice(args.length).filter((param) => !(param in named));
+
+        return fn(rest, (...more) => call(f, [...args, ...more], named));
+    };
+
+    const type = (kind, name, fields) => new FruType(kind, name, fields);
+
+    const make = (type, args, named = {}) => {
+        if (!(type instanceof FruType)) fail(`\`${typeOf(type)}\` is not instantiatable`);
+
+        const values = new Map();
+
+        args.forEach((value, k) => {
+            if (k >= type.$fields.length) fail(`expected at most ${type.$fields.length} fields`);
+            values.set(type.$fields[k], value);
+        });
+
+        for (const [name, value] of Object.entries(named)) {
+            if (values.has(name)) fail(`field \`${name}\` is set more than once`);
+            values.set(name, value);
+        }
+
+        const object = new FruObject(type);
+
+        for (const field of type.$fields) {
+            if (!values.has(field)) fail(`missing field \`${field}\``);
+            object[field] = values.get(field);
+            values.delete(field);
+        }
+
+        for (const name of values.keys()) fail(`field \`${name}\` does not exist`);
+
+        return object;
+    };
+
+    // structs are copied when they are stored, like in the interpreter
+    const copy = (value) => {
+        if (!(value instanceof FruObject) || value.$type.$kind !== "struct") return value;
+
+        const object = new FruObject(value.$type);
+
+        for (const field of value.$type.$fields) {
+            object[field] = copy(value[field]);
+        }
+
+        return object;
+    };
+
+    const get = (target, name) => {
+        if (target instanceof FruObject) {
+            const type = target.$type;
+
+            if (Object.hasOwn(target, name)) return target[name];
+
+            if (name in type.$properties) {
+                const { get } = type.$properties[name];
+                return get ? nah(get.call(target)) : fail(`property \`${name}\` has no getter`);
+            }
+
+            if (name in type.$methods) return bind(type.$methods[name], target);
+
+            target = type;
+        }
+
+        if (target instanceof FruType) {
+            if (name in target.$staticProperties) {
+                const { get } = target.$staticProperties[name];
+                return get ? nah(get.call(target)) : fail(`property \`${name}\` has no getter`);
+            }
+
+            if (name in target.$statics) return target.$statics[name];
+        }
+
+        fail(`prop \`${name}\` not found`);
+    };
+
+    const set = (target, name, value) => {
+        if (target instanceof FruObject) {
+            const type = target.$type;
+
+            if (Object.hasOwn(target, name)) {
+                if (type.$kind === "data") {
+                    fail(`cannot set field \`${name}\` in 'data' type \`${type.$name}\``);
+                }
+
+                target[name] = value;
+                return;
+            }
+
+            if (name in type.$properties) {
+                const { set } = type.$properties[name];
+                return set ? void set.call(target, value) : fail(`property \`${name}\` has no setter`);
+            }
+
+            target = type;
+        }
+
+        if (target instanceof FruType) {
+            if (name in target.$staticProperties) {
+                const { set } = target.$staticProperties[name];
+                return set ? void set.call(target, value) : fail(`property \`${name}\` has no setter`);
+            }
+
+            if (name in target.$statics) {
+                target.$statics[name] = value;
+                return;
+            }
+        }
+
+        fail(`prop \`${name}\` not found`);
+    };
+
+    const single = (name, args) => (args.length === 1 ? args[0] : fail(`\`${name}\` expects 1 argument`));
+
+    const builtins = {
+        print: (...args) => console.log(args.map((x) => `${show(x)} `).join("")),
+
+        assert_eq: (a, b) => (equals(a, b) ? true : fail(`assertion failed: ${show(a)} != ${show(b)}`)),
+
+        Bool: (...args) => {
+            const value = nah(single("Bool", args));
+            if (value === null) return false;
+            if (typeof value === "number") return value !== 0 && !Number.isNaN(value);
+            if (typeof value === "string") return value !== "";
+            return value !== false;
+        },
+
+        Number: (...args) => {
+            const value = single("Number", args);
+            if (typeof value === "number") return value;
+            if (typeof value === "boolean") return value ? 1 : 0;
+
+            if (typeof value === "string") {
+                const n = Number(value.trim());
+                return value.trim() === "" || Number.isNaN(n) ? fail(`cannot convert "${value}" to \`Number\``) : n;
+            }
+
+            fail(`cannot convert \`${typeOf(value)}\` to \`Number\``);
+        },
+
+        String: (...args) => show(single("String", args)),
+
+        op: (...args) => {
+            const name = single("op", args);
+            return fn(["left", "right"], (a, b) => op(name, a, b));
+        },
+    };
+
+    return { FruError, builtins, bind, call, cond, copy, curry, fn, get, make, op, operator, set, type };
+})();
@@ -0,0 +1,514 @@
+use std::{collections::HashMap, path::Path};
+
+use serde_json::{json, Value};
+use tree_sitter::Node;
+
+use crate::interpreter::{
+    builtins::{functions::builtin_functions, types::builtin_types},
+    error::FruError,
+    runner::read_source_file,
+    tree_sitter_parser,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Variable,
+    Parameter,
+    Function,
+    Type,
+    Field,
+    Property,
+    Method,
+    Operator,
+}
+
+/// Order of kinds in the LSP semantic tokens legend
+pub const TOKEN_KINDS: [TokenKind; 8] = [
+    TokenKind::Variable,
+    TokenKind::Parameter,
+    TokenKind::Function,
+    TokenKind::Type,
+    TokenKind::Field,
+    TokenKind::Property,
+    TokenKind::Method,
+    TokenKind::Operator,
+];
+
+/// Position is 1-based, columns and length are in UTF-16 code units as in LSP
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub line: usize,
+    pub column: usize,
+    pub length: usize,
+    pub kind: TokenKind,
+    pub declaration: bool,
+    pub text: String,
+}
+
+/// Declaration in the outline, types contain their members
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: TokenKind,
+    pub line: usize,
+    pub column: usize,
+    pub children: Vec<Symbol>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Highlights {
+    pub tokens: Vec<Token>,
+    pub outline: Vec<Symbol>,
+}
+
+impl TokenKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            TokenKind::Variable => "variable",
+            TokenKind::Parameter => "parameter",
+            TokenKind::Function => "function",
+            TokenKind::Type => "type",
+            TokenKind::Field => "field",
+            TokenKind::Property => "property",
+            TokenKind::Method => "method",
+            TokenKind::Operator => "operator",
+        }
+    }
+}
+
+impl Highlights {
+    pub fn to_json(&self) -> Value {
+        fn symbol_json(symbol: &Symbol) -> Value {
+            json!({
+                "name": symbol.name,
+                "kind": symbol.kind.name(),
+                "line": symbol.line,
+                "column": symbol.column,
+                "children": symbol.children.iter().map(symbol_json).collect::<Vec<_>>(),
+            })
+        }
+
+        json!({
+            "tokens": self.tokens.iter().map(|token| json!({
+                "line": token.line,
+                "column": token.column,
+                "length": token.length,
+                "kind": token.kind.name(),
+                "declaration": token.declaration,
+                "text": token.text,
+            })).collect::<Vec<_>>(),
+            "outline": self.outline.iter().map(symbol_json).collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// Tokens in the relative encoding of LSP `textDocument/semanticTokens`,
+/// token type is the index in `TOKEN_KINDS`, modifier bit 0 is `declaration`
+pub fn semantic_tokens(tokens: &[Token]) -> Vec<u32> {
+    let mut data = Vec::with_capacity(tokens.len() * 5);
+    let (mut line, mut column) = (1, 1);
+
+    for token in tokens {
+        if token.line != line {
+            column = 1;
+        }
+
+        data.extend([
+            (token.line - line) as u32,
+            (token.column - column) as u32,
+            token.length as u32,
+            TOKEN_KINDS.iter().position(|x| *x == token.kind).unwrap() as u32,
+            token.declaration as u32,
+        ]);
+
+        (line, column) = (token.line, token.column);
+    }
+
+    data
+}
+
+pub fn highlight_file(path: &Path) -> Result<Highlights, FruError> {
+    highlight_source(&read_source_file(path)?)
+}
+
+pub fn highlight_source(source: &str) -> Result<Highlights, FruError> {
+    let tree =
+        tree_sitter_parser::parse_tree(source).map_err(|err| FruError::new(err.to_string()))?;
+
+    let mut builtins = HashMap::new();
+    builtins.extend(builtin_functions().into_keys().map(|x| (x.to_string(), TokenKind::Function)));
+    builtins.extend(builtin_types().into_keys().map(|x| (x.to_string(), TokenKind::Type)));
+
+    let mut highlighter = Highlighter {
+        source,
+        builtins,
+        scopes: Vec::new(),
+        tokens: Vec::new(),
+    };
+
+    highlighter.walk(tree.root_node());
+
+    let mut tokens = highlighter.tokens;
+    tokens.sort_by_key(|x| (x.line, x.column));
+
+    Ok(Highlights {
+        tokens,
+        outline: outline(tree.root_node(), source),
+    })
+}
+
+struct Highlighter<'a> {
+    source: &'a str,
+    builtins: HashMap<String, TokenKind>,
+    scopes: Vec<HashMap<&'a str, TokenKind>>,
+    tokens: Vec<Token>,
+}
+
+impl<'a> Highlighter<'a> {
+    fn text(&self, node: Node) -> &'a str {
+        &self.source[node.byte_range()]
+    }
+
+    fn token(&mut self, node: Option<Node>, kind: TokenKind, declaration: bool) {
+        if let Some(node) = node {
+            let (line, column) = position(node, self.source);
+
+            self.tokens.push(Token {
+                line,
+                column,
+                length: self.text(node).encode_utf16().count(),
+                kind,
+                declaration,
+                text: self.text(node).to_string(),
+            });
+        }
+    }
+
+    fn declare(&mut self, node: Option<Node>, kind: TokenKind) {
+        if let Some(node) = node {
+            let name = self.text(node);
+            self.scopes.last_mut().unwrap().insert(name, kind);
+            self.token(Some(node), kind, true);
+        }
+    }
+
+    fn resolve(&mut self, node: Option<Node>) {
+        if let Some(node) = node {
+            let name = self.text(node);
+
+            let kind = self
+                .scopes
+                .iter()
+                .rev()
+                .find_map(|scope| scope.get(name).copied())
+                .or_else(|| self.builtins.get(name).copied())
+                .unwrap_or(TokenKind::Variable);
+
+            self.token(Some(node), kind, false);
+        }
+    }
+
+    // variables can be used before their declaration, if used from a function,
+    // so declarations of a block are known before walking it
+    fn hoist(&mut self, node: Node) {
+        for statement in node.children_by_field_name("body", &mut node.walk()) {
+            let kind = match statement.grammar_name() {
+                "let_statement" => let_kind(statement),
+                "type_statement" => TokenKind::Type,
+                _ => continue,
+            };
+
+            if let Some(ident) = statement.child_by_field_name("ident") {
+                let name = self.text(ident);
+                self.scopes.last_mut().unwrap().insert(name, kind);
+            }
+        }
+    }
+
+    fn walk_children(&mut self, node: Node) {
+        for child in node.named_children(&mut node.walk()) {
+            self.walk(child);
+        }
+    }
+
+    fn walk_child(&mut self, node: Node, field: &str) {
+        if let Some(child) = node.child_by_field_name(field) {
+            self.walk(child);
+        }
+    }
+
+    fn walk(&mut self, node: Node) {
+        match node.grammar_name() {
+            "source_file"
+            | "block_statement"
+            | "block_expression"
+            | "scope_modifier_statement"
+            | "scope_modifier_expression" => {
+                self.walk_child(node, "what");
+
+                self.scopes.push(HashMap::new());
+                self.hoist(node);
+
+                for child in node.children_by_field_name("body", &mut node.walk()) {
+                    self.walk(child);
+                }
+
+                self.walk_child(node, "expr");
+                self.scopes.pop();
+            }
+
+            "let_statement" => {
+                self.declare(node.child_by_field_name("ident"), let_kind(node));
+                self.walk_child(node, "value");
+            }
+
+            "set_statement" => {
+                self.resolve(node.child_by_field_name("ident"));
+                self.walk_child(node, "value");
+            }
+
+            "set_prop_statement" => {
+                self.walk_child(node, "what");
+                self.token(node.child_by_field_name("ident"), TokenKind::Field, false);
+                self.walk_child(node, "value");
+            }
+
+            "variable" => self.resolve(node.child_by_field_name("ident")),
+
+            "prop_access_expression" => {
+                self.walk_child(node, "what");
+
+                let is_called = node.parent().is_some_and(|parent| {
+                    matches!(
+                        parent.grammar_name(),
+                        "call_expression" | "curry_call_expression"
+                    ) && parent.child_by_field_name("what") == Some(node)
+                });
+
+                let kind = if is_called {
+                    TokenKind::Method
+                } else {
+                    TokenKind::Field
+                };
+
+                self.token(node.child_by_field_name("ident"), kind, false);
+            }
+
+            "binary_expression" => {
+                self.walk_child(node, "left");
+                self.token(
+                    node.child_by_field_name("operator"),
+                    TokenKind::Operator,
+                    false,
+                );
+                self.walk_child(node, "right");
+            }
+
+            "named_argument" => {
+                // named arguments of instantiation are fields
+                let is_instantiation = node
+                    .parent()
+                    .and_then(|x| x.parent())
+                    .is_some_and(|x| x.grammar_name() == "instantiation_expression");
+
+                let kind = if is_instantiation {
+                    TokenKind::Field
+                } else {
+                    TokenKind::Parameter
+                };
+
+                self.token(node.child_by_field_name("ident"), kind, false);
+                self.walk_child(node, "value");
+            }
+
+            "function_expression" => self.walk_function(node, &[]),
+
+            "operator_statement" => {
+                self.token(node.child_by_field_name("ident"), TokenKind::Operator, true);
+                self.token(
+                    node.child_by_field_name("left_type_ident"),
+                    TokenKind::Type,
+                    false,
+                );
+                self.token(
+                    node.child_by_field_name("right_type_ident"),
+                    TokenKind::Type,
+                    false,
+                );
+
+                self.scopes.push(HashMap::new());
+                self.declare(node.child_by_field_name("left_ident"), TokenKind::Parameter);
+                self.declare(
+                    node.child_by_field_name("right_ident"),
+                    TokenKind::Parameter,
+                );
+                self.walk_child(node, "body");
+                self.scopes.pop();
+            }
+
+            "type_statement" => self.walk_type(node),
+
+            _ => self.walk_children(node),
+        }
+    }
+
+    // functions and methods, `members` are fields and properties visible in the body
+    fn walk_function(&mut self, node: Node, members: &[(&'a str, TokenKind)]) {
+        self.scopes.push(members.iter().copied().collect());
+
+        if let Some(parameters) = node.child_by_field_name("parameters") {
+            for parameter in parameters.children_by_field_name("args", &mut parameters.walk()) {
+                self.walk_child(parameter, "value");
+                self.declare(parameter.child_by_field_name("ident"), TokenKind::Parameter);
+            }
+        }
+
+        self.walk_child(node, "body");
+        self.scopes.pop();
+    }
+
+    fn walk_type(&mut self, node: Node) {
+        self.declare(node.child_by_field_name("ident"), TokenKind::Type);
+
+        let members: Vec<_> = node.children_by_field_name("members", &mut node.walk()).collect();
+
+        let mut visible = Vec::new();
+
+        for member in &members {
+            let ident = member.child_by_field_name("ident");
+
+            let (ident, kind) = match member.grammar_name() {
+                "type_field" => (
+                    ident.and_then(|x| x.child_by_field_name("ident")),
+                    TokenKind::Field,
+                ),
+                _ => (ident, TokenKind::Property),
+            };
+
+            if let Some(ident) = ident {
+                visible.push((self.text(ident), kind));
+                self.token(Some(ident), kind, true);
+            }
+        }
+
+        for member in &members {
+            match member.grammar_name() {
+                "type_field" => {
+                    if let Some(ident) = member.child_by_field_name("ident") {
+                        let type_ident = ident.child_by_field_name("type_ident");
+                        self.token(type_ident, TokenKind::Type, false);
+                    }
+
+                    self.walk_child(*member, "value");
+                }
+
+                _ => {
+                    for item in member.children_by_field_name("items", &mut member.walk()) {
+                        self.scopes.push(visible.iter().copied().collect());
+
+                        if let Some(value) = item.child_by_field_name("value_ident") {
+                            self.declare(value.child_by_field_name("ident"), TokenKind::Parameter);
+                            let type_ident = value.child_by_field_name("type_ident");
+                            self.token(type_ident, TokenKind::Type, false);
+                        }
+
+                        self.walk_child(item, "body");
+                        self.scopes.pop();
+                    }
+                }
+            }
+        }
+
+        if let Some(implementation) = node.child_by_field_name("impl") {
+            for method in
+                implementation.children_by_field_name("methods", &mut implementation.walk())
+            {
+                self.token(method.child_by_field_name("ident"), TokenKind::Method, true);
+                self.walk_function(method, &visible);
+            }
+        }
+    }
+}
+
+fn let_kind(node: Node) -> TokenKind {
+    match node.child_by_field_name("value") {
+        Some(value) if value.grammar_name() == "function_expression" => TokenKind::Function,
+        _ => TokenKind::Variable,
+    }
+}
+
+fn position(node: Node, source: &str) -> (usize, usize) {
+    let point = node.start_position();
+    let line_start = node.start_byte() - point.column;
+
+    (
+        point.row + 1,
+        source[line_start..node.start_byte()].encode_utf16().count() + 1,
+    )
+}
+
+fn outline(root: Node, source: &str) -> Vec<Symbol> {
+    let symbol = |ident: Node, kind: TokenKind, children: Vec<Symbol>| {
+        let (line, column) = position(ident, source);
+
+        Symbol {
+            name: source[ident.byte_range()].to_string(),
+            kind,
+            line,
+            column,
+            children,
+        }
+    };
+
+    let mut symbols = Vec::new();
+
+    for statement in root.children_by_field_name("body", &mut root.walk()) {
+        let ident = statement.child_by_field_name("ident");
+
+        match (statement.grammar_name(), ident) {
+            ("let_statement", Some(ident)) => {
+                symbols.push(symbol(ident, let_kind(statement), vec![]))
+            }
+
+            ("operator_statement", Some(ident)) => {
+                symbols.push(symbol(ident, TokenKind::Operator, vec![]))
+            }
+
+            ("type_statement", Some(ident)) => {
+                let mut children = Vec::new();
+
+                for member in statement.children_by_field_name("members", &mut statement.walk()) {
+                    match (member.grammar_name(), member.child_by_field_name("ident")) {
+                        ("type_field", Some(ident)) => {
+                            if let Some(ident) = ident.child_by_field_name("ident") {
+                                children.push(symbol(ident, TokenKind::Field, vec![]));
+                            }
+                        }
+
+                        (_, Some(ident)) => {
+                            children.push(symbol(ident, TokenKind::Property, vec![]))
+                        }
+
+                        _ => {}
+                    }
+                }
+
+                if let Some(implementation) = statement.child_by_field_name("impl") {
+                    for method in
+                        implementation.children_by_field_name("methods", &mut implementation.walk())
+                    {
+                        if let Some(ident) = method.child_by_field_name("ident") {
+                            children.push(symbol(ident, TokenKind::Method, vec![]));
+                        }
+                    }
+                }
+
+                symbols.push(symbol(ident, TokenKind::Type, children));
+            }
+
+            _ => {}
+        }
+    }
+
+    symbols
+}
@@ -0,0 +1,620 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+
+use crate::interpreter::{
+    error::FruError,
+    expression::FruExpression,
+    identifier::Identifier,
+    manifest::MANIFEST_FILE,
+    runner::read_source_file,
+    statement::FruStatement,
+    value::{fru_value::FruValue, function::FormalParameters, function::SourceLocation},
+};
+use crate::tooling::{
+    collect_project_files, parse_recovering,
+    visitor::{walk_expression, walk_function, walk_statement, Visitor},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub rule: &'static str,
+    pub severity: Severity,
+    pub message: String,
+    pub location: Option<SourceLocation>,
+}
+
+pub trait LintRule {
+    fn name(&self) -> &'static str;
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, ast: &FruStatement) -> Vec<Finding>;
+}
+
+pub struct Finding {
+    pub location: Option<SourceLocation>,
+    pub message: String,
+}
+
+/// Severity overrides, `None` turns the rule off
+#[derive(Debug, Clone, Default)]
+pub struct LintConfig {
+    severities: HashMap<String, Option<Severity>>,
+}
+
+pub fn rules() -> Vec<Box<dyn LintRule>> {
+    vec![
+        Box::new(UnusedVariable),
+        Box::new(ShadowedVariable),
+        Box::new(RedeclaredVariable),
+        Box::new(UnreachableCode),
+        Box::new(NoEffect),
+        Box::new(SuspiciousComparison),
+    ]
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(location) = self.location {
+            write!(f, "{}:{}: ", location.line, location.column)?;
+        }
+
+        write!(f, "{}[{}]: {}", self.severity, self.rule, self.message)
+    }
+}
+
+impl LintConfig {
+    /// Reads the `[lint]` table of the manifest, where every key is a rule and value is `error`, `warning` or `off`
+    pub fn parse(project_file: &str) -> Result<LintConfig, FruError> {
+        let table: toml::Table = project_file
+            .parse()
+            .map_err(|err| FruError::new(format!("invalid {}: {}", MANIFEST_FILE, err)))?;
+
+        let mut config = LintConfig::default();
+
+        let lint = match table.get("lint") {
+            Some(toml::Value::Table(lint)) => lint,
+            Some(_) => return FruError::new_res("`lint` must be a table"),
+            None => return Ok(config),
+        };
+
+        for (rule, severity) in lint {
+            let severity = match severity.as_str() {
+                Some("error") => Some(Severity::Error),
+                Some("warning") => Some(Severity::Warning),
+                Some("off") => None,
+                _ => {
+                    return FruError::new_res(format!(
+                        "severity of `{}` must be \"error\", \"warning\" or \"off\"",
+                        rule
+                    ))
+                }
+            };
+
+            config.set(rule, severity)?;
+        }
+
+        Ok(config)
+    }
+
+    /// Uses the project file from the directory of `path` or the closest one above it
+    pub fn find(path: &Path) -> Result<LintConfig, FruError> {
+        let start = if path.is_dir() {
+            Some(path)
+        } else {
+            path.parent()
+        };
+
+        for dir in start.into_iter().flat_map(Path::ancestors) {
+            let project_file = dir.join(MANIFEST_FILE);
+
+            if project_file.is_file() {
+                let source = read_to_string(&project_file).map_err(|err| {
+                    FruError::new(format!("Error reading file {project_file:?} {err}"))
+                })?;
+
+                return LintConfig::parse(&source);
+            }
+        }
+
+        Ok(LintConfig::default())
+    }
+
+    pub fn set(&mut self, rule: &str, severity: Option<Severity>) -> Result<(), FruError> {
+        if !rules().iter().any(|x| x.name() == rule) {
+            return FruError::new_res(format!("unknown lint rule `{}`", rule));
+        }
+
+        self.severities.insert(rule.to_string(), severity);
+
+        Ok(())
+    }
+
+    fn severity_of(&self, rule: &dyn LintRule) -> Option<Severity> {
+        match self.severities.get(rule.name()) {
+            Some(severity) => *severity,
+            None => Some(rule.default_severity()),
+        }
+    }
+}
+
+pub fn lint_source(source: String, config: &LintConfig) -> Result<Vec<Diagnostic>, FruError> {
+    let (ast, mut diagnostics) = parse_recovering(source);
+
+    for rule in rules() {
+        let severity = match config.severity_of(&*rule) {
+            Some(severity) => severity,
+            None => continue,
+        };
+
+        diagnostics.extend(rule.check(&ast).into_iter().map(|finding| Diagnostic {
+            rule: rule.name(),
+            severity,
+            message: finding.message,
+            location: finding.location,
+        }));
+    }
+
+    diagnostics.sort_by_key(|x| x.location.map(|x| (x.line, x.column)));
+
+    Ok(diagnostics)
+}
+
+pub fn lint_path(
+    path: &Path,
+    config: &LintConfig,
+) -> Result<Vec<(PathBuf, Vec<Diagnostic>)>, FruError> {
+    collect_project_files(path)?
+        .into_iter()
+        .map(|file| {
+            let diagnostics = lint_source(read_source_file(&file)?, config)?;
+            Ok((file, diagnostics))
+        })
+        .collect()
+}
+
+fn location_of(statement: &FruStatement) -> Option<SourceLocation> {
+    match statement {
+        FruStatement::Located { location, .. } => Some(*location),
+        _ => None,
+    }
+}
+
+/// Keeps track of the innermost located statement
+#[derive(Default)]
+struct Findings {
+    location: Option<SourceLocation>,
+    found: Vec<Finding>,
+}
+
+impl Findings {
+    fn report(&mut self, message: String) {
+        self.found.push(Finding {
+            location: self.location,
+            message,
+        });
+    }
+
+    fn enter(&mut self, statement: &FruStatement) -> Option<SourceLocation> {
+        let previous = self.location;
+
+        if let Some(location) = location_of(statement) {
+            self.location = Some(location);
+        }
+
+        previous
+    }
+}
+
+// unused and shadowed variables
+
+pub struct UnusedVariable;
+
+pub struct ShadowedVariable;
+
+pub struct RedeclaredVariable;
+
+struct Declaration {
+    ident: Identifier,
+    location: Option<SourceLocation>,
+    function_depth: usize,
+    used: bool,
+}
+
+struct LexicalScope {
+    declarations: Vec<Declaration>,
+    // variables of the global scope and of scope modifiers can be used from other places
+    is_exported: bool,
+}
+
+#[derive(Default)]
+struct VariableAnalysis {
+    findings: Findings,
+    scopes: Vec<LexicalScope>,
+    function_depth: usize,
+    unused: Vec<Finding>,
+    shadowed: Vec<Finding>,
+    redeclared: Vec<Finding>,
+}
+
+impl VariableAnalysis {
+    fn run(ast: &FruStatement) -> VariableAnalysis {
+        let mut analysis = VariableAnalysis::default();
+        analysis.visit_statement(ast);
+        analysis
+    }
+
+    fn in_scope(&mut self, is_exported: bool, f: impl FnOnce(&mut Self)) {
+        self.scopes.push(LexicalScope {
+            declarations: Vec::new(),
+            is_exported,
+        });
+
+        f(self);
+
+        let scope = self.scopes.pop().expect("scope was pushed above");
+
+        if scope.is_exported {
+            return;
+        }
+
+        for declaration in scope.declarations {
+            if !declaration.used && !declaration.ident.to_string().starts_with('_') {
+                self.unused.push(Finding {
+                    location: declaration.location,
+                    message: format!("variable `{}` is never used", declaration.ident),
+                });
+            }
+        }
+    }
+
+    fn declare(&mut self, ident: Identifier, used: bool) {
+        let function_depth = self.function_depth;
+
+        // fails at runtime, so it is not reported as shadowing too
+        let redeclares = self
+            .scopes
+            .last()
+            .is_some_and(|scope| scope.declarations.iter().any(|x| x.ident == ident));
+
+        let shadows = !redeclares
+            && self.scopes.iter().rev().skip(1).any(|scope| {
+                !scope.is_exported
+                    && scope.declarations.iter().any(|declaration| {
+                        declaration.ident == ident && declaration.function_depth == function_depth
+                    })
+            });
+
+        if redeclares {
+            self.redeclared.push(Finding {
+                location: self.findings.location,
+                message: format!("variable `{}` is already declared in this scope", ident),
+            });
+        }
+
+        if shadows {
+            self.shadowed.push(Finding {
+                location: self.findings.location,
+                message: format!(
+                    "variable `{}` shadows a variable from an outer block",
+                    ident
+                ),
+            });
+        }
+
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.declarations.push(Declaration {
+                ident,
+                location: self.findings.location,
+                function_depth,
+                used,
+            });
+        }
+    }
+
+    fn use_variable(&mut self, ident: Identifier) {
+        for scope in self.scopes.iter_mut().rev() {
+            if let Some(declaration) =
+                scope.declarations.iter_mut().rev().find(|x| x.ident == ident)
+            {
+                declaration.used = true;
+                return;
+            }
+        }
+    }
+}
+
+impl Visitor for VariableAnalysis {
+    fn visit_statement(&mut self, statement: &FruStatement) {
+        match statement {
+            FruStatement::Located { .. } => {
+                let previous = self.findings.enter(statement);
+                walk_statement(self, statement);
+                self.findings.location = previous;
+            }
+
+            FruStatement::SourceCode { .. } => {
+                self.in_scope(true, |this| walk_statement(this, statement))
+            }
+
+            FruStatement::Block { .. } => {
+                self.in_scope(false, |this| walk_statement(this, statement))
+            }
+
+            FruStatement::ScopeModifier { what, body } => {
+                self.visit_expression(what);
+                self.in_scope(true, |this| {
+                    for statement in body {
+                        this.visit_statement(statement);
+                    }
+                });
+            }
+
+            FruStatement::Let { ident, value } => {
+                self.visit_expression(value);
+                self.declare(*ident, false);
+            }
+
+            _ => walk_statement(self, statement),
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &FruExpression) {
+        match expression {
+            FruExpression::Variable { ident } => self.use_variable(*ident),
+
+            // variables can be reached through the scope object
+            FruExpression::ScopeAccessor => {
+                for scope in &mut self.scopes {
+                    for declaration in &mut scope.declarations {
+                        declaration.used = true;
+                    }
+                }
+            }
+
+            FruExpression::Block { .. } => {
+                self.in_scope(false, |this| walk_expression(this, expression))
+            }
+
+            FruExpression::ScopeModifier { what, body, expr } => {
+                self.visit_expression(what);
+                self.in_scope(true, |this| {
+                    for statement in body {
+                        this.visit_statement(statement);
+                    }
+                    this.visit_expression(expr);
+                });
+            }
+
+            _ => walk_expression(self, expression),
+        }
+    }
+
+    fn visit_function(&mut self, parameters: Option<&FormalParameters>, body: &FruStatement) {
+        self.function_depth += 1;
+
+        self.in_scope(false, |this| {
+            if let Some(parameters) = parameters {
+                for (ident, _) in &parameters.args {
+                    this.declare(*ident, true);
+                }
+            }
+
+            walk_function(this, parameters, body);
+        });
+
+        self.function_depth -= 1;
+    }
+}
+
+impl LintRule for UnusedVariable {
+    fn name(&self) -> &'static str {
+        "unused-variable"
+    }
+
+    fn check(&self, ast: &FruStatement) -> Vec<Finding> {
+        VariableAnalysis::run(ast).unused
+    }
+}
+
+impl LintRule for ShadowedVariable {
+    fn name(&self) -> &'static str {
+        "shadowed-variable"
+    }
+
+    fn check(&self, ast: &FruStatement) -> Vec<Finding> {
+        VariableAnalysis::run(ast).shadowed
+    }
+}
+
+impl LintRule for RedeclaredVariable {
+    fn name(&self) -> &'static str {
+        "redeclared-variable"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, ast: &FruStatement) -> Vec<Finding> {
+        VariableAnalysis::run(ast).redeclared
+    }
+}
+
+// unreachable code
+
+pub struct UnreachableCode;
+
+#[derive(Default)]
+struct UnreachableVisitor {
+    findings: Findings,
+}
+
+impl UnreachableVisitor {
+    fn check_body(&mut self, body: &[FruStatement]) {
+        let jump = body.iter().position(|statement| {
+            matches!(
+                statement.unlocated(),
+                FruStatement::Return { .. } | FruStatement::Break | FruStatement::Continue
+            )
+        });
+
+        if let Some(unreachable) = jump.and_then(|jump| body.get(jump + 1)) {
+            self.findings.found.push(Finding {
+                location: location_of(unreachable),
+                message: "unreachable statement".to_string(),
+            });
+        }
+    }
+}
+
+impl Visitor for UnreachableVisitor {
+    fn visit_statement(&mut self, statement: &FruStatement) {
+        match statement {
+            FruStatement::SourceCode { body }
+            | FruStatement::Block { body }
+            | FruStatement::ScopeModifier { body, .. } => self.check_body(body),
+
+            _ => {}
+        }
+
+        walk_statement(self, statement);
+    }
+
+    fn visit_expression(&mut self, expression: &FruExpression) {
+        match expression {
+            FruExpression::Block { body, .. } | FruExpression::ScopeModifier { body, .. } => {
+                self.check_body(body)
+            }
+
+            _ => {}
+        }
+
+        walk_expression(self, expression);
+    }
+}
+
+impl LintRule for UnreachableCode {
+    fn name(&self) -> &'static str {
+        "unreachable-code"
+    }
+
+    fn check(&self, ast: &FruStatement) -> Vec<Finding> {
+        let mut visitor = UnreachableVisitor::default();
+        visitor.visit_statement(ast);
+        visitor.findings.found
+    }
+}
+
+// expression statements
+
+pub struct NoEffect;
+
+pub struct SuspiciousComparison;
+
+/// Reports expression statements for which `check` returns a message
+struct ExpressionStatementVisitor<F: Fn(&FruExpression) -> Option<String>> {
+    findings: Findings,
+    check: F,
+}
+
+impl<F: Fn(&FruExpression) -> Option<String>> Visitor for ExpressionStatementVisitor<F> {
+    fn visit_statement(&mut self, statement: &FruStatement) {
+        let previous = self.findings.enter(statement);
+
+        if let FruStatement::Expression { value } = statement {
+            if let Some(message) = (self.check)(value) {
+                self.findings.report(message);
+            }
+        }
+
+        walk_statement(self, statement);
+
+        self.findings.location = previous;
+    }
+}
+
+fn check_expression_statements(
+    ast: &FruStatement,
+    check: impl Fn(&FruExpression) -> Option<String>,
+) -> Vec<Finding> {
+    let mut visitor = ExpressionStatementVisitor {
+        findings: Findings::default(),
+        check,
+    };
+
+    visitor.visit_statement(ast);
+    visitor.findings.found
+}
+
+fn is_comparison(expression: &FruExpression) -> bool {
+    matches!(expression, FruExpression::Binary { operator, .. } if operator.to_string() == "==")
+}
+
+fn has_no_effect(expression: &FruExpression) -> bool {
+    match expression {
+        FruExpression::Literal { .. }
+        | FruExpression::Variable { .. }
+        | FruExpression::ScopeAccessor
+        | FruExpression::Function { .. } => true,
+
+        FruExpression::PropAccess { what, .. } => has_no_effect(what),
+
+        FruExpression::Binary { left, right, .. } => has_no_effect(left) && has_no_effect(right),
+
+        _ => false,
+    }
+}
+
+impl LintRule for NoEffect {
+    fn name(&self) -> &'static str {
+        "no-effect"
+    }
+
+    fn check(&self, ast: &FruStatement) -> Vec<Finding> {
+        check_expression_statements(ast, |value| {
+            // string literals are used as docstrings, comparisons have their own rule
+            let ignored = matches!(
+                value,
+                FruExpression::Literal {
+                    value: FruValue::String(_)
+                }
+            ) || is_comparison(value);
+
+            (!ignored && has_no_effect(value)).then(|| "expression has no effect".to_string())
+        })
+    }
+}
+
+impl LintRule for SuspiciousComparison {
+    fn name(&self) -> &'static str {
+        "suspicious-comparison"
+    }
+
+    fn check(&self, ast: &FruStatement) -> Vec<Finding> {
+        check_expression_statements(ast, |value| {
+            is_comparison(value).then(|| "result of `==` is unused, did you mean `=`?".to_string())
+        })
+    }
+}
@@ -0,0 +1,83 @@
+use std::{
+    fs::read_dir,
+    path::{Path, PathBuf},
+};
+
+use crate::interpreter::{
+    error::FruError,
+    manifest::{Manifest, MANIFEST_FILE},
+    statement::FruStatement,
+    tree_sitter_parser,
+    value::function::SourceLocation,
+};
+use crate::tooling::lint::{Diagnostic, Severity};
+
+pub mod check;
+pub mod doc;
+pub mod emit_js;
+pub mod highlight;
+pub mod lint;
+pub mod package;
+pub mod repl;
+pub mod visitor;
+
+/// The file itself, or every `.fru` file in the directory and its subdirectories
+pub fn collect_source_files(path: &Path) -> Result<Vec<PathBuf>, FruError> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let mut files = Vec::new();
+
+    let entries = read_dir(path)
+        .map_err(|err| FruError::new(format!("Error reading directory {path:?} {err}")))?;
+
+    for entry in entries {
+        let entry = entry.map_err(|err| FruError::new(err.to_string()))?.path();
+
+        if entry.is_dir() {
+            files.extend(collect_source_files(&entry)?);
+        } else if entry.extension().is_some_and(|ext| ext == "fru") {
+            files.push(entry);
+        }
+    }
+
+    files.sort();
+
+    Ok(files)
+}
+
+/// Source dirs of the project if `path` is its root, otherwise the same as `collect_source_files`
+pub fn collect_project_files(path: &Path) -> Result<Vec<PathBuf>, FruError> {
+    if !path.join(MANIFEST_FILE).is_file() {
+        return collect_source_files(path);
+    }
+
+    let mut files = Vec::new();
+
+    for dir in Manifest::load(path)?.source_paths() {
+        files.extend(collect_source_files(&dir)?);
+    }
+
+    Ok(files)
+}
+
+/// AST of what could be parsed and syntax errors as diagnostics, so tools report them all at once
+pub fn parse_recovering(source: String) -> (Box<FruStatement>, Vec<Diagnostic>) {
+    let (ast, errors) = tree_sitter_parser::parse_with_recovery(source);
+
+    let diagnostics = errors
+        .iter()
+        .map(|err| Diagnostic {
+            rule: "syntax-error",
+            severity: Severity::Error,
+            message: err.message(),
+            location: Some(SourceLocation {
+                line: err.range().start_point.row + 1,
+                column: err.range().start_point.column + 1,
+            }),
+        })
+        .collect();
+
+    (ast, diagnostics)
+}
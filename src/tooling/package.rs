@@ -0,0 +1,333 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs::{create_dir_all, read_to_string, rename, write},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::interpreter::{
+    error::FruError,
+    manifest::{check_name, Dependency, Manifest, MANIFEST_FILE, VENDOR_DIR},
+};
+
+/// Name of the file with exact commits of git dependencies
+pub const LOCK_FILE: &str = "fruit.lock";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Lockfile {
+    pub packages: BTreeMap<String, LockedPackage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockedPackage {
+    pub git: String,
+    pub rev: Option<String>,
+    pub commit: String,
+}
+
+impl Lockfile {
+    pub fn parse(source: &str) -> Result<Lockfile, FruError> {
+        let table: toml::Table = source
+            .parse()
+            .map_err(|err| FruError::new(format!("invalid {}: {}", LOCK_FILE, err)))?;
+
+        let mut packages = BTreeMap::new();
+
+        let entries = match table.get("package") {
+            Some(toml::Value::Array(entries)) => entries.as_slice(),
+            None => &[],
+            _ => return FruError::new_res(format!("invalid {}: bad package list", LOCK_FILE)),
+        };
+
+        for entry in entries {
+            let field = |name: &str| entry.get(name).and_then(|x| x.as_str()).map(String::from);
+
+            match (field("name"), field("git"), field("commit")) {
+                (Some(name), Some(git), Some(commit)) => {
+                    let rev = field("rev");
+                    packages.insert(name, LockedPackage { git, rev, commit });
+                }
+
+                _ => return FruError::new_res(format!("invalid {}: bad package", LOCK_FILE)),
+            }
+        }
+
+        Ok(Lockfile { packages })
+    }
+
+    /// Empty lockfile if the project has none yet
+    pub fn load(root: &Path) -> Result<Lockfile, FruError> {
+        let path = root.join(LOCK_FILE);
+
+        if !path.is_file() {
+            return Ok(Lockfile::default());
+        }
+
+        let source = read_to_string(&path)
+            .map_err(|err| FruError::new(format!("Error reading file {path:?} {err}")))?;
+
+        Lockfile::parse(&source)
+    }
+
+    pub fn save(&self, root: &Path) -> Result<(), FruError> {
+        let mut packages = toml_edit::ArrayOfTables::new();
+
+        for (name, package) in &self.packages {
+            let mut table = toml_edit::Table::new();
+            table.insert("name", toml_edit::value(name));
+            table.insert("git", toml_edit::value(&package.git));
+            if let Some(rev) = &package.rev {
+                table.insert("rev", toml_edit::value(rev));
+            }
+            table.insert("commit", toml_edit::value(&package.commit));
+            packages.push(table);
+        }
+
+        let mut document = toml_edit::DocumentMut::new();
+        document.insert("package", toml_edit::Item::ArrayOfTables(packages));
+
+        let path = root.join(LOCK_FILE);
+
+        write(
+            &path,
+            format!(
+                "# generated by `frugurt fetch`, do not edit\n\n{}",
+                document
+            ),
+        )
+        .map_err(|err| FruError::new(format!("Error writing file {path:?} {err}")))
+    }
+}
+
+/// Urls are git dependencies, everything else is a path to a project
+pub fn parse_source(source: &str) -> Dependency {
+    let is_git = ["https://", "http://", "ssh://", "git://", "file://", "git@"]
+        .iter()
+        .any(|prefix| source.starts_with(prefix))
+        || source.ends_with(".git");
+
+    if is_git {
+        Dependency::Git {
+            url: source.to_string(),
+            rev: None,
+        }
+    } else {
+        Dependency::Path(PathBuf::from(source))
+    }
+}
+
+/// Adds a dependency to the manifest of the project in `root` and fetches it,
+/// the name defaults to the name of the package being added
+pub fn add(root: &Path, source: &str, name: Option<&str>) -> Result<String, FruError> {
+    let manifest = Manifest::load(root)?;
+    let dependency = parse_source(source);
+
+    let name = match &dependency {
+        Dependency::Path(path) => match name {
+            Some(name) => name.to_string(),
+            None => Manifest::load(&root.join(path))?.name,
+        },
+
+        Dependency::Git { url, .. } => {
+            let vendor = root.join(VENDOR_DIR);
+            let guess = name.map_or_else(|| repository_name(url), String::from);
+            check_name(&guess, "dependency")?;
+
+            if manifest.dependencies.contains_key(&guess) {
+                return FruError::new_res(format!("dependency `{}` already exists", guess));
+            }
+
+            fetch_git(&vendor.join(&guess), url, None)?;
+            // the manifest checks the name of the package
+            let package = Manifest::load(&vendor.join(&guess))?.name;
+
+            match name {
+                Some(name) => name.to_string(),
+                None if package == guess => guess,
+                None => {
+                    let target = vendor.join(&package);
+
+                    if !target.exists() {
+                        rename(vendor.join(&guess), &target)
+                            .map_err(|err| FruError::new(err.to_string()))?;
+                    }
+
+                    package
+                }
+            }
+        }
+    };
+
+    check_name(&name, "dependency")?;
+
+    if manifest.dependencies.contains_key(&name) {
+        return FruError::new_res(format!("dependency `{}` already exists", name));
+    }
+
+    add_to_manifest(root, &name, &dependency)?;
+    fetch(root)?;
+
+    Ok(name)
+}
+
+/// Brings git dependencies of the project and of its dependencies to commits from lockfiles,
+/// dependencies that are not locked yet are fetched at their `rev` and locked
+pub fn fetch(root: &Path) -> Result<(), FruError> {
+    fetch_project(root, &mut HashSet::new())
+}
+
+fn fetch_project(root: &Path, visited: &mut HashSet<PathBuf>) -> Result<(), FruError> {
+    let canonical = root
+        .canonicalize()
+        .map_err(|err| FruError::new(format!("Error reading directory {root:?} {err}")))?;
+
+    if !visited.insert(canonical) {
+        return Ok(());
+    }
+
+    let manifest = Manifest::load(root)?;
+    let previous = Lockfile::load(root)?;
+    let mut lockfile = Lockfile::default();
+
+    for (name, dependency) in &manifest.dependencies {
+        let dependency_root = manifest.dependency_root(name).unwrap();
+
+        if let Dependency::Git { url, rev } = dependency {
+            // a lock entry is stale once the dependency is changed in the manifest
+            let locked = previous
+                .packages
+                .get(name)
+                .filter(|x| x.git == *url && x.rev == *rev)
+                .map(|x| x.commit.as_str());
+
+            let commit = fetch_git(&dependency_root, url, locked.or(rev.as_deref()))?;
+
+            lockfile.packages.insert(
+                name.clone(),
+                LockedPackage {
+                    git: url.clone(),
+                    rev: rev.clone(),
+                    commit,
+                },
+            );
+        }
+
+        if dependency_root.join(MANIFEST_FILE).is_file() {
+            fetch_project(&dependency_root, visited)?;
+        }
+    }
+
+    if lockfile != previous {
+        lockfile.save(root)?;
+    }
+
+    Ok(())
+}
+
+fn add_to_manifest(root: &Path, name: &str, dependency: &Dependency) -> Result<(), FruError> {
+    let path = root.join(MANIFEST_FILE);
+
+    let source = read_to_string(&path)
+        .map_err(|err| FruError::new(format!("Error reading file {path:?} {err}")))?;
+
+    // toml_edit keeps comments and formatting of the rest of the manifest
+    let mut document: toml_edit::DocumentMut = source
+        .parse()
+        .map_err(|err| FruError::new(format!("invalid {}: {}", MANIFEST_FILE, err)))?;
+
+    let dependencies = document
+        .entry("dependencies")
+        .or_insert(toml_edit::table())
+        .as_table_mut()
+        .ok_or_else(|| FruError::new("dependencies must be a table".to_string()))?;
+
+    let mut entry = toml_edit::InlineTable::new();
+
+    match dependency {
+        Dependency::Path(path) => {
+            entry.insert("path", path.to_string_lossy().as_ref().into());
+        }
+
+        Dependency::Git { url, rev } => {
+            entry.insert("git", url.as_str().into());
+            if let Some(rev) = rev {
+                entry.insert("rev", rev.as_str().into());
+            }
+        }
+    }
+
+    dependencies.insert(name, toml_edit::value(entry));
+
+    write(&path, document.to_string())
+        .map_err(|err| FruError::new(format!("Error writing file {path:?} {err}")))
+}
+
+// clones the repository if needed, checks out `rev` (or the default branch) and returns the commit
+fn fetch_git(dir: &Path, url: &str, rev: Option<&str>) -> Result<String, FruError> {
+    // manifests come from other people, their values must not be taken for options of git
+    if url.starts_with('-') {
+        return FruError::new_res(format!("git url `{}` must not start with `-`", url));
+    }
+
+    if let Some(rev) = rev.filter(|rev| rev.starts_with('-')) {
+        return FruError::new_res(format!("git rev `{}` must not start with `-`", rev));
+    }
+
+    if dir.join(".git").is_dir() {
+        git(dir, &["remote", "set-url", "--", "origin", url])?;
+        git(dir, &["fetch", "--quiet", "--tags", "origin"])?;
+    } else {
+        let parent = dir.parent().unwrap();
+
+        create_dir_all(parent)
+            .map_err(|err| FruError::new(format!("Error creating directory {parent:?} {err}")))?;
+
+        git(
+            parent,
+            &["clone", "--quiet", "--", url, &dir.to_string_lossy()],
+        )?;
+    }
+
+    let target = match rev {
+        // branches are taken from the remote, local ones are never updated
+        Some(rev) => match git(
+            dir,
+            &["rev-parse", "--verify", "--quiet", &format!("origin/{rev}")],
+        ) {
+            Ok(_) => format!("origin/{rev}"),
+            Err(_) => rev.to_string(),
+        },
+
+        None => "origin/HEAD".to_string(),
+    };
+
+    git(dir, &["checkout", "--quiet", "--detach", &target, "--"])?;
+
+    git(dir, &["rev-parse", "HEAD"])
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<String, FruError> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|err| FruError::new(format!("failed to run git: {}", err)))?;
+
+    if !output.status.success() {
+        return FruError::new_res(format!(
+            "git {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// `https://host/user/json-schema.git` -> `json_schema`
+fn repository_name(url: &str) -> String {
+    let last = url.trim_end_matches('/').rsplit(['/', ':']).next().unwrap_or(url);
+
+    last.strip_suffix(".git").unwrap_or(last).replace(['-', '.'], "_")
+}
@@ -0,0 +1,203 @@
+use std::io::{self, BufRead, Write};
+
+use crate::interpreter::{
+    error::FruError, expression::FruExpression, identifier::Identifier,
+    options::InterpreterOptions, runner::Interpreter, statement::FruStatement, tree_sitter_parser,
+    value::fru_value::FruValue,
+};
+use crate::stdlib::introspection::fru_inspect::{inspect, DEFAULT_DEPTH, DEFAULT_WIDTH};
+
+const HELP: &str = "\
+:type <expr>         type of the value of the expression
+:scope               variables declared in the session
+:operators [<type>]  operators, only the ones with the type on either side if it is given
+:ast                 parsed AST of the last input
+:help                this message
+:quit                exit";
+
+/// Interactive session, code of every input is executed in the same global scope
+pub struct Repl {
+    interpreter: Interpreter,
+    last_ast: Option<FruStatement>,
+}
+
+impl Repl {
+    pub fn new(options: InterpreterOptions) -> Repl {
+        Repl {
+            interpreter: Interpreter::new(options),
+            last_ast: None,
+        }
+    }
+
+    /// Runs code or a `:command`, returns what should be printed
+    pub fn eval(&mut self, input: &str) -> Result<Option<String>, FruError> {
+        let input = input.trim();
+
+        let (command, rest) = match input.strip_prefix(':') {
+            Some(command) => command.split_once(' ').unwrap_or((command, "")),
+            None => return self.run(input),
+        };
+
+        match command {
+            "type" => {
+                let value = self.evaluate(rest)?;
+                Ok(Some(value.get_type_identifier().to_string()))
+            }
+
+            "scope" => {
+                let mut variables: Vec<_> = self
+                    .interpreter
+                    .global_scope()
+                    .variables()
+                    .into_iter()
+                    .map(|(ident, value)| {
+                        format!("{}: {} = {:?}", ident, value.get_type_identifier(), value)
+                    })
+                    .collect();
+
+                variables.sort();
+
+                Ok(Some(variables.join("\n")).filter(|x| !x.is_empty()))
+            }
+
+            "operators" => {
+                let ident = Identifier::new(rest.trim());
+
+                let mut operators: Vec<_> = self
+                    .interpreter
+                    .global_scope()
+                    .operators()
+                    .into_iter()
+                    .map(|(op, _)| op)
+                    .filter(|op| rest.is_empty() || op.left() == ident || op.right() == ident)
+                    .map(|op| op.to_string())
+                    .collect();
+
+                operators.sort();
+
+                Ok(Some(operators.join("\n")).filter(|x| !x.is_empty()))
+            }
+
+            "ast" => match &self.last_ast {
+                Some(ast) => Ok(Some(format!("{:#?}", ast))),
+                None => FruError::new_res("nothing was entered yet"),
+            },
+
+            "help" => Ok(Some(HELP.to_string())),
+
+            _ => FruError::new_res(format!("unknown command `:{}`, see `:help`", command)),
+        }
+    }
+
+    // expressions are printed, so `1 + 2` works without a semicolon
+    fn run(&mut self, input: &str) -> Result<Option<String>, FruError> {
+        let ast = parse_input(input)?;
+        self.last_ast = Some(ast.clone());
+
+        if let Some(expression) = single_expression(&ast) {
+            let value = self.interpreter.evaluate(expression)?;
+
+            return Ok(match value {
+                FruValue::Nah => None,
+                value => Some(inspect(&value, DEFAULT_DEPTH, DEFAULT_WIDTH)),
+            });
+        }
+
+        self.interpreter.execute_ast(&ast).map(|_| None)
+    }
+
+    fn evaluate(&mut self, input: &str) -> Result<FruValue, FruError> {
+        let ast = parse_input(input)?;
+
+        match single_expression(&ast) {
+            Some(expression) => self.interpreter.evaluate(expression),
+            None => FruError::new_res("expected an expression"),
+        }
+    }
+}
+
+/// Reads inputs until `:quit` or end of input, inputs with unclosed brackets continue on the next line
+pub fn run_repl(
+    options: InterpreterOptions,
+    input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<()> {
+    let mut repl = Repl::new(options);
+    let mut buffer = String::new();
+
+    write!(output, ">>> ")?;
+    output.flush()?;
+
+    for line in input.lines() {
+        buffer.push_str(&line?);
+        buffer.push('\n');
+
+        if is_unfinished(&buffer) {
+            write!(output, "... ")?;
+            output.flush()?;
+            continue;
+        }
+
+        if matches!(buffer.trim(), ":quit" | ":q") {
+            return Ok(());
+        }
+
+        match repl.eval(&buffer) {
+            Ok(Some(text)) => writeln!(output, "{}", text)?,
+            Ok(None) => {}
+            Err(err) => writeln!(output, "{}", err)?,
+        }
+
+        buffer.clear();
+
+        write!(output, ">>> ")?;
+        output.flush()?;
+    }
+
+    Ok(())
+}
+
+fn parse_input(input: &str) -> Result<FruStatement, FruError> {
+    tree_sitter_parser::parse(input.to_string())
+        .or_else(|err| tree_sitter_parser::parse(format!("{};", input)).map_err(|_| err))
+        .map(|ast| *ast)
+        .map_err(|err| FruError::new(err.to_string()))
+}
+
+fn single_expression(ast: &FruStatement) -> Option<&FruExpression> {
+    match ast {
+        FruStatement::SourceCode { body } if body.len() == 1 => match body[0].unlocated() {
+            FruStatement::Expression { value } => Some(value),
+            _ => None,
+        },
+
+        _ => None,
+    }
+}
+
+// more `{` or `(` than closing ones, strings are skipped
+fn is_unfinished(source: &str) -> bool {
+    let mut depth = 0;
+    let mut chars = source.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '(' | '[' => depth += 1,
+            '}' | ')' | ']' => depth -= 1,
+            '"' => {
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    depth > 0
+}
@@ -0,0 +1,181 @@
+use crate::interpreter::{
+    ast_helpers::RawMethod, expression::FruExpression, statement::FruStatement,
+    value::function::FormalParameters,
+};
+
+/// Walks the AST, every method defaults to visiting the children of the node
+pub trait Visitor {
+    fn visit_statement(&mut self, statement: &FruStatement) {
+        walk_statement(self, statement)
+    }
+
+    fn visit_expression(&mut self, expression: &FruExpression) {
+        walk_expression(self, expression)
+    }
+
+    // bodies of functions, methods and operators
+    fn visit_function(&mut self, parameters: Option<&FormalParameters>, body: &FruStatement) {
+        walk_function(self, parameters, body)
+    }
+}
+
+pub fn walk_statement<V: Visitor + ?Sized>(visitor: &mut V, statement: &FruStatement) {
+    match statement {
+        FruStatement::SourceCode { body } | FruStatement::Block { body } => {
+            for statement in body {
+                visitor.visit_statement(statement);
+            }
+        }
+
+        FruStatement::ScopeModifier { what, body } => {
+            visitor.visit_expression(what);
+
+            for statement in body {
+                visitor.visit_statement(statement);
+            }
+        }
+
+        FruStatement::Expression { value }
+        | FruStatement::Let { value, .. }
+        | FruStatement::Set { value, .. } => visitor.visit_expression(value),
+
+        FruStatement::SetProp { what, value, .. } => {
+            visitor.visit_expression(what);
+            visitor.visit_expression(value);
+        }
+
+        FruStatement::If {
+            condition,
+            then_body,
+            else_body,
+        } => {
+            visitor.visit_expression(condition);
+            visitor.visit_statement(then_body);
+
+            if let Some(else_body) = else_body {
+                visitor.visit_statement(else_body);
+            }
+        }
+
+        FruStatement::While { condition, body } => {
+            visitor.visit_expression(condition);
+            visitor.visit_statement(body);
+        }
+
+        FruStatement::Return { value } => {
+            if let Some(value) = value {
+                visitor.visit_expression(value);
+            }
+        }
+
+        FruStatement::Break | FruStatement::Continue => {}
+
+        FruStatement::Operator { body, .. } => visitor.visit_function(None, body),
+
+        FruStatement::Type {
+            static_fields,
+            properties,
+            static_properties,
+            methods,
+            ..
+        } => {
+            for field in static_fields {
+                if let Some(value) = &field.value {
+                    visitor.visit_expression(value);
+                }
+            }
+
+            for property in properties.values().chain(static_properties.values()) {
+                if let Some(getter) = &property.getter {
+                    visitor.visit_expression(getter);
+                }
+
+                if let Some((_, setter)) = &property.setter {
+                    visitor.visit_function(None, setter);
+                }
+            }
+
+            for RawMethod {
+                parameters, body, ..
+            } in methods
+            {
+                visitor.visit_function(Some(parameters), body);
+            }
+        }
+
+        FruStatement::Located { statement, .. } => visitor.visit_statement(statement),
+    }
+}
+
+pub fn walk_expression<V: Visitor + ?Sized>(visitor: &mut V, expression: &FruExpression) {
+    match expression {
+        FruExpression::Literal { .. }
+        | FruExpression::Variable { .. }
+        | FruExpression::ScopeAccessor => {}
+
+        FruExpression::Function { args, body, .. } => visitor.visit_function(Some(args), body),
+
+        FruExpression::Block { body, expr } => {
+            for statement in body {
+                visitor.visit_statement(statement);
+            }
+
+            visitor.visit_expression(expr);
+        }
+
+        FruExpression::ScopeModifier { what, body, expr } => {
+            visitor.visit_expression(what);
+
+            for statement in body {
+                visitor.visit_statement(statement);
+            }
+
+            visitor.visit_expression(expr);
+        }
+
+        FruExpression::Call { what, args }
+        | FruExpression::CurryCall { what, args }
+        | FruExpression::Instantiation { what, args } => {
+            visitor.visit_expression(what);
+
+            for (_, arg) in &args.args {
+                visitor.visit_expression(arg);
+            }
+        }
+
+        FruExpression::PropAccess { what, .. } => visitor.visit_expression(what),
+
+        FruExpression::Binary { left, right, .. } => {
+            visitor.visit_expression(left);
+            visitor.visit_expression(right);
+        }
+
+        FruExpression::If {
+            condition,
+            then_body,
+            else_body,
+        } => {
+            visitor.visit_expression(condition);
+            visitor.visit_expression(then_body);
+            visitor.visit_expression(else_body);
+        }
+
+        FruExpression::Import { path } => visitor.visit_expression(path),
+    }
+}
+
+pub fn walk_function<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    parameters: Option<&FormalParameters>,
+    body: &FruStatement,
+) {
+    if let Some(parameters) = parameters {
+        for (_, default) in &parameters.args {
+            if let Some(default) = default {
+                visitor.visit_expression(default);
+            }
+        }
+    }
+
+    visitor.visit_statement(body);
+}
@@ -0,0 +1,60 @@
+use crate::run;
+
+#[test]
+fn test_inspect() {
+    run(r#"
+            let q = quote(x + 1);
+
+            assert_eq(q.kind, "Binary");
+            assert_eq(q.operator, "+");
+            assert_eq(q.left.kind, "Variable");
+            assert_eq(q.left.ident, "x");
+            assert_eq(q.right.value, 1);
+
+            let c = quote(f(1, y: 2));
+
+            assert_eq(c.what.ident, "f");
+            assert_eq(c.arg_count, 2);
+            assert_eq(c.arg(1).value, 2);
+            assert_eq(c.arg_name(0), nah);
+            assert_eq(c.arg_name(1), "y");
+        "#)
+}
+
+#[test]
+fn test_eval_in() {
+    run(r#"
+            let q = quote(x * 2);
+
+            let x = 21;
+            assert_eq(q.eval_in(scope()), 42);
+
+            let s = {
+                let x = 5;
+                scope()
+            };
+            assert_eq(q.eval_in(s), 10);
+        "#)
+}
+
+#[test]
+fn test_construct() {
+    run(r#"
+            let add = fn(a, b) { a + b };
+
+            let c = Ast.call(Ast.variable("add"), 1, b: Ast.binary("*", 2, Ast.variable("x")));
+            let x = 3;
+
+            assert_eq(c.eval_in(scope()), 7);
+            assert_eq(Ast.condition(Ast.literal(true), 1, 2).eval_in(scope()), 1);
+            assert_eq(Ast.prop(quote(v), "len").kind, "PropAccess");
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "prop `left` not found in `Variable` Ast")]
+fn test_wrong_prop() {
+    run(r#"
+            quote(x).left;
+        "#)
+}
@@ -0,0 +1,142 @@
+use crate::run;
+
+#[test]
+fn test_producer_consumer() {
+    run(r#"
+            let ch = Channel:{};
+
+            spawn(fn() {
+                let i = 1;
+                while i <= 5 {
+                    ch.send(i);
+                    i = i + 1;
+                }
+            });
+
+            let sum = 0;
+            let j = 0;
+            while j < 5 {
+                sum = sum + ch.recv();
+                j = j + 1;
+            }
+
+            assert_eq(sum, 15);
+        "#)
+}
+
+#[test]
+fn test_bounded() {
+    run(r#"
+            let ch = Channel:{ capacity: 1 };
+            assert_eq(ch.capacity, 1);
+
+            spawn(fn() {
+                assert_eq(ch.recv(), 1);
+            });
+
+            ch.send(1);
+            ch.send(2);
+
+            assert_eq(ch.len, 1);
+            assert_eq(ch.try_recv(), 2);
+            assert_eq(ch.try_recv(), nah);
+        "#)
+}
+
+#[test]
+fn test_ping_pong_between_tasks() {
+    run(r#"
+            let ping = Channel:{};
+            let pong = Channel:{};
+            let log = "";
+
+            let a = spawn(fn() {
+                let i = 0;
+                while i < 3 {
+                    ping.send(i);
+                    let n = pong.recv();
+                    log = log <> "a" <> String(n);
+                    i = i + 1;
+                }
+            });
+
+            let b = spawn(fn() {
+                let i = 0;
+                while i < 3 {
+                    let n = ping.recv();
+                    log = log <> "b" <> String(n);
+                    pong.send(n * 10);
+                    i = i + 1;
+                }
+            });
+
+            a.join();
+            b.join();
+
+            assert_eq(log, "b0a0b1a10b2a20");
+        "#)
+}
+
+#[test]
+fn test_ping_pong_with_task() {
+    run(r#"
+            let ping = Channel:{};
+            let pong = Channel:{};
+
+            spawn(fn() {
+                let n = ping.recv();
+                while n >= 0 {
+                    pong.send(n + 1);
+                    n = ping.recv();
+                }
+            });
+
+            let i = 0;
+            while i < 3 {
+                ping.send(i);
+                assert_eq(pong.recv(), i + 1);
+                i = i + 1;
+            }
+
+            ping.send(-1);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "channel is empty and no task can send to it")]
+fn test_tasks_waiting_for_each_other() {
+    run(r#"
+            let a = Channel:{};
+            let b = Channel:{};
+
+            spawn(fn() { b.send(a.recv()) });
+            spawn(fn() { a.send(b.recv()) });
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "channel is full and no task can receive from it")]
+fn test_full() {
+    run(r#"
+            let ch = Channel:{ 1 };
+            ch.send(1);
+            ch.send(2);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "channel is empty and no task can send to it")]
+fn test_empty() {
+    run(r#"
+            let ch = Channel:{};
+            ch.recv();
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "channel capacity must be a positive integer")]
+fn test_invalid_capacity() {
+    run(r#"
+            Channel:{ 0 };
+        "#)
+}
@@ -0,0 +1,65 @@
+use crate::run;
+
+#[test]
+fn test_string_chars() {
+    run(r#"
+            let s = "añ1";
+
+            assert_eq(s.len, 3);
+            assert_eq(String(s.get(1)), "ñ");
+            assert_eq(s.get(0) == Char("a"), true);
+            assert_eq(s.get(2).is_digit(), true);
+            assert_eq(s.get(1).is_alpha(), true);
+        "#)
+}
+
+#[test]
+fn test_codes() {
+    run(r#"
+            assert_eq(Char("a").code(), 97);
+            assert_eq(Char.from_code(65) <> "BC", "ABC");
+            assert_eq(Char("a") < Char("b"), true);
+            assert_eq(Char("z") != Char("z"), false);
+        "#)
+}
+
+#[test]
+fn test_tokenize() {
+    run(r#"
+            let words = 0;
+            let digits = 0;
+            let in_word = false;
+
+            "let x1 = 42;".each(fn(c) {
+                if c.is_digit() {
+                    digits = digits + 1;
+                }
+
+                if c.is_alpha() {
+                    words = words + if in_word { 0 } else { 1 };
+                    in_word = true;
+                } else {
+                    in_word = c.is_digit() && in_word;
+                }
+            });
+
+            assert_eq(words, 2);
+            assert_eq(digits, 3);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`Char` expects a string of 1 character, got \"ab\"")]
+fn test_not_one_char() {
+    run(r#"
+            Char("ab");
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "index 3 is out of `String` of length 3")]
+fn test_out_of_bounds() {
+    run(r#"
+            "abc".get(3);
+        "#)
+}
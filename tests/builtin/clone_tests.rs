@@ -0,0 +1,95 @@
+use crate::run;
+
+#[test]
+fn test_clone() {
+    run(r#"
+            class Node {
+                value;
+                next;
+            }
+
+            let a = Node:{ 1, nah };
+            let b = Node:{ 2, a };
+
+            let c = clone(b);
+            c.value = 20;
+            c.next.value = 10;
+
+            assert_eq(b.value, 2);
+            assert_eq(a.value, 10);
+        "#)
+}
+
+#[test]
+fn test_deep_clone() {
+    run(r#"
+            class Node {
+                value;
+                next;
+            }
+
+            let a = Node:{ 1, nah };
+            let b = Node:{ 2, a };
+            a.next = b;
+
+            let c = deep_clone(a);
+            c.value = 10;
+            c.next.value = 20;
+
+            assert_eq(a.value, 1);
+            assert_eq(b.value, 2);
+            assert_eq(c.next.next.value, 10);
+        "#)
+}
+
+#[test]
+fn test_collections() {
+    run(r#"
+            class Box {
+                value;
+            }
+
+            let d = Deque:{ Box:{ 1 } };
+            let shallow = clone(d);
+            let deep = deep_clone(d);
+
+            shallow.push_back(2);
+            deep.front().value = 3;
+
+            assert_eq(d.len, 1);
+            assert_eq(shallow.len, 2);
+            assert_eq(d.front().value, 1);
+
+            let m = TreeMap:{};
+            m.set("box", Box:{ 1 });
+            deep_clone(m).get("box").value = 5;
+
+            assert_eq(m.get("box").value, 1);
+        "#)
+}
+
+#[test]
+fn test_clone_override() {
+    run(r#"
+            class Handle {
+                id;
+            } impl {
+                _clone() {
+                    Handle:{ id + 100 }
+                }
+            }
+
+            assert_eq(deep_clone(Tuple(Handle:{ 1 })).get(0).id, 101);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "cannot clone `Deque` that contains itself")]
+fn test_self_containing_collection() {
+    run(r#"
+            let d = Deque:{};
+            d.push_back(d);
+
+            deep_clone(d);
+        "#)
+}
@@ -0,0 +1,60 @@
+use crate::run;
+
+#[test]
+fn test_arithmetic() {
+    run(r#"
+            let a = Complex:{ 1, 2 };
+            let b = Complex:{ re: 3, im: -1 };
+
+            assert_eq(a + b == Complex:{ 4, 1 }, true);
+            assert_eq(a - b == Complex:{ -2, 3 }, true);
+            assert_eq(a * b == Complex:{ 5, 5 }, true);
+            assert_eq(a * b / b == a, true);
+            assert_eq(Complex.i * Complex.i == Complex:{ -1, 0 }, true);
+        "#)
+}
+
+#[test]
+fn test_mixed_with_number() {
+    run(r#"
+            let z = Complex:{ 1, 2 };
+
+            assert_eq(z * 2 == Complex:{ 2, 4 }, true);
+            assert_eq(2 * z == z * 2, true);
+            assert_eq(1 - z == Complex:{ 0, -2 }, true);
+            assert_eq(1 / Complex.i == Complex:{ 0, -1 }, true);
+        "#)
+}
+
+#[test]
+fn test_methods() {
+    run(r#"
+            let z = Complex:{ 3, 4 };
+
+            assert_eq(z.re, 3);
+            assert_eq(z.im, 4);
+            assert_eq(z.abs(), 5);
+            assert_eq(z.conj() == Complex:{ 3, -4 }, true);
+            assert_eq(Complex:{ 0, 1 }.arg() * 2, 3.141592653589793);
+            assert_eq(String(z.conj()), "3-4i");
+            assert_eq(String(Complex.polar(2, 0)), "2+0i");
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "division by zero")]
+fn test_division_by_zero() {
+    run(r#"
+            Complex:{ 1, 1 } / Complex:{ 0, 0 };
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "prop `re` is read-only")]
+fn test_read_only() {
+    run(r#"
+            let z = Complex:{ 3, 4 };
+            assert_eq(props_of(z), Tuple("re", "im"));
+            z.re = 0;
+        "#)
+}
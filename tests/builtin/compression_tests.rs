@@ -0,0 +1,114 @@
+use std::io::Write;
+
+use flate2::{write::GzEncoder, Compression};
+use zip::{write::FileOptions, ZipWriter};
+
+use crate::run;
+
+#[test]
+fn test_gzip_round_trip() {
+    run(r#"
+            let text = "name,amount\n" * 100;
+            let compressed = Gzip.compress(text);
+
+            assert_eq(compressed.len < text.len, true);
+            assert_eq(Gzip.decompress(compressed), text);
+            assert_eq(Gzip.decompress(Gzip.compress("привет")), "привет");
+        "#);
+}
+
+#[test]
+#[should_panic(expected = "is not a hex string of bytes")]
+fn test_gzip_decompress_not_hex() {
+    run(r#"
+            Gzip.decompress("hello");
+        "#);
+}
+
+#[test]
+fn test_gzip_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let export = dir.path().join("export.csv.gz");
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(b"id,total\n1,20\n").unwrap();
+    std::fs::write(&export, encoder.finish().unwrap()).unwrap();
+
+    run(&format!(
+        r#"
+            assert_eq(Gzip.read({:?}), "id,total\n1,20\n");
+
+            let path = {:?};
+            Gzip.write(path, "written");
+            assert_eq(Gzip.read(path), "written");
+        "#,
+        export,
+        dir.path().join("out.gz")
+    ));
+}
+
+#[test]
+fn test_zip() {
+    let dir = tempfile::tempdir().unwrap();
+    let archive = dir.path().join("export.zip");
+
+    let mut writer = ZipWriter::new(std::fs::File::create(&archive).unwrap());
+    writer.add_directory("data/", FileOptions::default()).unwrap();
+    writer.start_file("data/users.csv", FileOptions::default()).unwrap();
+    writer.write_all(b"id,name\n1,ann\n").unwrap();
+    writer.start_file("README", FileOptions::default()).unwrap();
+    writer.write_all(b"exported").unwrap();
+    writer.finish().unwrap();
+
+    run(&format!(
+        r#"
+            let archive = Zip.open({0:?});
+            let target = {1:?};
+
+            assert_eq(archive.entries(), Tuple("data/", "data/users.csv", "README"));
+            assert_eq(archive.read("README"), "exported");
+
+            archive.extract("data/users.csv", target);
+            assert_eq(Fs.read(target), "id,name\n1,ann\n");
+
+            with(archive, fn(archive) {{}});
+            assert_eq(archive.path, {0:?});
+        "#,
+        archive,
+        dir.path().join("users.csv")
+    ));
+}
+
+#[test]
+#[should_panic(expected = "is closed")]
+fn test_zip_closed() {
+    let dir = tempfile::tempdir().unwrap();
+    let archive = dir.path().join("empty.zip");
+
+    ZipWriter::new(std::fs::File::create(&archive).unwrap()).finish().unwrap();
+
+    run(&format!(
+        r#"
+            let archive = Zip.open({:?});
+            with(archive, fn(archive) {{}});
+            archive.entries();
+        "#,
+        archive
+    ));
+}
+
+#[test]
+#[should_panic(expected = "cannot read `missing.txt`")]
+fn test_zip_missing_entry() {
+    let dir = tempfile::tempdir().unwrap();
+    let archive = dir.path().join("empty.zip");
+
+    ZipWriter::new(std::fs::File::create(&archive).unwrap()).finish().unwrap();
+
+    run(&format!(
+        r#"
+            Zip.open({:?}).read("missing.txt");
+        "#,
+        archive
+    ));
+}
@@ -0,0 +1,69 @@
+use crate::run;
+
+#[test]
+fn test_bool() {
+    run(r#"
+            assert_eq(Bool(nah), false);
+            assert_eq(Bool(0), false);
+            assert_eq(Bool(""), false);
+            assert_eq(Bool(false), false);
+
+            assert_eq(Bool(-1), true);
+            assert_eq(Bool("false"), true);
+            assert_eq(Bool(print), true);
+
+            if Bool("text") {
+            } else {
+                assert_eq(1, 2);
+            }
+        "#)
+}
+
+#[test]
+fn test_number() {
+    run(r#"
+            assert_eq(Number(" 4.5 "), 4.5);
+            assert_eq(Number(true), 1);
+            assert_eq(Number(false), 0);
+            assert_eq(Number(7), 7);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "cannot convert \"four\" to `Number`")]
+fn test_number_error() {
+    run(r#"
+            Number("four");
+        "#)
+}
+
+#[test]
+fn test_string() {
+    run(r#"
+            struct Point { x; y; }
+
+            assert_eq(String(42), "42");
+            assert_eq(String(nah), "nah");
+            assert_eq(String(Point:{ 1, 2 }), "Point{x=1, y=2}");
+            assert_eq(String(1) <> String(true), "1true");
+        "#)
+}
+
+#[test]
+fn test_static_methods() {
+    run(r#"
+            assert_eq(Number.parse(" 12 "), 12);
+            assert_eq(Bool(Number.parse("twelve")), false);
+            assert_eq(String.from_chars(Tuple(Char("h"), "i")), "hi");
+            assert_eq(Bool.from(""), false);
+            assert_eq(String(Number), "Number");
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`from_chars` expects characters, got `Number`")]
+fn test_from_chars_wrong_item() {
+    run(r#"
+            String.from_chars(Tuple(1));
+        "#)
+}
@@ -0,0 +1,51 @@
+use crate::run;
+
+#[test]
+fn test_exact_arithmetic() {
+    run(r#"
+            let a = Decimal("0.1");
+            let b = Decimal("0.2");
+
+            assert_eq(a + b == Decimal("0.3"), true);
+            assert_eq(String(a + b), "0.3");
+            assert_eq(String(Decimal("1.10") * 3), "3.30");
+            assert_eq(String(Decimal("-0.05") - 1), "-1.05");
+            assert_eq(String(10 / Decimal("4")), "2.5");
+            assert_eq(String(Decimal(1) / 3), "0.33333333333333333333");
+        "#)
+}
+
+#[test]
+fn test_comparison() {
+    run(r#"
+            assert_eq(Decimal("0.10") == Decimal("0.1"), true);
+            assert_eq(Decimal("2.5") > 2, true);
+            assert_eq(1 <= Decimal("0.999"), false);
+        "#)
+}
+
+#[test]
+fn test_methods() {
+    run(r#"
+            assert_eq(String(Decimal("2.345").round(2)), "2.34");
+            assert_eq(String(Decimal("2.355").round(2)), "2.36");
+            assert_eq(String(Decimal:{ "7" }.round(2)), "7");
+            assert_eq(Decimal("0.25").to_number(), 0.25);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "cannot convert 0.1 to `Decimal` exactly")]
+fn test_fractional_number() {
+    run(r#"
+            Decimal("1") + 0.1;
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "invalid decimal \"1.2.3\"")]
+fn test_invalid() {
+    run(r#"
+            Decimal("1.2.3");
+        "#)
+}
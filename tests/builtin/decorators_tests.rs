@@ -0,0 +1,121 @@
+use crate::run;
+
+#[test]
+fn test_memoize() {
+    run(r#"
+            let calls = 0;
+
+            let square = memoize(fn(x) {
+                calls = calls + 1;
+                x * x
+            });
+
+            assert_eq(square(3), 9);
+            assert_eq(square(3), 9);
+            assert_eq(square(4), 16);
+            assert_eq(calls, 2);
+        "#)
+}
+
+#[test]
+fn test_memoize_recursive() {
+    run(r#"
+            let calls = 0;
+
+            let fib = memoize(fn(n) {
+                calls = calls + 1;
+                if n < 2 {
+                    return n;
+                }
+                fib(n - 1) + fib(n - 2)
+            });
+
+            assert_eq(fib(20), 6765);
+            assert_eq(calls, 21);
+        "#)
+}
+
+#[test]
+fn test_memoize_max_entries() {
+    run(r#"
+            let calls = 0;
+
+            let square = memoize(fn(x) {
+                calls = calls + 1;
+                x * x
+            }, max_entries: 2);
+
+            square(1);
+            square(2);
+            square(1);
+            square(3); // evicts 2, which is the least recently used
+            assert_eq(calls, 3);
+
+            square(1);
+            assert_eq(calls, 3);
+
+            square(2);
+            assert_eq(calls, 4);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`max_entries` must be a positive integer")]
+fn test_memoize_bad_max_entries() {
+    run(r#"
+            memoize(fn(x) { x }, 0);
+        "#)
+}
+
+#[test]
+fn test_memoize_composite() {
+    run(r#"
+            struct Point {
+                x;
+                y;
+            }
+
+            let calls = 0;
+
+            let norm = memoize(fn(p, t) {
+                calls = calls + 1;
+                p.x * p.x + p.y * p.y + t.get(0)
+            });
+
+            assert_eq(norm(Point :{ 3, 4 }, Tuple(1)), 26);
+            assert_eq(norm(Point :{ 3, 4 }, Tuple(1)), 26);
+            assert_eq(calls, 1);
+
+            assert_eq(norm(Point :{ 4, 3 }, Tuple(1)), 26);
+            assert_eq(norm(Point :{ 3, 4 }, Tuple(2)), 27);
+            assert_eq(calls, 3);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`memoize` can't remember calls with `Function` arguments")]
+fn test_memoize_not_a_value() {
+    run(r#"
+            let id = memoize(fn(x) { x });
+
+            id(fn() {});
+        "#)
+}
+
+#[test]
+fn test_timed_and_deprecated() {
+    run(r#"
+            let add = deprecated(timed(fn(a, b) { a + b }, "add"), "use `+` instead");
+
+            assert_eq(add(1, 2), 3);
+            assert_eq(add(b: 5, a: 1), 6);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`memoize` expects a function, got `Number`")]
+fn test_memoize_number() {
+    run(r#"
+            memoize(5);
+        "#)
+}
@@ -0,0 +1,25 @@
+use crate::run;
+
+#[test]
+fn test_basics() {
+    run(r#"
+            let sql = dedent("\
+                SELECT name\
+                  FROM users\
+\
+                 WHERE id = 1\
+            ");
+
+            assert_eq(sql, "SELECT name\n  FROM users\n\n WHERE id = 1\n");
+            assert_eq(dedent("  a\n    b"), "a\n  b");
+            assert_eq(dedent(""), "");
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`dedent` expects `String`, got `Number`")]
+fn test_not_a_string() {
+    run(r#"
+            dedent(1);
+        "#)
+}
@@ -0,0 +1,66 @@
+use crate::run;
+
+#[test]
+fn test_docstring() {
+    run(r#"
+            let add = fn(a, b) {
+                "Adds two numbers.";
+                a + b
+            };
+
+            assert_eq(doc_of(add), "Adds two numbers.");
+            assert_eq(add(1, 2), 3);
+        "#)
+}
+
+#[test]
+fn test_doc_comment() {
+    run(r#"
+            /// Multiplies
+            /// two numbers.
+            let mul = fn(a, b) { a * b };
+
+            assert_eq(doc_of(mul), "Multiplies\ntwo numbers.");
+            assert_eq(doc_of(mul$(2)), "Multiplies\ntwo numbers.");
+        "#)
+}
+
+#[test]
+fn test_type_doc() {
+    run(r#"
+            /// Point on a plane.
+            struct Point {
+                x;
+                y;
+            } impl {
+                /// Length of the vector.
+                len() {
+                    x * x + y * y
+                }
+
+                static origin() {
+                    "Point at zero.";
+                    Point:{ 0, 0 }
+                }
+            }
+
+            let p = Point:{ 1, 2 };
+
+            assert_eq(doc_of(Point), "Point on a plane.");
+            assert_eq(doc_of(p), "Point on a plane.");
+            assert_eq(doc_of(p.len), "Length of the vector.");
+            assert_eq(doc_of(Point.origin), "Point at zero.");
+        "#)
+}
+
+#[test]
+fn test_no_doc() {
+    run(r#"
+            // ordinary comment
+            let f = fn() {};
+
+            assert_eq(doc_of(f), nah);
+            assert_eq(doc_of(print), nah);
+            assert_eq(doc_of(5), nah);
+        "#)
+}
@@ -0,0 +1,79 @@
+use crate::run;
+
+#[test]
+fn test_on_emit() {
+    run(r#"
+            let events = Events:{};
+            let log = "";
+
+            events.on("hit", fn(who, damage) {
+                log = log <> who <> ":" <> (damage * "!");
+            });
+            events.on("hit", fn(who, damage) {
+                log = log <> ";";
+            });
+
+            assert_eq(events.emit("hit", "orc", 3), 2);
+            assert_eq(events.emit("miss"), 0);
+            assert_eq(log, "orc:!!!;");
+        "#)
+}
+
+#[test]
+fn test_off() {
+    run(r#"
+            let events = Events:{};
+            let count = 0;
+
+            let handler = events.on("tick", fn() { count = count + 1; });
+            events.on("tick", fn() { count = count + 10; });
+
+            events.emit("tick");
+            events.off("tick", handler);
+            events.emit("tick");
+            events.off("tick");
+            events.emit("tick");
+
+            assert_eq(count, 21);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "event name must be `String`, got `Number`")]
+fn test_invalid_name() {
+    run(r#"
+            let events = Events:{};
+            events.emit(1);
+        "#)
+}
+
+#[test]
+fn test_watch() {
+    run(r#"
+            class Player {
+                health;
+                name;
+            }
+
+            let player = Player:{ 10, "hero" };
+            let events = Events:{};
+            let log = "";
+
+            events.on("health", fn(old, new) {
+                log = log <> String(old) <> "->" <> String(new) <> ";";
+            });
+            events.on("renamed", fn(old, new) {
+                log = log <> old <> "->" <> new <> ";";
+            });
+
+            let unwatch = events.watch(player, "health");
+            events.watch(player, "name", "renamed");
+
+            player.health = 7;
+            player.name = "villain";
+            unwatch();
+            player.health = 3;
+
+            assert_eq(log, "10->7;hero->villain;");
+        "#)
+}
@@ -0,0 +1,227 @@
+use std::fs::{create_dir_all, write};
+
+use tempfile::TempDir;
+
+use crate::run;
+
+fn project() -> TempDir {
+    let dir = tempfile::tempdir().unwrap();
+
+    for path in [
+        "src/main.fru",
+        "src/util/strings.fru",
+        "src/util/notes.txt",
+        "README.md",
+    ] {
+        let path = dir.path().join(path);
+        create_dir_all(path.parent().unwrap()).unwrap();
+        write(path, "").unwrap();
+    }
+
+    dir
+}
+
+#[test]
+fn test_walk() {
+    let dir = project();
+
+    run(&format!(
+        r#"
+            let root = {:?};
+            let found = "";
+
+            Fs.walk(root).each(fn(path) {{
+                found = found <> path <> ";";
+            }});
+
+            let expected = Template("{{r}}/README.md;{{r}}/src;{{r}}/src/main.fru;{{r}}/src/util;{{r}}/src/util/notes.txt;{{r}}/src/util/strings.fru;");
+            assert_eq(found, expected.render(r: root));
+        "#,
+        dir.path()
+    ))
+}
+
+#[test]
+fn test_walk_is_lazy() {
+    let dir = project();
+
+    run(&format!(
+        r#"
+            let paths = Fs.walk({:?});
+
+            assert_eq(Bool(paths.next()), true);
+            paths.each(fn(path) {{}});
+            assert_eq(paths.next(), nah);
+        "#,
+        dir.path()
+    ))
+}
+
+#[test]
+fn test_glob() {
+    let dir = project();
+
+    run(&format!(
+        r#"
+            let root = {:?};
+            let found = "";
+
+            Fs.glob(root <> "/**/*.fru").each(fn(path) {{
+                found = found <> path <> ";";
+            }});
+
+            assert_eq(found, root <> "/src/main.fru;" <> root <> "/src/util/strings.fru;");
+            assert_eq(Fs.glob(root <> "/src/*/n?tes.txt").next(), root <> "/src/util/notes.txt");
+            assert_eq(Fs.glob(root <> "/missing.fru").next(), nah);
+        "#,
+        dir.path()
+    ))
+}
+
+#[test]
+fn test_write_atomic() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+
+    run(&format!(
+        r#"
+            let path = {:?};
+
+            Fs.write_atomic(path, "version = 1");
+            Fs.write_atomic(path, "version = 2");
+            assert_eq(Fs.read(path), "version = 2");
+        "#,
+        path
+    ));
+
+    // only the target is left, the temporary file was renamed over it
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn test_temp_paths() {
+    run(r#"
+            let dir = Fs.temp_dir();
+            let file = Fs.temp_file();
+
+            Fs.write(file.path, "scratch");
+            Fs.write(dir.path <> "/a.txt", "a");
+
+            assert_eq(Fs.read(file.path), "scratch");
+            assert_eq(Fs.walk(dir.path).next(), dir.path <> "/a.txt");
+        "#)
+}
+
+#[test]
+fn test_watch() {
+    let dir = tempfile::tempdir().unwrap();
+
+    run(&format!(
+        r#"
+            let dir = {:?};
+            let events = "";
+
+            let watcher = Fs.watch(dir, fn(event, path) {{
+                events = events <> event <> " " <> path <> ";";
+            }}, 10);
+
+            Fs.write(dir <> "/a.txt", "a");
+            sleep(50);
+            Fs.write(dir <> "/a.txt", "longer");
+            sleep(50);
+            watcher.cancel();
+
+            assert_eq(events, "created " <> dir <> "/a.txt;modified " <> dir <> "/a.txt;");
+        "#,
+        dir.path()
+    ));
+}
+
+#[test]
+fn test_watch_removed() {
+    run(r#"
+            let file = Fs.temp_file();
+            let path = file.path;
+            let events = "";
+
+            let watcher = Fs.watch(path, fn(event, path) {
+                events = events <> event <> ";";
+            }, 10);
+
+            file = nah;
+            sleep(50);
+            watcher.cancel();
+
+            assert_eq(events, "removed;");
+        "#)
+}
+
+#[test]
+fn test_open_lines() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.log");
+    write(&path, "INFO start\r\nERROR disk\nINFO stop\n").unwrap();
+
+    run(&format!(
+        r#"
+            let file = Fs.open({:?});
+
+            let lines = file.lines();
+            assert_eq(lines.next(), "INFO start");
+
+            let errors = 0;
+            lines.each(fn(line) {{
+                if Regex("^ERROR").is_match(line) {{
+                    errors = errors + 1;
+                }}
+            }});
+
+            assert_eq(errors, 1);
+            assert_eq(lines.next(), nah);
+            assert_eq(file.lines().next(), "INFO start");
+        "#,
+        path
+    ));
+}
+
+#[test]
+#[should_panic(expected = "is closed")]
+fn test_with_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.log");
+    write(&path, "first\nsecond\n").unwrap();
+
+    run(&format!(
+        r#"
+            let lines = with(Fs.open({:?}), fn(file) {{
+                let lines = file.lines();
+                assert_eq(lines.next(), "first");
+                lines
+            }});
+
+            lines.next();
+        "#,
+        path
+    ));
+}
+
+#[test]
+#[should_panic(expected = "cannot read")]
+fn test_with_temp_file() {
+    run(r#"
+            let path = with(Fs.temp_file(), fn(file) {
+                Fs.write(file.path, "scratch");
+                file.path
+            });
+
+            Fs.read(path);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "cannot open")]
+fn test_open_missing() {
+    run(r#"
+            Fs.open("this/file/does/not/exist.txt");
+        "#)
+}
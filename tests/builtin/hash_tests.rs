@@ -0,0 +1,68 @@
+use frugurt::interpreter::options::{Capabilities, InterpreterOptions};
+
+use crate::{run, run_with_options};
+
+#[test]
+fn test_secure_bytes() {
+    run(r#"
+            let token = Random.secure_bytes(16);
+            assert_eq(token.len, 32);
+            assert_eq(token == Random.secure_bytes(16), false);
+            assert_eq(Random.secure_bytes(0), "");
+        "#);
+}
+
+#[test]
+#[should_panic(expected = "capability `random` is not granted")]
+fn test_secure_bytes_denied() {
+    run_with_options(
+        r#"
+            Random.secure_bytes(16);
+        "#,
+        InterpreterOptions {
+            capabilities: Capabilities::none(),
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn test_sha256() {
+    run(r#"
+            assert_eq(Hash.sha256(""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+            assert_eq(Hash.sha256("abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        "#);
+}
+
+// the first one is test case 2 of RFC 4231, the last key is longer than a block
+#[test]
+fn test_hmac_sha256() {
+    run(r#"
+            assert_eq(
+                Hash.hmac_sha256("Jefe", "what do ya want for nothing?"),
+                "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+            );
+
+            assert_eq(
+                Hash.hmac_sha256("key", "The quick brown fox jumps over the lazy dog"),
+                "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+            );
+
+            assert_eq(
+                Hash.hmac_sha256("k" * 100, "long key"),
+                "5903eedefe0bba3f15b658715e41d71f1848739bddf19e497e968df5723dcd56"
+            );
+        "#);
+}
+
+#[test]
+fn test_equals_constant_time() {
+    run(r#"
+            let signature = Hash.hmac_sha256("secret", "body");
+
+            assert_eq(Hash.equals_constant_time(signature, Hash.hmac_sha256("secret", "body")), true);
+            assert_eq(Hash.equals_constant_time(signature, Hash.hmac_sha256("other", "body")), false);
+            assert_eq(Hash.equals_constant_time("abc", "ab"), false);
+            assert_eq(Hash.equals_constant_time("", ""), true);
+        "#);
+}
@@ -0,0 +1,86 @@
+use crate::run;
+
+#[test]
+fn test_flat() {
+    run(r#"
+            struct Point {
+                x;
+                y;
+            }
+
+            assert_eq(inspect(Point:{ 1, "two" }), "Point{x: 1, y: \"two\"}");
+            assert_eq(inspect(Deque:{ 1, 2 }), "Deque[1, 2]");
+            assert_eq(inspect(5), "5");
+            assert_eq(inspect("text"), "\"text\"");
+        "#)
+}
+
+#[test]
+fn test_width() {
+    run(r#"
+            struct Point {
+                x;
+                y;
+            }
+
+            let m = TreeMap:{};
+            m.set("a", Point:{ 1, 2 });
+            m.set("b", Deque:{});
+
+            assert_eq(
+                inspect(m, 6, 30),
+                "TreeMap{\n    \"a\": Point{x: 1, y: 2},\n    \"b\": Deque[],\n}"
+            );
+        "#)
+}
+
+#[test]
+fn test_depth() {
+    run(r#"
+            struct Box {
+                value;
+            }
+
+            let b = Box:{ Box:{ Deque:{ 1 } } };
+
+            assert_eq(inspect(b, 1), "Box{value: Box{...}}");
+            assert_eq(inspect(b, 0), "Box{...}");
+        "#)
+}
+
+#[test]
+fn test_cycle() {
+    run(r#"
+            class Node {
+                value;
+                next;
+            }
+
+            let a = Node:{ 1, nah };
+            let b = Node:{ 2, a };
+            a.next = b;
+
+            assert_eq(inspect(a), "&1 Node{value: 1, next: Node{value: 2, next: *1}}");
+        "#)
+}
+
+#[test]
+fn test_shared() {
+    run(r#"
+            struct Box {
+                value;
+            }
+
+            let shared = Box:{ 1 };
+
+            assert_eq(inspect(Deque:{ shared, shared }), "Deque[&1 Box{value: 1}, *1]");
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "depth of `inspect` must be a non-negative integer")]
+fn test_wrong_depth() {
+    run(r#"
+            inspect(1, -1);
+        "#)
+}
@@ -0,0 +1,51 @@
+use crate::run;
+
+#[test]
+fn test_format_number() {
+    run(r#"
+        assert_eq(Locale.format_number(1234567.5, "en-US"), "1,234,567.5");
+        assert_eq(Locale.format_number(1234567.5, "de-DE"), "1.234.567,5");
+        assert_eq(Locale.format_number(-1234.5, "ru", 2), "-1\u{a0}234,50");
+        assert_eq(Locale.format_number(1234, "es-ES"), "1234");
+        assert_eq(Locale.format_number(12345, "es_es"), "12.345");
+    "#);
+}
+
+#[test]
+fn test_format_date() {
+    // 2024-03-05 12:00 UTC
+    run(r#"
+        let t = 1709640000;
+
+        assert_eq(Locale.format_date(t, "en-US"), "Mar 5, 2024");
+        assert_eq(Locale.format_date(t, "en-US", "short"), "3/5/24");
+        assert_eq(Locale.format_date(t, "en-GB", "short"), "05/03/2024");
+        assert_eq(Locale.format_date(t, "de", "long"), "5. März 2024");
+        assert_eq(Locale.format_date(t, "es-ES", "long"), "5 de marzo de 2024");
+        assert_eq(Locale.format_date(t, "ja-JP", "long"), "2024年3月5日");
+    "#);
+}
+
+#[test]
+fn test_dates_before_epoch() {
+    run(r#"
+        assert_eq(Locale.format_date(-1, "en-GB", "short"), "31/12/1969");
+        assert_eq(Locale.format_date(951782400, "en-GB", "long"), "29 February 2000");
+    "#);
+}
+
+#[test]
+#[should_panic(expected = "unknown locale \"xx-XX\"")]
+fn test_unknown_locale() {
+    run(r#"
+        Locale.format_number(1, "xx-XX");
+    "#);
+}
+
+#[test]
+#[should_panic(expected = "unknown date style \"tiny\"")]
+fn test_unknown_style() {
+    run(r#"
+        Locale.format_date(0, "en-US", "tiny");
+    "#);
+}
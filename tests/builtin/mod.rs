@@ -1 +1,41 @@
 mod asset_eq_tests;
+mod ast_tests;
+mod channel_tests;
+mod char_tests;
+mod clone_tests;
+mod complex_tests;
+#[cfg(feature = "compression")]
+mod compression_tests;
+mod conversion_tests;
+mod decimal_tests;
+mod decorators_tests;
+mod dedent_tests;
+mod doc_of_tests;
+mod events_tests;
+mod fs_tests;
+mod hash_tests;
+mod inspect_tests;
+#[cfg(feature = "locale")]
+mod locale_tests;
+mod number_tests;
+mod observe_tests;
+mod operators_of_tests;
+mod props_of_tests;
+mod queue_tests;
+mod regex_tests;
+#[cfg(feature = "sqlite")]
+mod sqlite_tests;
+mod store_tests;
+mod string_tests;
+mod sys_tests;
+mod task_tests;
+mod template_tests;
+mod timer_tests;
+mod tree_tests;
+mod tuple_tests;
+mod type_of_tests;
+mod units_tests;
+mod vector_tests;
+mod weak_tests;
+mod with_tests;
+mod worker_tests;
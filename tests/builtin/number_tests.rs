@@ -0,0 +1,51 @@
+use crate::run;
+
+#[test]
+fn test_parse_number() {
+    run(r#"
+            assert_eq(parse_number(" 42 "), 42);
+            assert_eq(parse_number("-1.5e3"), -1500);
+            assert_eq(parse_number("forty two"), nah);
+            assert_eq(parse_number(""), nah);
+            assert_eq(parse_number("inf"), nah);
+        "#)
+}
+
+#[test]
+fn test_to_fixed() {
+    run(r#"
+            assert_eq(to_fixed(3.14159, 2), "3.14");
+            assert_eq(to_fixed(2, 3), "2.000");
+            assert_eq(to_fixed(2.5, 0), "2");
+        "#)
+}
+
+#[test]
+fn test_to_precision() {
+    run(r#"
+            assert_eq(to_precision(123.456, 4), "123.5");
+            assert_eq(to_precision(0.000123, 2), "0.00012");
+            assert_eq(to_precision(123456, 2), "1.2e+5");
+            assert_eq(to_precision(0.0000001234, 3), "1.23e-7");
+            assert_eq(to_precision(9.99, 2), "10");
+        "#)
+}
+
+#[test]
+fn test_format_number() {
+    run(r#"
+            assert_eq(format_number(1234567.891, 2), "1,234,567.89");
+            assert_eq(format_number(-1234), "-1,234");
+            assert_eq(format_number(999), "999");
+            assert_eq(format_number(0.5), "0.5");
+            assert_eq(format_number(-0.001, 1), "0.0");
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "number of digits must be an integer from 1 to 100")]
+fn test_wrong_digits() {
+    run(r#"
+            to_precision(5, 0);
+        "#)
+}
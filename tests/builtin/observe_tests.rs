@@ -0,0 +1,49 @@
+use crate::run;
+
+#[test]
+fn test_observe() {
+    run(r#"
+            class Model {
+                count;
+            } impl {
+                inc() {
+                    count = count + 1;
+                }
+            }
+
+            let m = Model:{ 0 };
+            let log = "";
+
+            let stop = observe(m, "count", fn(old, new) {
+                log = log <> String(old) <> "->" <> String(new) <> ";";
+            });
+
+            m.count = 5;
+            m.inc();
+            stop();
+            m.inc();
+
+            assert_eq(log, "0->5;5->6;");
+            assert_eq(m.count, 7);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`Model` has no field `total`")]
+fn test_unknown_field() {
+    run(r#"
+            class Model {
+                count;
+            }
+
+            observe(Model:{ 0 }, "total", fn(old, new) {});
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`observe` expects an object, got `Number`")]
+fn test_not_object() {
+    run(r#"
+            observe(1, "count", fn(old, new) {});
+        "#)
+}
@@ -0,0 +1,49 @@
+use crate::run;
+
+#[test]
+fn test_user_type() {
+    run(r#"
+            struct Vector {
+                x;
+                y;
+            }
+
+            operator + (a : Vector, b : Vector) {
+                Vector:{ a.x + b.x, a.y + b.y }
+            }
+
+            commutative operator * (k : Number, v : Vector) {
+                Vector:{ k * v.x, k * v.y }
+            }
+
+            assert_eq(operators_of(Vector), "Number * Vector\nVector * Number\nVector + Vector");
+        "#)
+}
+
+#[test]
+fn test_builtin_type() {
+    run(r#"
+            assert_eq(operators_of("Bool"), "Bool && Bool\nBool || Bool");
+        "#)
+}
+
+#[test]
+fn test_scope() {
+    run(r#"
+            let f = fn() {
+                operator <> (a : Bool, b : Number) {
+                    b
+                }
+
+                scope()
+            };
+
+            assert_eq(operators_of("Bool", f()), "Bool && Bool\nBool <> Number\nBool || Bool");
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`operators_of` expects a type or a name of a type, got `Number`")]
+fn test_wrong_argument() {
+    run("operators_of(5);")
+}
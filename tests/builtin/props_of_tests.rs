@@ -0,0 +1,35 @@
+use crate::run;
+
+#[test]
+fn test_object() {
+    run(r#"
+            struct Point {
+                x;
+                y;
+            }
+
+            assert_eq(props_of(Point:{ 1, 2 }), Tuple("x", "y"));
+        "#)
+}
+
+#[test]
+fn test_native_object() {
+    run(r#"
+            let f = fn() {
+                let b = 2;
+                let a = 1;
+                scope()
+            };
+
+            assert_eq(props_of(Tuple(1, 2)), Tuple("len"));
+            assert_eq(props_of(f()), Tuple("a", "b"));
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`props_of` expects an object, got `Number`")]
+fn test_wrong_value() {
+    run(r#"
+            props_of(1);
+        "#)
+}
@@ -0,0 +1,78 @@
+use crate::run;
+
+#[test]
+fn test_deque() {
+    run(r#"
+            let d = Deque:{ 2, 3 };
+            d.push_front(1);
+            d.push_back(4);
+
+            assert_eq(d.len, 4);
+            assert_eq(String(d), "Deque[1, 2, 3, 4]");
+            assert_eq(d.front(), 1);
+            assert_eq(d.back(), 4);
+            assert_eq(d.pop_front(), 1);
+            assert_eq(d.pop_back(), 4);
+
+            let sum = 0;
+            d.each(fn(x) {
+                sum = sum + x;
+            });
+            assert_eq(sum, 5);
+
+            d.pop_back();
+            d.pop_back();
+            assert_eq(d.pop_back(), nah);
+            assert_eq(d.front(), nah);
+        "#)
+}
+
+#[test]
+fn test_heap() {
+    run(r#"
+            let h = Heap:{};
+            h.push(5);
+            h.push(1);
+            h.push(4);
+            h.push(2);
+
+            assert_eq(h.len, 4);
+            assert_eq(h.peek(), 1);
+
+            let order = "";
+            while h.len > 0 {
+                order = order <> String(h.pop());
+            }
+            assert_eq(order, "1245");
+            assert_eq(h.pop(), nah);
+        "#)
+}
+
+#[test]
+fn test_heap_comparator() {
+    run(r#"
+            struct Job {
+                name;
+                priority;
+            }
+
+            let h = Heap:{ less: fn(a, b) { a.priority > b.priority } };
+            h.push(Job:{ "low", 1 });
+            h.push(Job:{ "high", 10 });
+            h.push(Job:{ "mid", 5 });
+
+            assert_eq(h.pop().name, "high");
+            assert_eq(h.pop().name, "mid");
+            assert_eq(h.pop().name, "low");
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`less` of `Heap` must return `Bool`, got `Number`")]
+fn test_heap_comparator_error() {
+    run(r#"
+            let h = Heap:{ fn(a, b) { 1 } };
+            h.push(1);
+            h.push(2);
+        "#)
+}
@@ -0,0 +1,70 @@
+use crate::run;
+
+#[test]
+fn test_match() {
+    run(r#"
+            let email = Regex("(\\w+)@(\\w+)\\.com", "i");
+
+            assert_eq(email.is_match("Bob@MAIL.COM"), true);
+            assert_eq(email.is_match("bob at mail"), false);
+            assert_eq(email.find("write to a@b.com or c@d.com"), "a@b.com");
+            assert_eq(email.find("nothing here"), nah);
+            assert_eq(String(email), "/(\\w+)@(\\w+)\\.com/i");
+        "#)
+}
+
+#[test]
+fn test_captures_and_replace() {
+    run(r#"
+            let date = Regex("(\\d+)-(\\d+)-(\\d+)");
+
+            let parts = date.captures("due 2024-05-17");
+            assert_eq(parts.get(0), "2024-05-17");
+            assert_eq(parts.get(1), "2024");
+            assert_eq(date.captures("no date"), nah);
+
+            assert_eq(date.replace("2024-05-17, 2023-01-02", "$3.$2.$1"), "17.05.2024, 02.01.2023");
+        "#)
+}
+
+#[test]
+fn test_each_match() {
+    run(r#"
+            let total = 0;
+
+            Regex("\\d+").each_match("1 apple, 20 pears, 300 plums", fn(n) {
+                total = total + Number(n);
+            });
+
+            assert_eq(total, 321);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "invalid regex /a(b/")]
+fn test_invalid_literal_before_running() {
+    run(r#"
+            let never_called = fn() {
+                Regex("a(b")
+            };
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "unknown `Regex` flag `q`")]
+fn test_unknown_flag() {
+    run(r#"
+            let flags = "q";
+            Regex("x", flags);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`Regex` can't be used as a name")]
+fn test_declared() {
+    run(r#"
+            let escape = fn(Regex) {
+                Regex("a(b")
+            };
+        "#)
+}
@@ -0,0 +1,65 @@
+use crate::run;
+
+#[test]
+fn test_execute_and_query() {
+    run(r#"
+            let db = Sqlite.open(":memory:");
+
+            db.execute("CREATE TABLE users (name TEXT, age INTEGER, email TEXT)");
+            assert_eq(db.execute("INSERT INTO users VALUES (?1, ?2, ?3)", "Bob", 42, nah), 1);
+            db.execute("INSERT INTO users VALUES (:name, :age, :email)", name: "Alice", age: 7, email: "a@b.c");
+
+            let rows = db.query("SELECT * FROM users WHERE age > ?1 ORDER BY age", 0);
+
+            assert_eq(rows.len, 2);
+            assert_eq(rows.get(0).get("name"), "Alice");
+            assert_eq(rows.get(0).get("email"), "a@b.c");
+            assert_eq(rows.get(1).get("age"), 42);
+            assert_eq(rows.get(1).get("email"), nah);
+        "#)
+}
+
+#[test]
+fn test_durable() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("state.db");
+
+    run(&format!(
+        r#"
+            Sqlite.open({path:?}).execute("CREATE TABLE runs (n INTEGER)");
+            Sqlite.open({path:?}).execute("INSERT INTO runs VALUES (1)");
+
+            assert_eq(Sqlite.open({path:?}).query("SELECT n FROM runs").len, 1);
+        "#
+    ))
+}
+
+#[test]
+#[should_panic(expected = "sqlite: ")]
+fn test_sql_error() {
+    run(r#"
+            Sqlite.open(":memory:").query("SELECT * FROM missing");
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`execute` expects parameters either all with names or all without")]
+fn test_mixed_params() {
+    run(r#"
+            Sqlite.open(":memory:").execute("SELECT ?1, :a", 1, a: 2);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "is closed")]
+fn test_with_connection() {
+    run(r#"
+            let db = Sqlite.open(":memory:");
+
+            with(db, fn(db) {
+                db.execute("CREATE TABLE users (name TEXT)");
+            });
+
+            db.query("SELECT * FROM users");
+        "#)
+}
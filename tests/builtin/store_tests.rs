@@ -0,0 +1,61 @@
+use crate::run;
+
+#[test]
+fn test_persistence() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("state.json");
+
+    run(&format!(
+        r#"
+            let store = Store.open({path:?});
+            assert_eq(store.get("runs"), nah);
+
+            store.set("runs", 1);
+            store.set("last", Tuple("ok", nah, true));
+            store.set("temporary", "x");
+            assert_eq(store.delete("temporary"), true);
+            assert_eq(store.delete("temporary"), false);
+
+            let again = Store.open({path:?});
+            again.set("runs", again.get("runs") + 1);
+
+            assert_eq(again.get("runs"), 2);
+            assert_eq(String(again.get("last")), "(ok, nah, true)");
+            assert_eq(String(again.keys()), "(last, runs)");
+        "#
+    ));
+
+    let text = std::fs::read_to_string(path).unwrap();
+    assert!(text.contains(r#""runs": 2"#));
+}
+
+#[test]
+#[should_panic(expected = "is closed")]
+fn test_with_store() {
+    let dir = tempfile::tempdir().unwrap();
+
+    run(&format!(
+        r#"
+            let store = Store.open({:?});
+            with(store, fn(store) {{
+                store.set("runs", 1);
+            }});
+
+            store.get("runs");
+        "#,
+        dir.path().join("state.json")
+    ));
+}
+
+#[test]
+#[should_panic(expected = "`Function` cannot be saved in `Store`")]
+fn test_unsupported_value() {
+    let dir = tempfile::tempdir().unwrap();
+
+    run(&format!(
+        r#"
+            Store.open({:?}).set("callback", fn() {{}});
+        "#,
+        dir.path().join("state.json")
+    ))
+}
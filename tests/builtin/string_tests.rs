@@ -0,0 +1,42 @@
+use crate::run;
+
+#[test]
+fn test_normalize() {
+    run(r#"
+        let composed = "caf\u{e9}";
+        let decomposed = "cafe\u{301}";
+
+        assert_eq(composed == decomposed, false);
+        assert_eq(composed.normalize("NFC"), decomposed.normalize("NFC"));
+        assert_eq(composed.normalize("NFD"), decomposed);
+        assert_eq(decomposed.normalize("NFC").len, 4);
+        assert_eq("\u{fb01}le".normalize("NFKC"), "file");
+        assert_eq("\u{fb01}le".normalize("NFC"), "\u{fb01}le");
+    "#);
+}
+
+#[test]
+#[should_panic(expected = "unknown normalization form \"NFX\"")]
+fn test_normalize_unknown_form() {
+    run(r#"
+        "text".normalize("NFX");
+    "#);
+}
+
+#[test]
+fn test_case_fold() {
+    run(r#"
+        assert_eq("Straße".case_fold(), "strasse");
+        assert_eq("ΣΊΣΥΦΟΣ".case_fold(), "σίσυφοσ");
+        assert_eq("İstanbul".case_fold() == "istanbul", false);
+    "#);
+}
+
+#[test]
+fn test_equals_ignore_case() {
+    run(r#"
+        assert_eq("STRASSE".equals_ignore_case("Straße"), true);
+        assert_eq("CAF\u{c9}".equals_ignore_case("cafe\u{301}"), true);
+        assert_eq("abc".equals_ignore_case("abd"), false);
+    "#);
+}
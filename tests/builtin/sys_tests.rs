@@ -0,0 +1,101 @@
+use frugurt::{
+    interpreter::{
+        options::{Capabilities, InterpreterOptions},
+        runner::Interpreter,
+    },
+    stdlib::prelude::fru_prelude::StdModule,
+};
+
+use crate::run;
+
+#[test]
+fn test_platform_and_version() {
+    run(&format!(
+        r#"
+            assert_eq(Sys.platform(), "{}");
+            assert_eq(Sys.version(), "{}");
+        "#,
+        std::env::consts::OS,
+        env!("CARGO_PKG_VERSION")
+    ));
+}
+
+#[test]
+fn test_cfg() {
+    run(&format!(
+        r#"
+            let platform = "unknown";
+
+            /// @cfg({})
+            platform = "this";
+
+            /// @cfg(not({}))
+            platform = "other";
+
+            assert_eq(platform, "this");
+        "#,
+        std::env::consts::OS,
+        std::env::consts::OS
+    ));
+}
+
+#[test]
+fn test_cfg_declarations() {
+    run(&format!(
+        r#"
+            /// @cfg(any(unix, windows))
+            let separator = "{}";
+
+            /// @cfg(not(any(unix, windows)))
+            let separator = "?";
+
+            assert_eq(separator, "{}");
+        "#,
+        std::path::MAIN_SEPARATOR,
+        std::path::MAIN_SEPARATOR
+    ));
+}
+
+#[test]
+#[should_panic(expected = "unknown `@cfg` operator `either`")]
+fn test_cfg_unknown_operator() {
+    run(r#"
+            /// @cfg(either(unix, windows))
+            print(1);
+        "#);
+}
+
+#[test]
+fn test_has_feature() {
+    run(&format!(
+        r#"
+            assert_eq(has_feature("coroutines"), true);
+            assert_eq(has_feature("fs"), true);
+            assert_eq(has_feature("sqlite"), {});
+            assert_eq(has_feature("http"), false);
+        "#,
+        cfg!(feature = "sqlite")
+    ));
+}
+
+#[test]
+fn test_has_feature_of_embedder() {
+    let interpreter = Interpreter::builder()
+        .options(InterpreterOptions {
+            capabilities: Capabilities::none(),
+            ..Default::default()
+        })
+        .without_module(StdModule::Tasks)
+        .build();
+
+    interpreter
+        .execute(
+            r#"
+                assert_eq(has_feature("coroutines"), false);
+                assert_eq(has_feature("fs"), false);
+                assert_eq(has_feature("macros"), true);
+            "#
+            .to_string(),
+        )
+        .unwrap();
+}
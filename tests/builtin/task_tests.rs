@@ -0,0 +1,120 @@
+use crate::run;
+
+#[test]
+fn test_yield_now() {
+    run(r#"
+            let log = "";
+
+            let t = spawn(fn() {
+                log = log <> "b";
+                5
+            });
+
+            log = log <> "a";
+            assert_eq(t.is_done, false);
+
+            yield_now();
+
+            log = log <> "c";
+            assert_eq(log, "abc");
+            assert_eq(t.is_done, true);
+            assert_eq(t.join(), 5);
+        "#)
+}
+
+#[test]
+fn test_join() {
+    run(r#"
+            let t1 = spawn(fn() { 1 });
+            let t2 = spawn(fn() { t1.join() + 1 });
+
+            assert_eq(t2.join(), 2);
+            assert_eq(t1.is_done, true);
+        "#)
+}
+
+#[test]
+fn test_spawn_from_task() {
+    run(r#"
+            let log = "";
+
+            spawn(fn() {
+                spawn(fn() {
+                    log = log <> "c";
+                });
+                log = log <> "b";
+                yield_now();
+                log = log <> "d";
+            });
+
+            log = log <> "a";
+            yield_now();
+            assert_eq(log, "ab");
+
+            yield_now();
+            assert_eq(log, "abcd");
+        "#)
+}
+
+#[test]
+fn test_interleaving() {
+    run(r#"
+            let log = "";
+
+            let worker = fn(name) {
+                fn() {
+                    let i = 0;
+                    while i < 3 {
+                        log = log <> name <> String(i);
+                        i = i + 1;
+                        yield_now();
+                    }
+                }
+            };
+
+            let a = spawn(worker("A"));
+            let b = spawn(worker("B"));
+
+            a.join();
+            b.join();
+
+            assert_eq(log, "A0B0A1B1A2B2");
+        "#)
+}
+
+#[test]
+fn test_join_waits_for_suspended_task() {
+    run(r#"
+            let log = "";
+
+            let t = spawn(fn() {
+                log = log <> "a";
+                yield_now();
+                log = log <> "c";
+                3
+            });
+
+            yield_now();
+            log = log <> "b";
+
+            assert_eq(t.join(), 3);
+            assert_eq(log, "abc");
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "task is already running")]
+fn test_join_self() {
+    run(r#"
+            let t = spawn(fn() { t.join() });
+            t.join();
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "division by zero")]
+fn test_error_propagation() {
+    run(r#"
+            spawn(fn() { 1 / 0 });
+        "#)
+}
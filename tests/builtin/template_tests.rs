@@ -0,0 +1,54 @@
+use crate::run;
+
+#[test]
+fn test_render() {
+    run(r#"
+            let greeting = Template("Hello, {name}! You have {count} new messages");
+
+            assert_eq(greeting.render(name: "Bob", count: 3), "Hello, Bob! You have 3 new messages");
+            assert_eq(greeting.render(count: 0, name: "Alice"), "Hello, Alice! You have 0 new messages");
+            assert_eq(greeting.source, "Hello, {name}! You have {count} new messages");
+        "#)
+}
+
+#[test]
+fn test_render_from_props() {
+    run(r#"
+            struct User {
+                name;
+                age;
+            }
+
+            let t = Template("{name} is {age}, {{braces}} stay");
+
+            assert_eq(t.render(User:{ "Bob", 42 }), "Bob is 42, {braces} stay");
+
+            let name = "Alice";
+            let age = 7;
+            assert_eq(t.render(scope()), "Alice is 7, {braces} stay");
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "value of `{count}` is not given")]
+fn test_missing_value() {
+    run(r#"
+            Template("{name}: {count}").render(name: "Bob");
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "invalid template: `{first name}` is not a placeholder")]
+fn test_invalid_placeholder() {
+    run(r#"
+            Template("Hello, {first name}");
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "invalid template: `{name` is not closed")]
+fn test_unclosed_placeholder() {
+    run(r#"
+            Template("Hello, {name");
+        "#)
+}
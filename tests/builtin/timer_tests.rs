@@ -0,0 +1,74 @@
+use frugurt::interpreter::options::{Capabilities, InterpreterOptions};
+
+use crate::{run, run_with_options};
+
+#[test]
+fn test_after() {
+    run(r#"
+            let log = "";
+
+            Timer.after(20, fn() { log = log <> "b"; });
+            Timer.after(10, fn() { log = log <> "a"; });
+
+            assert_eq(log, "");
+            sleep(50);
+            assert_eq(log, "ab");
+        "#)
+}
+
+#[test]
+fn test_every() {
+    run(r#"
+            let count = 0;
+
+            let t = Timer.every(5, fn() {
+                count = count + 1;
+                if count == 3 {
+                    t.cancel();
+                }
+            });
+
+            assert_eq(t.is_active, true);
+            sleep(100);
+            assert_eq(count, 3);
+            assert_eq(t.is_active, false);
+        "#)
+}
+
+#[test]
+fn test_cancel() {
+    run(r#"
+            let fired = false;
+
+            let t = Timer.after(5, fn() { fired = true; });
+            t.cancel();
+
+            sleep(20);
+            assert_eq(fired, false);
+        "#)
+}
+
+#[test]
+fn test_channel_with_timer() {
+    run(r#"
+            let ch = Channel:{};
+
+            Timer.after(5, fn() { ch.send(1); });
+
+            assert_eq(ch.recv(), 1);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "capability `time` is not granted")]
+fn test_time_denied() {
+    run_with_options(
+        r#"
+            sleep(1);
+        "#,
+        InterpreterOptions {
+            capabilities: Capabilities::none(),
+            ..Default::default()
+        },
+    )
+}
@@ -0,0 +1,66 @@
+use crate::run;
+
+#[test]
+fn test_tree_map() {
+    run(r#"
+            let m = TreeMap:{};
+            m.set("b", 2);
+            m.set("a", 1);
+            m.set("c", 3);
+
+            assert_eq(m.len, 3);
+            assert_eq(m.get("a"), 1);
+            assert_eq(m.get("z"), nah);
+            assert_eq(m.first(), "a");
+            assert_eq(m.last(), "c");
+            assert_eq(String(m), "TreeMap{a: 1, b: 2, c: 3}");
+
+            let order = "";
+            m.each(fn(key, value) {
+                order = order <> key;
+            });
+            assert_eq(order, "abc");
+
+            assert_eq(m.remove("b"), 2);
+            assert_eq(m.has("b"), false);
+        "#)
+}
+
+#[test]
+fn test_tree_map_range() {
+    run(r#"
+            let m = TreeMap:{};
+            m.set(10, "ten");
+            m.set(1, "one");
+            m.set(5, "five");
+
+            assert_eq(String(m.range(1, 10)), "TreeMap{1: one, 5: five}");
+            assert_eq(String(m.range(5, nah)), "TreeMap{5: five, 10: ten}");
+            assert_eq(m.range(7, 3).len, 0);
+        "#)
+}
+
+#[test]
+fn test_tree_set() {
+    run(r#"
+            let s = TreeSet:{ 3, 1, 2, 3 };
+
+            assert_eq(s.len, 3);
+            assert_eq(s.add(0), true);
+            assert_eq(s.add(1), false);
+            assert_eq(s.first(), 0);
+            assert_eq(s.last(), 3);
+            assert_eq(String(s.range(1, 3)), "TreeSet{1, 2}");
+            assert_eq(s.remove(2), true);
+            assert_eq(String(s), "TreeSet{0, 1, 3}");
+            assert_eq(TreeSet:{}.first(), nah);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "key must be `Bool`, `Number` or `String`, got `Function`")]
+fn test_wrong_key() {
+    run(r#"
+            TreeSet:{ print };
+        "#)
+}
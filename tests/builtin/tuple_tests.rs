@@ -0,0 +1,54 @@
+use crate::run;
+
+#[test]
+fn test_basics() {
+    run(r#"
+            let min_max = fn(a, b) {
+                if a < b {
+                    Tuple(a, b)
+                } else {
+                    Tuple(b, a)
+                }
+            };
+
+            let t = min_max(5, 2);
+
+            assert_eq(t.len, 2);
+            assert_eq(t.get(0), 2);
+            assert_eq(t.get(1), 5);
+            assert_eq(String(t), "(2, 5)");
+            assert_eq(String(Tuple:{ "a", nah }), "(a, nah)");
+        "#)
+}
+
+#[test]
+fn test_unpack() {
+    run(r#"
+            let divmod = fn(a, b) {
+                Tuple((a - a % b) / b, a % b)
+            };
+
+            let text = divmod(17, 5).unpack(fn(q, r) {
+                String(q) <> " rem " <> String(r)
+            });
+
+            assert_eq(text, "3 rem 2");
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "index of `Tuple` of length 2 must be an integer from 0, got 2")]
+fn test_out_of_range() {
+    run(r#"
+            Tuple(1, 2).get(2);
+        "#)
+}
+
+#[test]
+fn test_eq() {
+    run(r#"
+            assert_eq(Tuple(1, "a") == Tuple(1, "a"), true);
+            assert_eq(Tuple(1, 2) != Tuple(2, 1), true);
+            assert_eq(Tuple(Tuple(1)) == Tuple(Tuple(1)), true);
+        "#)
+}
@@ -0,0 +1,49 @@
+use crate::run;
+
+#[test]
+fn test_primitives() {
+    run(r#"
+            assert_eq(type_of(nah), Nah);
+            assert_eq(type_of(1), Number);
+            assert_eq(type_of("a"), String);
+            assert_eq(type_of(true), Bool);
+            assert_eq(type_of(print), Function);
+            assert_eq(type_of(fn() {}), Function);
+        "#)
+}
+
+#[test]
+fn test_types() {
+    run(r#"
+            struct Point {
+                x;
+                y;
+            }
+
+            assert_eq(type_of(Point:{ 1, 2 }), Point);
+            assert_eq(type_of(Point), Type);
+            assert_eq(type_of(Point:{ 1, 2 }) == Point, true);
+        "#)
+}
+
+#[test]
+fn test_native_objects() {
+    run(r#"
+            assert_eq(type_of(Tuple(1, 2)), Tuple);
+            assert_eq(type_of(Complex:{ 1, 2 }), Complex);
+            assert_eq(type_of(Char("a")) != Tuple, true);
+            assert_eq(type_of(scope()), type_of(global()));
+        "#)
+}
+
+#[test]
+fn test_shadowed() {
+    run(r#"
+            let f = fn() {
+                let Number = 5;
+                type_of(1)
+            };
+
+            assert_eq(String(f()), "Number");
+        "#)
+}
@@ -0,0 +1,53 @@
+use crate::run;
+
+#[test]
+fn test_arithmetic() {
+    run(r#"
+            let metres = Unit.metre;
+            let centimetres = Unit.cm;
+
+            let d = 5 * metres + 3 * centimetres;
+
+            assert_eq(String(d), "5.03 m");
+            assert_eq(d.amount, 5.03);
+            assert_eq(d - 3 * centimetres == 5 * metres, true);
+            assert_eq(String(100 * Unit.km / (2 * Unit.h)), "50 km/h");
+            assert_eq(String(Unit.kg * Unit.m / (Unit.s * Unit.s)), "1 kg*m/s^2");
+            assert_eq(String(Unit.m * Unit.m), "1 m^2");
+            assert_eq(String(1 / Unit.s), "1 1/s");
+        "#)
+}
+
+#[test]
+fn test_conversion() {
+    run(r#"
+            let d = 5 * Unit.m;
+
+            assert_eq(String(d.to(Unit.cm)), "500 cm");
+            assert_eq(d / (50 * Unit.cm), 10);
+            assert_eq(d == 500 * Unit.cm, true);
+            assert_eq(d > 4 * Unit.m, true);
+            assert_eq(d == 5 * Unit.s, false);
+
+            let inch = Unit:{ "in", 2.54 * Unit.cm };
+
+            assert_eq(String(12 * inch), "12 in");
+            assert_eq(String((12 * inch).to(Unit.cm)), "30.48 cm");
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "cannot add `m` and `s`, their dimensions differ")]
+fn test_dimension_mismatch() {
+    run(r#"
+            5 * Unit.m + 3 * Unit.s;
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "cannot convert `m` to `kg`")]
+fn test_wrong_conversion() {
+    run(r#"
+            Unit.m.to(Unit.kg);
+        "#)
+}
@@ -0,0 +1,75 @@
+use crate::run;
+
+#[test]
+fn test_vec2() {
+    run(r#"
+            let a = Vec2:{ 3, 4 };
+            let b = Vec2:{ y: 1, x: 2 };
+
+            assert_eq(a + b == Vec2:{ 5, 5 }, true);
+            assert_eq(a - b == Vec2:{ 1, 3 }, true);
+            assert_eq(2 * a == a * 2, true);
+            assert_eq(a / 2 == Vec2:{ 1.5, 2 }, true);
+            assert_eq(a.x, 3);
+            assert_eq(a.length(), 5);
+            assert_eq(a.normalize() == Vec2:{ 0.6, 0.8 }, true);
+            assert_eq(a.dot(b), 10);
+            assert_eq(a.cross(b), -5);
+            assert_eq(Vec2.zero.lerp(a, 0.5) == Vec2:{ 1.5, 2 }, true);
+            assert_eq(String(a), "Vec2(3, 4)");
+        "#)
+}
+
+#[test]
+fn test_vec3() {
+    run(r#"
+            let x = Vec3:{ 1, 0, 0 };
+            let y = Vec3:{ 0, 1, 0 };
+
+            assert_eq(x.cross(y) == Vec3:{ 0, 0, 1 }, true);
+            assert_eq(x.dot(y), 0);
+            assert_eq((x + y).z, 0);
+        "#)
+}
+
+#[test]
+fn test_mat3() {
+    run(r#"
+            let m = Mat3.translation(1, 2) * Mat3.scale(2, 3);
+
+            assert_eq(m * Vec2:{ 1, 1 } == Vec2:{ 3, 5 }, true);
+            assert_eq(m * Vec3:{ 1, 1, 0 } == Vec3:{ 2, 3, 0 }, true);
+            assert_eq(m.determinant(), 6);
+            assert_eq(m * m.inverse() == Mat3.identity, true);
+            assert_eq(m.transpose().get(2, 1), 2);
+            assert_eq(String(Mat3.identity * 2), "Mat3[[2, 0, 0], [0, 2, 0], [0, 0, 2]]");
+        "#)
+}
+
+#[test]
+fn test_user_type_shadows_builtin() {
+    run(r#"
+            struct Vec2 {
+                x;
+                y;
+            }
+
+            assert_eq(Vec2:{ 1, 2 }, Vec2:{ 1, 2 });
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "cannot normalize a zero vector")]
+fn test_normalize_zero() {
+    run(r#"
+            Vec3.zero.normalize();
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "matrix is singular and has no inverse")]
+fn test_singular() {
+    run(r#"
+            Mat3.scale(0, 1).inverse();
+        "#)
+}
@@ -0,0 +1,50 @@
+use crate::run;
+
+#[test]
+fn test_weak() {
+    run(r#"
+            class Box {
+                f;
+            }
+
+            let b = Box:{ 5 };
+            let w = weak(b);
+
+            assert_eq(w.is_alive, true);
+            assert_eq(w.get().f, 5);
+
+            b = nah;
+
+            assert_eq(w.is_alive, false);
+            assert_eq(w.get(), nah);
+        "#)
+}
+
+#[test]
+fn test_weak_in_drop() {
+    run(r#"
+            let w = nah;
+            let seen = 0;
+
+            class Box {
+            } impl {
+                _drop() {
+                    seen = w.get();
+                }
+            }
+
+            let b = Box:{};
+            w = weak(b);
+            b = nah;
+
+            assert_eq(seen, nah);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`weak` expects an object, got `Number`")]
+fn test_weak_number() {
+    run(r#"
+            weak(5);
+        "#)
+}
@@ -0,0 +1,79 @@
+use crate::run;
+
+#[test]
+fn test_with() {
+    run(r#"
+            let log = "";
+
+            class Resource {
+                name;
+            } impl {
+                _close() {
+                    log = log <> "close " <> name;
+                }
+            }
+
+            let result = with(Resource:{ "a" }, fn(r) {
+                log = log <> "use " <> r.name <> ";";
+                return 5;
+            });
+
+            assert_eq(result, 5);
+            assert_eq(log, "use a;close a");
+        "#)
+}
+
+#[test]
+fn test_with_exit() {
+    run(r#"
+            let exited = false;
+
+            class Lock {
+            } impl {
+                _exit() {
+                    exited = true;
+                }
+            }
+
+            with(Lock:{}, fn(l) {});
+
+            assert_eq(exited, true);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "assertion failed")]
+fn test_with_error() {
+    run(r#"
+            class Resource {
+            } impl {
+                _close() {}
+            }
+
+            with(Resource:{}, fn(r) {
+                assert_eq(1, 2);
+            });
+        "#)
+}
+
+#[test]
+fn test_with_timer() {
+    run(r#"
+            let fired = false;
+
+            with(Timer.after(0, fn() { fired = true; }), fn(t) {
+                assert_eq(t.is_active, true);
+            });
+
+            sleep(10);
+            assert_eq(fired, false);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`Number` has neither `_close` nor `_exit` method")]
+fn test_with_number() {
+    run(r#"
+            with(5, fn(x) {});
+        "#)
+}
@@ -0,0 +1,92 @@
+use crate::run;
+
+#[test]
+fn test_ping_pong() {
+    run(r#"
+            let worker = Worker:{
+                source: "let n = Parent.recv(); while n != 0 { Parent.send(n * 2); n = Parent.recv(); }"
+            };
+
+            worker.send(1);
+            assert_eq(worker.recv(), 2);
+
+            worker.send(21);
+            assert_eq(worker.recv(), 42);
+
+            worker.send(0);
+            worker.join();
+            assert_eq(worker.is_finished, true);
+        "#)
+}
+
+#[test]
+fn test_strings() {
+    run(r#"
+            let worker = Worker:{ source: "Parent.send(Parent.recv() <> \" world\");" };
+
+            worker.send("hello");
+            assert_eq(worker.recv(), "hello world");
+        "#)
+}
+
+#[test]
+fn test_composite_messages() {
+    run(r#"
+            let worker = Worker:{
+                source: "struct Point { x; y; } let p = Parent.recv(); Parent.send(Tuple(p.x + p.y, nah));"
+            };
+
+            struct Point {
+                x;
+                y;
+            }
+
+            let map = TreeMap:{};
+            map.set(1, Point:{ 3, 4 });
+            map.set("z", Tuple("a", nah));
+
+            worker.send(Point:{ 1, 2 });
+            assert_eq(worker.recv(), Tuple(3, nah));
+
+            let echo = Worker:{ source: "struct Point { x; y; } Parent.send(Parent.recv());" };
+            echo.send(map);
+            let copy = echo.recv();
+
+            assert_eq(copy.get(1), Point:{ 3, 4 });
+            assert_eq(copy.get("z"), Tuple("a", nah));
+        "#)
+}
+
+#[test]
+#[should_panic(
+    expected = "object of `Point` is received, but there is no struct or data type with this name"
+)]
+fn test_unknown_type() {
+    run(r#"
+            struct Point {
+                x;
+            }
+
+            let worker = Worker:{ source: "Parent.recv();" };
+            worker.send(Point:{ 1 });
+            worker.join();
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "cannot be sent to another interpreter")]
+fn test_not_sendable() {
+    run(r#"
+            let worker = Worker:{ source: "" };
+            worker.send(fn() {});
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "worker failed")]
+fn test_worker_error() {
+    run(r#"
+            let worker = Worker:{ source: "assert_eq(1, 2);" };
+            worker.join();
+        "#)
+}
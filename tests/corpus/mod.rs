@@ -0,0 +1,84 @@
+use std::{
+    env,
+    fs::{read_dir, read_to_string, write},
+    path::{Path, PathBuf},
+};
+
+use frugurt::interpreter::{ast_json_serializer::to_json, tree_sitter_parser};
+
+// run with `FRUGURT_BLESS=1` to write the current ASTs as the golden ones
+const BLESS: &str = "FRUGURT_BLESS";
+
+fn collect_snippets(dir: &Path) -> Vec<PathBuf> {
+    let mut snippets: Vec<_> = read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|x| x == "fru"))
+        .collect();
+
+    snippets.sort();
+    snippets
+}
+
+fn parse_snippet(path: &Path) -> Result<String, String> {
+    let source = read_to_string(path).unwrap();
+
+    let ast = tree_sitter_parser::parse(source).map_err(|err| format!("can't parse: {}", err))?;
+    let json = to_json(&ast).map_err(|err| format!("can't serialize: {}", err))?;
+
+    Ok(serde_json::to_string_pretty(&json).unwrap() + "\n")
+}
+
+fn check_snippet(path: &Path, bless: bool) -> Result<(), String> {
+    let actual = parse_snippet(path)?;
+    let golden_path = path.with_extension("json");
+
+    if bless {
+        write(&golden_path, actual).unwrap();
+        return Ok(());
+    }
+
+    let Ok(expected) = read_to_string(&golden_path) else {
+        return Err(format!("no golden AST, run with {}=1 to write it", BLESS));
+    };
+
+    if actual == expected {
+        return Ok(());
+    }
+
+    // the first line that differs is enough to find the node that changed
+    let (line, (expected, actual)) = (expected.lines().zip(actual.lines()).enumerate())
+        .find(|(_, (expected, actual))| expected != actual)
+        .unwrap_or((
+            expected.lines().count().min(actual.lines().count()),
+            ("", ""),
+        ));
+
+    Err(format!(
+        "AST differs from the golden one at line {}:\n  expected: {}\n  actual:   {}",
+        line + 1,
+        expected.trim(),
+        actual.trim()
+    ))
+}
+
+#[test]
+fn test_corpus() {
+    let bless = env::var_os(BLESS).is_some();
+    let snippets = collect_snippets(&Path::new(env!("CARGO_MANIFEST_DIR")).join("corpus"));
+
+    assert!(!snippets.is_empty(), "no corpus snippets found");
+
+    let failures: Vec<_> = snippets
+        .iter()
+        .filter_map(|path| {
+            check_snippet(path, bless)
+                .err()
+                .map(|err| format!("{}: {}", path.display(), err))
+        })
+        .collect();
+
+    if !failures.is_empty() {
+        panic!("{}", failures.join("\n\n"));
+    }
+}
@@ -0,0 +1,154 @@
+use std::fs::write;
+
+use frugurt::interpreter::{
+    ast_json_parser::{parse, AstJsonError},
+    ast_json_serializer::to_json,
+    identifier::Identifier,
+    options::InterpreterOptions,
+    runner::{execute_ast_file_with_options, Interpreter},
+    tree_sitter_parser,
+};
+
+const SOURCE: &str = r#"
+struct Point {
+    x;
+    y;
+} impl {
+    length2() {
+        x * x + y * y
+    }
+}
+
+let scale = fn (p, k=2) {
+    Point:{ x: p.x * k, y: p.y * k }
+};
+
+let p = scale(Point:{ 1, 2 });
+let total = 0;
+
+while total < p.length2() {
+    total = total + 10;
+}
+"#;
+
+#[test]
+fn test_round_trip() {
+    let ast = tree_sitter_parser::parse(SOURCE.to_string()).unwrap();
+    let json = to_json(&ast).unwrap();
+
+    let parsed = parse(&json.to_string()).unwrap();
+    assert_eq!(to_json(&parsed).unwrap(), json);
+
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+    interpreter.execute_ast(&parsed).unwrap();
+
+    assert_eq!(
+        format!("{:?}", interpreter.get_global("total").unwrap()),
+        "20"
+    );
+}
+
+#[test]
+fn test_hand_written() {
+    let ast = parse(
+        r#"{
+            "version": 2,
+            "ast": {
+                "node": "SourceCode",
+                "body": [{
+                    "node": "Let",
+                    "ident": "x",
+                    "value": {
+                        "node": "Binary",
+                        "operator": "+",
+                        "left": {"node": "Literal", "value": 1},
+                        "right": {"node": "Literal", "value": 2}
+                    }
+                }]
+            }
+        }"#,
+    )
+    .unwrap();
+
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+    interpreter.execute_ast(&ast).unwrap();
+
+    assert_eq!(format!("{:?}", interpreter.get_global("x").unwrap()), "3");
+}
+
+#[test]
+fn test_version() {
+    assert_eq!(
+        parse(r#"{"version": 1, "ast": {"node": "SourceCode", "body": []}}"#).err(),
+        Some(AstJsonError::new(
+            "$.version",
+            "unsupported AST version 1, expected 2"
+        ))
+    );
+}
+
+#[test]
+fn test_errors_have_paths() {
+    let cases = [
+        (
+            r#"{"node": "Break"}"#,
+            AstJsonError::new("$", "missing field `version`"),
+        ),
+        (
+            r#"{"version": 2, "ast": {"node": "SourceCode", "body": [{"node": "Jump"}]}}"#,
+            AstJsonError::new("$.ast.body[0]", "unknown statement `Jump`"),
+        ),
+        (
+            r#"{"version": 2, "ast": {"node": "Expression", "value": {"node": "Variable", "ident": "x", "name": "y"}}}"#,
+            AstJsonError::new("$.ast.value", "unknown field `name`"),
+        ),
+        (
+            r#"{"version": 2, "ast": {"node": "Let", "ident": "x", "value": {"node": "Literal", "value": [1]}}}"#,
+            AstJsonError::new(
+                "$.ast.value.value",
+                "expected null, a bool, a number or a string",
+            ),
+        ),
+        (
+            r#"{"version": 2, "ast": {"node": "Return", "value": {"node": "Variable", "ident": 5}}}"#,
+            AstJsonError::new("$.ast.value.ident", "expected an identifier"),
+        ),
+    ];
+
+    for (json, expected) in cases {
+        assert_eq!(parse(json).err(), Some(expected), "{}", json);
+    }
+}
+
+#[test]
+fn test_ast_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("tree.json");
+
+    write(
+        &path,
+        r#"{"version": 2, "ast": {"node": "SourceCode", "body": [
+            {"node": "Let", "ident": "x", "value": {"node": "Literal", "value": "hi"}}
+        ]}}"#,
+    )
+    .unwrap();
+
+    let scope = execute_ast_file_with_options(&path, InterpreterOptions::default()).unwrap();
+    assert_eq!(
+        format!("{:?}", scope.get_variable(Identifier::new("x")).unwrap()),
+        "hi"
+    );
+
+    write(&path, r#"{"version": 2}"#).unwrap();
+
+    let err = execute_ast_file_with_options(&path, InterpreterOptions::default())
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "invalid AST in {}: missing field `ast` at $",
+            path.display()
+        )
+    );
+}
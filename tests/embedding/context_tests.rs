@@ -0,0 +1,57 @@
+use frugurt::interpreter::{
+    identifier::Identifier, options::InterpreterOptions, runner::Interpreter,
+    value::fru_value::FruValue,
+};
+
+#[test]
+fn test_contexts_are_isolated() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+
+    interpreter.execute(r#"let host = "main";"#.to_string()).unwrap();
+    interpreter
+        .execute_in("a", r#"let secret = 1; print = 5;"#.to_string())
+        .unwrap();
+    interpreter.execute_in("b", r#"let secret = 2;"#.to_string()).unwrap();
+
+    interpreter
+        .execute_in(
+            "b",
+            r#"
+            assert_eq(secret, 2);
+            assert_eq(global().secret, 2);
+            print("builtins are not affected by other contexts");
+            "#
+            .to_string(),
+        )
+        .unwrap();
+
+    let secret = Identifier::new("secret");
+    assert!(
+        matches!(interpreter.context("a").get_variable(secret), Ok(FruValue::Number(n)) if n == 1.0)
+    );
+    assert!(interpreter.get_global("secret").is_err());
+}
+
+#[test]
+#[should_panic(expected = "variable `host` does not exist")]
+fn test_main_globals_are_hidden() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+
+    interpreter.execute(r#"let host = "main";"#.to_string()).unwrap();
+
+    if let Err(err) = interpreter.execute_in("plugin", "host;".to_string()) {
+        panic!("{}", err);
+    }
+}
+
+#[test]
+fn test_remove_context() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+
+    interpreter.execute_in("plugin", "let x = 1;".to_string()).unwrap();
+    assert!(interpreter.remove_context("plugin"));
+    assert!(!interpreter.remove_context("plugin"));
+
+    // a new context with the same name starts empty
+    interpreter.execute_in("plugin", "let x = 2;".to_string()).unwrap();
+}
@@ -0,0 +1,83 @@
+use frugurt::interpreter::{options::InterpreterOptions, runner::Interpreter};
+
+fn debug_of_global(source: &str, ident: &str) -> String {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+    interpreter.execute(source.to_string()).unwrap();
+
+    format!("{:?}", interpreter.get_global(ident).unwrap())
+}
+
+#[test]
+fn test_object_cycle() {
+    let text = debug_of_global(
+        r#"
+            class Node {
+                value;
+                next;
+            }
+
+            let a = Node:{ 1, nah };
+            a.next = Node:{ 2, a };
+        "#,
+        "a",
+    );
+
+    assert_eq!(text, "Node{value=1, next=Node{value=2, next=<cycle>}}");
+}
+
+#[test]
+fn test_collection_cycle() {
+    let text = debug_of_global(
+        r#"
+            let d = Deque:{ 1 };
+            d.push_back(d);
+        "#,
+        "d",
+    );
+
+    assert_eq!(text, "Deque[1, <cycle>]");
+}
+
+// a value shared by two fields is not a cycle
+#[test]
+fn test_shared() {
+    let text = debug_of_global(
+        r#"
+            struct Pair {
+                left;
+                right;
+            }
+
+            let d = Deque:{ 1 };
+            let p = Pair:{ d, d };
+        "#,
+        "p",
+    );
+
+    assert_eq!(text, "Pair{left=Deque[1], right=Deque[1]}");
+}
+
+#[test]
+fn test_scope() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+
+    interpreter
+        .execute(
+            r#"
+            class Holder {
+                value;
+            }
+
+            let h = Holder:{ nah };
+            h.value = h;
+            let x = 1;
+            "#
+            .to_string(),
+        )
+        .unwrap();
+
+    assert_eq!(
+        format!("{:?}", interpreter.global_scope()),
+        "Scope{Holder=Holder, h=Holder{value=<cycle>}, x=1}"
+    );
+}
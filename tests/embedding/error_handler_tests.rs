@@ -0,0 +1,128 @@
+use std::{cell::RefCell, rc::Rc};
+
+use frugurt::interpreter::{
+    error::{FruError, FruErrorKind},
+    options::InterpreterOptions,
+    runner::Interpreter,
+};
+
+// what the handler has seen, errors themselves can't be cloned
+struct Reported {
+    message: String,
+    kind: FruErrorKind,
+    line: Option<usize>,
+    frames: usize,
+}
+
+fn collect(interpreter: &Interpreter) -> Rc<RefCell<Vec<Reported>>> {
+    let errors = Rc::new(RefCell::new(Vec::new()));
+    let handler_errors = errors.clone();
+
+    interpreter.set_error_handler(move |err: &FruError| {
+        handler_errors.borrow_mut().push(Reported {
+            message: err.to_string(),
+            kind: err.kind(),
+            line: err.span().map(|span| span.start.line),
+            frames: err.trace().len(),
+        })
+    });
+
+    errors
+}
+
+#[test]
+fn test_timer_errors_do_not_stop_others() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+    let errors = collect(&interpreter);
+
+    interpreter
+        .execute(
+            r#"
+            let log = "";
+
+            Timer.after(10, fn() { log = log <> "a"; missing; });
+            Timer.after(20, fn() { log = log <> "b"; });
+            spawn(fn() { log = log <> "c"; also_missing; });
+            "#
+            .to_string(),
+        )
+        .unwrap();
+
+    assert_eq!(
+        format!("{:?}", interpreter.get_global("log").unwrap()),
+        "cab"
+    );
+
+    let messages: Vec<_> = errors.borrow().iter().map(|x| x.message.clone()).collect();
+
+    assert_eq!(
+        messages,
+        vec![
+            "variable `also_missing` does not exist",
+            "variable `missing` does not exist"
+        ]
+    );
+
+    assert_eq!(errors.borrow()[1].line, Some(4));
+}
+
+#[test]
+fn test_uncaught_errors_are_reported_and_returned() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+    let errors = collect(&interpreter);
+
+    let err = interpreter
+        .execute(
+            r#"
+            let f = fn() { 1 / nothing };
+            f();
+            "#
+            .to_string(),
+        )
+        .unwrap_err();
+
+    let errors = errors.borrow();
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, err.to_string());
+    assert_eq!(errors[0].frames, 1);
+}
+
+#[test]
+fn test_script_errors_are_reported() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+    let errors = collect(&interpreter);
+
+    let script = interpreter
+        .compile("let update = fn(dt) { dt + nothing };".to_string())
+        .unwrap();
+
+    script.run().unwrap();
+    assert!(script.call("update", vec![]).is_err());
+
+    assert_eq!(errors.borrow().len(), 1);
+}
+
+#[test]
+fn test_limits_stop_the_scheduler() {
+    let interpreter = Interpreter::new(InterpreterOptions {
+        fuel: Some(50),
+        ..InterpreterOptions::default()
+    });
+    let errors = collect(&interpreter);
+
+    let err = interpreter
+        .execute(
+            r#"
+            spawn(fn() {
+                while true {}
+            });
+            "#
+            .to_string(),
+        )
+        .unwrap_err();
+
+    assert_eq!(err.kind(), FruErrorKind::OutOfFuel);
+    assert_eq!(errors.borrow().len(), 1);
+    assert_eq!(errors.borrow()[0].kind, FruErrorKind::OutOfFuel);
+}
@@ -0,0 +1,211 @@
+use std::fs::write;
+
+use frugurt::interpreter::{
+    runner::{execute_file, execute_source_code},
+    value::function::SourceLocation,
+};
+
+const SOURCE: &str = r#"let inner = fn(x) {
+    x + nope
+};
+
+let outer = fn() {
+    inner(1)
+};
+
+outer();
+"#;
+
+#[test]
+fn test_span_and_trace() {
+    let err = execute_source_code(SOURCE.to_string()).err().unwrap();
+
+    let span = err.span().unwrap();
+    assert_eq!(span.start, SourceLocation { line: 2, column: 5 });
+    assert_eq!(
+        span.end,
+        SourceLocation {
+            line: 2,
+            column: 13
+        }
+    );
+
+    let trace: Vec<_> = err
+        .trace()
+        .iter()
+        .map(|x| (x.name.as_str(), x.call.as_ref().unwrap().start.line))
+        .collect();
+
+    assert_eq!(
+        trace,
+        vec![("function `inner`", 6), ("function `outer`", 9)]
+    );
+}
+
+#[test]
+fn test_render() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("main.fru");
+    write(&path, SOURCE).unwrap();
+
+    let err = execute_file(&path).err().unwrap();
+
+    let expected = format!(
+        "error: variable `nope` does not exist
+ --> {0}:2:5
+  |
+2 |     x + nope
+  |     ^^^^^^^^
+  in function `inner`, called at {0}:6:5
+  in function `outer`, called at {0}:9:1
+",
+        path.display()
+    );
+
+    assert_eq!(err.render(&path), expected);
+}
+
+#[test]
+fn test_imported_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let module = dir.path().join("module.fru");
+    write(&module, "let x = 1;\nlet y = x + missing;\n").unwrap();
+
+    let main = dir.path().join("main.fru");
+    write(
+        &main,
+        format!("let m = import {:?};\n", module.display().to_string()),
+    )
+    .unwrap();
+
+    let err = execute_file(&main).err().unwrap();
+
+    assert_eq!(err.span().unwrap().file.as_deref(), Some(module.as_path()));
+    assert!(err.render(&main).contains(&format!(
+        "in module `{}`, called at {}:1:1",
+        module.display(),
+        main.display()
+    )));
+}
+
+#[test]
+fn test_hint() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("main.fru");
+    write(&path, "let total = 0;\ntotl = 1;\n").unwrap();
+
+    let err = execute_file(&path).err().unwrap();
+
+    assert_eq!(err.hint(), Some("did you mean `total`?"));
+    assert_eq!(
+        err.to_string(),
+        "variable `totl` does not exist, did you mean `total`?"
+    );
+    assert!(err.render(&path).ends_with("^\n = help: did you mean `total`?\n"));
+}
+
+#[test]
+fn test_no_hint() {
+    let err = execute_source_code("let counter = 0;\nsum = 1;".to_string()).err().unwrap();
+
+    assert_eq!(err.to_string(), "variable `sum` does not exist");
+    assert_eq!(err.hint(), None);
+}
+
+fn trace_of(source: &str) -> Vec<(String, Option<usize>)> {
+    let err = execute_source_code(source.to_string()).err().unwrap();
+
+    err.trace()
+        .iter()
+        .map(|x| (x.name.clone(), x.call.as_ref().map(|call| call.start.line)))
+        .collect()
+}
+
+#[test]
+fn test_property_frames() {
+    let trace = trace_of(
+        r#"struct Account {
+    balance;
+
+    Ratio {
+        get {
+            let r = balance / nope;
+            r
+        }
+        set(value) {
+            balance = missing;
+        }
+    }
+}
+
+let a = Account:{ 1 };
+let r = a.Ratio;
+"#,
+    );
+
+    assert_eq!(
+        trace,
+        vec![("getter of `Account.Ratio`".to_string(), Some(16))]
+    );
+
+    let trace = trace_of(
+        r#"struct Account {
+    balance;
+
+    Ratio {
+        set(value) {
+            balance = balance / nope;
+        }
+    }
+}
+
+let a = Account:{ 1 };
+a.Ratio = 2;
+"#,
+    );
+
+    assert_eq!(
+        trace,
+        vec![("setter of `Account.Ratio`".to_string(), Some(12))]
+    );
+}
+
+#[test]
+fn test_observer_frame() {
+    let trace = trace_of(
+        r#"class Model {
+    title;
+}
+
+let m = Model:{ "draft" };
+observe(m, "title", fn(old, new) {
+    nope;
+});
+
+m.title = "final";
+"#,
+    );
+
+    assert_eq!(
+        trace,
+        vec![
+            ("anonymous function".to_string(), None),
+            ("observer of `Model.title`".to_string(), Some(10)),
+        ]
+    );
+}
+
+#[test]
+fn test_instantiation_frame() {
+    let trace = trace_of(
+        r#"struct Point {
+    x;
+    y;
+}
+
+let p = Point:{ 1 };
+"#,
+    );
+
+    assert_eq!(trace, vec![("instantiation of `Point`".to_string(), None)]);
+}
@@ -0,0 +1,203 @@
+use std::{
+    cell::RefCell,
+    future::{ready, Future},
+    pin::Pin,
+    rc::Rc,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+    thread,
+    time::Duration,
+};
+
+use frugurt::{
+    interpreter::{
+        error::FruError, options::InterpreterOptions, runner::Interpreter,
+        value::fru_value::FruValue,
+    },
+    stdlib::future::fru_future::FruFuture,
+};
+
+#[derive(Default)]
+struct Slot {
+    value: Option<f64>,
+    waker: Option<Waker>,
+}
+
+// resolved by another thread, like a reply from the host's event loop.
+// The reply is requested on the first poll, so it can't arrive before the script awaits it
+struct Delayed {
+    value: f64,
+    slot: Arc<Mutex<Slot>>,
+    requested: bool,
+}
+
+impl Delayed {
+    fn resolve_later(value: f64) -> Delayed {
+        Delayed {
+            value,
+            slot: Arc::new(Mutex::new(Slot::default())),
+            requested: false,
+        }
+    }
+
+    fn request(&self) {
+        let value = self.value;
+        let shared = self.slot.clone();
+
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+
+            let mut slot = shared.lock().unwrap();
+            slot.value = Some(value);
+            if let Some(waker) = slot.waker.take() {
+                waker.wake();
+            }
+        });
+    }
+}
+
+impl Future for Delayed {
+    type Output = Result<FruValue, FruError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let mut slot = this.slot.lock().unwrap();
+
+        match slot.value {
+            Some(value) => Poll::Ready(Ok(FruValue::Number(value))),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+
+                if !this.requested {
+                    this.requested = true;
+                    this.request();
+                }
+
+                Poll::Pending
+            }
+        }
+    }
+}
+
+// answered by the event loop of the host, that runs on the thread of the interpreter
+struct HostRequest {
+    slot: Rc<RefCell<Slot>>,
+}
+
+impl Future for HostRequest {
+    type Output = Result<FruValue, FruError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = self.slot.borrow_mut();
+
+        match slot.value {
+            Some(value) => Poll::Ready(Ok(FruValue::Number(value))),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+fn execute_with_future(code: &str, future: FruValue) {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+
+    interpreter.set_global("answer", future);
+
+    if let Err(err) = interpreter.execute(code.to_owned()) {
+        panic!("{}", err)
+    }
+}
+
+#[test]
+fn test_await_host_future() {
+    execute_with_future(
+        r#"
+            assert_eq(answer.is_ready, false);
+            assert_eq(await(answer), 42);
+            assert_eq(answer.is_ready, true);
+            assert_eq(await(answer), 42);
+        "#,
+        FruFuture::new_value(Delayed::resolve_later(42.0)),
+    )
+}
+
+#[test]
+fn test_tasks_run_while_waiting() {
+    execute_with_future(
+        r#"
+            let log = "";
+            spawn(fn() { log = "task"; });
+
+            await(answer);
+            assert_eq(log, "task");
+        "#,
+        FruFuture::new_value(Delayed::resolve_later(1.0)),
+    )
+}
+
+#[test]
+fn test_await_ready() {
+    execute_with_future(
+        r#"
+            assert_eq(await(answer), "ready");
+            assert_eq(await(5), 5);
+        "#,
+        FruFuture::new_value(ready(Ok(FruValue::String("ready".to_string())))),
+    )
+}
+
+#[test]
+#[should_panic(expected = "host failure")]
+fn test_failed_future() {
+    execute_with_future(
+        r#"
+            await(answer);
+        "#,
+        FruFuture::new_value(ready(FruError::new_res("host failure"))),
+    )
+}
+
+#[test]
+fn test_host_loop_on_same_thread() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+    let slot = Rc::new(RefCell::new(Slot::default()));
+
+    interpreter.set_global(
+        "answer",
+        FruFuture::new_value(HostRequest { slot: slot.clone() }),
+    );
+
+    interpreter
+        .start(
+            r#"
+                let log = "start";
+                let value = await(answer);
+                log = log <> " " <> String(value);
+            "#
+            .to_owned(),
+        )
+        .unwrap();
+
+    let mut ticks = 0;
+
+    while interpreter.run_ready().unwrap() {
+        assert!(
+            matches!(interpreter.get_global("log"), Ok(FruValue::String(log)) if log == "start")
+        );
+
+        ticks += 1;
+        assert!(ticks < 100);
+
+        // the host answers on a later tick of its loop
+        if ticks == 3 {
+            let mut slot = slot.borrow_mut();
+            slot.value = Some(7.0);
+            slot.waker.take().unwrap().wake();
+        }
+    }
+
+    assert_eq!(ticks, 3);
+    assert!(matches!(interpreter.get_global("log"), Ok(FruValue::String(log)) if log == "start 7"));
+}
@@ -0,0 +1,109 @@
+use std::{cell::RefCell, collections::HashMap, path::Path, rc::Rc};
+
+use frugurt::interpreter::{
+    options::{Capabilities, InterpreterOptions},
+    runner::Interpreter,
+};
+
+fn capture(lines: &Rc<RefCell<Vec<String>>>) -> impl FnMut(&str) + 'static {
+    let lines = lines.clone();
+    move |line| lines.borrow_mut().push(line.to_string())
+}
+
+#[test]
+fn test_print_hook() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+    let lines = Rc::new(RefCell::new(Vec::new()));
+
+    interpreter.on_print(capture(&lines));
+
+    interpreter.execute(r#"print("hello", 1, true); print();"#.to_string()).unwrap();
+
+    assert_eq!(
+        *lines.borrow(),
+        vec!["hello 1 true".to_string(), String::new()]
+    );
+}
+
+#[test]
+fn test_log_hook() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+    let logs = Rc::new(RefCell::new(Vec::new()));
+
+    interpreter.on_log(capture(&logs));
+
+    interpreter
+        .execute(
+            r#"
+            let old = deprecated(fn() { 1 }, "use new");
+            old();
+            old();
+
+            timed(fn() {}, "nothing")();
+            "#
+            .to_string(),
+        )
+        .unwrap();
+
+    let logs = logs.borrow();
+
+    assert_eq!(logs.len(), 2);
+    assert_eq!(logs[0], "warning: use new");
+    assert!(logs[1].starts_with("nothing took "));
+}
+
+#[test]
+fn test_module_loader() {
+    let modules = HashMap::from([(
+        "assets/math.fru",
+        "let double = fn(x) { x * 2 };".to_string(),
+    )]);
+
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+
+    interpreter.set_module_loader(move |path: &Path| modules.get(path.to_str()?).cloned());
+
+    interpreter
+        .execute(
+            r#"
+            let math = import "assets/math.fru";
+            assert_eq(math.double(21), 42);
+            "#
+            .to_string(),
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_module_loader_without_fs() {
+    let interpreter = Interpreter::new(InterpreterOptions {
+        capabilities: Capabilities::none(),
+        ..InterpreterOptions::default()
+    });
+
+    interpreter.set_module_loader(|path: &Path| {
+        (path == Path::new("config")).then(|| "let volume = 7;".to_string())
+    });
+
+    interpreter
+        .execute(r#"assert_eq((import "config").volume, 7);"#.to_string())
+        .unwrap();
+
+    // anything the loader doesn't know is still read from disk, which needs `Fs`
+    let err = interpreter.execute(r#"import "missing.fru";"#.to_string()).unwrap_err();
+
+    assert!(err.to_string().contains("capability `fs`"), "{}", err);
+}
+
+#[test]
+fn test_errors_in_loaded_modules() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+
+    interpreter.set_module_loader(|_: &Path| Some("let x = nope;".to_string()));
+
+    let err = interpreter.execute(r#"import "broken.fru";"#.to_string()).unwrap_err();
+
+    let frames: Vec<_> = err.trace().iter().map(|x| x.name.as_str()).collect();
+
+    assert_eq!(frames, vec!["module `broken.fru`"]);
+}
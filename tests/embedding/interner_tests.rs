@@ -0,0 +1,79 @@
+use frugurt::interpreter::{
+    identifier::Identifier, options::InterpreterOptions, runner::execute_source_code,
+    runner::Interpreter,
+};
+
+#[test]
+fn test_interpreter_owns_its_names() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+    let before = interpreter.runtime().interned_names();
+
+    interpreter.execute("let interner_owned_name = 1;".to_string()).unwrap();
+
+    assert!(interpreter.runtime().interned_names() > before);
+}
+
+#[test]
+fn test_start_interns_in_the_interpreter() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+    let before = interpreter.runtime().interned_names();
+
+    interpreter.start("let interner_started_name = 1;".to_string()).unwrap();
+
+    assert!(interpreter.runtime().interned_names() > before);
+}
+
+#[test]
+fn test_other_interpreter_names() {
+    let first = Interpreter::new(InterpreterOptions::default());
+    let second = Interpreter::new(InterpreterOptions::default());
+
+    first
+        .execute("struct InternerFirstProbe {} let probe = InternerFirstProbe:{};".to_string())
+        .unwrap();
+    let probe = first.get_global("probe").unwrap();
+
+    // found in the interpreter that has interned the name while another one is running
+    let shown = second.runtime().enter(|| format!("{:?}", probe));
+    assert!(shown.contains("InternerFirstProbe"));
+}
+
+// names of dropped interpreters are kept per thread, so tests running in parallel don't trim each other's
+#[test]
+fn test_trim() {
+    let scope = execute_source_code(
+        r#"
+            struct InternerTrimProbe {}
+            struct InternerKeptProbe {}
+            let probe = InternerTrimProbe:{};
+            let kept = InternerKeptProbe:{};
+        "#
+        .to_string(),
+    )
+    .unwrap();
+
+    let probe = scope.get_variable(Identifier::new("probe")).unwrap();
+    assert!(format!("{:?}", probe).contains("InternerTrimProbe"));
+
+    // interned by the host, so the name is never trimmed
+    let kept = scope.get_variable(Identifier::new("kept")).unwrap();
+    Identifier::new("InternerKeptProbe");
+
+    // the interpreter is dropped, so nothing but `probe` keeps the name of its type
+    assert!(Identifier::interner_stats().unused > 0);
+    assert!(Identifier::trim() > 0);
+
+    assert!(format!("{:?}", probe).contains("<trimmed>"));
+    assert!(format!("{:?}", kept).contains("InternerKeptProbe"));
+    assert_eq!(Identifier::new("probe").to_string(), "probe");
+}
+
+#[test]
+fn test_stats() {
+    let stats = Identifier::interner_stats();
+
+    // names of builtins are interned before `main`
+    assert!(stats.permanent > 0);
+    assert!(stats.names >= stats.permanent + stats.unused);
+    assert!(stats.capacity >= stats.names);
+}
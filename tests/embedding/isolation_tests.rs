@@ -0,0 +1,37 @@
+use frugurt::interpreter::{options::InterpreterOptions, runner::Interpreter};
+
+const DECLARE: &str = r#"
+    let declare = macro(fn(e) {
+        quote({
+            let hidden = unquote(e);
+            props_of(scope()).get(0)
+        })
+    });
+"#;
+
+fn expand_twice() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+
+    interpreter
+        .execute(format!(
+            r#"
+                {}
+                assert_eq(declare(1), "hidden$m0");
+                assert_eq(declare(2), "hidden$m1");
+            "#,
+            DECLARE
+        ))
+        .unwrap();
+}
+
+#[test]
+fn test_macro_expansions_are_numbered_per_interpreter() {
+    // other interpreters, including the ones of tests running in parallel, don't shift the numbers
+    let threads: Vec<_> = (0..4).map(|_| std::thread::spawn(expand_twice)).collect();
+
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    expand_twice();
+}
@@ -0,0 +1,16 @@
+mod ast_json_tests;
+mod context_tests;
+mod debug_tests;
+mod error_handler_tests;
+mod error_tests;
+mod future_tests;
+mod hooks_tests;
+mod interner_tests;
+mod isolation_tests;
+mod observe_tests;
+mod prelude_tests;
+mod reload_tests;
+mod script_tests;
+#[cfg(feature = "send")]
+mod send_tests;
+mod warnings_tests;
@@ -0,0 +1,49 @@
+use std::{cell::RefCell, rc::Rc};
+
+use frugurt::interpreter::{
+    identifier::Identifier, options::InterpreterOptions, runner::Interpreter,
+    value::fru_value::FruValue,
+};
+
+#[test]
+fn test_host_observer() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+
+    interpreter
+        .execute(
+            r#"
+            class Model {
+                title;
+            }
+
+            let model = Model:{ "draft" };
+            "#
+            .to_string(),
+        )
+        .unwrap();
+
+    let model = match interpreter.get_global("model") {
+        Ok(FruValue::Object(model)) => model,
+        _ => panic!("`model` is not an object"),
+    };
+
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let log = seen.clone();
+
+    let id = model
+        .observe(
+            Identifier::new("title"),
+            Rc::new(move |old, new| {
+                log.borrow_mut().push(format!("{:?} -> {:?}", old, new));
+                Ok(())
+            }),
+        )
+        .unwrap();
+
+    interpreter.execute(r#"model.title = "final";"#.to_string()).unwrap();
+
+    assert!(model.unobserve(id));
+    interpreter.execute(r#"model.title = "again";"#.to_string()).unwrap();
+
+    assert_eq!(*seen.borrow(), vec!["draft -> final"]);
+}
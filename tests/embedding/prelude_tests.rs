@@ -0,0 +1,142 @@
+use std::{any::Any, rc::Rc};
+
+use frugurt::{
+    interpreter::{
+        error::FruError,
+        identifier::{Identifier, OperatorIdentifier},
+        runner::Interpreter,
+        value::{
+            fru_value::FruValue,
+            function::EvaluatedArgumentList,
+            native::object::{INativeObject, NativeObject},
+            operator::AnyOperator,
+        },
+    },
+    stdlib::prelude::fru_prelude::{Prelude, StdModule},
+};
+
+struct MoneyType;
+
+struct Money {
+    cents: f64,
+}
+
+impl INativeObject for MoneyType {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn call(&self, args: EvaluatedArgumentList) -> Result<FruValue, FruError> {
+        match args.args.as_slice() {
+            [(None, FruValue::Number(cents))] => Ok(money(*cents)),
+            _ => FruError::new_res("`Money` expects cents"),
+        }
+    }
+
+    fn operators(&self) -> Vec<(OperatorIdentifier, AnyOperator)> {
+        let money = Identifier::new("Money");
+
+        vec![(
+            OperatorIdentifier::new(Identifier::new("+"), money, money),
+            AnyOperator::BuiltinOperator(money_plus),
+        )]
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+impl INativeObject for Money {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_type_identifier(&self) -> Identifier {
+        Identifier::new("Money")
+    }
+
+    fn get_prop(&self, _: Identifier) -> Result<FruValue, FruError> {
+        Ok(FruValue::Number(self.cents))
+    }
+
+    fn fru_clone(self: Rc<Self>) -> Rc<dyn INativeObject> {
+        self
+    }
+}
+
+fn money(cents: f64) -> FruValue {
+    FruValue::NativeObject(NativeObject::new(Rc::new(Money { cents })))
+}
+
+fn cents(value: FruValue) -> f64 {
+    match value {
+        FruValue::NativeObject(object) => object.downcast::<Money>().unwrap().cents,
+        _ => unreachable!(),
+    }
+}
+
+fn money_plus(left: FruValue, right: FruValue) -> Result<FruValue, FruError> {
+    Ok(money(cents(left) + cents(right)))
+}
+
+#[test]
+fn test_without_module() {
+    let interpreter = Interpreter::builder().without_module(StdModule::Fs).build();
+
+    interpreter
+        .execute(r#"assert_eq(Regex("a").is_match("cat"), true);"#.to_string())
+        .unwrap();
+
+    let err = interpreter.execute(r#"Fs.read("x");"#.to_string()).unwrap_err();
+    assert!(
+        err.to_string().contains("variable `Fs` does not exist"),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_custom_prelude() {
+    let prelude = Prelude::empty()
+        .with_module(StdModule::Core)
+        .with_value("VERSION", FruValue::Number(3.0))
+        .with_function("double", |args| match args.args.as_slice() {
+            [(_, FruValue::Number(n))] => Ok(FruValue::Number(n * 2.0)),
+            _ => Ok(FruValue::Nah),
+        });
+
+    let interpreter = Interpreter::builder().prelude(prelude).build();
+
+    interpreter.execute(r#"assert_eq(double(VERSION), 6);"#.to_string()).unwrap();
+
+    let err = interpreter.execute(r#"memoize(print);"#.to_string()).unwrap_err();
+    assert!(
+        err.to_string().contains("variable `memoize` does not exist"),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_modules_cover_standard() {
+    let mut prelude = Prelude::standard();
+
+    for module in StdModule::ALL {
+        prelude = prelude.without_module(module);
+    }
+
+    assert_eq!(prelude.into_parts().0.len(), 0);
+}
+
+#[test]
+fn test_native_type_operators() {
+    let prelude = Prelude::standard().with_value(
+        "Money",
+        FruValue::NativeObject(NativeObject::new(Rc::new(MoneyType))),
+    );
+
+    let interpreter = Interpreter::builder().prelude(prelude).build();
+
+    interpreter
+        .execute(r#"assert_eq((Money(150) + Money(75)).cents, 225);"#.to_string())
+        .unwrap();
+}
@@ -0,0 +1,103 @@
+use frugurt::interpreter::{
+    error::FruError, options::InterpreterOptions, runner::Interpreter, value::fru_value::FruValue,
+};
+
+fn number(interpreter: &Interpreter, ident: &str) -> f64 {
+    match interpreter.get_global(ident).unwrap() {
+        FruValue::Number(n) => n,
+        other => panic!("expected a number, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_keeps_data_and_replaces_functions() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+
+    interpreter
+        .execute(
+            r#"
+            let score = 0;
+            let bonus = fn() { 1 };
+            let tick = fn() { score = score + bonus(); };
+            "#
+            .to_string(),
+        )
+        .unwrap();
+
+    interpreter.execute("tick(); tick();".to_string()).unwrap();
+    assert_eq!(number(&interpreter, "score"), 2.0);
+
+    interpreter
+        .reload(
+            r#"
+            let score = 0;
+            let bonus = fn() { 10 };
+            let tick = fn() { score = score + bonus(); };
+            let level = 1;
+            "#
+            .to_string(),
+        )
+        .unwrap();
+
+    interpreter.execute("tick();".to_string()).unwrap();
+    assert_eq!(number(&interpreter, "score"), 12.0);
+    assert_eq!(number(&interpreter, "level"), 1.0);
+}
+
+#[test]
+fn test_migrate() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+
+    interpreter.execute(r#"let health = 70;"#.to_string()).unwrap();
+
+    let mut migrated = Vec::new();
+
+    interpreter
+        .reload_with(r#"let health = "full";"#.to_string(), |name, old, _| {
+            migrated.push(name.to_string());
+            Ok(FruValue::String(format!("{:?} of 100", old)))
+        })
+        .unwrap();
+
+    assert_eq!(migrated, ["health"]);
+    assert!(
+        matches!(interpreter.get_global("health").unwrap(), FruValue::String(s) if s == "70 of 100")
+    );
+}
+
+#[test]
+fn test_failed_reload_keeps_program() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+
+    interpreter.execute(r#"let score = 5;"#.to_string()).unwrap();
+
+    let err: FruError = interpreter
+        .reload(r#"let score = 0; let broken = 1 / 0;"#.to_string())
+        .unwrap_err();
+
+    assert_eq!(err.to_string(), "division by zero");
+    assert_eq!(number(&interpreter, "score"), 5.0);
+    assert!(interpreter.get_global("broken").is_err());
+}
+
+#[test]
+fn test_failed_migration_keeps_program() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+
+    interpreter.execute(r#"let a = 1; let b = 2;"#.to_string()).unwrap();
+
+    let err = interpreter
+        .reload_with(
+            r#"let a = "one"; let b = "two"; let added = 3;"#.to_string(),
+            |name, _, new| match name {
+                "b" => FruError::new_res("`b` can't be migrated"),
+                _ => Ok(new),
+            },
+        )
+        .unwrap_err();
+
+    assert_eq!(err.to_string(), "`b` can't be migrated");
+    assert_eq!(number(&interpreter, "a"), 1.0);
+    assert_eq!(number(&interpreter, "b"), 2.0);
+    assert!(interpreter.get_global("added").is_err());
+}
@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+
+use frugurt::interpreter::{
+    options::InterpreterOptions, runner::Interpreter, value::fru_value::FruValue,
+};
+
+const ENTITY: &str = r#"
+    let health = 100;
+
+    let damage = fn(amount) {
+        health = health - amount;
+        health
+    };
+"#;
+
+fn number(value: FruValue) -> f64 {
+    match value {
+        FruValue::Number(n) => n,
+        other => panic!("expected a number, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_compile_and_call() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+    let script = interpreter.compile(ENTITY.to_string()).unwrap();
+
+    script.run().unwrap();
+
+    assert_eq!(
+        number(script.call("damage", vec![FruValue::Number(30.0)]).unwrap()),
+        70.0
+    );
+    assert_eq!(
+        number(script.call("damage", vec![FruValue::Number(30.0)]).unwrap()),
+        40.0
+    );
+}
+
+#[test]
+fn test_instances_have_their_own_state() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+    let script = interpreter.compile(ENTITY.to_string()).unwrap();
+
+    let goblin = script.instance();
+    let orc = script.instance();
+
+    goblin.run().unwrap();
+    orc.run().unwrap();
+
+    goblin.call("damage", vec![FruValue::Number(90.0)]).unwrap();
+    assert_eq!(
+        number(orc.call("damage", vec![FruValue::Number(10.0)]).unwrap()),
+        90.0
+    );
+
+    // scripts don't see the globals of the interpreter, nor the interpreter theirs
+    assert!(interpreter.get_global("health").is_err());
+}
+
+#[test]
+fn test_run_many_times() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+    let script = interpreter.compile("let total = 0; total = total + 1;".to_string()).unwrap();
+
+    for _ in 0..3 {
+        script.instance().run().unwrap();
+    }
+
+    // declarations stay in the scope of the script, so it can't declare them again
+    script.run().unwrap();
+    assert!(script.run().is_err());
+}
+
+#[test]
+fn test_compile_error() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+
+    assert!(interpreter.compile("let = ;".to_string()).is_err());
+}
+
+#[test]
+#[should_panic(expected = "variable `missing` does not exist")]
+fn test_call_missing() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+    let script = interpreter.compile(ENTITY.to_string()).unwrap();
+
+    script.run().unwrap();
+    script.call("missing", Vec::new()).unwrap();
+}
+
+#[test]
+fn test_typed_functions() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+
+    let script = interpreter
+        .compile(
+            r#"
+                let update = fn(dt, name) {
+                    name <> " moved by " <> String(dt * 2)
+                };
+
+                let sum = fn(numbers) {
+                    numbers.unpack(fn(a, b, c) { a + b + c })
+                };
+
+                let scores = fn() {
+                    let scores = TreeMap:{};
+                    scores.set("ann", 3);
+                    scores.set("bob", nah);
+                    scores
+                };
+            "#
+            .to_string(),
+        )
+        .unwrap();
+
+    script.run().unwrap();
+
+    let moved: String = script.get_function("update").unwrap().call((1.5_f64, "player")).unwrap();
+    assert_eq!(moved, "player moved by 3");
+
+    let total: i64 = script.get_function("sum").unwrap().call((vec![1, 2, 3],)).unwrap();
+    assert_eq!(total, 6);
+
+    let scores: HashMap<String, Option<u32>> =
+        script.get_function("scores").unwrap().call(()).unwrap();
+    assert_eq!(
+        scores,
+        HashMap::from([("ann".to_string(), Some(3)), ("bob".to_string(), None)])
+    );
+}
+
+#[test]
+#[should_panic(expected = "expected `Bool`, got `String`")]
+fn test_typed_function_wrong_result() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+    let script = interpreter.compile(r#"let f = fn() { "yes" };"#.to_string()).unwrap();
+
+    script.run().unwrap();
+
+    let _: bool = script.get_function("f").unwrap().call(()).unwrap();
+}
+
+#[test]
+fn test_typed_integer_bounds() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+    let script = interpreter
+        .compile(r#"let pow = fn(n) { 2 ** n }; let neg = fn(n) { 0 - 2 ** n };"#.to_string())
+        .unwrap();
+
+    script.run().unwrap();
+
+    let pow = script.get_function("pow").unwrap();
+    let neg = script.get_function("neg").unwrap();
+
+    assert_eq!(pow.call::<_, u64>((63,)).unwrap(), 1 << 63);
+    assert!(pow.call::<_, u64>((64,)).is_err());
+    assert!(pow.call::<_, i64>((63,)).is_err());
+    assert_eq!(neg.call::<_, i64>((63,)).unwrap(), i64::MIN);
+    assert_eq!(pow.call::<_, u32>((31,)).unwrap(), 1 << 31);
+    assert!(pow.call::<_, u32>((32,)).is_err());
+    assert!(pow.call::<_, usize>((usize::BITS,)).is_err());
+}
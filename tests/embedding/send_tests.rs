@@ -0,0 +1,93 @@
+use std::{sync::Arc, thread};
+
+use frugurt::interpreter::{
+    options::InterpreterOptions, send::SendInterpreter, value::sendable::SendableValue,
+};
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn test_is_send_and_sync() {
+    assert_send_sync::<SendInterpreter>();
+}
+
+#[test]
+fn test_shared_between_threads() {
+    let interpreter = Arc::new(SendInterpreter::new(InterpreterOptions::default()));
+
+    interpreter.execute("let hits = 0;".to_string()).unwrap();
+
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let interpreter = interpreter.clone();
+            thread::spawn(move || interpreter.execute("hits = hits + 1;".to_string()).unwrap())
+        })
+        .collect();
+
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    assert_eq!(
+        interpreter.get_global("hits").unwrap(),
+        SendableValue::Number(4.0)
+    );
+}
+
+#[test]
+fn test_globals() {
+    let interpreter = SendInterpreter::new(InterpreterOptions::default());
+
+    interpreter
+        .set_global("name", SendableValue::String("frugurt".to_string()))
+        .unwrap();
+    interpreter.execute(r#"let greeting = "hello, " <> name;"#.to_string()).unwrap();
+
+    assert_eq!(
+        interpreter.get_global("greeting").unwrap(),
+        SendableValue::String("hello, frugurt".to_string())
+    );
+
+    interpreter.execute("let f = fn() {};".to_string()).unwrap();
+
+    let err = interpreter.get_global("f").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`Function` cannot be sent to another interpreter"
+    );
+}
+
+#[test]
+fn test_composite_globals() {
+    let interpreter = SendInterpreter::new(InterpreterOptions::default());
+
+    interpreter.execute("struct Point { x; y; }".to_string()).unwrap();
+
+    let point = SendableValue::Object {
+        type_name: "Point".to_string(),
+        fields: vec![
+            ("x".to_string(), SendableValue::Number(1.0)),
+            ("y".to_string(), SendableValue::Number(2.0)),
+        ],
+    };
+
+    interpreter.set_global("p", point.clone()).unwrap();
+    interpreter.execute("let pair = Tuple(p.x + p.y, p);".to_string()).unwrap();
+
+    assert_eq!(
+        interpreter.get_global("pair").unwrap(),
+        SendableValue::Tuple(vec![SendableValue::Number(3.0), point])
+    );
+}
+
+#[test]
+fn test_with() {
+    let interpreter = SendInterpreter::new(InterpreterOptions::default());
+
+    let names = interpreter.with(|interpreter| {
+        interpreter.execute("let x = 1; let y = 2;".to_string()).unwrap();
+        interpreter.global_scope().variables().len()
+    });
+
+    assert!(names >= 2);
+}
@@ -0,0 +1,132 @@
+use frugurt::interpreter::{
+    options::InterpreterOptions,
+    runner::Interpreter,
+    runtime::{Warning, WarningKind},
+};
+
+fn warnings_of(code: &str, options: InterpreterOptions) -> Vec<Warning> {
+    let interpreter = Interpreter::new(options);
+    interpreter.execute(code.to_owned()).unwrap();
+    interpreter.warnings()
+}
+
+fn with_warnings() -> InterpreterOptions {
+    InterpreterOptions {
+        warnings: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_deprecation() {
+    let warnings = warnings_of(
+        r#"
+            let old = deprecated(fn(x) { x }, "use `new` instead");
+
+            old(1);
+            old(2);
+            old(3);
+        "#,
+        with_warnings(),
+    );
+
+    assert_eq!(
+        warnings,
+        vec![Warning {
+            kind: WarningKind::Deprecation,
+            message: "use `new` instead".to_string(),
+            count: 3,
+        }]
+    );
+
+    assert_eq!(
+        warnings[0].to_string(),
+        "warning[deprecation]: use `new` instead (3 times)"
+    );
+}
+
+#[test]
+fn test_implicit_conversion() {
+    let warnings = warnings_of(
+        r#"
+            let n = 3;
+
+            while n {
+                n = n - 1;
+            }
+
+            if "text" {}
+        "#,
+        InterpreterOptions {
+            truthy_conditions: true,
+            ..with_warnings()
+        },
+    );
+
+    let messages: Vec<_> = warnings.iter().map(|x| (x.kind, x.count)).collect();
+
+    assert_eq!(
+        messages,
+        vec![
+            (WarningKind::ImplicitConversion, 4),
+            (WarningKind::ImplicitConversion, 1)
+        ]
+    );
+
+    assert_eq!(
+        warnings[1].message,
+        "`String` in if condition is converted to `Bool`"
+    );
+}
+
+#[test]
+fn test_shadowing() {
+    let warnings = warnings_of(
+        r#"
+            let x = 1;
+            let print = 2;
+
+            {
+                let x = 2;
+                let y = 3;
+            }
+
+            let f = fn(x) {
+                let x = x + 1;
+            };
+
+            f(1);
+        "#,
+        with_warnings(),
+    );
+
+    assert_eq!(
+        warnings,
+        vec![Warning {
+            kind: WarningKind::Shadowing,
+            message: "`x` hides a variable of an outer scope".to_string(),
+            count: 2,
+        }]
+    );
+}
+
+#[test]
+fn test_disabled() {
+    let warnings = warnings_of(
+        r#"
+            let x = 1;
+
+            {
+                let x = 2;
+            }
+
+            if 1 {}
+        "#,
+        InterpreterOptions {
+            truthy_conditions: true,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(warnings, vec![]);
+}
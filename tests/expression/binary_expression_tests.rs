@@ -15,3 +15,68 @@ fn test_error_propagation_2() {
             0 + bar;
         "#)
 }
+
+#[test]
+fn test_chained_comparison() {
+    run(r#"
+            let x = 5;
+
+            assert_eq(0 <= x < 10, true);
+            assert_eq(0 <= x < 5, false);
+            assert_eq(10 > x >= 5 > 0, true);
+            assert_eq(1 < 2 > 0, true);
+            assert_eq("a" < "b" < "c", true);
+        "#)
+}
+
+#[test]
+fn test_chained_comparison_evaluates_once() {
+    run(r#"
+            let calls = 0;
+            let middle = fn() {
+                calls = calls + 1;
+                5
+            };
+
+            assert_eq(0 < middle() < 10, true);
+            assert_eq(calls, 1);
+        "#)
+}
+
+#[test]
+fn test_chained_comparison_short_circuits() {
+    run(r#"
+            let calls = 0;
+            let f = fn(x) {
+                calls = calls + 1;
+                x
+            };
+
+            assert_eq(f(5) < f(1) < f(2) < f(3), false);
+            assert_eq(calls, 2);
+
+            calls = 0;
+            assert_eq(f(1) < f(2) < f(0) < f(3), false);
+            assert_eq(calls, 3);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "operator `Operator(Bool < Number)` does not exist")]
+fn test_parenthesized_comparison() {
+    run(r#"
+            (1 < 2) < 3;
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`Number` declared by the program has no builtin operators")]
+fn test_shadowed_builtin_type() {
+    run(r#"
+            struct Number {
+                x;
+            }
+
+            Number:{ 1 } + 1;
+        "#)
+}
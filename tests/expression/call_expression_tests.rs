@@ -1,7 +1,7 @@
 use crate::run;
 
 #[test]
-#[should_panic(expected = "DoesNotExist")]
+#[should_panic(expected = "there is no parameter named `c`")]
 fn test_named_error_1() {
     run(r#"
             let f = fn (a = 1, b = 2) {2 * a + b};
@@ -11,7 +11,7 @@ fn test_named_error_1() {
 }
 
 #[test]
-#[should_panic(expected = "SameSetTwice")]
+#[should_panic(expected = "argument `a` is set twice")]
 fn test_named_error_2() {
     run(r#"
             let f = fn (a = 1, b = 2) {2 * a + b};
@@ -21,7 +21,7 @@ fn test_named_error_2() {
 }
 
 #[test]
-#[should_panic(expected = "SameSetTwice")]
+#[should_panic(expected = "argument `a` is set twice")]
 fn test_named_error_3() {
     run(r#"
             let f = fn (a = 1, b = 2) {2 * a + b};
@@ -31,7 +31,7 @@ fn test_named_error_3() {
 }
 
 #[test]
-#[should_panic(expected = "NotSet")]
+#[should_panic(expected = "missing argument `a`")]
 fn test_named_error_4() {
     run(r#"
             let f = fn (a, b = 2) {2 * a + b};
@@ -69,7 +69,7 @@ fn test_named_error_7() {
 }
 
 #[test]
-#[should_panic(expected = "TooMany")]
+#[should_panic(expected = "too many arguments")]
 fn test_count_error_1() {
     run(r#"
             let f = fn (a, b) {};
@@ -79,7 +79,7 @@ fn test_count_error_1() {
 }
 
 #[test]
-#[should_panic(expected = "NotSet")]
+#[should_panic(expected = "missing argument `b`")]
 fn test_count_error_2() {
     run(r#"
             let f = fn (a, b) {};
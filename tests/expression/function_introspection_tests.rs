@@ -0,0 +1,62 @@
+use crate::run;
+
+#[test]
+fn test_function_props() {
+    run(r#"
+            let add = fn(a, b, c = 5) { a + b + c };
+
+            assert_eq(add.name, "add");
+            assert_eq(add.arity, 3);
+            assert_eq(add.parameters, Tuple("a", "b", "c"));
+            assert_eq(add.location, "2:23");
+
+            assert_eq((fn() {}).name, nah);
+        "#)
+}
+
+#[test]
+fn test_curried_props() {
+    run(r#"
+            let add = fn(a, b, c) { a + b + c };
+            let add1 = add$(1, c: 3);
+
+            assert_eq(add1.name, "add");
+            assert_eq(add1.arity, 1);
+            assert_eq(add1.parameters, Tuple("b"));
+        "#)
+}
+
+#[test]
+fn test_builtin_props() {
+    run(r#"
+            assert_eq(print.name, "print");
+            assert_eq(print.arity, nah);
+            assert_eq(print.location, nah);
+        "#)
+}
+
+#[test]
+fn test_method_props() {
+    run(r#"
+            struct Vec {
+                x;
+            } impl {
+                scale(k) {
+                    x = x * k;
+                }
+            }
+
+            let v = Vec:{ 1 };
+            assert_eq(v.scale.name, "scale");
+            assert_eq(v.scale.parameters, Tuple("k"));
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "in function `add`: too many arguments")]
+fn test_error_has_name() {
+    run(r#"
+            let add = fn(a, b) { a + b };
+            add(1, 2, 3);
+        "#)
+}
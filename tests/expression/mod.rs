@@ -3,7 +3,11 @@ mod block_expression_tests;
 mod call_expression_tests;
 mod curry_call_expression_tests;
 mod function_expression_tests;
+mod function_introspection_tests;
 mod if_expression_tests;
 mod instantiation_expression_tests;
+mod operator_property_tests;
+mod operator_section_tests;
 mod prop_access_tests;
 mod variable_expression_tests;
+mod when_expression_tests;
@@ -0,0 +1,156 @@
+use proptest::prelude::*;
+
+use crate::run;
+
+// arithmetic on small numbers, so every intermediate value is exact
+#[derive(Debug, Clone)]
+enum Expr {
+    Number(u8),
+    Binary(Box<Expr>, &'static str, Box<Expr>),
+}
+
+impl Expr {
+    fn value(&self) -> i64 {
+        match self {
+            Expr::Number(n) => *n as i64,
+            Expr::Binary(left, "+", right) => left.value() + right.value(),
+            Expr::Binary(left, "-", right) => left.value() - right.value(),
+            Expr::Binary(left, _, right) => left.value() * right.value(),
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::Number(_) => 3,
+            Expr::Binary(_, "*", _) => 2,
+            Expr::Binary(..) => 1,
+        }
+    }
+
+    // parentheses only where precedence and left associativity need them
+    fn minimal(&self) -> String {
+        match self {
+            Expr::Number(n) => n.to_string(),
+            Expr::Binary(left, operator, right) => {
+                let left = if left.precedence() < self.precedence() {
+                    format!("({})", left.minimal())
+                } else {
+                    left.minimal()
+                };
+
+                let right = if right.precedence() <= self.precedence() {
+                    format!("({})", right.minimal())
+                } else {
+                    right.minimal()
+                };
+
+                format!("{} {} {}", left, operator, right)
+            }
+        }
+    }
+
+    fn parenthesized(&self) -> String {
+        match self {
+            Expr::Number(n) => n.to_string(),
+            Expr::Binary(left, operator, right) => {
+                format!(
+                    "({} {} {})",
+                    left.parenthesized(),
+                    operator,
+                    right.parenthesized()
+                )
+            }
+        }
+    }
+}
+
+fn expr() -> impl Strategy<Value = Expr> {
+    (0..10u8).prop_map(Expr::Number).prop_recursive(3, 8, 2, |inner| {
+        (
+            inner.clone(),
+            prop_oneof![Just("+"), Just("-"), Just("*")],
+            inner,
+        )
+            .prop_map(|(left, operator, right)| Expr::Binary(left.into(), operator, right.into()))
+    })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn test_precedence(e in expr()) {
+        run(&format!(
+            "assert_eq({}, {}); assert_eq({}, {});",
+            e.minimal(),
+            e.value(),
+            e.minimal(),
+            e.parenthesized()
+        ));
+    }
+
+    #[test]
+    fn test_number_commutativity(a in 0..1000u32, b in 0..1000u32) {
+        run(&format!(
+            "assert_eq({a} + {b}, {b} + {a}); assert_eq({a} * {b}, {b} * {a});"
+        ));
+    }
+
+    #[test]
+    fn test_number_comparisons(a in 0..1000u32, b in 0..1000u32) {
+        run(&format!(
+            "assert_eq({a} < {b}, {b} > {a});
+             assert_eq({a} <= {b}, {b} >= {a});
+             assert_eq({a} == {b}, {b} == {a});
+             assert_eq({a} != {b}, {a} < {b} || {a} > {b});"
+        ));
+    }
+
+    #[test]
+    fn test_bool_commutativity(a in any::<bool>(), b in any::<bool>()) {
+        run(&format!(
+            "assert_eq({a} && {b}, {b} && {a}); assert_eq({a} || {b}, {b} || {a});"
+        ));
+    }
+
+    #[test]
+    fn test_bool_precedence(a in any::<bool>(), b in any::<bool>(), c in any::<bool>()) {
+        run(&format!(
+            "assert_eq({a} || {b} && {c}, {a} || ({b} && {c}));
+             assert_eq({a} && {b} || {c}, ({a} && {b}) || {c});"
+        ));
+    }
+
+    #[test]
+    fn test_combine_associativity(a in "[a-z]{0,8}", b in "[a-z]{0,8}", c in "[a-z]{0,8}") {
+        run(&format!(
+            r#"assert_eq(("{a}" <> "{b}") <> "{c}", "{a}" <> ("{b}" <> "{c}"));
+               assert_eq("{a}" <> "{b}" <> "{c}", "{a}{b}{c}");"#
+        ));
+    }
+
+    #[test]
+    fn test_combine_precedence(a in "[a-z]{0,8}", b in "[a-z]{0,8}", n in 0..4u32) {
+        run(&format!(
+            r#"assert_eq("{a}" <> "{b}" * {n}, ("{a}" <> "{b}") * {n});
+               assert_eq("{a}" <> "{b}" == "{a}{b}", true);"#
+        ));
+    }
+
+    #[test]
+    fn test_declared_commutativity(k in 0..1000u32, amount in 0..1000u32) {
+        run(&format!(
+            "struct Money {{
+                amount;
+            }}
+
+            commutative operator * (k : Number, m : Money) {{
+                Money:{{ k * m.amount }}
+            }}
+
+            let m = Money:{{ {amount} }};
+            assert_eq({k} * m, m * {k});
+            assert_eq(({k} * m).amount, {k} * {amount});"
+        ));
+    }
+}
@@ -0,0 +1,78 @@
+use crate::run;
+
+#[test]
+fn test_basics() {
+    run(r#"
+            let plus = op("+");
+
+            assert_eq(plus(1, 2), 3);
+            assert_eq(op("<>")("a", "b"), "ab");
+            assert_eq(op("<")(1, 2), true);
+
+            let apply = fn(f, a, b) { f(a, b) };
+            assert_eq(apply(op("*"), 6, 7), 42);
+        "#)
+}
+
+#[test]
+fn test_declared_operator() {
+    run(r#"
+            struct Vec {
+                x;
+            }
+
+            operator + (a : Vec, b : Vec) {
+                Vec :{ a.x + b.x }
+            }
+
+            assert_eq(op("+")(Vec :{ 1 }, Vec :{ 2 }).x, 3);
+        "#)
+}
+
+#[test]
+fn test_heap() {
+    run(r#"
+            let heap = Heap:{ op(">") };
+
+            heap.push(1);
+            heap.push(3);
+            heap.push(2);
+
+            assert_eq(heap.pop(), 3);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`op` expects an operator as a string literal")]
+fn test_not_a_literal() {
+    run(r#"
+            let name = "+";
+            op(name);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`op` expects an operator, got \"nonsense\"")]
+fn test_not_an_operator() {
+    run(r#"
+            if false {
+                op("nonsense");
+            }
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "in function `(+)`: missing argument `right`")]
+fn test_one_argument() {
+    run(r#"
+            op("+")(1);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`op` can't be used as a name")]
+fn test_declared() {
+    run(r#"
+            let op = fn(name) { name <> "!" };
+        "#)
+}
@@ -0,0 +1,127 @@
+use crate::run;
+
+#[test]
+fn test_basics() {
+    run(r#"
+            let name = fn(n) {
+                when(n,
+                    case(1, "one"),
+                    case(2, 3, "two or three"),
+                    "many"
+                )
+            };
+
+            assert_eq(name(1), "one");
+            assert_eq(name(3), "two or three");
+            assert_eq(name(9), "many");
+            assert_eq(when("b", case("a", 1)), nah);
+        "#)
+}
+
+#[test]
+fn test_only_chosen_arm_is_evaluated() {
+    run(r#"
+            let calls = 0;
+            let subject = fn() {
+                calls = calls + 1;
+                2
+            };
+
+            let r = when(subject(),
+                case(1, 1 / 0),
+                case(2, "two"),
+                1 / 0
+            );
+
+            assert_eq(r, "two");
+            assert_eq(calls, 1);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "only the last one can be a default result")]
+fn test_default_in_the_middle() {
+    run(r#"
+            when(1, "default", case(1, 2));
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`case` can only be used inside of `when`")]
+fn test_case_outside_of_when() {
+    run(r#"
+            case(1, 2);
+        "#)
+}
+
+#[test]
+fn test_fallthrough() {
+    run(r#"
+            let log = "";
+            let note = fn(s) {
+                log = log <> s;
+            };
+
+            let r = when(1,
+                case(1, fallthrough(note("one "))),
+                case(2, fallthrough(note("two "))),
+                case(3, "three"),
+                "other"
+            );
+
+            assert_eq(r, "three");
+            assert_eq(log, "one two ");
+
+            assert_eq(when(2, case(1, "one"), case(2, fallthrough(note("last "))), "default"), "default");
+            assert_eq(log, "one two last ");
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`fallthrough` can only be the result of `case`")]
+fn test_fallthrough_outside_of_case() {
+    run(r#"
+            fallthrough(1);
+        "#)
+}
+
+#[test]
+#[should_panic(
+    expected = "`when` can't be used as a name, its calls are rewritten before the program runs"
+)]
+fn test_declared_below() {
+    run(r#"
+            let f = fn() { when(1, case(1, "builtin")) };
+            let when = fn(a, b) { "user" };
+            print(f());
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`when` can't be used as a name")]
+fn test_parameter() {
+    run(r#"
+            let f = fn(when) {
+                when(1, 2)
+            };
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`when` can't be used as a name")]
+fn test_field() {
+    run(r#"
+            class Rule {
+                when;
+            }
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`when` can't be used as a name")]
+fn test_assigned_in_scope() {
+    run(r#"
+            let s = scope();
+            s.when = fn(a, b) { "user" };
+        "#)
+}
@@ -1,20 +1,29 @@
-use crate::interpreter::runner::execute_source_code;
-
-#[path = "../src/interpreter/mod.rs"]
-mod interpreter;
-
-#[path = "../src/stdlib/mod.rs"]
-mod stdlib;
+use frugurt::interpreter::{
+    options::InterpreterOptions,
+    runner::{execute_source_code, execute_source_code_with_options},
+};
 
 mod builtin;
+mod corpus;
+mod embedding;
 mod expression;
 mod literal_expression;
 mod oop;
+mod project;
+mod sandbox;
 mod scope_manipulation;
+mod spec;
 mod statement;
+mod tooling;
 
 pub fn run(code: &str) {
     if let Err(err) = execute_source_code(code.to_owned()) {
         panic!("{}", err)
     }
 }
+
+pub fn run_with_options(code: &str, options: InterpreterOptions) {
+    if let Err(err) = execute_source_code_with_options(code.to_owned(), options) {
+        panic!("{}", err)
+    }
+}
@@ -16,6 +16,8 @@ fn test_operators() {
         assert_eq("hello" <> "world", "helloworld");
         assert_eq("hi mom" * 4, "hi momhi momhi momhi mom");
         assert_eq(3 * "kek, ", "kek, kek, kek, ");
+        assert_eq("abc" * 0, "");
+        assert_eq("" * 1000000, "");
         "#)
 }
 
@@ -47,6 +49,23 @@ fn test_string_times_float() {
         "#)
 }
 
+#[test]
+#[should_panic(expected = "is too long")]
+fn test_string_times_huge() {
+    run(r#"
+            "asd" * 10 ** 30;
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "is too long")]
+fn test_string_times_more_than_memory() {
+    // fits in `usize`, but no allocation can be that large
+    run(r#"
+            "abc" * 2 ** 62;
+        "#)
+}
+
 #[test]
 fn test_string_escaping() {
     run(r#"
@@ -0,0 +1,93 @@
+use frugurt::interpreter::{options::InterpreterOptions, runner::execute_source_code_with_options};
+
+use crate::run;
+
+const ACCOUNT: &str = r#"
+    class Account {
+        balance;
+    } impl {
+        withdraw(amount) {
+            balance = balance - amount;
+            balance
+        }
+
+        _requires_withdraw(amount) {
+            amount > 0 && amount <= balance
+        }
+
+        _ensures_withdraw(result, amount) {
+            result >= 0
+        }
+
+        static open(balance) {
+            Account:{ balance }
+        }
+
+        static _requires_open(balance) {
+            balance >= 0
+        }
+    }
+"#;
+
+#[test]
+fn test_contracts_hold() {
+    run(&format!(
+        r#"{ACCOUNT}
+            let account = Account.open(10);
+            assert_eq(account.withdraw(4), 6);
+            assert_eq(account.withdraw(6), 0);
+        "#
+    ))
+}
+
+#[test]
+#[should_panic(expected = "precondition of `Account.withdraw` is violated")]
+fn test_precondition() {
+    run(&format!(
+        r#"{ACCOUNT}
+            let account = Account:{{ 10 }};
+            account.withdraw(20);
+        "#
+    ))
+}
+
+#[test]
+#[should_panic(expected = "precondition of `Account.open` is violated")]
+fn test_static_precondition() {
+    run(&format!("{ACCOUNT} Account.open(0 - 1);"))
+}
+
+#[test]
+#[should_panic(expected = "postcondition of `Counter.next` is violated")]
+fn test_postcondition() {
+    run(r#"
+            struct Counter {
+                n;
+            } impl {
+                next() {
+                    n = n + 1;
+                    n * 2
+                }
+
+                _ensures_next(result) {
+                    result == n
+                }
+            }
+
+            Counter:{ 0 }.next();
+        "#)
+}
+
+#[test]
+fn test_skip_contracts() {
+    let options = InterpreterOptions {
+        skip_contracts: true,
+        ..Default::default()
+    };
+
+    let code = format!("{ACCOUNT} let a = Account:{{ 1 }}; assert_eq(a.withdraw(5), -4);");
+
+    if let Err(err) = execute_source_code_with_options(code, options) {
+        panic!("{}", err)
+    }
+}
@@ -0,0 +1,95 @@
+use frugurt::interpreter::options::InterpreterOptions;
+
+use crate::{run, run_with_options};
+
+const MONEY: &str = r#"
+    struct Money {
+        cents;
+    } impl {
+        _to_number() {
+            cents / 100
+        }
+
+        _to_string() {
+            String(cents / 100) <> "$"
+        }
+
+        _to_bool() {
+            cents != 0
+        }
+    }
+"#;
+
+#[test]
+fn test_conversions() {
+    run(&format!(
+        r#"{MONEY}
+            let m = Money:{{ 250 }};
+
+            assert_eq(Number(m), 2.5);
+            assert_eq(String(m), "2.5$");
+            assert_eq(Bool(m), true);
+            assert_eq(Bool(Money:{{ 0 }}), false);
+            assert_eq(Template("total: {{total}}").render(total: m), "total: 2.5$");
+        "#
+    ))
+}
+
+#[test]
+fn test_decimal() {
+    run(r#"
+            assert_eq(Number(Decimal("0.25")) * 4, 1);
+            assert_eq(Bool(Decimal("0.00")), false);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`_to_number` of `Weird` must return `Number`, got `String`")]
+fn test_wrong_result() {
+    run(r#"
+            struct Weird {
+            } impl {
+                _to_number() {
+                    "one"
+                }
+            }
+
+            Number(Weird:{});
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "Expected `Bool` in if condition, got `Money`")]
+fn test_conditions_need_bool() {
+    run(&format!(
+        r#"{MONEY}
+            let m = Money:{{ 1 }};
+            if m {{}}
+        "#
+    ))
+}
+
+#[test]
+fn test_truthy_conditions() {
+    let options = InterpreterOptions {
+        truthy_conditions: true,
+        ..Default::default()
+    };
+
+    run_with_options(
+        &format!(
+            r#"{MONEY}
+            let m = Money:{{ 0 }};
+            let paid = if m {{ "yes" }} else {{ "no" }};
+            assert_eq(paid, "no");
+
+            let n = 3;
+            while n {{
+                n = n - 1;
+            }}
+            assert_eq(n, 0);
+        "#
+        ),
+        options,
+    )
+}
@@ -0,0 +1,126 @@
+use frugurt::interpreter::{options::InterpreterOptions, runner::Interpreter};
+
+fn deprecations_of(code: &str) -> Vec<(String, usize)> {
+    let interpreter = Interpreter::new(InterpreterOptions {
+        warnings: true,
+        ..Default::default()
+    });
+
+    interpreter.execute(code.to_owned()).unwrap();
+
+    (interpreter.warnings().into_iter())
+        .map(|warning| (warning.message, warning.count))
+        .collect()
+}
+
+#[test]
+fn test_function() {
+    assert_eq!(
+        deprecations_of(
+            r#"
+/// Adds one
+/// @deprecated("use `inc` instead")
+let add_one = fn(x) { x + 1 };
+
+add_one(1);
+
+let i = 0;
+while i < 3 {
+    add_one(i);
+    i = i + 1;
+}
+"#
+        ),
+        vec![
+            (
+                "function `add_one` is deprecated: use `inc` instead at 6:1".to_string(),
+                1
+            ),
+            (
+                "function `add_one` is deprecated: use `inc` instead at 10:5".to_string(),
+                3
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_type_and_field() {
+    assert_eq!(
+        deprecations_of(
+            r#"
+/// @deprecated
+struct Old {
+    x;
+}
+
+struct Point {
+    x;
+    /// @deprecated(use `x` instead)
+    horizontal;
+}
+
+let o = Old:{ 1 };
+let p = Point:{ 1, 2 };
+p.x = p.horizontal;
+p.horizontal = 3;
+"#
+        ),
+        vec![
+            ("type `Old` is deprecated at 13:1".to_string(), 1),
+            (
+                "field `Point.horizontal` is deprecated: use `x` instead at 15:1".to_string(),
+                1
+            ),
+            (
+                "field `Point.horizontal` is deprecated: use `x` instead at 16:1".to_string(),
+                1
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_method() {
+    assert_eq!(
+        deprecations_of(
+            r#"
+struct Counter {
+    count;
+} impl {
+    /// @deprecated("use `next`")
+    increment() {
+        count + 1
+    }
+
+    next() {
+        count + 1
+    }
+}
+
+let c = Counter:{ 0 };
+c.increment();
+c.next();
+"#
+        ),
+        vec![(
+            "function `increment` is deprecated: use `next` at 16:1".to_string(),
+            1
+        )]
+    );
+}
+
+#[test]
+fn test_not_deprecated() {
+    assert_eq!(
+        deprecations_of(
+            r#"
+/// Not @deprecated, the note must start a line
+let f = fn() {};
+
+f();
+"#
+        ),
+        vec![]
+    );
+}
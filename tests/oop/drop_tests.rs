@@ -0,0 +1,69 @@
+use crate::run;
+
+#[test]
+fn test_drop() {
+    run(r#"
+            let log = "";
+
+            class Resource {
+                name;
+            } impl {
+                _drop() {
+                    log = log <> name <> ";";
+                }
+            }
+
+            let a = Resource:{ "a" };
+            let b = Resource:{ "b" };
+
+            a = nah;
+            assert_eq(log, "a;");
+
+            {
+                let c = Resource:{ "c" };
+            }
+            assert_eq(log, "a;c;");
+
+            b = nah;
+            assert_eq(log, "a;c;b;");
+        "#)
+}
+
+#[test]
+fn test_drop_once() {
+    run(r#"
+            let count = 0;
+
+            class Resource {
+            } impl {
+                _drop() {
+                    count = count + 1;
+                }
+            }
+
+            let a = Resource:{};
+            let b = a;
+
+            a = nah;
+            assert_eq(count, 0);
+
+            b = nah;
+            assert_eq(count, 1);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "error in `_drop` of `Resource`")]
+fn test_drop_error() {
+    run(r#"
+            class Resource {
+            } impl {
+                _drop() {
+                    assert_eq(1, 2);
+                }
+            }
+
+            let a = Resource:{};
+            a = nah;
+        "#)
+}
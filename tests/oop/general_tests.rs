@@ -63,6 +63,121 @@ fn test_operators() {
         "#)
 }
 
+#[test]
+fn test_commutative_operators() {
+    run(r#"
+            struct Pair {
+                x;
+                y;
+            }
+
+            commutative operator - (k : Number, v : Pair) {
+                Pair :{ k - v.x, k - v.y }
+            }
+
+            let v = Pair :{ 1, 2 };
+
+            assert_eq(10 - v, Pair :{ 9, 8 });
+            assert_eq(v - 10, Pair :{ 9, 8 });
+
+            let f = fn() {
+                commutative operator * (v : Pair, k : Number) {
+                    Pair :{ v.x * k, v.y * k }
+                }
+
+                assert_eq(v * 3, 3 * v);
+            };
+            f();
+
+            assert_eq(operators_of(Pair), "Number - Pair\nPair - Number");
+        "#)
+}
+
+#[test]
+fn test_structural_comparison() {
+    run(r#"
+            struct Version {
+                major;
+                minor;
+                tag;
+            }
+
+            let a = Version :{ 1, 2, "beta" };
+            let b = Version :{ 1, 10, "alpha" };
+
+            assert_eq(a == Version :{ 1, 2, "beta" }, true);
+            assert_eq(a != b, true);
+            assert_eq(a < b, true);
+            assert_eq(b >= a, true);
+            assert_eq(a <= a, true);
+
+            struct Range {
+                from;
+                to;
+            }
+
+            assert_eq(Range :{ a, b } < Range :{ b, a }, true);
+
+            operator < (x : Version, y : Version) {
+                x.tag < y.tag
+            }
+
+            assert_eq(a < b, false);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "values of `Number` and `String` cannot be ordered")]
+fn test_structural_comparison_error() {
+    run(r#"
+            struct Box {
+                value;
+            }
+
+            Box :{ 1 } < Box :{ "one" };
+        "#)
+}
+
+#[test]
+fn test_structural_comparison_of_classes() {
+    run(r#"
+            class Node {
+                pub next;
+            }
+
+            struct Link {
+                node;
+            }
+
+            let a = Node :{ nah };
+            a.next = a;
+            let b = Node :{ nah };
+            b.next = b;
+
+            assert_eq(a, b);
+            assert_eq(Link :{ a } == Link :{ a }, true);
+            assert_eq(Link :{ a } == Link :{ b }, false);
+            assert_eq(Link :{ a } <= Link :{ a }, true);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "operator `Operator(Node == Node)` does not exist")]
+fn test_no_structural_comparison_for_classes() {
+    run(r#"
+            class Node {
+                pub next;
+            }
+
+            let a = Node :{ nah };
+            a.next = a;
+            let b = Node :{ nah };
+            b.next = b;
+
+            a == b;
+        "#)
+}
+
 #[test]
 fn test_named_fields() {
     run(r#"
@@ -0,0 +1,85 @@
+use crate::run;
+
+#[test]
+fn test_lazy_static() {
+    run(r#"
+            let calls = 0;
+
+            struct Config {
+                static settings = lazy(fn() {
+                    calls = calls + 1;
+                    "loaded"
+                });
+            } impl {
+                static read() {
+                    settings
+                }
+            }
+
+            assert_eq(calls, 0);
+            assert_eq(Config.settings, "loaded");
+            assert_eq(Config.read(), "loaded");
+            assert_eq(calls, 1);
+        "#)
+}
+
+#[test]
+fn test_lazy_value() {
+    run(r#"
+            let calls = 0;
+            let l = lazy(fn() {
+                calls = calls + 1;
+                7
+            });
+
+            assert_eq(l.get(), 7);
+            assert_eq(l.get(), 7);
+            assert_eq(calls, 1);
+        "#)
+}
+
+#[test]
+fn test_cached_property() {
+    run(r#"
+            let calls = 0;
+
+            struct Rect {
+                w;
+                h;
+            } impl {
+                _cached_area() {
+                    calls = calls + 1;
+                    w * h
+                }
+
+                grow() {
+                    w = w + 1;
+                }
+
+                double() {
+                    area * 2
+                }
+            }
+
+            let r = Rect:{ 2, 3 };
+
+            assert_eq(r.area, 6);
+            assert_eq(r.double(), 12);
+            assert_eq(calls, 1);
+
+            r.h = 10;
+            assert_eq(r.area, 20);
+
+            r.grow();
+            assert_eq(r.area, 30);
+            assert_eq(calls, 3);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`lazy` expects a function, got `Number`")]
+fn test_lazy_not_function() {
+    run(r#"
+            lazy(1);
+        "#)
+}
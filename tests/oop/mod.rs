@@ -1,6 +1,11 @@
 mod class_tests;
+mod contract_tests;
+mod conversion_tests;
 mod data_tests;
+mod deprecation_tests;
+mod drop_tests;
 mod general_tests;
+mod lazy_tests;
 mod property_tests;
 mod static_tests;
 mod struct_tests;
@@ -0,0 +1,151 @@
+use std::{
+    fs::{create_dir_all, write},
+    path::{Path, PathBuf},
+};
+
+use frugurt::interpreter::{
+    manifest::{Dependency, Manifest},
+    options::InterpreterOptions,
+    runner::execute_file_with_options,
+};
+use tempfile::TempDir;
+
+fn write_file(path: PathBuf, content: &str) {
+    create_dir_all(path.parent().unwrap()).unwrap();
+    write(path, content).unwrap();
+}
+
+fn project() -> TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+
+    write_file(
+        root.join("app/fruit.toml"),
+        r#"
+[package]
+name = "app"
+version = "1.2.0"
+source_dirs = ["src", "lib"]
+
+[dependencies]
+math = { path = "../math" }
+"#,
+    );
+
+    write_file(
+        root.join("app/src/main.fru"),
+        r#"
+let util = import "util.fru";
+let helper = import "helper.fru";
+let math = import "math";
+let extra = import "math/extra.fru";
+
+assert_eq(util.x, 1);
+assert_eq(helper.y, 2);
+assert_eq(math.square(3), 9);
+assert_eq(extra.z, 3);
+"#,
+    );
+
+    write_file(root.join("app/src/util.fru"), "let x = 1;");
+    write_file(root.join("app/lib/helper.fru"), "let y = 2;");
+
+    write_file(
+        root.join("math/fruit.toml"),
+        r#"
+[package]
+name = "math"
+entry = "math.fru"
+source_dirs = ["."]
+"#,
+    );
+
+    write_file(root.join("math/math.fru"), "let square = fn(x) { x * x };");
+    write_file(root.join("math/extra.fru"), "let z = 3;");
+
+    dir
+}
+
+#[test]
+fn test_parse() {
+    let manifest = Manifest::parse(
+        r#"
+[package]
+name = "demo"
+
+[dependencies]
+utils = { path = "../utils" }
+json = { git = "https://example.com/json.git", rev = "v1" }
+"#,
+        Path::new("/project"),
+    )
+    .unwrap();
+
+    assert_eq!(manifest.name, "demo");
+    assert_eq!(manifest.version, "0.0.0");
+    assert_eq!(manifest.entry_path(), Path::new("/project/src/main.fru"));
+    assert_eq!(manifest.source_paths(), vec![Path::new("/project/src")]);
+    assert_eq!(
+        manifest.dependencies["utils"],
+        Dependency::Path(PathBuf::from("../utils"))
+    );
+    assert_eq!(
+        manifest.dependencies["json"],
+        Dependency::Git {
+            url: "https://example.com/json.git".to_string(),
+            rev: Some("v1".to_string()),
+        }
+    );
+    assert_eq!(
+        manifest.dependency_root("json").unwrap(),
+        Path::new("/project/.fruit/deps/json")
+    );
+}
+
+#[test]
+fn test_project() {
+    let dir = project();
+    let root = dir.path().join("app");
+
+    let manifest = Manifest::find(&root.join("src/main.fru")).unwrap().unwrap();
+    assert_eq!(manifest.name, "app");
+    assert_eq!(manifest.version, "1.2.0");
+
+    let options = InterpreterOptions {
+        project: Some(manifest.clone()),
+        ..Default::default()
+    };
+
+    if let Err(err) = execute_file_with_options(&manifest.entry_path(), options) {
+        panic!("{}", err)
+    }
+}
+
+#[test]
+fn test_missing_dependency_path() {
+    let err = Manifest::parse(
+        "[package]\nname = \"demo\"\n[dependencies]\nutils = \"1.0\"",
+        Path::new("."),
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "dependency `utils` must have either a `path` or a `git`"
+    );
+}
+
+#[test]
+fn test_names_must_be_identifiers() {
+    let err = Manifest::parse(
+        "[package]\nname = \"demo\"\n[dependencies]\n\"../../outside\" = { git = \"https://example.com/x.git\" }",
+        Path::new("."),
+    )
+    .unwrap_err();
+
+    assert!(err.to_string().starts_with("dependency name `../../outside` must consist of"));
+
+    let err = Manifest::parse("[package]\nname = \"a/b\"\n", Path::new(".")).unwrap_err();
+
+    assert!(err.to_string().starts_with("package name `a/b` must consist of"));
+}
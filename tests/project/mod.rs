@@ -0,0 +1,2 @@
+mod manifest_tests;
+mod package_tests;
@@ -0,0 +1,207 @@
+use std::{
+    fs::{create_dir_all, read_to_string, remove_file, write},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use frugurt::{
+    interpreter::manifest::{Dependency, Manifest},
+    tooling::package::{self, Lockfile},
+};
+use tempfile::TempDir;
+
+fn write_file(path: PathBuf, content: &str) {
+    create_dir_all(path.parent().unwrap()).unwrap();
+    write(path, content).unwrap();
+}
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{:?}", output);
+
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+fn commit(repo: &Path, file: &str, content: &str) -> String {
+    write_file(repo.join(file), content);
+    git(repo, &["add", "-A"]);
+    git(repo, &["commit", "--quiet", "-m", "update"]);
+    git(repo, &["rev-parse", "HEAD"])
+}
+
+// app project and a git repository `json-lib` with package `json`
+fn workspace() -> (TempDir, PathBuf, String) {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+
+    write_file(
+        root.join("app/fruit.toml"),
+        "# app manifest\n[package]\nname = \"app\"\n",
+    );
+
+    let repo = root.join("json-lib");
+    create_dir_all(&repo).unwrap();
+    git(&repo, &["init", "--quiet"]);
+    write_file(
+        repo.join("fruit.toml"),
+        "[package]\nname = \"json\"\nentry = \"json.fru\"\nsource_dirs = [\".\"]\n",
+    );
+    commit(&repo, "json.fru", "let version = 1;");
+
+    let url = format!("file://{}", repo.display());
+
+    (dir, repo, url)
+}
+
+#[test]
+fn test_parse_source() {
+    assert_eq!(
+        package::parse_source("../math"),
+        Dependency::Path(PathBuf::from("../math"))
+    );
+
+    assert!(matches!(
+        package::parse_source("https://example.com/user/json"),
+        Dependency::Git { .. }
+    ));
+
+    assert!(matches!(
+        package::parse_source("git@example.com:user/json.git"),
+        Dependency::Git { .. }
+    ));
+}
+
+#[test]
+fn test_add_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+
+    write_file(root.join("app/fruit.toml"), "[package]\nname = \"app\"\n");
+    write_file(root.join("math/fruit.toml"), "[package]\nname = \"math\"\n");
+
+    let app = root.join("app");
+    assert_eq!(package::add(&app, "../math", None).unwrap(), "math");
+
+    let manifest = Manifest::load(&app).unwrap();
+    assert_eq!(
+        manifest.dependencies["math"],
+        Dependency::Path(PathBuf::from("../math"))
+    );
+
+    let err = package::add(&app, "../math", None).unwrap_err();
+    assert_eq!(err.to_string(), "dependency `math` already exists");
+}
+
+#[test]
+fn test_add_git() {
+    let (dir, repo, url) = workspace();
+    let app = dir.path().join("app");
+
+    assert_eq!(package::add(&app, &url, None).unwrap(), "json");
+
+    let source = read_to_string(app.join("fruit.toml")).unwrap();
+    assert!(source.starts_with("# app manifest"));
+
+    let manifest = Manifest::load(&app).unwrap();
+    assert_eq!(
+        manifest.dependencies["json"],
+        Dependency::Git { url, rev: None }
+    );
+
+    let lockfile = Lockfile::load(&app).unwrap();
+    assert_eq!(
+        lockfile.packages["json"].commit,
+        git(&repo, &["rev-parse", "HEAD"])
+    );
+
+    assert_eq!(
+        manifest.resolve_import(Path::new("json")).unwrap().unwrap(),
+        app.join(".fruit/deps/json/json.fru")
+    );
+}
+
+#[test]
+fn test_fetch_locked() {
+    let (dir, repo, url) = workspace();
+    let app = dir.path().join("app");
+
+    package::add(&app, &url, None).unwrap();
+    let locked = Lockfile::load(&app).unwrap().packages["json"].commit.clone();
+
+    let latest = commit(&repo, "json.fru", "let version = 2;");
+
+    // lockfile keeps the old commit
+    package::fetch(&app).unwrap();
+    assert_eq!(
+        Lockfile::load(&app).unwrap().packages["json"].commit,
+        locked
+    );
+    assert_eq!(
+        read_to_string(app.join(".fruit/deps/json/json.fru")).unwrap(),
+        "let version = 1;"
+    );
+
+    // without it the latest commit is fetched
+    remove_file(app.join("fruit.lock")).unwrap();
+    package::fetch(&app).unwrap();
+    assert_eq!(
+        Lockfile::load(&app).unwrap().packages["json"].commit,
+        latest
+    );
+    assert_eq!(
+        read_to_string(app.join(".fruit/deps/json/json.fru")).unwrap(),
+        "let version = 2;"
+    );
+}
+
+#[test]
+fn test_not_fetched() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+
+    write_file(
+        root.join("fruit.toml"),
+        "[package]\nname = \"app\"\n[dependencies]\njson = { git = \"https://example.com/json.git\" }\n",
+    );
+
+    let manifest = Manifest::load(root).unwrap();
+    let err = manifest.resolve_import(Path::new("json")).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "dependency `json` is not fetched, run `frugurt fetch`"
+    );
+}
+
+#[test]
+fn test_git_options_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+
+    for (dependency, error) in [
+        (
+            "{ git = \"--upload-pack=touch pwned\" }",
+            "git url `--upload-pack=touch pwned` must not start with `-`",
+        ),
+        (
+            "{ git = \"https://example.com/json.git\", rev = \"--output=pwned\" }",
+            "git rev `--output=pwned` must not start with `-`",
+        ),
+    ] {
+        write_file(
+            root.join("fruit.toml"),
+            &format!("[package]\nname = \"app\"\n[dependencies]\njson = {dependency}\n"),
+        );
+
+        assert_eq!(package::fetch(root).unwrap_err().to_string(), error);
+    }
+
+    assert!(!root.join("pwned").exists());
+}
@@ -0,0 +1,73 @@
+use std::{thread, time::Duration};
+
+use frugurt::interpreter::{
+    error::FruErrorKind, options::InterpreterOptions, runner::execute_source_code_with_options,
+    runtime::CancellationToken,
+};
+
+fn run_cancellable(code: &str, token: CancellationToken) -> Option<FruErrorKind> {
+    execute_source_code_with_options(
+        code.to_owned(),
+        InterpreterOptions {
+            cancellation: Some(token),
+            ..Default::default()
+        },
+    )
+    .err()
+    .map(|err| err.kind())
+}
+
+#[test]
+fn test_cancel_infinite_loop() {
+    let token = CancellationToken::new();
+
+    let canceller = {
+        let token = token.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            token.cancel();
+        })
+    };
+
+    let result = run_cancellable(
+        r#"
+            while true {}
+        "#,
+        token,
+    );
+
+    canceller.join().unwrap();
+
+    assert_eq!(result, Some(FruErrorKind::Cancelled));
+}
+
+#[test]
+fn test_cancelled_before_start() {
+    let token = CancellationToken::new();
+    token.cancel();
+
+    let result = run_cancellable(
+        r#"
+            let f = fn() { f() };
+            f();
+        "#,
+        token,
+    );
+
+    assert_eq!(result, Some(FruErrorKind::Cancelled));
+}
+
+#[test]
+fn test_not_cancelled() {
+    let result = run_cancellable(
+        r#"
+            let i = 0;
+            while i < 100 {
+                i = i + 1;
+            }
+        "#,
+        CancellationToken::new(),
+    );
+
+    assert_eq!(result, None);
+}
@@ -0,0 +1,78 @@
+use std::io::Write;
+
+use frugurt::interpreter::options::{Capabilities, Capability, InterpreterOptions};
+use tempfile::NamedTempFile;
+
+use crate::run_with_options;
+
+fn temp_module(code: &str) -> NamedTempFile {
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(code.as_bytes()).unwrap();
+    file
+}
+
+#[test]
+fn test_import_granted() {
+    let module = temp_module("let x = 5;");
+
+    run_with_options(
+        &format!(
+            r#"
+            let m = import {:?};
+            assert_eq(m.x, 5);
+        "#,
+            module.path()
+        ),
+        InterpreterOptions {
+            capabilities: Capabilities::none().with(Capability::Fs),
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+#[should_panic(expected = "capability `fs` is not granted")]
+fn test_import_denied() {
+    let module = temp_module("let x = 5;");
+
+    run_with_options(
+        &format!(
+            r#"
+            import {:?};
+        "#,
+            module.path()
+        ),
+        InterpreterOptions {
+            capabilities: Capabilities::none(),
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+#[should_panic(expected = "capability `fs` is not granted")]
+fn test_fs_denied() {
+    run_with_options(
+        r#"
+            Fs.glob("**/*.fru");
+        "#,
+        InterpreterOptions {
+            capabilities: Capabilities::none(),
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+#[should_panic(expected = "capability `stdin` is not granted")]
+fn test_input_denied() {
+    run_with_options(
+        r#"
+            input("name: ");
+        "#,
+        InterpreterOptions {
+            capabilities: Capabilities::all().without(Capability::Stdin),
+            ..Default::default()
+        },
+    )
+}
@@ -0,0 +1,96 @@
+use std::{cell::Cell, rc::Rc};
+
+use frugurt::interpreter::{error::FruErrorKind, options::InterpreterOptions, runner::Interpreter};
+
+fn with_fuel(fuel: u64) -> Interpreter {
+    Interpreter::new(InterpreterOptions {
+        fuel: Some(fuel),
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_out_of_fuel() {
+    let interpreter = with_fuel(1000);
+
+    let err = interpreter.execute("while true {}".to_string()).unwrap_err();
+
+    assert_eq!(err.kind(), FruErrorKind::OutOfFuel);
+    assert_eq!(interpreter.fuel_remaining(), Some(0));
+}
+
+#[test]
+fn test_add_fuel() {
+    let interpreter = with_fuel(100);
+
+    interpreter.execute("let x = 1;".to_string()).unwrap();
+    let left = interpreter.fuel_remaining().unwrap();
+    assert!(left < 100);
+
+    let err = interpreter.execute("while x < 1000 { x = x + 1; }".to_string()).unwrap_err();
+    assert_eq!(err.kind(), FruErrorKind::OutOfFuel);
+
+    // the next frame continues where the script has stopped
+    interpreter.add_fuel(10_000);
+    interpreter
+        .execute("while x < 1000 { x = x + 1; } assert_eq(x, 1000);".to_string())
+        .unwrap();
+}
+
+#[test]
+fn test_on_low_fuel() {
+    let interpreter = with_fuel(50);
+    let refills = Rc::new(Cell::new(0));
+
+    interpreter.on_low_fuel(10, {
+        let refills = refills.clone();
+        move |remaining| {
+            assert_eq!(remaining, 10);
+            refills.set(refills.get() + 1);
+
+            if refills.get() < 3 {
+                50
+            } else {
+                0
+            }
+        }
+    });
+
+    let err = interpreter.execute("while true {}".to_string()).unwrap_err();
+
+    assert_eq!(err.kind(), FruErrorKind::OutOfFuel);
+    assert_eq!(refills.get(), 3);
+}
+
+#[test]
+fn test_on_low_fuel_below_threshold() {
+    let interpreter = with_fuel(5);
+    let calls = Rc::new(Cell::new(Vec::new()));
+
+    interpreter.on_low_fuel(10, {
+        let calls = calls.clone();
+        move |remaining| {
+            let mut seen = calls.take();
+            seen.push(remaining);
+            calls.set(seen);
+
+            // not enough to get above the threshold, so it is not called again
+            3
+        }
+    });
+
+    let err = interpreter.execute("while true {}".to_string()).unwrap_err();
+
+    assert_eq!(err.kind(), FruErrorKind::OutOfFuel);
+    assert_eq!(calls.take(), vec![4]);
+}
+
+#[test]
+fn test_unlimited() {
+    let interpreter = Interpreter::new(InterpreterOptions::default());
+
+    interpreter.add_fuel(10);
+    interpreter.execute("let x = 1;".to_string()).unwrap();
+
+    assert_eq!(interpreter.fuel_remaining(), None);
+}
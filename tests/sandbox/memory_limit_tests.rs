@@ -0,0 +1,125 @@
+use frugurt::interpreter::{
+    error::FruErrorKind, options::InterpreterOptions, runner::execute_source_code_with_options,
+};
+
+use crate::run_with_options;
+
+fn limited(bytes: usize) -> InterpreterOptions {
+    InterpreterOptions {
+        memory_limit: Some(bytes),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_garbage_is_released() {
+    run_with_options(
+        r#"
+            struct Box {
+                value;
+            }
+
+            let i = 0;
+            while i < 10000 {
+                let b = Box:{ i };
+                i = i + 1;
+            }
+        "#,
+        limited(64 * 1024),
+    )
+}
+
+#[test]
+#[should_panic(expected = "memory limit of 65536 bytes exceeded")]
+fn test_retained_objects() {
+    run_with_options(
+        r#"
+            class Node {
+                next;
+            }
+
+            let head = nah;
+            let i = 0;
+            while i < 10000 {
+                head = Node:{ head };
+                i = i + 1;
+            }
+        "#,
+        limited(64 * 1024),
+    )
+}
+
+#[test]
+#[should_panic(expected = "memory limit of 65536 bytes exceeded")]
+fn test_large_string() {
+    run_with_options(
+        r#"
+            let s = "abc" * 1000000;
+        "#,
+        limited(64 * 1024),
+    )
+}
+
+#[test]
+#[should_panic(expected = "memory limit of 65536 bytes exceeded")]
+fn test_retained_strings() {
+    run_with_options(
+        r#"
+            class Node {
+                next;
+                text;
+            }
+
+            let head = nah;
+            let i = 0;
+            while i < 10 {
+                head = Node:{ head, "abc" * 20000 };
+                i = i + 1;
+            }
+        "#,
+        limited(64 * 1024),
+    )
+}
+
+#[test]
+#[should_panic(expected = "memory limit of 65536 bytes exceeded")]
+fn test_retained_strings_in_variables() {
+    run_with_options(
+        r#"
+            let a = "abc" * 15000;
+            let b = "abc" * 15000;
+        "#,
+        limited(64 * 1024),
+    )
+}
+
+#[test]
+fn test_released_strings() {
+    run_with_options(
+        r#"
+            let s = "";
+            let i = 0;
+            while i < 10 {
+                s = "abc" * 8000;
+                i = i + 1;
+            }
+        "#,
+        limited(64 * 1024),
+    )
+}
+
+#[test]
+fn test_error_kind() {
+    let result = execute_source_code_with_options(
+        r#"
+            let s = "abc" <> "abc" * 1000000;
+        "#
+        .to_owned(),
+        limited(64 * 1024),
+    );
+
+    assert_eq!(
+        result.err().map(|err| err.kind()),
+        Some(FruErrorKind::MemoryLimitExceeded)
+    );
+}
@@ -0,0 +1,5 @@
+mod cancellation_tests;
+mod capability_tests;
+mod fuel_tests;
+mod memory_limit_tests;
+mod stack_limit_tests;
@@ -0,0 +1,136 @@
+use frugurt::interpreter::{
+    error::FruErrorKind, options::InterpreterOptions, runner::execute_source_code_with_options,
+};
+
+use crate::run_with_options;
+
+fn limited(bytes: usize) -> InterpreterOptions {
+    InterpreterOptions {
+        stack_limit: Some(bytes),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_shallow_recursion() {
+    run_with_options(
+        r#"
+            let depth = fn(n) {
+                if n == 0 { 0 } else { depth(n - 1) + 1 }
+            };
+
+            assert_eq(depth(5), 5);
+        "#,
+        limited(1024 * 1024),
+    )
+}
+
+#[test]
+fn test_infinite_recursion() {
+    let result = execute_source_code_with_options(
+        r#"
+            let forever = fn(n) {
+                forever(n + 1)
+            };
+
+            forever(0);
+        "#
+        .to_owned(),
+        limited(1024 * 1024),
+    );
+
+    assert_eq!(
+        result.err().map(|err| err.kind()),
+        Some(FruErrorKind::StackLimitExceeded)
+    );
+}
+
+#[test]
+fn test_infinite_recursion_without_limit() {
+    let result = execute_source_code_with_options(
+        "let f = fn(n) { f(n + 1) }; f(0);".to_owned(),
+        InterpreterOptions::default(),
+    );
+
+    assert_eq!(
+        result.err().map(|err| err.kind()),
+        Some(FruErrorKind::StackLimitExceeded)
+    );
+}
+
+#[test]
+fn test_comparison_of_long_chains() {
+    run_with_options(
+        r#"
+            class Node {
+                pub next;
+            }
+
+            let a = Node :{ nah };
+            a.next = a;
+            let b = Node :{ nah };
+            b.next = b;
+            assert_eq(a, b);
+
+            let i = 0;
+            while i < 100000 {
+                a = Node :{ a };
+                b = Node :{ b };
+                i = i + 1;
+            }
+            assert_eq(a, b);
+        "#,
+        limited(1024 * 1024),
+    )
+}
+
+#[test]
+#[should_panic(expected = "code is nested too deeply")]
+fn test_deeply_nested_code() {
+    let code = format!("let x = {}1{};", "(".repeat(10000), ")".repeat(10000));
+
+    run_with_options(&code, limited(1024 * 1024))
+}
+
+#[test]
+fn test_infinite_recursion_in_task() {
+    for options in [limited(1024 * 1024), InterpreterOptions::default()] {
+        let result = execute_source_code_with_options(
+            r#"
+                let forever = fn(n) {
+                    forever(n + 1)
+                };
+
+                spawn(fn() { forever(0) }).join();
+            "#
+            .to_owned(),
+            options,
+        );
+
+        assert_eq!(
+            result.err().map(|err| err.kind()),
+            Some(FruErrorKind::StackLimitExceeded)
+        );
+    }
+}
+
+#[test]
+fn test_recursion_in_default_argument() {
+    let result = execute_source_code_with_options(
+        r#"
+            let forever = fn(n = forever()) { n };
+
+            forever();
+        "#
+        .to_owned(),
+        limited(1024 * 1024),
+    );
+
+    let err = result.err().unwrap();
+
+    assert_eq!(err.kind(), FruErrorKind::StackLimitExceeded);
+    assert_eq!(
+        err.to_string(),
+        "stack limit exceeded, calls are nested too deeply"
+    );
+}
@@ -0,0 +1,195 @@
+use crate::run;
+
+#[test]
+fn test_scope() {
+    run(r#"
+            let f = fn() {
+                let a = 5;
+                scope()
+            };
+
+            let s = freeze(f());
+
+            assert_eq(s.a, 5);
+            assert_eq(scope s { a * 2 }, 10);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "scope is frozen, `a` cannot be declared or assigned in it")]
+fn test_scope_set() {
+    run(r#"
+            let f = fn() {
+                let a = 5;
+                scope()
+            };
+
+            let s = freeze(f());
+
+            scope s {
+                a = 6;
+            }
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "scope is frozen, `b` cannot be declared or assigned in it")]
+fn test_scope_let() {
+    run(r#"
+            let f = fn() {
+                scope()
+            };
+
+            let s = freeze(f());
+
+            s.b = 1;
+        "#)
+}
+
+#[test]
+fn test_object() {
+    run(r#"
+            struct Box {
+                value;
+            } impl {
+                get() {
+                    value
+                }
+            }
+
+            let b = freeze(Box:{ 1 });
+
+            assert_eq(b.get(), 1);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "object of `Box` is frozen, field `value` cannot be changed")]
+fn test_object_set() {
+    run(r#"
+            struct Box {
+                value;
+            }
+
+            let b = freeze(Box:{ 1 });
+
+            b.value = 2;
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "object of `Box` is frozen, field `value` cannot be changed")]
+fn test_object_method() {
+    run(r#"
+            struct Box {
+                value;
+            } impl {
+                bump() {
+                    value = value + 1;
+                }
+            }
+
+            let b = freeze(Box:{ 1 });
+
+            b.bump();
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`Deque` is frozen, `push_back` cannot be called")]
+fn test_deep_collection() {
+    run(r#"
+            struct Config {
+                items;
+                limits;
+            }
+
+            let c = freeze(Config:{ Deque:{ 1, 2 }, TreeMap:{} });
+
+            assert_eq(c.items.len, 2);
+            c.items.push_back(3);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "object of `Inner` is frozen, field `value` cannot be changed")]
+fn test_deep_object() {
+    run(r#"
+            struct Inner {
+                value;
+            }
+
+            struct Outer {
+                inner;
+            }
+
+            let o = freeze(Outer:{ Inner:{ 1 } });
+
+            o.inner.value = 2;
+        "#)
+}
+
+#[test]
+fn test_cycle() {
+    run(r#"
+            class Node {
+                next;
+            }
+
+            let a = Node:{ nah };
+            let b = Node:{ a };
+            a.next = b;
+
+            freeze(a);
+
+            assert_eq(is_frozen(a), true);
+            assert_eq(is_frozen(b), true);
+        "#)
+}
+
+#[test]
+fn test_is_frozen() {
+    run(r#"
+            struct Box {
+                value;
+            }
+
+            let d = Deque:{ 1 };
+            let b = Box:{ d };
+
+            assert_eq(is_frozen(b), false);
+            assert_eq(is_frozen(d), false);
+            assert_eq(is_frozen(1), true);
+            assert_eq(is_frozen("text"), true);
+
+            freeze(b);
+
+            assert_eq(is_frozen(b), true);
+            assert_eq(is_frozen(d), true);
+        "#)
+}
+
+#[test]
+fn test_clone_is_mutable() {
+    run(r#"
+            struct Box {
+                items;
+            }
+
+            let b = freeze(Box:{ Deque:{ 1 } });
+            let c = deep_clone(b);
+
+            assert_eq(is_frozen(c), false);
+
+            c.items.push_back(2);
+            c.items = Deque:{};
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`Channel` can't be frozen")]
+fn test_wrong_value() {
+    run(r#"
+            freeze(Channel:{});
+        "#)
+}
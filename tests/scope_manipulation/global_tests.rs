@@ -0,0 +1,34 @@
+use crate::run;
+
+#[test]
+fn test_basics() {
+    run(r#"
+            let configure = fn() {
+                let inner = fn() {
+                    global().level = 3;
+                };
+
+                inner();
+            };
+
+            configure();
+            assert_eq(level, 3);
+
+            let bump = fn() {
+                let level = 100;
+                global().level = global().level + 1;
+                level
+            };
+
+            assert_eq(bump(), 100);
+            assert_eq(level, 4);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`global` expects no arguments")]
+fn test_arguments() {
+    run(r#"
+            global(1);
+        "#)
+}
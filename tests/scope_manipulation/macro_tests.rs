@@ -0,0 +1,112 @@
+use crate::run;
+
+#[test]
+fn test_basics() {
+    run(r#"
+            let twice = macro(fn(e) {
+                quote(unquote(e) + unquote(e))
+            });
+
+            let calls = 0;
+            let next = fn() {
+                calls = calls + 1;
+                calls
+            };
+
+            assert_eq(twice(next()), 3);
+            assert_eq(calls, 2);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`twice` can't be used as a name")]
+fn test_declared() {
+    run(r#"
+            let twice = macro(fn(e) {
+                quote(unquote(e) + unquote(e))
+            });
+
+            let call = fn(twice) {
+                twice(1)
+            };
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "macro `twice` is used above its declaration")]
+fn test_used_above_declaration() {
+    run(r#"
+            let f = fn() { twice(1) };
+
+            let twice = macro(fn(e) {
+                quote(unquote(e) + unquote(e))
+            });
+        "#)
+}
+
+#[test]
+fn test_values_are_spliced_as_literals() {
+    run(r#"
+            let answer = macro(fn() {
+                quote(unquote(6 * 7))
+            });
+
+            assert_eq(answer(), 42);
+        "#)
+}
+
+#[test]
+fn test_hygiene() {
+    run(r#"
+            let add_one = macro(fn(e) {
+                quote({
+                    let y = 1;
+                    unquote(e) + y
+                })
+            });
+
+            let y = 10;
+
+            assert_eq(add_one(y), 11);
+        "#)
+}
+
+#[test]
+fn test_nested_expansion() {
+    run(r#"
+            let square = macro(fn(e) {
+                quote({
+                    let x = unquote(e);
+                    x * x
+                })
+            });
+
+            let fourth = macro(fn(e) {
+                quote(square(square(unquote(e))))
+            });
+
+            let x = 3;
+
+            assert_eq(fourth(x), 81);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "macro expansion of `forever` is too deep")]
+fn test_too_deep() {
+    run(r#"
+            let forever = macro(fn(e) {
+                quote(forever(unquote(e)))
+            });
+
+            forever(1);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "`unquote` can only be used inside of `quote`")]
+fn test_unquote_outside_of_quote() {
+    run(r#"
+            unquote(1);
+        "#)
+}
@@ -1 +1,4 @@
 mod basics;
+mod freeze_tests;
+mod global_tests;
+mod macro_tests;
@@ -0,0 +1,95 @@
+use std::{
+    fs::{read_dir, read_to_string},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+// every line that a program prints is written after it as `// expect: line`,
+// a program that must fail has `// expect error: message`, the message may be a part of the real one
+struct Expectations {
+    output: Vec<String>,
+    error: Option<String>,
+}
+
+fn parse_expectations(source: &str) -> Expectations {
+    let mut expectations = Expectations {
+        output: Vec::new(),
+        error: None,
+    };
+
+    for line in source.lines() {
+        let Some((_, comment)) = line.split_once("// expect") else {
+            continue;
+        };
+
+        if let Some(message) = comment.strip_prefix(" error:") {
+            expectations.error = Some(message.trim().to_string());
+        } else if let Some(output) = comment.strip_prefix(':') {
+            expectations.output.push(output.trim().to_string());
+        }
+    }
+
+    expectations
+}
+
+fn collect_specs(dir: &Path, specs: &mut Vec<PathBuf>) {
+    for entry in read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+
+        if path.is_dir() {
+            collect_specs(&path, specs);
+        } else if path.extension().is_some_and(|x| x == "fru") {
+            specs.push(path);
+        }
+    }
+}
+
+fn check_spec(path: &Path) -> Result<(), String> {
+    let expectations = parse_expectations(&read_to_string(path).unwrap());
+
+    let result = Command::new(env!("CARGO_BIN_EXE_frugurt")).arg(path).output().unwrap();
+
+    // `print` puts a space after every argument
+    let output: Vec<_> = String::from_utf8_lossy(&result.stdout)
+        .lines()
+        .map(|x| x.trim_end().to_string())
+        .collect();
+
+    if output != expectations.output {
+        return Err(format!(
+            "expected output {:?}, got {:?}",
+            expectations.output, output
+        ));
+    }
+
+    let stderr = String::from_utf8_lossy(&result.stderr);
+
+    match (&expectations.error, result.status.success()) {
+        (None, true) => Ok(()),
+        (None, false) => Err(format!("unexpected error:\n{}", stderr)),
+        (Some(message), true) => Err(format!("expected error `{}`, got none", message)),
+        (Some(message), false) if stderr.contains(message.as_str()) => Ok(()),
+        (Some(message), false) => Err(format!("expected error `{}`, got:\n{}", message, stderr)),
+    }
+}
+
+#[test]
+fn test_spec() {
+    let mut specs = Vec::new();
+    collect_specs(
+        &Path::new(env!("CARGO_MANIFEST_DIR")).join("spec"),
+        &mut specs,
+    );
+    specs.sort();
+
+    assert!(!specs.is_empty(), "no spec programs found");
+
+    let failures: Vec<_> = specs
+        .iter()
+        .filter_map(|path| check_spec(path).err().map(|err| format!("{}: {}", path.display(), err)))
+        .collect();
+
+    if !failures.is_empty() {
+        panic!("{}", failures.join("\n\n"));
+    }
+}
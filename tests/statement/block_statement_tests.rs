@@ -0,0 +1,69 @@
+use crate::run;
+
+#[test]
+fn test_variables_stay_in_block() {
+    run(r#"
+            let x = 1;
+
+            if true {
+                let x = 2;
+                let y = 3;
+            }
+
+            let i = 0;
+            while i < 3 {
+                let y = i;
+                i = i + 1;
+            }
+
+            assert_eq(x, 1);
+
+            let y = "outer";
+            assert_eq(y, "outer");
+        "#)
+}
+
+#[test]
+fn test_initializer_sees_outer_variable() {
+    run(r#"
+            let x = 1;
+
+            {
+                let x = x + 1;
+                assert_eq(x, 2);
+            }
+
+            {
+                let f = fn() { later };
+                let later = 5;
+                assert_eq(f(), 5);
+            }
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "variable `x` is used before its declaration in this block")]
+fn test_use_before_declaration() {
+    run(r#"
+            let x = 1;
+
+            {
+                print(x);
+                let x = 2;
+            }
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "variable `x` is used before its declaration in this block")]
+fn test_assignment_before_declaration() {
+    run(r#"
+            let x = 1;
+
+            let y = {
+                x = 5;
+                let x = 2;
+                x
+            };
+        "#)
+}
@@ -8,6 +8,14 @@ fn test_type_mismatch() {
         "#)
 }
 
+#[test]
+#[should_panic(expected = "convert it with `Bool(x)` or compare it explicitly")]
+fn test_type_mismatch_hint() {
+    run(r#"
+            if 1 {}
+        "#)
+}
+
 #[test]
 #[should_panic(expected = "division by zero")]
 fn test_error_propagation_1() {
@@ -37,3 +45,29 @@ fn test_error_propagation_3() {
             }
         "#)
 }
+
+#[test]
+fn test_else_if_chain() {
+    run(r#"
+            let classify = fn(age) {
+                let group = nah;
+
+                if age < 13 {
+                    group = "child";
+                } else if age < 18 {
+                    group = "teen";
+                } else if age < 65 {
+                    group = "adult";
+                } else {
+                    group = "senior";
+                }
+
+                group
+            };
+
+            assert_eq(classify(5), "child");
+            assert_eq(classify(15), "teen");
+            assert_eq(classify(30), "adult");
+            assert_eq(classify(70), "senior");
+        "#)
+}
@@ -33,3 +33,63 @@ fn test_faulty_assignment() {
             x.x = 1 / 0;
         "#)
 }
+
+#[test]
+fn test_shadowing() {
+    run(r#"
+            let x = 1;
+
+            {
+                let x = 2;
+                x = 3;
+                assert_eq(x, 3);
+            }
+
+            let f = fn(x) {
+                let x = x * 10;
+                x
+            };
+
+            assert_eq(f(5), 50);
+            assert_eq(x, 1);
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "variable `x` already exists in this scope, assign it with `x = ...`")]
+fn test_redeclaration() {
+    run(r#"
+            let x = 1;
+            let x = 2;
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "variable `conter` does not exist, did you mean `counter`?")]
+fn test_typo_suggestion() {
+    run(r#"
+            let counter = 0;
+
+            let f = fn() {
+                conter = counter + 1;
+            };
+
+            f();
+        "#)
+}
+
+#[test]
+#[should_panic(expected = "variable `valeu` does not exist, did you mean `value`?")]
+fn test_typo_suggestion_field() {
+    run(r#"
+            class Box {
+                value;
+            } impl {
+                set(new) {
+                    valeu = new;
+                }
+            }
+
+            Box:{ 1 }.set(2);
+        "#)
+}
@@ -1,3 +1,4 @@
+mod block_statement_tests;
 mod break_statement_tests;
 mod continue_statement_tests;
 mod if_statement_tests;
@@ -0,0 +1,167 @@
+use frugurt::{
+    interpreter::identifier::Identifier,
+    tooling::check::{check_source, default_globals},
+};
+
+fn check(source: &str) -> Vec<(&'static str, usize, String)> {
+    check_source(source.to_string(), &default_globals())
+        .unwrap()
+        .into_iter()
+        .map(|x| (x.rule, x.location.unwrap().line, x.message))
+        .collect()
+}
+
+#[test]
+fn test_undefined() {
+    let source = r#"
+let x = 1;
+print(x + y);
+z = 5;
+let f = fn(a, b = a) { a + b + c };
+"#;
+
+    assert_eq!(
+        check(source),
+        vec![
+            (
+                "undefined-variable",
+                3,
+                "variable `y` is not defined".to_string()
+            ),
+            (
+                "undefined-variable",
+                4,
+                "variable `z` is not defined".to_string()
+            ),
+            (
+                "undefined-variable",
+                5,
+                "variable `c` is not defined".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_use_before_declaration() {
+    let source = r#"
+print(x);
+let x = 1;
+
+let f = fn() { g() };
+let g = fn() { f() };
+{
+    print(inner);
+    let inner = 2;
+}
+"#;
+
+    assert_eq!(
+        check(source),
+        vec![
+            (
+                "use-before-declaration",
+                2,
+                "variable `x` is used before its declaration".to_string()
+            ),
+            (
+                "use-before-declaration",
+                8,
+                "variable `inner` is used before its declaration".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_types() {
+    let source = r#"
+struct Counter {
+    n;
+    static total = 0;
+
+    Double {
+        get => n * 2;
+        set(v) { n = v / 2; }
+    }
+} impl {
+    add(k) {
+        n = n + k;
+        total = total + k;
+        Double
+    }
+}
+
+operator + (a : Counter, b : Counter) {
+    Counter:{ a.n + b.n + missing }
+}
+"#;
+
+    assert_eq!(
+        check(source),
+        vec![(
+            "undefined-variable",
+            19,
+            "variable `missing` is not defined".to_string()
+        )]
+    );
+}
+
+#[test]
+fn test_globals() {
+    let source = "Parent.send(1); let obj = scope(); scope obj { unknown; }";
+
+    assert_eq!(check(source).len(), 1);
+
+    let mut globals = default_globals();
+    globals.insert(Identifier::new("Parent"));
+
+    assert!(check_source(source.to_string(), &globals).unwrap().is_empty());
+}
+
+#[test]
+fn test_global_assignments() {
+    let source = r#"
+let setup = fn() {
+    global().verbose = true;
+};
+
+global().late = 1;
+print(late, verbose);
+
+global().x;
+print(x);
+"#;
+
+    assert_eq!(
+        check(source),
+        vec![(
+            "undefined-variable",
+            10,
+            "variable `x` is not defined".to_string()
+        )]
+    );
+}
+
+#[test]
+fn test_syntax_errors() {
+    let source = r#"
+let a = 1;
+let b = ;
+print(a);
+let c = (1 + 2;
+print(d);
+"#;
+
+    let diagnostics = check(source);
+
+    let syntax_errors = diagnostics.iter().filter(|(rule, _, _)| *rule == "syntax-error").count();
+
+    // both broken statements are reported and the rest is still checked
+    assert!(syntax_errors >= 2, "{:?}", diagnostics);
+    assert!(diagnostics.contains(&(
+        "undefined-variable",
+        6,
+        "variable `d` is not defined".to_string()
+    )));
+}
@@ -0,0 +1,75 @@
+use std::fs::write;
+
+use frugurt::tooling::doc::{document_source, generate, render_markdown, DocFormat};
+
+const SOURCE: &str = r#"
+/// Point on a plane.
+struct Point {
+    pub x;
+    y;
+    static count = 0;
+} impl {
+    /// Scales the point.
+    scale(k, times = 1) {
+        x = x * k;
+    }
+
+    static origin() {
+        "Point at zero.";
+        Point:{ 0, 0 }
+    }
+}
+
+/// Adds points.
+operator + (a : Point, b : Point) {
+    Point:{ a.x + b.x, a.y + b.y }
+}
+
+/// Distance between points.
+let dist = fn(a, b) { 0 };
+
+let not_a_function = 5;
+"#;
+
+#[test]
+fn test_collect() {
+    let module = document_source("point.fru", SOURCE.to_string()).unwrap();
+
+    assert_eq!(module.types.len(), 1);
+    assert_eq!(module.types[0].doc.as_deref(), Some("Point on a plane."));
+    assert_eq!(module.types[0].fields, vec!["pub x", "y"]);
+    assert_eq!(module.types[0].static_fields, vec!["count"]);
+
+    let methods = &module.types[0].methods;
+    assert_eq!(methods[0].signature, "scale(k, times = ...)");
+    assert_eq!(methods[0].doc.as_deref(), Some("Scales the point."));
+    assert_eq!(methods[1].signature, "static origin()");
+    assert_eq!(methods[1].doc.as_deref(), Some("Point at zero."));
+
+    assert_eq!(module.functions.len(), 1);
+    assert_eq!(module.functions[0].signature, "dist(a, b)");
+
+    assert_eq!(module.operators[0].signature, "Point + Point");
+    assert_eq!(module.operators[0].doc.as_deref(), Some("Adds points."));
+}
+
+#[test]
+fn test_markdown() {
+    let module = document_source("point.fru", SOURCE.to_string()).unwrap();
+    let markdown = render_markdown(&[module]);
+
+    assert!(markdown.starts_with("# point.fru\n\n## struct Point\n\nPoint on a plane.\n"));
+    assert!(markdown.contains("### dist(a, b)\n\nDistance between points.\n"));
+}
+
+#[test]
+fn test_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path().join("point.fru"), SOURCE).unwrap();
+    write(dir.path().join("notes.txt"), "not frugurt").unwrap();
+
+    let html = generate(dir.path(), DocFormat::Html).unwrap();
+
+    assert!(html.contains("<h2>struct Point</h2>"));
+    assert!(!html.contains("not frugurt"));
+}
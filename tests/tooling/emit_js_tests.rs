@@ -0,0 +1,154 @@
+use frugurt::tooling::emit_js::emit_js;
+
+// the program without the runtime in front of it
+fn emit(source: &str) -> String {
+    let js = emit_js(source.to_string()).unwrap();
+    let start = js.rfind("\n{\n").unwrap();
+    js[start + 1..].to_string()
+}
+
+#[test]
+fn test_functions_and_control_flow() {
+    let source = r#"
+let add = fn(x, y = 1) { x + y };
+let i = 0;
+
+while i < 3 {
+    if i == 1 {
+        print(add(i, y: 10));
+    } else {
+        print(add(i));
+    }
+
+    i = i + 1;
+}
+"#;
+
+    assert_eq!(
+        emit(source),
+        r#"{
+    const { assert_eq, Bool, Number, op, print, String } = $fru.builtins;
+
+    let add = $fru.fn(["x", "y"], (x, y = 1) => {
+        return $fru.op("+", x, y);
+    });
+    let i = 0;
+    while ($fru.cond($fru.op("<", i, 3), "while")) {
+        if ($fru.cond($fru.op("==", i, 1), "if")) {
+            $fru.call(print, [$fru.call(add, [i], { y: 10 })]);
+        } else {
+            $fru.call(print, [$fru.call(add, [i])]);
+        }
+        i = $fru.op("+", i, 1);
+    }
+}
+"#
+    );
+}
+
+#[test]
+fn test_else_if_is_flat() {
+    let source = r#"
+let age = 20;
+
+if age < 13 {
+    print("child");
+} else if age < 18 {
+    print("teen");
+} else if age < 65 {
+    print("adult");
+} else {
+    print("senior");
+}
+"#;
+
+    assert_eq!(
+        emit(source),
+        r#"{
+    const { assert_eq, Bool, Number, op, print, String } = $fru.builtins;
+
+    let age = 20;
+    if ($fru.cond($fru.op("<", age, 13), "if")) {
+        $fru.call(print, ["child"]);
+    } else if ($fru.cond($fru.op("<", age, 18), "if")) {
+        $fru.call(print, ["teen"]);
+    } else if ($fru.cond($fru.op("<", age, 65), "if")) {
+        $fru.call(print, ["adult"]);
+    } else {
+        $fru.call(print, ["senior"]);
+    }
+}
+"#
+    );
+}
+
+#[test]
+fn test_types() {
+    let source = r#"
+struct Point {
+    x;
+    y;
+} impl {
+    length2() {
+        x * x + y * y
+    }
+}
+
+let p = Point:{ 3, 4 };
+let q = p;
+"#;
+
+    assert_eq!(
+        emit(source),
+        r#"{
+    const { assert_eq, Bool, Number, op, print, String } = $fru.builtins;
+
+    let Point = $fru.type("struct", "Point", ["x", "y"]);
+    Point.$methods.length2 = $fru.fn([], function () {
+        return $fru.op("+", $fru.op("*", this.x, this.x), $fru.op("*", this.y, this.y));
+    });
+    let p = $fru.make(Point, [3, 4]);
+    let q = $fru.copy(p);
+}
+"#
+    );
+}
+
+#[test]
+fn test_unsupported() {
+    assert_eq!(
+        emit_js(r#"let m = import "math.fru";"#.to_string()).err().unwrap().to_string(),
+        "`import` can't be translated to JavaScript"
+    );
+
+    assert_eq!(
+        emit_js("let f = fn() { let x = { return 1; 2 }; };".to_string())
+            .err()
+            .unwrap()
+            .to_string(),
+        "`return`, `break` or `continue` inside of a block expression can't be translated to JavaScript"
+    );
+}
+
+#[test]
+fn test_macros_are_expanded() {
+    let js = emit(
+        r#"
+let twice = macro(fn(e) {
+    quote(unquote(e) + unquote(e))
+});
+
+print(twice(2 * 3));
+print(when(6, case(1, "one"), "other"));
+"#,
+    );
+
+    assert!(!js.contains("twice"));
+    assert!(!js.contains("call(when"));
+    assert!(js.contains(r#"$fru.op("+", $fru.op("*", 2, 3), $fru.op("*", 2, 3))"#));
+
+    assert_eq!(
+        emit_js(r#"let r = Regex("a+");"#.to_string()).err().unwrap().to_string(),
+        "literal of type `Regex` can't be translated to JavaScript"
+    );
+}
@@ -0,0 +1,104 @@
+use frugurt::tooling::highlight::{highlight_source, semantic_tokens, TokenKind};
+
+const SOURCE: &str = r#"struct Point {
+    x;
+    y;
+} impl {
+    scale(k) {
+        x = x * k;
+    }
+}
+
+let origin = Point:{ x: 0, y: 0 };
+let shift = fn(p) { p.scale(2); p.x };
+"#;
+
+fn kinds(source: &str) -> Vec<(String, &'static str, bool)> {
+    highlight_source(source)
+        .unwrap()
+        .tokens
+        .into_iter()
+        .map(|x| (x.text, x.kind.name(), x.declaration))
+        .collect()
+}
+
+#[test]
+fn test_tokens() {
+    let expected = [
+        ("Point", "type", true),
+        ("x", "field", true),
+        ("y", "field", true),
+        ("scale", "method", true),
+        ("k", "parameter", true),
+        ("x", "field", false),
+        ("x", "field", false),
+        ("*", "operator", false),
+        ("k", "parameter", false),
+        ("origin", "variable", true),
+        ("Point", "type", false),
+        ("x", "field", false),
+        ("y", "field", false),
+        ("shift", "function", true),
+        ("p", "parameter", true),
+        ("p", "parameter", false),
+        ("scale", "method", false),
+        ("p", "parameter", false),
+        ("x", "field", false),
+    ];
+
+    let expected: Vec<_> = expected
+        .iter()
+        .map(|(text, kind, declaration)| (text.to_string(), *kind, *declaration))
+        .collect();
+
+    assert_eq!(kinds(SOURCE), expected);
+}
+
+#[test]
+fn test_resolution() {
+    let source = r#"
+let f = fn() { g(x) };
+let g = fn(x) { print(x) };
+let x = 1;
+"#;
+
+    let tokens = kinds(source);
+
+    // `g` and `x` are used before their declaration
+    assert_eq!(tokens[1], ("g".to_string(), "function", false));
+    assert_eq!(tokens[2], ("x".to_string(), "variable", false));
+    assert_eq!(tokens[5], ("print".to_string(), "function", false));
+}
+
+#[test]
+fn test_outline() {
+    let outline = highlight_source(SOURCE).unwrap().outline;
+
+    let names: Vec<_> = outline.iter().map(|x| (x.name.as_str(), x.kind)).collect();
+    assert_eq!(
+        names,
+        vec![
+            ("Point", TokenKind::Type),
+            ("origin", TokenKind::Variable),
+            ("shift", TokenKind::Function),
+        ]
+    );
+
+    let members: Vec<_> = outline[0].children.iter().map(|x| x.name.as_str()).collect();
+    assert_eq!(members, vec!["x", "y", "scale"]);
+    assert_eq!(
+        (outline[0].children[2].line, outline[0].children[2].column),
+        (5, 5)
+    );
+}
+
+#[test]
+fn test_semantic_tokens() {
+    let tokens = highlight_source("let a = 1;\nlet b = a;").unwrap().tokens;
+
+    // variable is the first kind in the legend, `declaration` is the first modifier
+    assert_eq!(
+        semantic_tokens(&tokens),
+        vec![0, 4, 1, 0, 1, 1, 4, 1, 0, 1, 0, 4, 1, 0, 0]
+    );
+}
@@ -0,0 +1,135 @@
+use frugurt::tooling::lint::{lint_source, LintConfig, Severity};
+
+fn lint(source: &str, config: &LintConfig) -> Vec<(&'static str, usize)> {
+    lint_source(source.to_string(), config)
+        .unwrap()
+        .iter()
+        .map(|x| (x.rule, x.location.unwrap().line))
+        .collect()
+}
+
+#[test]
+fn test_variables() {
+    let source = r#"
+let global = 5;
+
+let f = fn(unused_param) {
+    let x = 1;
+    let y = 2;
+    let _ignored = 3;
+    {
+        let x = 3;
+        print(x);
+    }
+    y
+};
+"#;
+
+    assert_eq!(
+        lint(source, &LintConfig::default()),
+        vec![("unused-variable", 5), ("shadowed-variable", 9)]
+    );
+}
+
+#[test]
+fn test_scope_keeps_variables() {
+    let source = r#"
+let f = fn() {
+    let x = 1;
+    scope()
+};
+"#;
+
+    assert_eq!(lint(source, &LintConfig::default()), vec![]);
+}
+
+#[test]
+fn test_redeclaration() {
+    let source = r#"
+let x = 1;
+let x = 2;
+
+let f = fn(a, a) {
+    let b = a;
+    {
+        let b = 1;
+        print(b);
+    }
+    b
+};
+"#;
+
+    let diagnostics = lint_source(source.to_string(), &LintConfig::default()).unwrap();
+
+    assert_eq!(
+        lint(source, &LintConfig::default()),
+        vec![
+            ("redeclared-variable", 3),
+            ("redeclared-variable", 5),
+            ("shadowed-variable", 8)
+        ]
+    );
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+}
+
+#[test]
+fn test_unreachable_and_no_effect() {
+    let source = r#"
+let f = fn(x) {
+    "docstring";
+    x + 1;
+    x == 2;
+    return x;
+    print(x);
+};
+"#;
+
+    assert_eq!(
+        lint(source, &LintConfig::default()),
+        vec![
+            ("no-effect", 4),
+            ("suspicious-comparison", 5),
+            ("unreachable-code", 7)
+        ]
+    );
+}
+
+#[test]
+fn test_config() {
+    let config = LintConfig::parse(
+        r#"
+[package]
+name = "demo"
+
+[lint]
+no-effect = "off"
+unreachable-code = "error"
+"#,
+    )
+    .unwrap();
+
+    let source = r#"
+while true {
+    5;
+    break;
+    print(1);
+}
+"#;
+
+    let diagnostics = lint_source(source.to_string(), &config).unwrap();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].rule, "unreachable-code");
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "5:5: error[unreachable-code]: unreachable statement"
+    );
+}
+
+#[test]
+fn test_unknown_rule() {
+    let err = LintConfig::parse("[lint]\nno-such-rule = \"off\"").unwrap_err();
+
+    assert_eq!(err.to_string(), "unknown lint rule `no-such-rule`");
+}
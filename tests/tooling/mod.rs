@@ -0,0 +1,6 @@
+mod check_tests;
+mod doc_tests;
+mod emit_js_tests;
+mod highlight_tests;
+mod lint_tests;
+mod repl_tests;
@@ -0,0 +1,90 @@
+use frugurt::{
+    interpreter::options::InterpreterOptions,
+    tooling::repl::{run_repl, Repl},
+};
+
+fn eval(repl: &mut Repl, input: &str) -> Option<String> {
+    repl.eval(input).unwrap_or_else(|err| panic!("{}", err))
+}
+
+#[test]
+fn test_session() {
+    let mut repl = Repl::new(InterpreterOptions::default());
+
+    assert_eq!(eval(&mut repl, "let x = 5;"), None);
+    assert_eq!(eval(&mut repl, "x * 2"), Some("10".to_string()));
+    assert_eq!(eval(&mut repl, "print(x);"), None);
+}
+
+#[test]
+fn test_echo() {
+    let mut repl = Repl::new(InterpreterOptions::default());
+
+    eval(&mut repl, "struct Point { x; y; }");
+
+    assert_eq!(eval(&mut repl, "\"text\""), Some("\"text\"".to_string()));
+    assert_eq!(
+        eval(&mut repl, "Point:{ 1, Deque:{ 2 } }"),
+        Some("Point{x: 1, y: Deque[2]}".to_string())
+    );
+}
+
+#[test]
+fn test_commands() {
+    let mut repl = Repl::new(InterpreterOptions::default());
+
+    eval(&mut repl, "struct Point { x; y; }");
+    eval(&mut repl, "let p = Point:{ 1, 2 };");
+    eval(&mut repl, "let name = \"frugurt\";");
+
+    assert_eq!(eval(&mut repl, ":type p"), Some("Point".to_string()));
+    assert_eq!(eval(&mut repl, ":type 1 + 2"), Some("Number".to_string()));
+
+    let scope = eval(&mut repl, ":scope").unwrap();
+    let lines: Vec<_> = scope.lines().collect();
+    assert!(lines[0].starts_with("Point: Type = "));
+    assert_eq!(lines[1], "name: String = frugurt");
+    assert!(lines[2].starts_with("p: Point = "));
+    assert_eq!(lines.len(), 3);
+
+    eval(&mut repl, "operator <> (a : Point, b : Point) { a }");
+    assert_eq!(
+        eval(&mut repl, ":operators Point"),
+        Some("Point <> Point".to_string())
+    );
+
+    let ast = eval(&mut repl, ":ast").unwrap();
+    assert!(ast.contains("Operator"));
+}
+
+#[test]
+fn test_errors() {
+    let mut repl = Repl::new(InterpreterOptions::default());
+
+    assert_eq!(
+        repl.eval(":ast").unwrap_err().to_string(),
+        "nothing was entered yet"
+    );
+
+    assert_eq!(
+        repl.eval(":nope").unwrap_err().to_string(),
+        "unknown command `:nope`, see `:help`"
+    );
+
+    // session goes on after an error
+    assert!(repl.eval("undefined_variable").is_err());
+    assert_eq!(eval(&mut repl, "1 + 1"), Some("2".to_string()));
+}
+
+#[test]
+fn test_multiline_input() {
+    let input = "let f = fn(x) {\n    x + 1\n};\nf(1)\n:quit\nf(2)\n";
+    let mut output = Vec::new();
+
+    run_repl(InterpreterOptions::default(), input.as_bytes(), &mut output).unwrap();
+
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        ">>> ... ... >>> 2\n>>> "
+    );
+}